@@ -0,0 +1,64 @@
+package main
+
+import (
+	"fmt"
+	"os"
+	"regexp"
+	"strconv"
+	"strings"
+)
+
+// fileArg is a file named on the command line, with the 1-based position to start at; a line of
+// zero when none was given.
+type fileArg struct {
+	path      string
+	line, col int
+}
+
+// positionSuffix matches a path followed by ":line" or ":line:col", as compilers and grep print
+// them, a trailing colon included.
+var positionSuffix = regexp.MustCompile(`^(.+?):(\d+)(?::(\d+))?:?$`)
+
+// parseFileArgs reads the files to open from the arguments left after the flags: paths, each
+// optionally followed by ":line" or ":line:col", and "+line", which like vim's puts the cursor on
+// that line of the first file wherever it is given.
+func parseFileArgs(args []string) ([]fileArg, error) {
+	var files []fileArg
+	line := 0
+	for _, arg := range args {
+		if n, ok := strings.CutPrefix(arg, "+"); ok {
+			l, err := strconv.Atoi(n)
+			if err != nil || l < 1 {
+				return nil, fmt.Errorf("invalid line number %q", arg)
+			}
+			line = l
+			continue
+		}
+		files = append(files, splitPosition(arg))
+	}
+
+	if line > 0 {
+		if len(files) == 0 {
+			return nil, fmt.Errorf("no file to go to line %d of", line)
+		}
+		files[0].line, files[0].col = line, 0
+	}
+	return files, nil
+}
+
+// splitPosition takes the position off the end of arg, unless a file is named by all of it.
+func splitPosition(arg string) fileArg {
+	if _, err := os.Stat(arg); err == nil {
+		return fileArg{path: arg}
+	}
+	m := positionSuffix.FindStringSubmatch(arg)
+	if m == nil {
+		return fileArg{path: arg}
+	}
+	f := fileArg{path: m[1]}
+	f.line, _ = strconv.Atoi(m[2])
+	if m[3] != "" {
+		f.col, _ = strconv.Atoi(m[3])
+	}
+	return f
+}
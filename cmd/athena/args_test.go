@@ -0,0 +1,48 @@
+package main
+
+import (
+	"os"
+	"path/filepath"
+	"reflect"
+	"testing"
+)
+
+func TestParseFileArgs(t *testing.T) {
+	dir := t.TempDir()
+	colon := filepath.Join(dir, "notes:12")
+	if err := os.WriteFile(colon, nil, 0644); err != nil {
+		t.Fatal(err)
+	}
+
+	tests := []struct {
+		name    string
+		args    []string
+		want    []fileArg
+		wantErr bool
+	}{
+		{name: "none", args: nil, want: nil},
+		{name: "files", args: []string{"a.go", "b.go"}, want: []fileArg{{path: "a.go"}, {path: "b.go"}}},
+		{name: "line", args: []string{"a.go:12"}, want: []fileArg{{path: "a.go", line: 12}}},
+		{name: "line and column", args: []string{"a.go:12:5"}, want: []fileArg{{path: "a.go", line: 12, col: 5}}},
+		{name: "trailing colon", args: []string{"a.go:12:5:"}, want: []fileArg{{path: "a.go", line: 12, col: 5}}},
+		{name: "not a number", args: []string{"a.go:x"}, want: []fileArg{{path: "a.go:x"}}},
+		{name: "existing file", args: []string{colon}, want: []fileArg{{path: colon}}},
+		{name: "plus line", args: []string{"+7", "a.go", "b.go"}, want: []fileArg{{path: "a.go", line: 7}, {path: "b.go"}}},
+		{name: "plus line after", args: []string{"a.go", "+7"}, want: []fileArg{{path: "a.go", line: 7}}},
+		{name: "plus line wins", args: []string{"+7", "a.go:3:2"}, want: []fileArg{{path: "a.go", line: 7}}},
+		{name: "invalid plus line", args: []string{"+x", "a.go"}, wantErr: true},
+		{name: "plus line without file", args: []string{"+7"}, wantErr: true},
+	}
+
+	for _, tt := range tests {
+		t.Run(tt.name, func(t *testing.T) {
+			got, err := parseFileArgs(tt.args)
+			if (err != nil) != tt.wantErr {
+				t.Fatalf("parseFileArgs(%q) error = %v, wantErr %v", tt.args, err, tt.wantErr)
+			}
+			if !reflect.DeepEqual(got, tt.want) {
+				t.Errorf("parseFileArgs(%q) = %+v, want %+v", tt.args, got, tt.want)
+			}
+		})
+	}
+}
@@ -1,9 +1,12 @@
 package main
 
 import (
+	"bufio"
 	"flag"
 	"fmt"
 	"os"
+	"path/filepath"
+	"strings"
 
 	"github.com/lg2m/athena/internal/athena"
 	"github.com/lg2m/athena/internal/athena/config"
@@ -12,38 +15,68 @@ import (
 func main() {
 	var configPath string
 	flag.StringVar(&configPath, "c", "", "Path to the configuration file (shorthand)")
+	flag.StringVar(&configPath, "config", "", "Path to the configuration file")
+	var readOnly bool
+	flag.BoolVar(&readOnly, "R", false, "Read-only mode: saving over a file needs :w!")
+	var sessionName string
+	flag.StringVar(&sessionName, "session", "", "Restore the named session, saved again on exit")
 
-	flag.Usage = func() {
-		fmt.Fprintf(flag.CommandLine.Output(), "Usage: %s [-c config_path] <filename>\n", os.Args[0])
-		flag.PrintDefaults()
-	}
+	flag.Usage = printUsage
 
 	flag.Parse()
 
-	args := flag.Args()
+	files, err := parseFileArgs(flag.Args())
+	if err != nil {
+		fmt.Printf("Error: %v\n", err)
+		os.Exit(1)
+	}
 
-	// Check if the filename is provided
-	if len(args) != 1 {
+	// Check if a file is provided, unless a session is restored
+	if len(files) == 0 && sessionName == "" {
 		flag.Usage()
 		os.Exit(1)
 	}
 
-	filePath := args[0]
+	var filePath string
+	if sessionName == "" {
+		filePath = files[0].path
+	} else if filePath, err = athena.SessionFile(sessionName); err != nil {
+		fmt.Printf("Error reading session: %v\n", err)
+		os.Exit(1)
+	}
 
 	// Load the configuration
 	cfg, errors := config.LoadConfig(&configPath)
+	if projectPath := config.FindProjectConfig(filepath.Dir(filePath)); projectPath != "" {
+		errors = append(errors, loadProjectConfig(cfg, projectPath)...)
+	}
 	if len(errors) > 0 {
 		for _, errMsg := range errors {
 			fmt.Println("Config error:", errMsg)
 		}
 		os.Exit(1)
 	}
+	cfg.Editor.ReadOnly = readOnly
 
 	a, err := athena.NewAthena(cfg, filePath)
 	if err != nil {
 		fmt.Printf("Error initializing Athena: %v\n", err)
 		os.Exit(1)
 	}
+	// restores the terminal on a panic before Run, which restores it itself
+	defer a.Close()
+
+	if sessionName != "" {
+		a.LoadSession(sessionName)
+	}
+
+	// the files are opened in order, so gn goes through them that way, and the first is shown
+	if len(files) > 0 {
+		for _, f := range files[1:] {
+			a.OpenFile(f.path, f.line, f.col)
+		}
+		a.OpenFile(files[0].path, files[0].line, files[0].col)
+	}
 
 	if err := a.Run(); err != nil {
 		fmt.Printf("Error running editor: %v\n", err)
@@ -51,6 +84,26 @@ func main() {
 	}
 }
 
-func printUsage() {
+// loadProjectConfig merges the project config at path over cfg, asking first when the project is
+// new or its config changed, since opening a file must not quietly run someone else's settings.
+func loadProjectConfig(cfg *config.Config, path string) []string {
+	trust := config.LoadTrust(config.DefaultTrustPath())
+	if !trust.IsTrusted(path) {
+		fmt.Printf("Load the project config %s? [y/N] ", path)
+		answer, _ := bufio.NewReader(os.Stdin).ReadString('\n')
+		if !strings.EqualFold(strings.TrimSpace(answer), "y") {
+			return nil
+		}
+		if err := trust.Trust(path); err != nil {
+			return []string{fmt.Sprintf("Error trusting project config: %v", err)}
+		}
+	}
+	return config.LoadProjectConfig(cfg, path)
+}
 
+// printUsage prints how to run athena and its flags, for -h and a missing file.
+func printUsage() {
+	fmt.Fprintf(flag.CommandLine.Output(), "Usage: %s [-c config_path] [-R] [+line] <file[:line[:col]]>...\n", os.Args[0])
+	fmt.Fprintf(flag.CommandLine.Output(), "       %s [-c config_path] [-R] -session <name> [file...]\n", os.Args[0])
+	flag.PrintDefaults()
 }
@@ -2,10 +2,15 @@ package athena
 
 import (
 	"fmt"
+	"os"
+	"os/exec"
+	"sync"
+	"time"
 
 	"github.com/gdamore/tcell/v2"
 	"github.com/lg2m/athena/internal/athena/config"
 	"github.com/lg2m/athena/internal/editor"
+	"github.com/lg2m/athena/internal/editor/state"
 	"github.com/lg2m/athena/internal/ui"
 )
 
@@ -15,13 +20,50 @@ type Athena struct {
 	cfg    *config.Config
 	editor *editor.Editor
 	views  struct {
-		gutters   *ui.GuttersView
-		document  *ui.DocumentView
-		statusBar *ui.StatusBarView
+		tabLine     *ui.TabLineView
+		statusBar   *ui.StatusBarView
+		commandLine *ui.CommandLineView
+		dividers    []*ui.DividerView // columns between side by side windows, see arrange
+		syntax      *ui.SyntaxCache   // highlights of the buffers, shared by the windows
+		rows        *ui.RowCache      // rows drawn for the lines of the buffers, shared by the windows
+		output      *ui.OutputView    // panel along the bottom for the output of commands
 	}
-	viewport *ui.Viewport // Shared viewport for synchronized scrolling
+	layout *layout    // windows splitting the screen, see window.go
+	focus  *window    // window keys go to, whose buffer is the editor's current one
+	tabs   []*tabPage // every tab page, the current one included, see tab.go
+	tab    int        // index of the current tab page
+	title  *terminalTitle
+
+	servers   languageServers // see lsp.go
+	autosave  autosave        // see swap.go
+	diskWatch diskWatch       // see disk.go
+	vcs       *versionControl // nil when not enabled, see vcs.go
+
+	// per-file views, when enabled
+	viewStore  *viewStore
+	viewPath   string              // real path of the file the view is tracked for
+	view       fileView            // latest view of that file, stored when it is left
+	visited    map[string]bool     // files whose saved position was already restored this run
+	baseEditor config.EditorConfig // editor settings from the config, before any :set
+
+	// sessions, see session.go
+	sessionDir string // where sessions are kept, "" when they can't be
+	session    string // name of the session last saved or loaded, saved again on exit
+
+	wake        chan struct{} // wakes the event loop to redraw, see wakeup
+	quitting    bool          // ":q" or the like was run
+	askedQuit   bool          // <c-c> asked about unsaved changes, see handleEvent
+	fedKeys     bool          // keys are fed rather than typed, see NewHeadless
+	restoreOnce sync.Once     // see restoreTerminal
 }
 
+// frameInterval is the delay between animation frames.
+const frameInterval = 16 * time.Millisecond
+
+// eventQueueSize is how many terminal events can wait for the event loop before reading them from
+// the terminal waits too.
+const eventQueueSize = 64
+
 // NewAthena creates an instance of the athena text-editor.
 func NewAthena(cfg *config.Config, filePath string) (*Athena, error) {
 	screen, err := tcell.NewScreen()
@@ -33,12 +75,47 @@ func NewAthena(cfg *config.Config, filePath string) (*Athena, error) {
 		return nil, fmt.Errorf("failed to initialize screen: %w", err)
 	}
 
+	a, err := newAthena(screen, cfg, filePath, newTerminalTitle(os.Stdout, cfg.Editor.Title))
+	if err != nil {
+		screen.Fini()
+		return nil, err
+	}
+	a.fillNewFiles(defaultTemplatesDir())
+	a.vcs = newVersionControl()
+	a.sessionDir = defaultSessionDir()
+	if cfg.Editor.Autosave.Swap {
+		a.startAutosave(defaultSwapDir(), time.Duration(cfg.Editor.Autosave.Interval)*time.Second)
+	}
+	a.startDiskWatch(diskCheckInterval)
+	return a, nil
+}
+
+// newAthena sets up the editor and views on an initialized screen.
+func newAthena(screen tcell.Screen, cfg *config.Config, filePath string, title *terminalTitle) (*Athena, error) {
 	a := &Athena{
-		screen:   screen,
-		cfg:      cfg,
-		editor:   editor.NewEditor(),
-		viewport: ui.NewViewport(cfg.Editor.ScrollPadding),
+		screen: screen,
+		cfg:    cfg,
+		editor: editor.NewEditor(),
+		title:  title,
+		wake:   make(chan struct{}, 1),
+	}
+	if cfg.Editor.Views {
+		a.viewStore = loadViews(defaultViewsPath())
+		a.visited = make(map[string]bool)
+		a.baseEditor = cfg.Editor
+		editor.Subscribe(a.editor, a.forgetVisited)
+	}
+	// pasted text is told apart from typing, see ui.DocumentView
+	screen.EnablePaste()
+	if cfg.Editor.Mouse {
+		screen.EnableMouse(tcell.MouseButtonEvents)
 	}
+	a.editor.SetTabWidth(cfg.Editor.TabWidth)
+	a.editor.SetReplaceSymlinks(cfg.Editor.Symlinks == config.SymlinkReplace)
+	a.editor.SetBackup(cfg.Editor.Autosave.Backup)
+	a.editor.SetOpenReadOnly(cfg.Editor.ReadOnly)
+	a.editor.SetWakeup(a.wakeup)
+	a.editor.SetPanicHandler(a.restoreTerminal)
 
 	if err := a.editor.OpenFile(filePath); err != nil {
 		return nil, fmt.Errorf("failed to load file: %w", err)
@@ -49,51 +126,335 @@ func NewAthena(cfg *config.Config, filePath string) (*Athena, error) {
 	return a, nil
 }
 
+// OpenFile opens path as a buffer, making it the current one, with the cursor at line and col,
+// both 1-based and clamped to the file; a line of zero leaves the cursor where it was, or where
+// the file's remembered view puts it. Failing to, it shows why in the message line.
+func (a *Athena) OpenFile(path string, line, col int) {
+	a.editor.SetError(a.openFileAt(path, line, col))
+}
+
+// openFileAt is OpenFile returning the error.
+func (a *Athena) openFileAt(path string, line, col int) error {
+	if err := a.editor.OpenFile(path); err != nil {
+		return err
+	}
+	if line <= 0 {
+		return nil
+	}
+
+	lines, err := a.editor.GetLineCount()
+	if err != nil {
+		return err
+	}
+	start, end, err := a.editor.LineRange(min(line, lines) - 1)
+	if err != nil {
+		return err
+	}
+	if err := a.editor.MoveCursorTo(start+min(max(col-1, 0), end-start), false); err != nil {
+		return err
+	}
+	// the position asked for wins over the remembered one
+	if realPath, err := a.editor.RealPath(); err == nil && a.visited != nil {
+		a.visited[realPath] = true
+	}
+	return nil
+}
+
 // Run starts the Athena application.
 func (a *Athena) Run() error {
-	defer a.screen.Fini()
+	// swap files go last, once nothing is left to save
+	defer a.stopAutosave()
+	defer a.stopDiskWatch()
+	// a save still writing in the background is finished before exiting
+	defer a.editor.WaitForSaves()
+	defer a.closeLanguageServers()
+	// also on a panic, before it is printed
+	defer a.restoreTerminal()
+	defer a.saveViews()
+	defer a.saveSessionOnExit()
 
-	for {
-		a.draw()
-		a.screen.Show()
+	// terminal events are read in the background, so the loop can wait for them, wake-ups and
+	// timeouts at once
+	events := make(chan tcell.Event, eventQueueSize)
+	stopEvents := make(chan struct{})
+	go func() {
+		defer a.restoreOnPanic()
+		a.screen.ChannelEvents(events, stopEvents)
+	}()
+	// first of all on the way out, so nothing reads the terminal while it is restored
+	defer close(stopEvents)
 
-		ev := a.screen.PollEvent()
+	for {
+		a.render()
+		nextFrame := time.Now().Add(a.frameGap())
 
-		switch ev := ev.(type) {
-		case *tcell.EventKey:
-			if ev.Key() == tcell.KeyCtrlC {
+		select {
+		case ev, ok := <-events:
+			if a.handleQueued(ev, ok) {
 				return nil
 			}
-		case *tcell.EventResize:
-			a.screen.Sync()
-			a.resizeViews()
+		case <-a.wake:
+		case <-a.timeout():
+			a.focus.document.ExpirePendingKeys(time.Now())
 		}
 
-		if a.views.document.HandleEvent(ev) {
+		// the rest of a burst, e.g. a key held down or a paste, is drawn in one frame
+		if a.gatherEvents(events, nextFrame) {
+			return nil
+		}
+	}
+}
+
+// handleQueued handles an event read from the event queue, reporting whether the event loop
+// should end: when the editor quits, or when the queue was closed as the screen was torn down.
+func (a *Athena) handleQueued(ev tcell.Event, ok bool) bool {
+	if !ok {
+		return true
+	}
+	return a.handleEvent(ev)
+}
+
+// gatherEvents handles the events already waiting and, until nextFrame is due, those still
+// coming, so the next frame draws them all; it reports whether the event loop should end.
+func (a *Athena) gatherEvents(events <-chan tcell.Event, nextFrame time.Time) bool {
+	var due <-chan time.Time
+	if wait := time.Until(nextFrame); wait > 0 {
+		due = time.After(wait)
+	}
+
+	for {
+		// waiting events first, whether or not the frame is due
+		select {
+		case ev, ok := <-events:
+			if a.handleQueued(ev, ok) {
+				return true
+			}
+			continue
+		case <-a.wake:
 			continue
+		default:
+		}
+		if due == nil {
+			return false
+		}
+
+		select {
+		case ev, ok := <-events:
+			if a.handleQueued(ev, ok) {
+				return true
+			}
+		case <-a.wake:
+		case <-due:
+			return false
 		}
 	}
 }
 
-func (a *Athena) initializeViews() {
-	a.views.gutters = ui.NewGuttersView(a.editor, a.cfg, a.viewport)
-	a.views.document = ui.NewDocumentView(a.editor, a.cfg, a.viewport)
-	a.views.statusBar = ui.NewStatusBarView(a.editor, &a.cfg.Editor)
-	a.resizeViews()
+// frameGap returns the least time between frames, for the max-fps setting.
+func (a *Athena) frameGap() time.Duration {
+	if a.cfg.Editor.MaxFPS <= 0 {
+		return 0
+	}
+	return time.Second / time.Duration(a.cfg.Editor.MaxFPS)
 }
 
-func (a *Athena) draw() {
+// render lays out and draws every view, then shows the frame.
+//
+//	Every view is drawn again each frame, into the screen's cell buffer rather than the terminal:
+//	Show compares it with the frame on the terminal and only writes the cells that changed, so a
+//	keystroke costs a few cells of output however much is redrawn. Lines an edit didn't touch are
+//	drawn from the rows kept for them, see ui.RowCache, rather than laid out again.
+func (a *Athena) render() {
+	// hooks run first, so what they change is drawn
+	a.editor.DispatchEvents()
+	if a.viewStore != nil {
+		a.trackView()
+		a.captureView()
+	}
+	a.syncDocuments()
+	a.checkSwap()
+	a.checkDisk()
+	a.syncVCS()
+	// the layout can change without a resize, e.g. when zen mode is toggled
+	a.resizeViews()
 	a.screen.Clear()
+	a.draw(a.screen)
+	a.views.rows.EndFrame()
+	a.placeCursor()
+	a.screen.Show()
+	a.updateTitle()
+}
+
+// handleEvent passes ev to the first view that takes it, reporting whether the editor should quit.
+func (a *Athena) handleEvent(ev tcell.Event) bool {
+	switch ev := ev.(type) {
+	case *tcell.EventKey:
+		if ev.Key() == tcell.KeyCtrlC {
+			// asks about unsaved changes like :q, and quits anyway when typed again while asking
+			if a.askedQuit {
+				return true
+			}
+			a.focus.document.ConfirmQuit()
+			a.askedQuit = a.views.commandLine.Active()
+			return a.quitting
+		}
+		a.askedQuit = false
+		a.focus.document.RecordKey(ev)
+		if !a.views.commandLine.Active() {
+			a.editor.ClearMessage()
+		}
+	case *tcell.EventResize:
+		a.screen.Sync()
+	}
+
+	// an edit made since the last event, e.g. a filter's output arriving, is undone apart from ev's
+	a.commitUndo()
+	switch {
+	case a.views.commandLine.HandleEvent(ev):
+	case a.views.output.HandleEvent(ev):
+	case a.focus.gutters.HandleEvent(ev):
+	default:
+		a.focus.document.HandleEvent(ev)
+	}
+	a.commitUndo()
+	return a.quitting
+}
+
+// commitUndo ends the undo step unless the change going into it isn't finished: an insert, or a
+// prompt like the one :s///c asks at each match.
+func (a *Athena) commitUndo() {
+	if a.editor.GetMode() != state.Insert && !a.views.commandLine.Active() {
+		a.editor.CommitUndo()
+	}
+}
+
+// placeCursor shows the terminal cursor where the focused document drew it, shaped for the mode;
+// the command line draws its own while a prompt is open.
+func (a *Athena) placeCursor() {
+	x, y, style, ok := a.focus.document.Cursor()
+	if !ok || a.views.commandLine.Active() {
+		a.screen.HideCursor()
+		return
+	}
+	a.screen.SetCursorStyle(style)
+	a.screen.ShowCursor(x, y)
+}
+
+// animating reports whether a window is still smoothly scrolling.
+func (a *Athena) animating() bool {
+	for _, w := range a.layout.windows() {
+		if w.viewport.Animating() {
+			return true
+		}
+	}
+	return false
+}
 
-	a.views.gutters.Draw(a.screen)
-	a.views.document.Draw(a.screen)
-	a.views.statusBar.Draw(a.screen)
+// wakeup wakes the event loop to redraw, e.g. once background work has finished. It never
+// blocks: wake-ups that come before the loop gets to the first are folded into it.
+func (a *Athena) wakeup() {
+	select {
+	case a.wake <- struct{}{}:
+	default:
+	}
+}
+
+// timeout returns a channel the event loop waits on to wake by itself: after a frame interval
+// while a window is still animating, so the animation continues, and when a partly typed key
+// sequence times out, so it stops showing as pending without waiting for another key. It is nil,
+// never ready, when neither is due.
+func (a *Athena) timeout() <-chan time.Time {
+	wait, due := time.Duration(0), false
+	if a.animating() {
+		wait, due = frameInterval, true
+	}
+	if deadline, ok := a.focus.document.PendingKeysDeadline(); ok {
+		if until := time.Until(deadline); !due || until < wait {
+			wait, due = until, true
+		}
+	}
+	if !due {
+		return nil
+	}
+	return time.After(wait)
+}
+
+// updateTitle sets the terminal title for the current buffer.
+func (a *Athena) updateTitle() {
+	name, _ := a.editor.FileName()
+	path, _ := a.editor.FilePath()
+	modified, _ := a.editor.Modified()
+	a.title.Set(name, path, modified)
+}
+
+// runExternal suspends the screen while cmd runs, so it can use the terminal, e.g. for sudo's password prompt.
+func (a *Athena) runExternal(cmd *exec.Cmd) error {
+	if err := a.screen.Suspend(); err != nil {
+		return err
+	}
+	defer func() {
+		_ = a.screen.Resume()
+	}()
+
+	return cmd.Run()
+}
+
+func (a *Athena) initializeViews() {
+	a.views.commandLine = ui.NewCommandLineView(a.editor)
+	a.views.syntax = ui.NewSyntaxCache(a.editor)
+	a.views.rows = ui.NewRowCache(a.editor)
+	a.views.tabLine = ui.NewTabLineView(a.cfg)
+	a.views.statusBar = ui.NewStatusBarView(a.editor, a.cfg)
+	a.views.output = ui.NewOutputView(a.cfg)
+	a.views.output.SetWakeup(a.wakeup)
+	a.focus = a.newWindow()
+	a.layout = &layout{window: a.focus}
+	a.tabs = []*tabPage{{layout: a.layout, focus: a.focus}}
+	a.resizeViews()
+}
+
+// draw draws every view on a blank surface.
+func (a *Athena) draw(surface ui.Surface) {
+	if a.focus.document.Zen() {
+		a.focus.document.Draw(surface)
+	} else {
+		a.drawWindows(surface)
+		a.views.tabLine.SetTabs(a.tabLabels(), a.tab)
+		a.views.tabLine.Draw(surface)
+		a.views.statusBar.Draw(surface)
+		a.views.output.Draw(surface)
+	}
+	a.views.commandLine.Draw(surface)
+	// popups go over everything, whichever window they reach into
+	a.focus.document.DrawOverlays(surface)
 }
 
 func (a *Athena) resizeViews() {
 	width, height := a.screen.Size()
+	a.views.dividers = nil
 
-	a.views.gutters.Resize(0, 0, 6, height-1)
-	a.views.document.Resize(6, 0, width-6, height-1)
-	a.views.statusBar.Resize(0, height-1, width, 1)
+	if a.focus.document.Zen() {
+		// only the focused window's text, centered, and the command line for prompts and messages
+		textWidth := min(a.cfg.Editor.Zen.Width, width)
+		a.focus.gutters.Resize(0, 0, 0, 0)
+		a.focus.document.Resize((width-textWidth)/2, 0, textWidth, height-1)
+		a.views.tabLine.Resize(0, 0, 0, 0)
+		a.views.statusBar.Resize(0, 0, 0, 0)
+		a.views.output.Resize(0, 0, 0, 0)
+		a.views.commandLine.Resize(0, height-1, width, 1)
+		return
+	}
+
+	// the tab line only shows once there are tab pages to tell apart
+	top := 0
+	if len(a.tabs) > 1 {
+		top = 1
+	}
+	a.views.tabLine.Resize(0, 0, width, top)
+	// the output panel, while open, goes between the windows and the status bar
+	panel := a.views.output.Height(height - 2 - top)
+	a.arrange(a.layout, 0, top, width, height-2-top-panel)
+	a.views.output.Resize(0, height-2-panel, width, panel)
+	a.views.statusBar.Resize(0, height-2, width, 1)
+	a.views.commandLine.Resize(0, height-1, width, 1)
 }
@@ -4,14 +4,18 @@ import (
 	"fmt"
 	"os"
 	"path/filepath"
+	"unicode/utf8"
 
 	"github.com/BurntSushi/toml"
 )
 
 // Config represents the entire app config.
 type Config struct {
-	Editor EditorConfig `toml:"editor"`
-	Keymap KeymapConfig `toml:"keys"`
+	Editor    EditorConfig              `toml:"editor"`
+	Keymap    KeymapConfig              `toml:"keys"`
+	Languages map[string]LanguageOptions `toml:"language"` // keyed by file type, e.g. "go"
+	Aliases   map[string]string          `toml:"aliases"`  // other names for named commands, e.g. "left" for "move_left"
+	Themes    map[string]*ThemeConfig    `toml:"-"`        // read from the themes directory, keyed by name
 }
 
 // LoadConfig loads the configuration from default path or arg.
@@ -20,9 +24,13 @@ func LoadConfig(filePath *string) (*Config, []string) {
 	var errors []string
 
 	// Load from file and merge
-	fileCfg, fileErrors := loadConfigFile(filePath)
+	fileCfg, meta, fileErrors := loadConfigFile(filePath)
 	errors = append(errors, fileErrors...)
-	mergeConfig(defaultCfg, fileCfg)
+	mergeConfig(defaultCfg, fileCfg, meta)
+
+	themes, themeErrors := LoadThemes(themesDir(filePath))
+	defaultCfg.Themes = themes
+	errors = append(errors, themeErrors...)
 
 	validateErrors := validateAndFixConfig(defaultCfg)
 	errors = append(errors, validateErrors...)
@@ -35,58 +43,101 @@ func defaultConfig() *Config {
 	return &Config{
 		Editor: EditorConfig{
 			ScrollPadding: 5,
+			TabWidth:      4,
 			LineNumber:    LineNumberRelative,
 			CursorShape: CursorShapeConfig{
 				Insert: CursorBar,
 				Normal: CursorBlock,
 			},
 			BufferLine: true,
-			Gutters:    []GutterOption{GutterSpacer, GutterLineNumbers, GutterSpacer},
+			EOFMarker:  "~",
+			Title:      "{name}{modified} - athena",
+			Hyperlinks: true,
+			MaxFPS:     60,
+			AutoPairs:  []string{"()", "[]", "{}", `""`, "''"},
+			Symlinks:   SymlinkWriteThrough,
+			Gutters:    []GutterOption{GutterDiagnostics, GutterLineNumbers, GutterSpacer},
+			GutterClick: GutterClickConfig{
+				GutterDiagnostics: GutterActionShowDiagnostic,
+				GutterLineNumbers: GutterActionSelectLine,
+			},
 			StatusBar: StatusBarConfig{
-				Left:   []StatusBarOption{SectionMode},
-				Center: []StatusBarOption{SectionFileName, SectionVersionControl},
-				Right:  []StatusBarOption{SectionCursorPercentage, SectionCursorPos, SectionLineCount, SectionFileType},
+				Left:   []StatusBarOption{SectionMode, SectionRecording, SectionSaving},
+				Center: []StatusBarOption{SectionFileName, SectionVersionControl, SectionDiffStats},
+				Right:  []StatusBarOption{SectionDiagnostics, SectionPendingKeys, SectionCursorPercentage, SectionCursorPos, SectionLineCount, SectionFileType},
 				Mode: StatusBarModeConfig{
-					Normal: "NOR",
-					Insert: "INS",
+					Normal:  "NOR",
+					Insert:  "INS",
+					Command: "CMD",
 				},
 			},
+			Search: SearchConfig{
+				WrapScan: true,
+			},
+			Indent: IndentConfig{
+				Auto:  true,
+				After: []string{"{", "(", "["},
+			},
+			Zen: ZenConfig{
+				Width: 80,
+			},
+			Autosave: AutosaveConfig{
+				Swap:     true,
+				Interval: 4,
+			},
 		},
 		Keymap: defaultKeymap(),
+		Languages: map[string]LanguageOptions{
+			"go": {IndentTabs: true},
+			"py": {IndentAfter: []string{":", "(", "[", "{"}},
+			"rs": {AutoPairs: []string{"()", "[]", "{}", `""`}},
+		},
+		Aliases: map[string]string{},
 	}
 }
 
-func loadConfigFile(filePath *string) (*Config, []string) {
+func loadConfigFile(filePath *string) (*Config, toml.MetaData, []string) {
 	var errors []string
 	if filePath == nil || *filePath == "" {
 		homeDir, err := os.UserHomeDir()
 		if err != nil {
 			errors = append(errors, fmt.Sprintf("Error finding home directory: %v", err))
-			return nil, errors
+			return nil, toml.MetaData{}, errors
 		}
 		cfgPath := filepath.Join(homeDir, ".config", "athena", "config.toml")
 		filePath = &cfgPath
 	}
 
 	if _, err := os.Stat(*filePath); os.IsNotExist(err) {
-		return nil, errors // No file, no problem
+		return nil, toml.MetaData{}, errors // No file, no problem
 	}
 
 	cfg := &Config{}
-	if _, err := toml.DecodeFile(*filePath, cfg); err != nil {
+	meta, err := toml.DecodeFile(*filePath, cfg)
+	if err != nil {
 		errors = append(errors, fmt.Sprintf("Error decoding file: %v", err))
 	}
 
-	return cfg, errors
+	return cfg, meta, errors
 }
 
-func mergeConfig(dst *Config, src *Config) {
+// mergeConfig merges the file config over dst; meta tells which keys the file set, so booleans can default to true.
+func mergeConfig(dst *Config, src *Config, meta toml.MetaData) {
 	if src == nil {
 		return
 	}
 	if src.Editor.ScrollPadding != 0 {
 		dst.Editor.ScrollPadding = src.Editor.ScrollPadding
 	}
+	if src.Editor.TabWidth != 0 {
+		dst.Editor.TabWidth = src.Editor.TabWidth
+	}
+	if src.Editor.TextWidth != 0 {
+		dst.Editor.TextWidth = src.Editor.TextWidth
+	}
+	if src.Editor.Symlinks != "" {
+		dst.Editor.Symlinks = src.Editor.Symlinks
+	}
 	if src.Editor.LineNumber != "" {
 		dst.Editor.LineNumber = src.Editor.LineNumber
 	}
@@ -96,10 +147,58 @@ func mergeConfig(dst *Config, src *Config) {
 	if src.Editor.CursorShape.Normal != "" {
 		dst.Editor.CursorShape.Normal = src.Editor.CursorShape.Normal
 	}
-	dst.Editor.BufferLine = src.Editor.BufferLine
+	// booleans only override when set, so a project config leaves the user's alone
+	if meta.IsDefined("editor", "buffer-line") {
+		dst.Editor.BufferLine = src.Editor.BufferLine
+	}
+	if meta.IsDefined("editor", "cursor-line") {
+		dst.Editor.CursorLine = src.Editor.CursorLine
+	}
+	if meta.IsDefined("editor", "cursor-column") {
+		dst.Editor.CursorColumn = src.Editor.CursorColumn
+	}
+	if meta.IsDefined("editor", "soft-wrap") {
+		dst.Editor.SoftWrap = src.Editor.SoftWrap
+	}
+	if meta.IsDefined("editor", "display-line-movement") {
+		dst.Editor.DisplayLineMovement = src.Editor.DisplayLineMovement
+	}
+	if meta.IsDefined("editor", "smooth-scroll") {
+		dst.Editor.SmoothScroll = src.Editor.SmoothScroll
+	}
+	if meta.IsDefined("editor", "max-fps") {
+		dst.Editor.MaxFPS = src.Editor.MaxFPS
+	}
+	if meta.IsDefined("editor", "accessibility") {
+		dst.Editor.Accessibility = src.Editor.Accessibility
+	}
+	if meta.IsDefined("editor", "views") {
+		dst.Editor.Views = src.Editor.Views
+	}
+	if meta.IsDefined("editor", "mouse") {
+		dst.Editor.Mouse = src.Editor.Mouse
+	}
+	if meta.IsDefined("editor", "eof-marker") {
+		dst.Editor.EOFMarker = src.Editor.EOFMarker
+	}
+	if meta.IsDefined("editor", "scrollbar") {
+		dst.Editor.Scrollbar = src.Editor.Scrollbar
+	}
+	if meta.IsDefined("editor", "title") {
+		dst.Editor.Title = src.Editor.Title
+	}
+	if src.Editor.Theme != "" {
+		dst.Editor.Theme = src.Editor.Theme
+	}
+	if meta.IsDefined("editor", "hyperlinks") {
+		dst.Editor.Hyperlinks = src.Editor.Hyperlinks
+	}
 	if len(src.Editor.Gutters) > 0 {
 		dst.Editor.Gutters = src.Editor.Gutters
 	}
+	for gutter, action := range src.Editor.GutterClick {
+		dst.Editor.GutterClick[gutter] = action
+	}
 	if len(src.Editor.StatusBar.Left) > 0 {
 		dst.Editor.StatusBar.Left = src.Editor.StatusBar.Left
 	}
@@ -115,12 +214,58 @@ func mergeConfig(dst *Config, src *Config) {
 	if src.Editor.StatusBar.Mode.Insert != "" {
 		dst.Editor.StatusBar.Mode.Insert = src.Editor.StatusBar.Mode.Insert
 	}
-	for key, action := range src.Keymap.Normal {
-		dst.Keymap.Normal[key] = action
+	if src.Editor.StatusBar.Mode.Command != "" {
+		dst.Editor.StatusBar.Mode.Command = src.Editor.StatusBar.Mode.Command
+	}
+	if meta.IsDefined("editor", "search", "ignore-case") {
+		dst.Editor.Search.IgnoreCase = src.Editor.Search.IgnoreCase
+	}
+	if meta.IsDefined("editor", "search", "smart-case") {
+		dst.Editor.Search.SmartCase = src.Editor.Search.SmartCase
+	}
+	if meta.IsDefined("editor", "search", "wrap-scan") {
+		dst.Editor.Search.WrapScan = src.Editor.Search.WrapScan
+	}
+	if meta.IsDefined("editor", "search", "regex") {
+		dst.Editor.Search.Regex = src.Editor.Search.Regex
+	}
+	if meta.IsDefined("editor", "indent", "auto") {
+		dst.Editor.Indent.Auto = src.Editor.Indent.Auto
+	}
+	if src.Editor.AutoPairs != nil {
+		dst.Editor.AutoPairs = src.Editor.AutoPairs
+	}
+	if src.Editor.Indent.After != nil {
+		dst.Editor.Indent.After = src.Editor.Indent.After
+	}
+	if src.Editor.Zen.Width != 0 {
+		dst.Editor.Zen.Width = src.Editor.Zen.Width
+	}
+	if meta.IsDefined("editor", "autosave", "swap") {
+		dst.Editor.Autosave.Swap = src.Editor.Autosave.Swap
+	}
+	if src.Editor.Autosave.Interval != 0 {
+		dst.Editor.Autosave.Interval = src.Editor.Autosave.Interval
+	}
+	if meta.IsDefined("editor", "autosave", "backup") {
+		dst.Editor.Autosave.Backup = src.Editor.Autosave.Backup
+	}
+	for fileType, lang := range src.Languages {
+		dst.Languages[fileType] = lang
+	}
+	for alias, name := range src.Aliases {
+		dst.Aliases[alias] = name
 	}
-	for key, action := range src.Keymap.Insert {
-		dst.Keymap.Insert[key] = action
+	// the layout moves the defaults; the file's own bindings still win
+	if src.Keymap.Layout != "" {
+		dst.Keymap.Layout = src.Keymap.Layout
+		dst.Keymap.Normal = applyLayout(dst.Keymap.Normal, src.Keymap.Layout)
 	}
+	if meta.IsDefined("keys", "timeout") {
+		dst.Keymap.Timeout = src.Keymap.Timeout
+	}
+	mergeKeyMap(dst.Keymap.Normal, src.Keymap.Normal)
+	mergeKeyMap(dst.Keymap.Insert, src.Keymap.Insert)
 }
 
 // validateAndFixConfig validates and ensures the values are in a usable state.
@@ -130,12 +275,77 @@ func validateAndFixConfig(cfg *Config) []string {
 	// Validate Editor Config
 	editor := &cfg.Editor
 
+	// Validate TabWidth
+	if editor.TabWidth <= 0 {
+		errors = append(errors, fmt.Sprintf("Invalid tab-width option: %d", editor.TabWidth))
+		editor.TabWidth = 4
+	}
+
+	// Validate TextWidth
+	if editor.TextWidth < 0 {
+		errors = append(errors, fmt.Sprintf("Invalid text-width option: %d", editor.TextWidth))
+		editor.TextWidth = 0
+	}
+
+	// Validate Zen
+	if editor.Zen.Width <= 0 {
+		errors = append(errors, fmt.Sprintf("Invalid zen width option: %d", editor.Zen.Width))
+		editor.Zen.Width = 80
+	}
+
+	// Validate Autosave
+	if editor.Autosave.Interval <= 0 {
+		errors = append(errors, fmt.Sprintf("Invalid autosave interval option: %d", editor.Autosave.Interval))
+		editor.Autosave.Interval = 4
+	}
+
+	// Validate AutoPairs
+	editor.AutoPairs = filterValidAutoPairs(editor.AutoPairs, "auto-pairs", &errors)
+
+	// Validate Languages
+	for fileType, lang := range cfg.Languages {
+		if lang.TabWidth < 0 {
+			errors = append(errors, fmt.Sprintf("Invalid tab-width option for %s: %d", fileType, lang.TabWidth))
+			lang.TabWidth = 0
+		}
+		lang.AutoPairs = filterValidAutoPairs(lang.AutoPairs, "auto-pairs for "+fileType, &errors)
+		if lang.BlockComment != nil && (len(lang.BlockComment) != 2 || lang.BlockComment[0] == "" || lang.BlockComment[1] == "") {
+			errors = append(errors, fmt.Sprintf("Invalid block-comment option for %s: %q", fileType, lang.BlockComment))
+			lang.BlockComment = nil
+		}
+		cfg.Languages[fileType] = lang
+	}
+
+	// Validate Aliases
+	for alias, name := range cfg.Aliases {
+		if name == "" || name == alias {
+			errors = append(errors, fmt.Sprintf("Invalid alias %s: %q", alias, name))
+			delete(cfg.Aliases, alias)
+		}
+	}
+
 	// Validate LineNumber
 	if !editor.LineNumber.IsValid() {
 		errors = append(errors, fmt.Sprintf("Invalid line-number option: %s", editor.LineNumber))
 		editor.LineNumber = LineNumberRelative // Reset to default
 	}
 
+	// Validate Symlinks
+	if !editor.Symlinks.IsValid() {
+		errors = append(errors, fmt.Sprintf("Invalid symlinks option: %s", editor.Symlinks))
+		editor.Symlinks = SymlinkWriteThrough
+	}
+
+	if utf8.RuneCountInString(editor.EOFMarker) > 1 {
+		errors = append(errors, fmt.Sprintf("Invalid eof-marker option: %s", editor.EOFMarker))
+		editor.EOFMarker = "~"
+	}
+
+	if editor.MaxFPS < 0 {
+		errors = append(errors, fmt.Sprintf("Invalid max-fps option: %d", editor.MaxFPS))
+		editor.MaxFPS = defaultConfig().Editor.MaxFPS
+	}
+
 	// Validate CursorShape
 	if !editor.CursorShape.Insert.IsValid() {
 		errors = append(errors, fmt.Sprintf("Invalid cursor-shape insert option: %s", editor.CursorShape.Insert))
@@ -146,9 +356,32 @@ func validateAndFixConfig(cfg *Config) []string {
 		editor.CursorShape.Normal = CursorBlock
 	}
 
+	if !cfg.HasTheme(editor.Theme) {
+		errors = append(errors, fmt.Sprintf("Invalid theme option: %s", editor.Theme))
+		editor.Theme = ""
+	}
+
+	// Validate keyboard layout
+	if cfg.Keymap.Layout != "" && !IsValidLayout(cfg.Keymap.Layout) {
+		errors = append(errors, fmt.Sprintf("Invalid keys layout option: %s", cfg.Keymap.Layout))
+		cfg.Keymap.Layout = ""
+	}
+	if cfg.Keymap.Timeout < 0 {
+		errors = append(errors, fmt.Sprintf("Invalid keys timeout option: %d", cfg.Keymap.Timeout))
+		cfg.Keymap.Timeout = defaultKeymap().Timeout
+	}
+
 	// Validate Gutters
 	editor.Gutters = filterValidGutters(editor.Gutters, &errors)
 
+	// Validate GutterClick
+	for gutter, action := range editor.GutterClick {
+		if !gutter.IsValid() || !action.IsValid() {
+			errors = append(errors, fmt.Sprintf("Invalid gutter-click option: %s = %s", gutter, action))
+			delete(editor.GutterClick, gutter)
+		}
+	}
+
 	// Validate StatusBar
 	validateStatusBarConfig(&editor.StatusBar, &errors)
 
@@ -169,7 +402,24 @@ func filterValidGutters(gutters []GutterOption, errors *[]string) []GutterOption
 		}
 	}
 	if len(valid) == 0 {
-		return []GutterOption{GutterSpacer, GutterLineNumbers, GutterSpacer} // Default
+		return []GutterOption{GutterDiagnostics, GutterLineNumbers, GutterSpacer} // Default
+	}
+	return valid
+}
+
+// filterValidAutoPairs drops the pairs that aren't an opening and a closing character; none set
+// stays nil, so a language without the option falls back to the editor's.
+func filterValidAutoPairs(pairs []string, option string, errors *[]string) []string {
+	if pairs == nil {
+		return nil
+	}
+	valid := make([]string, 0, len(pairs))
+	for _, pair := range pairs {
+		if utf8.RuneCountInString(pair) == 2 {
+			valid = append(valid, pair)
+		} else {
+			*errors = append(*errors, fmt.Sprintf("Invalid %s option: %q", option, pair))
+		}
 	}
 	return valid
 }
@@ -6,11 +6,12 @@ type LineNumberOption string
 const (
 	LineNumberAbsolute LineNumberOption = "absolute"
 	LineNumberRelative LineNumberOption = "relative"
+	LineNumberNone     LineNumberOption = "none" // hides the gutter
 )
 
 func (o LineNumberOption) IsValid() bool {
 	switch o {
-	case LineNumberAbsolute, LineNumberRelative:
+	case LineNumberAbsolute, LineNumberRelative, LineNumberNone:
 		return true
 	default:
 		return false
@@ -46,14 +47,55 @@ type CursorShapeConfig struct {
 type GutterOption string
 
 const (
-	GutterDiff        GutterOption = "diff"
+	GutterDiagnostics GutterOption = "diagnostics" // a sign on lines a language server found problems on
+	GutterDiff        GutterOption = "diff"        // a sign on lines changed since the last commit
+	GutterSigns       GutterOption = "signs"       // the most important sign of any kind, marks included
 	GutterLineNumbers GutterOption = "line-numbers"
 	GutterSpacer      GutterOption = "spacer"
 )
 
 func (o GutterOption) IsValid() bool {
 	switch o {
-	case GutterDiff, GutterLineNumbers, GutterSpacer:
+	case GutterDiagnostics, GutterDiff, GutterSigns, GutterLineNumbers, GutterSpacer:
+		return true
+	default:
+		return false
+	}
+}
+
+// GutterAction is what clicking a gutter column does.
+type GutterAction string
+
+const (
+	GutterActionNone           GutterAction = "none"
+	GutterActionMoveCursor     GutterAction = "move-cursor"     // put the cursor at the start of the line
+	GutterActionSelectLine     GutterAction = "select-line"     // select the line
+	GutterActionShowDiagnostic GutterAction = "show-diagnostic" // show the message of the line's problem
+)
+
+func (a GutterAction) IsValid() bool {
+	switch a {
+	case GutterActionNone, GutterActionMoveCursor, GutterActionSelectLine, GutterActionShowDiagnostic:
+		return true
+	default:
+		return false
+	}
+}
+
+// GutterClickConfig maps gutter columns to what clicking them does.
+type GutterClickConfig map[GutterOption]GutterAction
+
+// SymlinkPolicy defines how saving a file opened through a symlink treats the link.
+type SymlinkPolicy string
+
+const (
+	SymlinkWriteThrough SymlinkPolicy = "write-through" // write to the link target, keeping the link
+	SymlinkReplace      SymlinkPolicy = "replace"       // replace the link with a regular file
+)
+
+func (p SymlinkPolicy) IsValid() bool {
+	switch p {
+	case SymlinkWriteThrough, SymlinkReplace:
 		return true
 	default:
 		return false
@@ -69,19 +111,28 @@ const (
 	SectionFileAbsPath      StatusBarOption = "file-absolute-path"
 	SectionFileModified     StatusBarOption = "file-modified"
 	SectionFileEncoding     StatusBarOption = "file-encoding"
+	SectionLineEnding       StatusBarOption = "line-ending" // LF or CRLF, and whether the file lacks a final newline
 	SectionFileType         StatusBarOption = "file-type"
-	SectionVersionControl   StatusBarOption = "version-control"
+	SectionVersionControl   StatusBarOption = "version-control" // git branch of the file
+	SectionDiffStats        StatusBarOption = "diff-stats"      // lines added and removed since the last commit
+	SectionDiagnostics      StatusBarOption = "diagnostics"     // errors and warnings from language servers
 	SectionCursorPos        StatusBarOption = "cursor-position"
 	SectionLineCount        StatusBarOption = "line-count"
 	SectionCursorPercentage StatusBarOption = "cursor-percentage"
+	SectionRecording        StatusBarOption = "recording"    // register a macro is being recorded into
+	SectionSaving           StatusBarOption = "saving"       // shown while a save is being written
+	SectionPendingKeys      StatusBarOption = "pending-keys" // count and keys of an unfinished command
+	SectionWordCount        StatusBarOption = "word-count"   // live word count, shown for prose files
 	SectionSpacer           StatusBarOption = "spacer"
 )
 
 func (o StatusBarOption) IsValid() bool {
 	switch o {
 	case SectionMode, SectionFileName, SectionFileAbsPath, SectionFileModified,
-		SectionFileEncoding, SectionFileType, SectionVersionControl,
-		SectionCursorPos, SectionLineCount, SectionCursorPercentage, SectionSpacer:
+		SectionFileEncoding, SectionLineEnding, SectionFileType, SectionVersionControl,
+		SectionCursorPos, SectionLineCount, SectionCursorPercentage, SectionRecording,
+		SectionSaving, SectionPendingKeys, SectionWordCount, SectionDiffStats, SectionDiagnostics,
+		SectionSpacer:
 		return true
 	default:
 		return false
@@ -90,8 +141,9 @@ func (o StatusBarOption) IsValid() bool {
 
 // StatusBarModeConfig represents the mode names.
 type StatusBarModeConfig struct {
-	Normal string `toml:"normal"`
-	Insert string `toml:"insert"`
+	Normal  string `toml:"normal"`
+	Insert  string `toml:"insert"`
+	Command string `toml:"command"`
 }
 
 // StatusBarConfig represents status bar configurations.
@@ -102,12 +154,63 @@ type StatusBarConfig struct {
 	Mode   StatusBarModeConfig `toml:"mode"`
 }
 
+// SearchConfig controls how searches match and move.
+type SearchConfig struct {
+	IgnoreCase bool `toml:"ignore-case"` // match regardless of case
+	SmartCase  bool `toml:"smart-case"`  // match case-sensitively when the pattern has uppercase
+	WrapScan   bool `toml:"wrap-scan"`   // wrap around the ends of the buffer
+	Regex      bool `toml:"regex"`       // patterns are regular expressions instead of literal text
+}
+
+// IndentConfig controls the indentation new lines start with.
+type IndentConfig struct {
+	Auto  bool     `toml:"auto"`  // start a new line at the indentation of the one it was opened from
+	After []string `toml:"after"` // line endings that make the new line a level deeper, e.g. "{"
+}
+
+// ZenConfig controls the distraction-free writing layout.
+type ZenConfig struct {
+	Width int `toml:"width"` // width of the centered text column
+}
+
+// AutosaveConfig controls the swap files modified buffers are written to for crash recovery, and
+// the backups written before saving.
+type AutosaveConfig struct {
+	Swap     bool `toml:"swap"`     // write modified buffers to swap files in the background
+	Interval int  `toml:"interval"` // seconds between swap file writes
+	Backup   bool `toml:"backup"`   // copy a file to "<name>~" before saving over it
+}
+
 // EditorConfig represents editor-specific configurations
 type EditorConfig struct {
-	ScrollPadding int               `toml:"scroll-padding"` // padding around edge of screen
-	LineNumber    LineNumberOption  `toml:"line-number"`    // absolute or relative
-	CursorShape   CursorShapeConfig `toml:"cursor-shape"`
-	BufferLine    bool              `toml:"buffer-line"` // whether to render buffer line
-	Gutters       []GutterOption    `toml:"gutters"`
-	StatusBar     StatusBarConfig   `toml:"status-bar"`
+	ScrollPadding       int               `toml:"scroll-padding"` // padding around edge of screen
+	TabWidth            int               `toml:"tab-width"`      // cells between tab stops
+	TextWidth           int               `toml:"text-width"`     // width gq reflows at, 0 for the default
+	LineNumber          LineNumberOption  `toml:"line-number"`    // absolute, relative or none
+	CursorShape         CursorShapeConfig `toml:"cursor-shape"`
+	CursorLine          bool              `toml:"cursor-line"`           // give the cursor line a background of its own
+	CursorColumn        bool              `toml:"cursor-column"`         // and the cursor column too
+	BufferLine          bool              `toml:"buffer-line"`           // whether to render buffer line
+	EOFMarker           string            `toml:"eof-marker"`            // one character drawn on rows past the end of the buffer, empty for none
+	Scrollbar           bool              `toml:"scrollbar"`             // a column beside each window showing the part of the buffer in view
+	SoftWrap            bool              `toml:"soft-wrap"`             // wrap long lines at the view width
+	DisplayLineMovement bool              `toml:"display-line-movement"` // j/k move by display row like gj/gk
+	SmoothScroll        bool              `toml:"smooth-scroll"`         // animate scrolling over a few frames
+	MaxFPS              int               `toml:"max-fps"`               // frames drawn a second at most, keys coming faster are drawn together; 0 for no cap
+	Accessibility       bool              `toml:"accessibility"`         // high contrast, no animations, ASCII markers
+	Theme               string            `toml:"theme"`                 // theme file in the themes directory, without ".toml"; empty for the built-in theme
+	Title               string            `toml:"title"`                 // terminal title template, empty leaves the title alone
+	Hyperlinks          bool              `toml:"hyperlinks"`            // make URLs in buffers clickable (OSC 8)
+	Symlinks            SymlinkPolicy     `toml:"symlinks"`              // write-through or replace
+	Views               bool              `toml:"views"`                 // remember the cursor, scroll and :set options per file
+	Mouse               bool              `toml:"mouse"`                 // clicks in the gutter run the gutter-click actions
+	ReadOnly            bool              `toml:"-"`                     // buffers start read-only, set by athena -R; see ui's exSet for ":set readonly"
+	AutoPairs           []string          `toml:"auto-pairs"`            // brackets and quotes typed in pairs in insert mode, e.g. "()"
+	Gutters             []GutterOption    `toml:"gutters"`
+	GutterClick         GutterClickConfig `toml:"gutter-click"` // action per gutter column, when the mouse is enabled
+	StatusBar           StatusBarConfig   `toml:"status-bar"`
+	Search              SearchConfig      `toml:"search"`
+	Indent              IndentConfig      `toml:"indent"`
+	Zen                 ZenConfig         `toml:"zen"`
+	Autosave            AutosaveConfig    `toml:"autosave"`
 }
@@ -7,12 +7,55 @@ type KeyMap map[string]KeyAction
 
 // KeymapConfig represents key mappings
 type KeymapConfig struct {
-	Normal KeyMap `toml:"normal"`
-	Insert KeyMap `toml:"insert"`
+	Layout  string `toml:"layout"`  // keyboard layout preset the defaults are moved for
+	Timeout int    `toml:"timeout"` // milliseconds to wait for the next key of a sequence, 0 waits forever
+	Normal  KeyMap `toml:"normal"`
+	Insert  KeyMap `toml:"insert"`
+}
+
+// ResolveAlias returns the named command an alias from the [aliases] table stands for, or name
+// itself when it isn't an alias; aliases don't name other aliases.
+func (c *Config) ResolveAlias(name string) string {
+	if target, ok := c.Aliases[name]; ok {
+		return target
+	}
+	return name
+}
+
+// mergeKeyMap adds the bindings of src to dst, replacing those bound to the same keys.
+//
+//	Key sequences merge key by key, so binding "gx" keeps the other "g" bindings; binding
+//	a sequence's prefix to an action replaces the whole sequence.
+func mergeKeyMap(dst, src KeyMap) {
+	for key, action := range src {
+		dst[key] = mergeKeyAction(dst[key], action)
+	}
+}
+
+// mergeKeyAction returns action merged over the existing binding of the same key.
+func mergeKeyAction(existing, action KeyAction) KeyAction {
+	nested, ok := action.(map[string]interface{})
+	if !ok {
+		return action
+	}
+	current, ok := existing.(map[string]interface{})
+	if !ok {
+		return nested
+	}
+
+	merged := make(map[string]interface{}, len(current)+len(nested))
+	for key, a := range current {
+		merged[key] = a
+	}
+	for key, a := range nested {
+		merged[key] = mergeKeyAction(merged[key], a)
+	}
+	return merged
 }
 
 func defaultKeymap() KeymapConfig {
 	return KeymapConfig{
+		Timeout: 1000,
 		Normal: map[string]KeyAction{
 			"i": "enter_insert_mode",
 			"j": "move_down",
@@ -21,22 +64,126 @@ func defaultKeymap() KeymapConfig {
 			"l": "move_right",
 			"w": "move_next_word",
 			"b": "move_prev_word",
-			"g": map[string]string{
+			":": "enter_command_mode",
+			"/": "search_forward",
+			"?": "search_backward",
+			"n": "search_next",
+			"N": "search_prev",
+			"*": "search_word_forward",
+			"#": "search_word_backward",
+			"q": "record_macro",
+			"@": "play_macro",
+			"m": "set_mark",
+			"'": "jump_to_mark_line",
+			"`": "jump_to_mark",
+			"%": "match_pair",
+			"p": "paste_after",
+			"P": "paste_before",
+			"x": "delete_char",
+			"X": "delete_char_before",
+			"r": "replace_char",
+			"~": "toggle_case",
+			"d": "operator_delete",
+			"c": "operator_change",
+			"y": "operator_yank",
+			">": "operator_indent",
+			"<": "operator_dedent",
+			"J": "join_lines",
+			"S": "surround_selection",
+			"G": "go_to_bottom",
+			".": "repeat_change",
+			"u": "undo",
+			"K": "hover",
+			"]": map[string]interface{}{
+				"p": "paste_after_indented",
+				"c": "next_hunk",
+				"q": "quickfix_next",
+			},
+			"[": map[string]interface{}{
+				"p": "paste_before_indented",
+				"c": "prev_hunk",
+				"q": "quickfix_prev",
+			},
+			"<space>": map[string]interface{}{
+				"/": "grep_prompt",
+			},
+			"\"": "select_register", // the register the next paste takes from, e.g. "2p
+			"g": map[string]interface{}{
 				"g": "go_to_top",
 				"e": "go_to_bottom",
 				"h": "go_to_line_start",
 				"l": "go_to_line_end",
+				"j": "move_display_down",
+				"k": "move_display_up",
+				"n": "buffer_next",
+				"p": "buffer_prev",
+				"t": "tab_next",
+				"T": "tab_prev",
+				"q": "reflow_paragraph",
+				"c": "operator_comment",
+				"N": "toggle_line_numbers",
+				"R": "toggle_relative_line_numbers",
+				";": "prev_change",
+				",": "next_change",
+				"d": "go_to_definition",
+
+				"<c-g>": "count_text",
+			},
+			"<c-w>": map[string]interface{}{
+				"s": "window_split",
+				"v": "window_vsplit",
+				"h": "window_left",
+				"j": "window_down",
+				"k": "window_up",
+				"l": "window_right",
+				"w": "window_next",
+				"c": "window_close",
+				"o": "window_only",
+				"+": "window_grow",
+				"-": "window_shrink",
+				">": "window_wider",
+				"<": "window_narrower",
+				"=": "window_equalize",
+				"z": "window_zoom",
+				"!": "output_toggle",
+				"J": "output_scroll_down",
+				"K": "output_scroll_up",
 			},
 			"<left>":  "move_left",
 			"<right>": "move_right",
 			"<up>":    "move_up",
 			"<down>":  "move_down",
+			"<c-d>":   "scroll_half_page_down",
+			"<c-u>":   "scroll_half_page_up",
+			"<c-o>":   "jump_older",
+			"<c-r>":   "redo",
+			"<tab>":   "jump_newer", // the same key as <c-i> in terminals
+			"<esc>":   "collapse_selection",
+
+			"<s-left>":  "select_left",
+			"<s-right>": "select_right",
+			"<s-up>":    "select_up",
+			"<s-down>":  "select_down",
 		},
 		Insert: map[string]KeyAction{
-			"<esc>": "enter_normal_mode",
-			"<cr>":  "new_line",
-			"<bs>":  "delete_backwards",
-			"<del>": "delete_forward",
+			"<esc>":   "enter_normal_mode",
+			"<cr>":    "new_line",
+			"<bs>":    "delete_backwards",
+			"<del>":   "delete_forward",
+			"<left>":  "move_left",
+			"<right>": "move_right",
+			"<up>":    "move_up",
+			"<down>":  "move_down",
+			"<c-n>":   "complete",
+			"<c-v>":   "insert_literal",
+			"<c-w>":   "delete_word_before",
+			"<c-u>":   "delete_line_start",
+			"<a-d>":   "delete_word_after",
+
+			"<s-left>":  "select_left",
+			"<s-right>": "select_right",
+			"<s-up>":    "select_up",
+			"<s-down>":  "select_down",
 		},
 	}
 }
@@ -0,0 +1,50 @@
+package config
+
+import (
+	"reflect"
+	"testing"
+)
+
+func TestMergeKeyMap(t *testing.T) {
+	tests := []struct {
+		name string
+		src  KeyMap
+		key  string
+		want KeyAction
+	}{
+		{name: "new key", src: KeyMap{"x": "delete_forward"}, key: "x", want: "delete_forward"},
+		{name: "rebound key", src: KeyMap{"w": "move_prev_word"}, key: "w", want: "move_prev_word"},
+		{name: "untouched key", src: KeyMap{"x": "delete_forward"}, key: "w", want: "move_next_word"},
+		{
+			name: "sequence keeps defaults",
+			src:  KeyMap{"g": map[string]interface{}{"x": "count_text"}},
+			key:  "g",
+			want: map[string]interface{}{"x": "count_text", "g": "go_to_top"},
+		},
+		{
+			name: "prefix replaces sequence",
+			src:  KeyMap{"g": "go_to_top"},
+			key:  "g",
+			want: "go_to_top",
+		},
+		{
+			name: "new sequence",
+			src:  KeyMap{"<space>": map[string]interface{}{"f": "search_forward"}},
+			key:  "<space>",
+			want: map[string]interface{}{"f": "search_forward"},
+		},
+	}
+
+	for _, tt := range tests {
+		t.Run(tt.name, func(t *testing.T) {
+			dst := KeyMap{
+				"w": "move_next_word",
+				"g": map[string]interface{}{"g": "go_to_top"},
+			}
+			mergeKeyMap(dst, tt.src)
+			if got := dst[tt.key]; !reflect.DeepEqual(got, tt.want) {
+				t.Errorf("merged[%q] = %v, want %v", tt.key, got, tt.want)
+			}
+		})
+	}
+}
@@ -0,0 +1,63 @@
+package config
+
+import "github.com/lg2m/athena/internal/editor/format"
+
+// LanguageOptions holds settings that differ between languages.
+type LanguageOptions struct {
+	IndentTabs     bool     `toml:"indent-tabs"`     // indent with tabs instead of spaces
+	TabWidth       int      `toml:"tab-width"`       // overrides editor.tab-width when set
+	IndentAfter    []string `toml:"indent-after"`    // overrides editor.indent.after when set
+	AutoPairs      []string `toml:"auto-pairs"`      // overrides editor.auto-pairs when set, [] for none
+	Comment        string   `toml:"comment"`         // starts a line comment, e.g. "//"; gc comments lines with it
+	BlockComment   []string `toml:"block-comment"`   // start and end of a block comment, e.g. ["/*", "*/"]
+	LanguageServer []string `toml:"language-server"` // command and arguments of a server speaking LSP over stdio
+}
+
+// Indent returns how code of fileType is indented, falling back to the editor settings.
+func (c *Config) Indent(fileType string) format.Indent {
+	in := format.Indent{TabWidth: c.Editor.TabWidth}
+	if lang, ok := c.Languages[fileType]; ok {
+		in.Tabs = lang.IndentTabs
+		if lang.TabWidth > 0 {
+			in.TabWidth = lang.TabWidth
+		}
+	}
+	return in
+}
+
+// IndentAfter returns the line endings after which a new line of fileType is indented a level
+// deeper, falling back to the editor settings.
+func (c *Config) IndentAfter(fileType string) []string {
+	if lang, ok := c.Languages[fileType]; ok && lang.IndentAfter != nil {
+		return lang.IndentAfter
+	}
+	return c.Editor.Indent.After
+}
+
+// AutoPairs returns the brackets and quotes typed in pairs in code of fileType, falling back to the
+// editor settings.
+func (c *Config) AutoPairs(fileType string) []string {
+	if lang, ok := c.Languages[fileType]; ok && lang.AutoPairs != nil {
+		return lang.AutoPairs
+	}
+	return c.Editor.AutoPairs
+}
+
+// Comment returns the strings code of fileType is commented with: those athena knows for it, with
+// the ones set for the language over them.
+func (c *Config) Comment(fileType string) format.Comment {
+	comment, _ := format.CommentFor(fileType)
+	lang := c.Languages[fileType]
+	if lang.Comment != "" {
+		comment.Line = lang.Comment
+	}
+	if len(lang.BlockComment) == 2 {
+		comment.BlockStart, comment.BlockEnd = lang.BlockComment[0], lang.BlockComment[1]
+	}
+	return comment
+}
+
+// LanguageServer returns the command running the language server for fileType, if one is set.
+func (c *Config) LanguageServer(fileType string) []string {
+	return c.Languages[fileType].LanguageServer
+}
@@ -0,0 +1,71 @@
+package config
+
+import "strings"
+
+// layoutPresets move the default normal mode keys for other keyboard layouts.
+//
+//	Each preset maps a qwerty key to the key in the same role on that layout. Presets are
+//	permutations, so a displaced binding takes over the key that replaced it; uppercase
+//	keys follow their lowercase counterparts.
+var layoutPresets = map[string]map[string]string{
+	"qwerty": {},
+	"colemak": {
+		"j": "n", "k": "e", "l": "i",
+		"n": "j", "e": "k", "i": "l",
+	},
+	"dvorak": {
+		"h": "d", "j": "h", "k": "t", "l": "n",
+		"d": "j", "t": "k", "n": "l",
+	},
+	"workman": {
+		"h": "y", "j": "n", "k": "e", "l": "o",
+		"y": "h", "n": "j", "e": "k", "o": "l",
+	},
+}
+
+// IsValidLayout reports whether name is a known keyboard layout preset.
+func IsValidLayout(name string) bool {
+	_, ok := layoutPresets[name]
+	return ok
+}
+
+// applyLayout returns keymap with its keys moved for the named layout preset.
+func applyLayout(keymap KeyMap, layout string) KeyMap {
+	preset := layoutPresets[layout]
+	if len(preset) == 0 {
+		return keymap
+	}
+
+	remapped := make(KeyMap, len(keymap))
+	for key, action := range keymap {
+		remapped[remapKey(key, preset)] = remapNested(action, preset)
+	}
+	return remapped
+}
+
+// remapNested moves the keys of a nested key sequence, leaving plain actions untouched.
+func remapNested(action KeyAction, preset map[string]string) KeyAction {
+	nested, ok := action.(map[string]interface{})
+	if !ok {
+		return action
+	}
+
+	remapped := make(map[string]interface{}, len(nested))
+	for key, a := range nested {
+		remapped[remapKey(key, preset)] = remapNested(a, preset)
+	}
+	return remapped
+}
+
+// remapKey returns the key that takes the role of key on the layout.
+func remapKey(key string, preset map[string]string) string {
+	if to, ok := preset[key]; ok {
+		return to
+	}
+	if lower := strings.ToLower(key); lower != key {
+		if to, ok := preset[lower]; ok {
+			return strings.ToUpper(to)
+		}
+	}
+	return key
+}
@@ -0,0 +1,45 @@
+package config
+
+import "testing"
+
+func TestApplyLayout(t *testing.T) {
+	tests := []struct {
+		name   string
+		layout string
+		key    string
+		want   string
+	}{
+		{name: "qwerty unchanged", layout: "qwerty", key: "j", want: "move_down"},
+		{name: "colemak movement", layout: "colemak", key: "n", want: "move_down"},
+		{name: "colemak displaced", layout: "colemak", key: "j", want: "search_next"},
+		{name: "colemak uppercase", layout: "colemak", key: "J", want: "search_prev"},
+		{name: "colemak insert", layout: "colemak", key: "l", want: "enter_insert_mode"},
+		{name: "dvorak movement", layout: "dvorak", key: "t", want: "move_up"},
+		{name: "workman movement", layout: "workman", key: "o", want: "move_right"},
+		{name: "untouched key", layout: "workman", key: ":", want: "enter_command_mode"},
+	}
+
+	for _, tt := range tests {
+		t.Run(tt.name, func(t *testing.T) {
+			keymap := applyLayout(defaultKeymap().Normal, tt.layout)
+			if got := keymap[tt.key]; got != tt.want {
+				t.Errorf("applyLayout(%s)[%q] = %v, want %q", tt.layout, tt.key, got, tt.want)
+			}
+		})
+	}
+}
+
+func TestApplyLayoutNested(t *testing.T) {
+	keymap := applyLayout(defaultKeymap().Normal, "colemak")
+
+	g, ok := keymap["g"].(map[string]interface{})
+	if !ok {
+		t.Fatalf("g is %T, want nested map", keymap["g"])
+	}
+	if got := g["n"]; got != "move_display_down" {
+		t.Errorf("gn = %v, want move_display_down", got)
+	}
+	if got := g["j"]; got != "buffer_next" {
+		t.Errorf("gj = %v, want buffer_next", got)
+	}
+}
@@ -0,0 +1,230 @@
+package config
+
+import (
+	"errors"
+	"fmt"
+	"strconv"
+	"strings"
+)
+
+var (
+	ErrUnknownOption   = errors.New("unknown option")
+	ErrInvalidArgument = errors.New("invalid argument")
+)
+
+// editorOption describes an editor setting that can be changed at runtime with ":set".
+//
+//	Exactly one of flag, number or get and set is given; get and set derive a flag from other settings.
+type editorOption struct {
+	names     []string // full name first, followed by abbreviations
+	flag      func(cfg *EditorConfig) *bool
+	number    func(cfg *EditorConfig) *int
+	allowZero bool // zero is a valid number, usually meaning "off"
+	buffer    bool // kept by each buffer, only passed through the config by ":set", so never synced
+	get       func(cfg *EditorConfig) bool
+	set       func(cfg *EditorConfig, on bool)
+}
+
+var editorOptions = []editorOption{
+	{
+		names: []string{"ignorecase", "ic"},
+		flag:  func(cfg *EditorConfig) *bool { return &cfg.Search.IgnoreCase },
+	},
+	{
+		names: []string{"smartcase", "scs"},
+		flag:  func(cfg *EditorConfig) *bool { return &cfg.Search.SmartCase },
+	},
+	{
+		names: []string{"wrapscan", "ws"},
+		flag:  func(cfg *EditorConfig) *bool { return &cfg.Search.WrapScan },
+	},
+	{
+		names: []string{"regex", "re"},
+		flag:  func(cfg *EditorConfig) *bool { return &cfg.Search.Regex },
+	},
+	{
+		names: []string{"number", "nu"},
+		get:   func(cfg *EditorConfig) bool { return cfg.LineNumber != LineNumberNone },
+		set: func(cfg *EditorConfig, on bool) {
+			switch {
+			case on && cfg.LineNumber == LineNumberNone:
+				cfg.LineNumber = LineNumberAbsolute
+			case !on && cfg.LineNumber == LineNumberAbsolute:
+				// relative numbers stay on by themselves, like vim
+				cfg.LineNumber = LineNumberNone
+			}
+		},
+	},
+	{
+		names: []string{"relativenumber", "rnu"},
+		get:   func(cfg *EditorConfig) bool { return cfg.LineNumber == LineNumberRelative },
+		set: func(cfg *EditorConfig, on bool) {
+			switch {
+			case on:
+				cfg.LineNumber = LineNumberRelative
+			case cfg.LineNumber == LineNumberRelative:
+				cfg.LineNumber = LineNumberAbsolute
+			}
+		},
+	},
+	{
+		names: []string{"autoindent", "ai"},
+		flag:  func(cfg *EditorConfig) *bool { return &cfg.Indent.Auto },
+	},
+	{
+		names: []string{"smoothscroll", "sms"},
+		flag:  func(cfg *EditorConfig) *bool { return &cfg.SmoothScroll },
+	},
+	{
+		names: []string{"cursorline", "cul"},
+		flag:  func(cfg *EditorConfig) *bool { return &cfg.CursorLine },
+	},
+	{
+		names: []string{"cursorcolumn", "cuc"},
+		flag:  func(cfg *EditorConfig) *bool { return &cfg.CursorColumn },
+	},
+	{
+		names: []string{"scrollbar"},
+		flag:  func(cfg *EditorConfig) *bool { return &cfg.Scrollbar },
+	},
+	{
+		names: []string{"wrap"},
+		flag:  func(cfg *EditorConfig) *bool { return &cfg.SoftWrap },
+	},
+	{
+		names:  []string{"readonly", "ro"},
+		flag:   func(cfg *EditorConfig) *bool { return &cfg.ReadOnly },
+		buffer: true,
+	},
+	{
+		names:  []string{"tabstop", "ts"},
+		number: func(cfg *EditorConfig) *int { return &cfg.TabWidth },
+	},
+	{
+		names:     []string{"textwidth", "tw"},
+		number:    func(cfg *EditorConfig) *int { return &cfg.TextWidth },
+		allowZero: true,
+	},
+}
+
+// SetOption applies a single ":set" expression to cfg, returning a message to show if the option was queried.
+//
+//	Supported forms are "name", "noname", "invname", "name!", "name?" and "name=value".
+func SetOption(cfg *EditorConfig, expr string) (string, error) {
+	name, value, hasValue := strings.Cut(expr, "=")
+
+	query := strings.HasSuffix(name, "?")
+	name = strings.TrimSuffix(name, "?")
+	toggle := strings.HasSuffix(name, "!")
+	name = strings.TrimSuffix(name, "!")
+
+	opt, ok := findOption(name)
+	negate, invert := false, false
+	if !ok && strings.HasPrefix(name, "no") {
+		opt, ok = findOption(strings.TrimPrefix(name, "no"))
+		negate = ok
+	}
+	if !ok && strings.HasPrefix(name, "inv") {
+		opt, ok = findOption(strings.TrimPrefix(name, "inv"))
+		invert = ok
+	}
+	if !ok {
+		return "", fmt.Errorf("%w: %s", ErrUnknownOption, name)
+	}
+
+	if opt.number == nil {
+		if hasValue {
+			return "", fmt.Errorf("%w: %s", ErrInvalidArgument, expr)
+		}
+
+		get, set := opt.accessors()
+		switch {
+		case query:
+			if get(cfg) {
+				return opt.names[0], nil
+			}
+			return "no" + opt.names[0], nil
+		case toggle, invert:
+			set(cfg, !get(cfg))
+		default:
+			set(cfg, !negate)
+		}
+		return "", nil
+	}
+
+	number := opt.number(cfg)
+	if negate || invert || toggle {
+		return "", fmt.Errorf("%w: %s", ErrInvalidArgument, expr)
+	}
+	if query || !hasValue {
+		return fmt.Sprintf("%s=%d", opt.names[0], *number), nil
+	}
+
+	n, err := strconv.Atoi(value)
+	if err != nil || n < 0 || (n == 0 && !opt.allowZero) {
+		return "", fmt.Errorf("%w: %s", ErrInvalidArgument, expr)
+	}
+	*number = n
+	return "", nil
+}
+
+// SyncOptions changes the options of dst to those of src, returning the ":set" expressions that took
+// effect, e.g. "nowrap" or "tabstop=8"; replaying them on a copy of dst's old options gives src's.
+//
+//	Derived flags depend on each other, so a second pass applies what the first could not,
+//	e.g. "nonumber" only turns line numbers off once relative numbers are.
+func SyncOptions(dst, src *EditorConfig) []string {
+	var applied []string
+	for range 2 {
+		for _, opt := range editorOptions {
+			name := opt.names[0]
+			if opt.buffer {
+				continue
+			}
+			if opt.number != nil {
+				if *opt.number(dst) != *opt.number(src) {
+					*opt.number(dst) = *opt.number(src)
+					applied = append(applied, fmt.Sprintf("%s=%d", name, *opt.number(src)))
+				}
+				continue
+			}
+
+			get, set := opt.accessors()
+			want := get(src)
+			if get(dst) == want {
+				continue
+			}
+			set(dst, want)
+			if get(dst) != want {
+				continue
+			}
+			if !want {
+				name = "no" + name
+			}
+			applied = append(applied, name)
+		}
+	}
+	return applied
+}
+
+// accessors returns the functions reading and writing a flag option, plain or derived.
+func (opt editorOption) accessors() (get func(cfg *EditorConfig) bool, set func(cfg *EditorConfig, on bool)) {
+	if opt.flag != nil {
+		get = func(cfg *EditorConfig) bool { return *opt.flag(cfg) }
+		set = func(cfg *EditorConfig, on bool) { *opt.flag(cfg) = on }
+		return get, set
+	}
+	return opt.get, opt.set
+}
+
+// findOption looks up an option by its full name or abbreviation.
+func findOption(name string) (editorOption, bool) {
+	for _, opt := range editorOptions {
+		for _, n := range opt.names {
+			if n == name {
+				return opt, true
+			}
+		}
+	}
+	return editorOption{}, false
+}
@@ -0,0 +1,88 @@
+package config
+
+import (
+	"errors"
+	"reflect"
+	"testing"
+)
+
+func TestSetOption(t *testing.T) {
+	tests := []struct {
+		name    string
+		expr    string
+		check   func(cfg *EditorConfig) bool
+		wantMsg string
+		wantErr error
+	}{
+		{name: "enable flag", expr: "ignorecase", check: func(cfg *EditorConfig) bool { return cfg.Search.IgnoreCase }},
+		{name: "abbreviation", expr: "scs", check: func(cfg *EditorConfig) bool { return cfg.Search.SmartCase }},
+		{name: "disable flag", expr: "nows", check: func(cfg *EditorConfig) bool { return !cfg.Search.WrapScan }},
+		{name: "invert flag", expr: "invwrapscan", check: func(cfg *EditorConfig) bool { return !cfg.Search.WrapScan }},
+		{name: "toggle flag", expr: "ic!", check: func(cfg *EditorConfig) bool { return cfg.Search.IgnoreCase }},
+		{name: "query flag", expr: "ws?", wantMsg: "wrapscan"},
+		{name: "set number", expr: "ts=8", check: func(cfg *EditorConfig) bool { return cfg.TabWidth == 8 }},
+		{name: "query number", expr: "tabstop", wantMsg: "tabstop=4"},
+		{name: "invalid number", expr: "ts=abc", wantErr: ErrInvalidArgument},
+		{name: "derived flag", expr: "nornu", check: func(cfg *EditorConfig) bool { return cfg.LineNumber == LineNumberAbsolute }},
+		{name: "query derived flag", expr: "nu?", wantMsg: "number"},
+		{name: "value on flag", expr: "ic=1", wantErr: ErrInvalidArgument},
+		{name: "unknown", expr: "foo", wantErr: ErrUnknownOption},
+	}
+
+	for _, tt := range tests {
+		t.Run(tt.name, func(t *testing.T) {
+			cfg := defaultConfig().Editor
+			msg, err := SetOption(&cfg, tt.expr)
+			if !errors.Is(err, tt.wantErr) {
+				t.Fatalf("SetOption(%q) error = %v, want %v", tt.expr, err, tt.wantErr)
+			}
+			if msg != tt.wantMsg {
+				t.Errorf("SetOption(%q) message = %q, want %q", tt.expr, msg, tt.wantMsg)
+			}
+			if tt.check != nil && !tt.check(&cfg) {
+				t.Errorf("SetOption(%q) did not apply", tt.expr)
+			}
+		})
+	}
+}
+
+func TestSyncOptions(t *testing.T) {
+	tests := []struct {
+		name   string
+		change []string
+		want   []string
+	}{
+		{name: "unchanged", want: nil},
+		{name: "flag", change: []string{"nowrapscan", "ic"}, want: []string{"ignorecase", "nowrapscan"}},
+		{name: "number", change: []string{"ts=8"}, want: []string{"tabstop=8"}},
+		{name: "absolute numbers", change: []string{"nornu"}, want: []string{"norelativenumber"}},
+		{name: "no numbers", change: []string{"nornu", "nonu"}, want: []string{"norelativenumber", "nonumber"}},
+		{name: "buffer option", change: []string{"ro"}, want: nil},
+	}
+
+	for _, tt := range tests {
+		t.Run(tt.name, func(t *testing.T) {
+			base := defaultConfig().Editor
+			cfg := base
+			for _, expr := range tt.change {
+				if _, err := SetOption(&cfg, expr); err != nil {
+					t.Fatalf("SetOption(%q) error = %v", expr, err)
+				}
+			}
+
+			got := SyncOptions(&base, &cfg)
+			if !reflect.DeepEqual(got, tt.want) {
+				t.Errorf("SyncOptions() = %q, want %q", got, tt.want)
+			}
+
+			// replaying the expressions on the defaults gives the same options
+			replay := defaultConfig().Editor
+			for _, expr := range got {
+				_, _ = SetOption(&replay, expr)
+			}
+			if again := SyncOptions(&replay, &cfg); len(again) != 0 {
+				t.Errorf("options differ after replaying %q: %q", got, again)
+			}
+		})
+	}
+}
@@ -0,0 +1,111 @@
+package config
+
+import (
+	"crypto/sha256"
+	"encoding/hex"
+	"os"
+	"path/filepath"
+
+	"github.com/BurntSushi/toml"
+)
+
+// ProjectConfigName is the file a project keeps its own settings in, at its root.
+const ProjectConfigName = ".athena.toml"
+
+// FindProjectConfig returns the project config closest to dir, searching up to the filesystem
+// root, or "" when there is none.
+func FindProjectConfig(dir string) string {
+	dir, err := filepath.Abs(dir)
+	if err != nil {
+		return ""
+	}
+	for {
+		path := filepath.Join(dir, ProjectConfigName)
+		if info, err := os.Stat(path); err == nil && !info.IsDir() {
+			return path
+		}
+		parent := filepath.Dir(dir)
+		if parent == dir {
+			return ""
+		}
+		dir = parent
+	}
+}
+
+// LoadProjectConfig merges the project config at path over cfg; only the settings it sets win.
+func LoadProjectConfig(cfg *Config, path string) []string {
+	projectCfg, meta, errors := loadConfigFile(&path)
+	mergeConfig(cfg, projectCfg, meta)
+	return append(errors, validateAndFixConfig(cfg)...)
+}
+
+// TrustStore remembers the project configs the user agreed to load.
+//
+//	A config is keyed by its path and trusted with its content, so a config changed since,
+//	e.g. by pulling a branch, has to be agreed to again.
+type TrustStore struct {
+	path    string
+	Trusted map[string]string `toml:"trusted"` // path to the sha256 of the trusted content
+}
+
+// LoadTrust reads the trust store saved at path; a missing or unreadable file trusts nothing.
+func LoadTrust(path string) *TrustStore {
+	s := &TrustStore{path: path}
+	if _, err := toml.DecodeFile(path, s); err != nil || s.Trusted == nil {
+		s.Trusted = make(map[string]string)
+	}
+	return s
+}
+
+// DefaultTrustPath returns where trusted projects are saved.
+func DefaultTrustPath() string {
+	homeDir, err := os.UserHomeDir()
+	if err != nil {
+		return ""
+	}
+	return filepath.Join(homeDir, ".local", "state", "athena", "trusted.toml")
+}
+
+// IsTrusted reports whether the config at path was trusted with its current content.
+func (s *TrustStore) IsTrusted(path string) bool {
+	sum, err := fileSum(path)
+	return err == nil && s.Trusted[path] == sum
+}
+
+// Trust trusts the config at path with its current content and saves the store.
+func (s *TrustStore) Trust(path string) error {
+	sum, err := fileSum(path)
+	if err != nil {
+		return err
+	}
+	s.Trusted[path] = sum
+	return s.save()
+}
+
+func (s *TrustStore) save() error {
+	if s.path == "" {
+		return nil
+	}
+	if err := os.MkdirAll(filepath.Dir(s.path), 0755); err != nil {
+		return err
+	}
+
+	file, err := os.Create(s.path)
+	if err != nil {
+		return err
+	}
+	if err := toml.NewEncoder(file).Encode(s); err != nil {
+		file.Close()
+		return err
+	}
+	return file.Close()
+}
+
+func fileSum(path string) (string, error) {
+	data, err := os.ReadFile(path)
+	if err != nil {
+		return "", err
+	}
+	sum := sha256.Sum256(data)
+	return hex.EncodeToString(sum[:]), nil
+}
@@ -0,0 +1,88 @@
+package config
+
+import (
+	"os"
+	"path/filepath"
+	"testing"
+)
+
+func TestFindProjectConfig(t *testing.T) {
+	root := t.TempDir()
+	nested := filepath.Join(root, "project", "sub", "dir")
+	if err := os.MkdirAll(nested, 0755); err != nil {
+		t.Fatal(err)
+	}
+	project := filepath.Join(root, "project", ProjectConfigName)
+	if err := os.WriteFile(project, []byte("[editor]\ntab-width = 2\n"), 0644); err != nil {
+		t.Fatal(err)
+	}
+
+	tests := []struct {
+		name string
+		dir  string
+		want string
+	}{
+		{name: "project root", dir: filepath.Join(root, "project"), want: project},
+		{name: "nested directory", dir: nested, want: project},
+		{name: "outside the project", dir: root, want: ""},
+	}
+
+	for _, tt := range tests {
+		t.Run(tt.name, func(t *testing.T) {
+			if got := FindProjectConfig(tt.dir); got != tt.want {
+				t.Errorf("FindProjectConfig(%q) = %q, want %q", tt.dir, got, tt.want)
+			}
+		})
+	}
+}
+
+func TestLoadProjectConfig(t *testing.T) {
+	path := filepath.Join(t.TempDir(), ProjectConfigName)
+	content := "[editor]\ntab-width = 2\n\n[language.go]\nindent-tabs = true\n"
+	if err := os.WriteFile(path, []byte(content), 0644); err != nil {
+		t.Fatal(err)
+	}
+
+	cfg := defaultConfig()
+	cfg.Editor.SoftWrap = true // as if the user config set it
+	if errs := LoadProjectConfig(cfg, path); len(errs) > 0 {
+		t.Fatalf("LoadProjectConfig() errors = %v", errs)
+	}
+
+	if cfg.Editor.TabWidth != 2 {
+		t.Errorf("TabWidth = %d, want 2", cfg.Editor.TabWidth)
+	}
+	if !cfg.Languages["go"].IndentTabs {
+		t.Errorf("Languages[go].IndentTabs = false, want true")
+	}
+	if !cfg.Editor.SoftWrap || !cfg.Editor.BufferLine {
+		t.Errorf("SoftWrap, BufferLine = %v, %v, want settings the project leaves alone kept", cfg.Editor.SoftWrap, cfg.Editor.BufferLine)
+	}
+}
+
+func TestTrustStore(t *testing.T) {
+	dir := t.TempDir()
+	path := filepath.Join(dir, ProjectConfigName)
+	if err := os.WriteFile(path, []byte("[editor]\ntab-width = 2\n"), 0644); err != nil {
+		t.Fatal(err)
+	}
+	storePath := filepath.Join(dir, "state", "trusted.toml")
+
+	store := LoadTrust(storePath)
+	if store.IsTrusted(path) {
+		t.Fatalf("IsTrusted() = true for a new project")
+	}
+	if err := store.Trust(path); err != nil {
+		t.Fatalf("Trust() error = %v", err)
+	}
+	if !LoadTrust(storePath).IsTrusted(path) {
+		t.Errorf("IsTrusted() = false after Trust(), reloaded")
+	}
+
+	if err := os.WriteFile(path, []byte("[editor]\ntab-width = 8\n"), 0644); err != nil {
+		t.Fatal(err)
+	}
+	if store.IsTrusted(path) {
+		t.Errorf("IsTrusted() = true after the config changed")
+	}
+}
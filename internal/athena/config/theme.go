@@ -0,0 +1,150 @@
+package config
+
+import (
+	"fmt"
+	"os"
+	"path/filepath"
+	"sort"
+	"strings"
+
+	"github.com/BurntSushi/toml"
+	"github.com/gdamore/tcell/v2"
+)
+
+// ThemeStyle is how a theme draws one kind of text; colors are names like "navy" or "#rrggbb",
+// and empty ones are left to the terminal.
+type ThemeStyle struct {
+	Fg        string `toml:"fg"`
+	Bg        string `toml:"bg"`
+	Bold      bool   `toml:"bold"`
+	Italic    bool   `toml:"italic"`
+	Underline bool   `toml:"underline"`
+	Reverse   bool   `toml:"reverse"`
+}
+
+// IsZero reports whether the style sets nothing, so the built-in one applies.
+func (s ThemeStyle) IsZero() bool {
+	return s == ThemeStyle{}
+}
+
+// Style returns the style as drawn.
+func (s ThemeStyle) Style() tcell.Style {
+	style := tcell.StyleDefault
+	if s.Fg != "" {
+		style = style.Foreground(tcell.GetColor(s.Fg))
+	}
+	if s.Bg != "" {
+		style = style.Background(tcell.GetColor(s.Bg))
+	}
+	return style.Bold(s.Bold).Italic(s.Italic).Underline(s.Underline).Reverse(s.Reverse)
+}
+
+// ThemeConfig is a theme file, styling the parts of the editor and syntax highlighting.
+//
+//	Parts it leaves out keep the built-in styles. Syntax styles are keyed by capture, e.g.
+//	"keyword" or "string.special"; a capture without one uses its parent's.
+type ThemeConfig struct {
+	Text           ThemeStyle            `toml:"text"` // plain text and the document background
+	Selection      ThemeStyle            `toml:"selection"`
+	Match          ThemeStyle            `toml:"match"`
+	CurrentMatch   ThemeStyle            `toml:"current-match"`
+	MatchPair      ThemeStyle            `toml:"match-pair"`
+	Gutter         ThemeStyle            `toml:"gutter"`
+	GutterCurrent  ThemeStyle            `toml:"gutter-current"` // the cursor line's gutter
+	CursorLine     ThemeStyle            `toml:"cursor-line"`    // background of the cursor line and column, when enabled
+	EOFMarker      ThemeStyle            `toml:"eof-marker"`     // rows past the end of the buffer
+	Scrollbar      ThemeStyle            `toml:"scrollbar"`
+	ScrollbarThumb ThemeStyle            `toml:"scrollbar-thumb"` // the part of the buffer in view
+	StatusBar      ThemeStyle            `toml:"status-bar"`
+	Menu           ThemeStyle            `toml:"menu"`
+	MenuBorder     ThemeStyle            `toml:"menu-border"`
+	Syntax         map[string]ThemeStyle `toml:"syntax"`
+}
+
+// styles returns every style of the theme, keyed by where it is set in the file.
+func (t *ThemeConfig) styles() map[string]ThemeStyle {
+	styles := map[string]ThemeStyle{
+		"text":            t.Text,
+		"selection":       t.Selection,
+		"match":           t.Match,
+		"current-match":   t.CurrentMatch,
+		"match-pair":      t.MatchPair,
+		"gutter":          t.Gutter,
+		"gutter-current":  t.GutterCurrent,
+		"cursor-line":     t.CursorLine,
+		"eof-marker":      t.EOFMarker,
+		"scrollbar":       t.Scrollbar,
+		"scrollbar-thumb": t.ScrollbarThumb,
+		"status-bar":      t.StatusBar,
+		"menu":            t.Menu,
+		"menu-border":     t.MenuBorder,
+	}
+	for capture, style := range t.Syntax {
+		styles["syntax."+capture] = style
+	}
+	return styles
+}
+
+// themesDir returns the directory themes are kept in, next to the config file.
+func themesDir(configPath *string) string {
+	if configPath != nil && *configPath != "" {
+		return filepath.Join(filepath.Dir(*configPath), "themes")
+	}
+	homeDir, err := os.UserHomeDir()
+	if err != nil {
+		return ""
+	}
+	return filepath.Join(homeDir, ".config", "athena", "themes")
+}
+
+// LoadThemes reads every "<name>.toml" theme in dir, keyed by name; a missing dir has none.
+func LoadThemes(dir string) (map[string]*ThemeConfig, []string) {
+	themes := make(map[string]*ThemeConfig)
+	paths, err := filepath.Glob(filepath.Join(dir, "*.toml"))
+	if err != nil {
+		return themes, []string{fmt.Sprintf("Error listing themes: %v", err)}
+	}
+
+	var errors []string
+	for _, path := range paths {
+		name := strings.TrimSuffix(filepath.Base(path), ".toml")
+		theme := &ThemeConfig{}
+		if _, err := toml.DecodeFile(path, theme); err != nil {
+			errors = append(errors, fmt.Sprintf("Error decoding theme %s: %v", name, err))
+			continue
+		}
+		errors = append(errors, validateTheme(name, theme)...)
+		themes[name] = theme
+	}
+	return themes, errors
+}
+
+// validateTheme reports the colors of theme that aren't names or "#rrggbb".
+func validateTheme(name string, theme *ThemeConfig) []string {
+	var errors []string
+	for part, style := range theme.styles() {
+		for _, color := range []string{style.Fg, style.Bg} {
+			if color != "" && tcell.GetColor(color) == tcell.ColorDefault && color != "default" {
+				errors = append(errors, fmt.Sprintf("Invalid color in theme %s: %s = %s", name, part, color))
+			}
+		}
+	}
+	sort.Strings(errors)
+	return errors
+}
+
+// ThemeNames returns the names of the loaded themes, sorted.
+func (c *Config) ThemeNames() []string {
+	names := make([]string, 0, len(c.Themes))
+	for name := range c.Themes {
+		names = append(names, name)
+	}
+	sort.Strings(names)
+	return names
+}
+
+// HasTheme reports whether name can be selected as the theme; "" is the built-in one.
+func (c *Config) HasTheme(name string) bool {
+	_, ok := c.Themes[name]
+	return name == "" || ok
+}
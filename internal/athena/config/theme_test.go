@@ -0,0 +1,86 @@
+package config
+
+import (
+	"os"
+	"path/filepath"
+	"reflect"
+	"testing"
+
+	"github.com/gdamore/tcell/v2"
+)
+
+func TestLoadThemes(t *testing.T) {
+	dir := t.TempDir()
+	files := map[string]string{
+		"night.toml":  "[text]\nfg = \"#c0caf5\"\nbg = \"#1a1b26\"\n\n[syntax]\nkeyword = { fg = \"purple\", bold = true }\n",
+		"broken.toml": "[gutter]\nfg = \"not-a-color\"\n",
+		"notes.txt":   "not a theme",
+	}
+	for name, content := range files {
+		if err := os.WriteFile(filepath.Join(dir, name), []byte(content), 0644); err != nil {
+			t.Fatal(err)
+		}
+	}
+
+	themes, errs := LoadThemes(dir)
+	wantErrs := []string{"Invalid color in theme broken: gutter = not-a-color"}
+	if !reflect.DeepEqual(errs, wantErrs) {
+		t.Errorf("LoadThemes() errors = %q, want %q", errs, wantErrs)
+	}
+	if len(themes) != 2 {
+		t.Errorf("LoadThemes() loaded %d themes, want 2", len(themes))
+	}
+
+	night := themes["night"]
+	if night == nil {
+		t.Fatal("theme night not loaded")
+	}
+	wantText := tcell.StyleDefault.Foreground(tcell.NewHexColor(0xc0caf5)).Background(tcell.NewHexColor(0x1a1b26))
+	if got := night.Text.Style(); got != wantText {
+		t.Errorf("text style = %v, want %v", got, wantText)
+	}
+	wantKeyword := tcell.StyleDefault.Foreground(tcell.ColorPurple).Bold(true)
+	if got := night.Syntax["keyword"].Style(); got != wantKeyword {
+		t.Errorf("keyword style = %v, want %v", got, wantKeyword)
+	}
+	if !night.Selection.IsZero() {
+		t.Errorf("selection = %+v, want unset", night.Selection)
+	}
+}
+
+func TestLoadConfigTheme(t *testing.T) {
+	tests := []struct {
+		name      string
+		theme     string
+		wantTheme string
+		wantErr   bool
+	}{
+		{name: "theme file", theme: "night", wantTheme: "night"},
+		{name: "built-in", theme: "", wantTheme: ""},
+		{name: "missing", theme: "day", wantTheme: "", wantErr: true},
+	}
+
+	for _, tt := range tests {
+		t.Run(tt.name, func(t *testing.T) {
+			dir := t.TempDir()
+			if err := os.Mkdir(filepath.Join(dir, "themes"), 0755); err != nil {
+				t.Fatal(err)
+			}
+			if err := os.WriteFile(filepath.Join(dir, "themes", "night.toml"), []byte("[text]\nbg = \"black\"\n"), 0644); err != nil {
+				t.Fatal(err)
+			}
+			path := filepath.Join(dir, "config.toml")
+			if err := os.WriteFile(path, []byte("[editor]\ntheme = \""+tt.theme+"\"\n"), 0644); err != nil {
+				t.Fatal(err)
+			}
+
+			cfg, errs := LoadConfig(&path)
+			if gotErr := len(errs) > 0; gotErr != tt.wantErr {
+				t.Errorf("LoadConfig() errors = %v, want error %v", errs, tt.wantErr)
+			}
+			if cfg.Editor.Theme != tt.wantTheme {
+				t.Errorf("Theme = %q, want %q", cfg.Editor.Theme, tt.wantTheme)
+			}
+		})
+	}
+}
@@ -0,0 +1,154 @@
+package athena
+
+import (
+	"fmt"
+	"slices"
+	"sync"
+	"time"
+)
+
+// diskCheckInterval is how often the files of the open buffers are checked for changes made by
+// other programs while the editor waits for keys.
+const diskCheckInterval = 2 * time.Second
+
+// diskWatch is the background loop waking the editor when a file changed on disk, see
+// startDiskWatch.
+type diskWatch struct {
+	stop chan struct{} // closed to end the loop, nil when it isn't running
+	done chan struct{} // closed once the loop has ended
+
+	mu      sync.Mutex
+	changed map[string]bool // buffers whose files were found changed, until they are shown
+}
+
+// startDiskWatch checks the files of the open buffers every interval, until stopDiskWatch,
+// redrawing when another program changed one so it is reloaded or asked about without waiting for
+// a key.
+func (a *Athena) startDiskWatch(interval time.Duration) {
+	a.diskWatch.stop, a.diskWatch.done = make(chan struct{}), make(chan struct{})
+
+	stop, done := a.diskWatch.stop, a.diskWatch.done
+	go func() {
+		defer a.restoreOnPanic()
+		defer close(done)
+		ticker := time.NewTicker(interval)
+		defer ticker.Stop()
+		for {
+			select {
+			case <-stop:
+				return
+			case <-ticker.C:
+				if a.noticeDiskChanges() {
+					a.wakeup()
+				}
+			}
+		}
+	}()
+}
+
+// stopDiskWatch ends the loop started by startDiskWatch.
+func (a *Athena) stopDiskWatch() {
+	if a.diskWatch.stop == nil {
+		return
+	}
+	close(a.diskWatch.stop)
+	<-a.diskWatch.done
+	a.diskWatch.stop = nil
+}
+
+// noticeDiskChanges checks the file of every open buffer, recording those another program changed
+// to be handled once shown; it reports whether it found one it hadn't recorded yet.
+func (a *Athena) noticeDiskChanges() bool {
+	a.diskWatch.mu.Lock()
+	defer a.diskWatch.mu.Unlock()
+
+	paths := a.editor.GetBufferList()
+	found := false
+	for _, path := range paths {
+		if changed, _ := a.editor.BufferChangedOnDisk(path); changed && !a.diskWatch.changed[path] {
+			if a.diskWatch.changed == nil {
+				a.diskWatch.changed = make(map[string]bool)
+			}
+			a.diskWatch.changed[path] = true
+			found = true
+		}
+	}
+	// closed since
+	for path := range a.diskWatch.changed {
+		if !slices.Contains(paths, path) {
+			delete(a.diskWatch.changed, path)
+		}
+	}
+	return found
+}
+
+// takeDiskChange reports whether the disk watch recorded the file of path as changed, forgetting it.
+func (a *Athena) takeDiskChange(path string) bool {
+	a.diskWatch.mu.Lock()
+	defer a.diskWatch.mu.Unlock()
+
+	changed := a.diskWatch.changed[path]
+	delete(a.diskWatch.changed, path)
+	return changed
+}
+
+// checkDisk notices when another program changed the current buffer's file: a buffer without
+// changes of its own is reloaded, and one with changes asks whether to reload it or keep them.
+//
+//	The current buffer is checked every frame, so one that becomes current is handled at once,
+//	whether or not the disk watch found it changed.
+func (a *Athena) checkDisk() {
+	if a.views.commandLine.Active() {
+		return
+	}
+	if changed, err := a.editor.ChangedOnDisk(); err != nil || !changed {
+		return
+	}
+	if path, err := a.editor.FilePath(); err == nil {
+		a.takeDiskChange(path)
+	}
+	name, _ := a.editor.FileName()
+
+	if modified, _ := a.editor.Modified(); !modified {
+		a.reloadChanged(name)
+		return
+	}
+
+	prompt := fmt.Sprintf("%q changed on disk; reload it and drop your changes (y/n)?", name)
+	a.views.commandLine.Confirm(prompt, func(key rune) {
+		if key != 'y' {
+			_ = a.editor.KeepChangedOnDisk()
+			a.editor.SetMessage(fmt.Sprintf("kept your changes to %q; :w saves them over the file", name))
+			return
+		}
+		if err := a.editor.ReloadCurrentBuffer(true); err != nil {
+			a.editor.SetError(err)
+			return
+		}
+		a.editor.SetMessage(fmt.Sprintf("%q reloaded", name))
+	})
+}
+
+// reloadShown reloads the buffer of a window drawn without the focus when the disk watch found
+// its file changed; one with changes of its own waits to ask until it is current, see checkDisk.
+func (a *Athena) reloadShown() {
+	path, err := a.editor.FilePath()
+	if err != nil {
+		return
+	}
+	if modified, _ := a.editor.Modified(); modified || !a.takeDiskChange(path) {
+		return
+	}
+	name, _ := a.editor.FileName()
+	a.reloadChanged(name)
+}
+
+// reloadChanged reloads the current buffer, named name, which has no changes of its own, from its
+// file changed on disk.
+func (a *Athena) reloadChanged(name string) {
+	if err := a.editor.ReloadCurrentBuffer(false); err != nil {
+		a.editor.SetError(err)
+		return
+	}
+	a.editor.SetMessage(fmt.Sprintf("%q changed on disk, reloaded", name))
+}
@@ -0,0 +1,118 @@
+package athena
+
+import (
+	"fmt"
+	"io"
+	"strings"
+	"time"
+
+	"github.com/gdamore/tcell/v2"
+	"github.com/lg2m/athena/internal/athena/config"
+	"github.com/lg2m/athena/internal/editor"
+	"github.com/lg2m/athena/internal/editor/state"
+	"github.com/lg2m/athena/internal/ui"
+)
+
+// Headless runs athena on a simulated screen, for tests that type keys and check the buffer,
+// cursor and drawn frame.
+//
+//	Keys go through the same views and keymaps as in a terminal; nothing is drawn to one, and the
+//	title and saved views are left alone.
+type Headless struct {
+	athena *Athena
+	screen tcell.SimulationScreen
+	quit   bool
+}
+
+// NewHeadless opens filePath in an editor drawn on a width by height simulated screen.
+func NewHeadless(cfg *config.Config, filePath string, width, height int) (*Headless, error) {
+	screen := tcell.NewSimulationScreen("UTF-8")
+	if err := screen.Init(); err != nil {
+		return nil, fmt.Errorf("failed to initialize screen: %w", err)
+	}
+	screen.SetSize(width, height)
+
+	// views are remembered per user, which tests must not touch
+	cfg.Editor.Views = false
+	a, err := newAthena(screen, cfg, filePath, newTerminalTitle(io.Discard, ""))
+	if err != nil {
+		screen.Fini()
+		return nil, err
+	}
+	// fed keys arrive faster than anyone types, which must not read as a paste
+	a.fedKeys = true
+	a.focus.document.SetPasteBurstGap(0)
+
+	h := &Headless{athena: a, screen: screen}
+	a.render()
+	return h, nil
+}
+
+// Type handles keys written in keymap notation, e.g. "ihello<esc>", redrawing after each one.
+//
+//	Keys typed after <c-c> quit are ignored.
+func (h *Headless) Type(keys string) {
+	for _, ev := range ui.ParseKeys(keys) {
+		if h.quit {
+			return
+		}
+		h.quit = h.athena.handleEvent(ev)
+		h.athena.render()
+	}
+}
+
+// Wait lets d pass without typing, so a partly typed key sequence can time out.
+func (h *Headless) Wait(d time.Duration) {
+	h.athena.focus.document.ExpirePendingKeys(time.Now().Add(d))
+	h.athena.render()
+}
+
+// Quit reports whether a key typed asked the editor to quit.
+func (h *Headless) Quit() bool {
+	return h.quit
+}
+
+// Text returns the content of the current buffer.
+func (h *Headless) Text() (string, error) {
+	return h.athena.editor.Text()
+}
+
+// Cursor returns the line and column of the cursor, both 0-based.
+func (h *Headless) Cursor() (int, int, error) {
+	return h.athena.editor.GetCurrentPosition()
+}
+
+// Mode returns the editor mode.
+func (h *Headless) Mode() state.EditorMode {
+	return h.athena.editor.GetMode()
+}
+
+// Message returns the message shown on the command line, if any.
+func (h *Headless) Message() editor.Message {
+	return h.athena.editor.Message()
+}
+
+// Frame returns the rows of the last frame drawn, with trailing blanks trimmed.
+func (h *Headless) Frame() []string {
+	cells, width, height := h.screen.GetContents()
+	rows := make([]string, height)
+	for y := range rows {
+		var row strings.Builder
+		for x := 0; x < width; x++ {
+			cell := cells[y*width+x]
+			if len(cell.Runes) == 0 {
+				row.WriteByte(' ')
+				continue
+			}
+			row.WriteString(string(cell.Runes))
+		}
+		rows[y] = strings.TrimRight(row.String(), " ")
+	}
+	return rows
+}
+
+// Close stops autosaving, if started, and tears the simulated screen down.
+func (h *Headless) Close() {
+	h.athena.stopAutosave()
+	h.athena.restoreTerminal()
+}
@@ -0,0 +1,2685 @@
+package athena
+
+import (
+	"bytes"
+	"errors"
+	"io"
+	"os"
+	"path/filepath"
+	"reflect"
+	"slices"
+	"strings"
+	"testing"
+	"time"
+
+	"github.com/gdamore/tcell/v2"
+	"github.com/lg2m/athena/internal/athena/config"
+	"github.com/lg2m/athena/internal/editor"
+	"github.com/lg2m/athena/internal/editor/buffer"
+	"github.com/lg2m/athena/internal/editor/state"
+	"github.com/lg2m/athena/internal/editor/treesitter"
+	"github.com/lg2m/athena/internal/lsp"
+	"github.com/lg2m/athena/internal/ui"
+	"github.com/lg2m/athena/internal/vcs"
+)
+
+// newTestHeadless opens a file holding text with the default config on an 80x24 screen.
+func newTestHeadless(t *testing.T, text string) *Headless {
+	t.Helper()
+	dir := t.TempDir()
+	path := filepath.Join(dir, "test.txt")
+	if err := os.WriteFile(path, []byte(text), 0644); err != nil {
+		t.Fatal(err)
+	}
+
+	// a config path that doesn't exist leaves the defaults
+	configPath := filepath.Join(dir, "config.toml")
+	cfg, errs := config.LoadConfig(&configPath)
+	if len(errs) > 0 {
+		t.Fatalf("LoadConfig() errors = %v", errs)
+	}
+
+	h, err := NewHeadless(cfg, path, 80, 24)
+	if err != nil {
+		t.Fatalf("NewHeadless() error = %v", err)
+	}
+	t.Cleanup(h.Close)
+	return h
+}
+
+func TestHeadlessKeys(t *testing.T) {
+	tests := []struct {
+		name       string
+		keys       string
+		wantText   string
+		wantLine   int
+		wantColumn int
+		wantMode   state.EditorMode
+	}{
+		{name: "insert", keys: "ihi <esc>", wantText: "hi one\ntwo\nthree\n", wantColumn: 3, wantMode: state.Normal},
+		{name: "insert space", keys: "ia b<esc>", wantText: "a bone\ntwo\nthree\n", wantColumn: 3, wantMode: state.Normal},
+		{name: "still inserting", keys: "ihi", wantText: "hione\ntwo\nthree\n", wantColumn: 2, wantMode: state.Insert},
+		{name: "count", keys: "2j", wantText: "one\ntwo\nthree\n", wantLine: 2, wantMode: state.Normal},
+		{name: "delete line", keys: "jdd", wantText: "one\nthree\n", wantLine: 1, wantMode: state.Normal},
+		{name: "cancelled prompt", keys: ":dd<esc>", wantText: "one\ntwo\nthree\n", wantMode: state.Normal},
+		{name: "search", keys: "/t<cr>", wantText: "one\ntwo\nthree\n", wantLine: 1, wantMode: state.Normal},
+		{name: "search next", keys: "/t<cr>n", wantText: "one\ntwo\nthree\n", wantLine: 2, wantMode: state.Normal},
+		{name: "search backward", keys: "?o<cr>", wantText: "one\ntwo\nthree\n", wantLine: 1, wantColumn: 2, wantMode: state.Normal},
+		{name: "substitute line", keys: "j:s/t/T/<cr>", wantText: "one\nTwo\nthree\n", wantLine: 1, wantMode: state.Normal},
+		{name: "substitute all", keys: ":%s/e/E/g<cr>", wantText: "onE\ntwo\nthrEE\n", wantLine: 2, wantColumn: 4, wantMode: state.Normal},
+		{name: "count matches", keys: ":%s/e//gn<cr>", wantText: "one\ntwo\nthree\n", wantMode: state.Normal},
+		{name: "empty search repeats", keys: "/t<cr>/<cr>", wantText: "one\ntwo\nthree\n", wantLine: 2, wantMode: state.Normal},
+	}
+
+	for _, tt := range tests {
+		t.Run(tt.name, func(t *testing.T) {
+			h := newTestHeadless(t, "one\ntwo\nthree\n")
+			h.Type(tt.keys)
+
+			text, err := h.Text()
+			if err != nil {
+				t.Fatalf("Text() error = %v", err)
+			}
+			if text != tt.wantText {
+				t.Errorf("Type(%q) text = %q, want %q", tt.keys, text, tt.wantText)
+			}
+			line, col, err := h.Cursor()
+			if err != nil {
+				t.Fatalf("Cursor() error = %v", err)
+			}
+			if line != tt.wantLine || col != tt.wantColumn {
+				t.Errorf("Type(%q) cursor = %d:%d, want %d:%d", tt.keys, line, col, tt.wantLine, tt.wantColumn)
+			}
+			if mode := h.Mode(); mode != tt.wantMode {
+				t.Errorf("Type(%q) mode = %v, want %v", tt.keys, mode, tt.wantMode)
+			}
+		})
+	}
+}
+
+func TestHeadlessNumberedRegisters(t *testing.T) {
+	tests := []struct {
+		name     string
+		keys     string
+		wantText string
+		wantErr  string
+	}{
+		{name: "unnamed", keys: "yyjddp", wantText: "one\nthree\ntwo\n"},
+		{name: "latest", keys: "yyjdd\"1p", wantText: "one\nthree\ntwo\n"},
+		{name: "overwritten yank", keys: "yyjdd\"2p", wantText: "one\nthree\none\n"},
+		{name: "only for the next command", keys: "yyjdd\"2lp", wantText: "one\nthree\ntwo\n"},
+		{name: "empty", keys: "yyjdd\"3p", wantText: "one\nthree\n", wantErr: "nothing in register"},
+		{name: "invalid", keys: "yy\"xp", wantText: "one\ntwo\nthree\n", wantErr: "invalid register"},
+	}
+	for _, tt := range tests {
+		t.Run(tt.name, func(t *testing.T) {
+			h := newTestHeadless(t, "one\ntwo\nthree\n")
+			h.Type(tt.keys)
+			if text, _ := h.Text(); text != tt.wantText {
+				t.Errorf("Type(%q) text = %q, want %q", tt.keys, text, tt.wantText)
+			}
+			if msg := h.Message(); tt.wantErr != "" && (!msg.IsError || msg.Text != tt.wantErr) {
+				t.Errorf("Message() = %q, want the error %q", msg.Text, tt.wantErr)
+			}
+		})
+	}
+
+	h := newTestHeadless(t, "one\ntwo\nthree\n")
+	h.Type("yyjdd:reg<cr>")
+	if msg := h.Message(); msg.Text != `"1 two^J  "2 one^J` {
+		t.Errorf(":reg message = %q, want the numbered registers", msg.Text)
+	}
+}
+
+func TestHeadlessCharacterCommands(t *testing.T) {
+	// the accent is a combining mark and the emoji carries a skin tone, each a single character
+	const text = "ae\u0301b 👍🏽c\nnext\n"
+	tests := []struct {
+		name     string
+		keys     string
+		wantText string
+		wantErr  string
+	}{
+		{name: "delete", keys: "lx", wantText: "ab 👍🏽c\nnext\n"},
+		{name: "delete emoji", keys: "llllx", wantText: "ae\u0301b c\nnext\n"},
+		{name: "delete count", keys: "3x", wantText: " 👍🏽c\nnext\n"},
+		{name: "delete stops at line end", keys: "10x", wantText: "\nnext\n"},
+		{name: "delete into register", keys: "xp", wantText: "e\u0301ab 👍🏽c\nnext\n"},
+		{name: "delete before", keys: "llX", wantText: "ab 👍🏽c\nnext\n"},
+		{name: "delete before at line start", keys: "jX", wantText: text},
+		{name: "replace", keys: "lrx", wantText: "axb 👍🏽c\nnext\n"},
+		{name: "replace count", keys: "3rx", wantText: "xxx 👍🏽c\nnext\n"},
+		{name: "replace past line end", keys: "9rx", wantText: text, wantErr: "not enough characters on the line"},
+		{name: "toggle case", keys: "~", wantText: "Ae\u0301b 👍🏽c\nnext\n"},
+		{name: "toggle case count", keys: "3~", wantText: "AE\u0301B 👍🏽c\nnext\n"},
+		{name: "toggle case moves on", keys: "~~", wantText: "AE\u0301b 👍🏽c\nnext\n"},
+	}
+	for _, tt := range tests {
+		t.Run(tt.name, func(t *testing.T) {
+			h := newTestHeadless(t, text)
+			h.Type(tt.keys)
+			if text, _ := h.Text(); text != tt.wantText {
+				t.Errorf("Type(%q) text = %q, want %q", tt.keys, text, tt.wantText)
+			}
+			if msg := h.Message(); tt.wantErr != "" && (!msg.IsError || msg.Text != tt.wantErr) {
+				t.Errorf("Message() = %q, want the error %q", msg.Text, tt.wantErr)
+			}
+		})
+	}
+}
+
+func TestHeadlessGoToLine(t *testing.T) {
+	text := strings.Repeat("line\n", 99) + "last"
+	tests := []struct {
+		name     string
+		keys     string
+		wantLine int
+	}{
+		{name: "first", keys: "jjgg", wantLine: 0},
+		{name: "count", keys: "50gg", wantLine: 49},
+		{name: "last", keys: "G", wantLine: 99},
+		{name: "last with count", keys: "50G", wantLine: 49},
+		{name: "prompt", keys: ":50<cr>", wantLine: 49},
+		{name: "prompt past the end", keys: ":500<cr>", wantLine: 99},
+		{name: "prompt relative", keys: "10G:+5<cr>", wantLine: 14},
+		{name: "jump back", keys: ":50<cr><c-o>", wantLine: 0},
+	}
+	for _, tt := range tests {
+		t.Run(tt.name, func(t *testing.T) {
+			h := newTestHeadless(t, text)
+			h.Type(tt.keys)
+			if line, _, _ := h.Cursor(); line != tt.wantLine {
+				t.Errorf("Type(%q) cursor line = %d, want %d", tt.keys, line, tt.wantLine)
+			}
+		})
+	}
+
+	// the line is brought to the middle of the view, like zz
+	h := newTestHeadless(t, text)
+	h.Type(":50<cr>")
+	if offset := h.athena.focus.viewport.Offset(); offset != 49-22/2 {
+		t.Errorf("viewport offset after :50 = %d, want line 50 centered", offset)
+	}
+}
+
+func TestHeadlessInsertDeletions(t *testing.T) {
+	tests := []struct {
+		name     string
+		keys     string
+		wantText string
+	}{
+		{name: "word", keys: "i  foo.bar baz<c-w>", wantText: "  foo.bar end\nnext\n"},
+		{name: "punctuation apart", keys: "i  foo.bar baz<c-w><c-w>", wantText: "  foo.end\nnext\n"},
+		{name: "whole grapheme", keys: "iab 👍🏽<c-w>", wantText: "ab end\nnext\n"},
+		{name: "joins lines", keys: "ji<c-w>", wantText: "endnext\n"},
+		{name: "to indentation", keys: "i  foo.bar<c-u>", wantText: "  end\nnext\n"},
+		{name: "indentation too", keys: "i  foo<c-u><c-u>", wantText: "end\nnext\n"},
+		{name: "word after", keys: "i  <a-d>", wantText: "  \nnext\n"},
+		{name: "joins line below", keys: "i<a-d><a-d>", wantText: "next\n"},
+		{name: "undone with the insert", keys: "ione two<c-w>three<esc>u", wantText: "end\nnext\n"},
+		{name: "join undone", keys: "ji<c-w><esc>u", wantText: "end\nnext\n"},
+	}
+	for _, tt := range tests {
+		t.Run(tt.name, func(t *testing.T) {
+			h := newTestHeadless(t, "end\nnext\n")
+			h.Type(tt.keys)
+			if text, _ := h.Text(); text != tt.wantText {
+				t.Errorf("Type(%q) text = %q, want %q", tt.keys, text, tt.wantText)
+			}
+		})
+	}
+}
+
+func TestHeadlessAutoPairs(t *testing.T) {
+	tests := []struct {
+		name       string
+		keys       string
+		pairs      []string // for the file's language, when set
+		wantText   string
+		wantColumn int
+	}{
+		{name: "open", keys: "i(", wantText: "()\n", wantColumn: 1},
+		{name: "type inside", keys: "i(ab", wantText: "(ab)\n", wantColumn: 3},
+		{name: "step over closer", keys: "i(ab)", wantText: "(ab)\n", wantColumn: 4},
+		{name: "nested", keys: "i([])", wantText: "([])\n", wantColumn: 3},
+		{name: "quote", keys: `i"a"`, wantText: "\"a\"\n", wantColumn: 3},
+		{name: "apostrophe after word", keys: "idon't", wantText: "don't\n", wantColumn: 5},
+		{name: "not before word", keys: "ia<left>(", wantText: "(a\n", wantColumn: 1},
+		{name: "closer alone", keys: "i)", wantText: ")\n", wantColumn: 1},
+		{name: "backspace deletes pair", keys: "i(<bs>", wantText: "\n"},
+		{name: "backspace after typing", keys: "i(a<bs><bs>", wantText: "\n"},
+		{name: "forgotten out of insert mode", keys: "i(<esc>i)", wantText: "())\n", wantColumn: 2},
+		{name: "off for language", keys: "i(", pairs: []string{}, wantText: "(\n", wantColumn: 1},
+	}
+	for _, tt := range tests {
+		t.Run(tt.name, func(t *testing.T) {
+			h := newTestHeadless(t, "\n")
+			if tt.pairs != nil {
+				h.athena.cfg.Languages["txt"] = config.LanguageOptions{AutoPairs: tt.pairs}
+			}
+			h.Type(tt.keys)
+			if text, _ := h.Text(); text != tt.wantText {
+				t.Errorf("Type(%q) text = %q, want %q", tt.keys, text, tt.wantText)
+			}
+			if _, col, _ := h.Cursor(); col != tt.wantColumn {
+				t.Errorf("Type(%q) cursor column = %d, want %d", tt.keys, col, tt.wantColumn)
+			}
+		})
+	}
+}
+
+func TestHeadlessInsertLiteral(t *testing.T) {
+	tests := []struct {
+		name       string
+		keys       string
+		wantText   string
+		wantColumn int
+	}{
+		{name: "key as is", keys: "i<c-v>(", wantText: "(\n", wantColumn: 1},
+		{name: "escape cancels", keys: "i<c-v><esc>a", wantText: "a\n", wantColumn: 1},
+		{name: "code point ended by a key", keys: "i<c-v>u2014 ", wantText: "\u2014 \n", wantColumn: 2},
+		{name: "code point ended by escape", keys: "i<c-v>u1F600<esc>a", wantText: "\U0001F600a\n", wantColumn: 2},
+		{name: "six digits end it", keys: "i<c-v>u01F600a", wantText: "\U0001F600a\n", wantColumn: 2},
+		{name: "no digits", keys: "i<c-v>uz", wantText: "z\n", wantColumn: 1},
+		{name: "past unicode", keys: "i<c-v>u110000", wantText: "\n"},
+		{name: "joins the character before", keys: "ie<c-v>u0301a", wantText: "e\u0301a\n", wantColumn: 2},
+	}
+	for _, tt := range tests {
+		t.Run(tt.name, func(t *testing.T) {
+			h := newTestHeadless(t, "\n")
+			h.Type(tt.keys)
+			if text, _ := h.Text(); text != tt.wantText {
+				t.Errorf("Type(%q) text = %q, want %q", tt.keys, text, tt.wantText)
+			}
+			if _, col, _ := h.Cursor(); col != tt.wantColumn {
+				t.Errorf("Type(%q) cursor column = %d, want %d", tt.keys, col, tt.wantColumn)
+			}
+			if mode := h.Mode(); mode != state.Insert {
+				t.Errorf("Type(%q) mode = %v, want insert", tt.keys, mode)
+			}
+		})
+	}
+}
+
+func TestHeadlessComment(t *testing.T) {
+	text := "one\n  two\nthree\n"
+	tests := []struct {
+		name     string
+		keys     string
+		comment  string // for the file's language, none when empty
+		wantText string
+		wantErr  string
+	}{
+		{name: "line", keys: "gcc", comment: "#", wantText: "# one\n  two\nthree\n"},
+		{name: "keeps indentation", keys: "jgcc", comment: "#", wantText: "one\n  # two\nthree\n"},
+		{name: "count", keys: "2gcc", comment: "#", wantText: "# one\n#   two\nthree\n"},
+		{name: "operator again", keys: "gcgc", comment: "#", wantText: "# one\n  two\nthree\n"},
+		{name: "motion", keys: "jgcj", comment: "//", wantText: "one\n//   two\n// three\n"},
+		{name: "uncomment", keys: "gccgcc", comment: "#", wantText: text},
+		{name: "selection", keys: "<s-down><s-right>gc", comment: "#", wantText: "# one\n#   two\nthree\n"},
+		{name: "no comment string", keys: "gcc", wantText: text, wantErr: "no comment string for this file type"},
+	}
+	for _, tt := range tests {
+		t.Run(tt.name, func(t *testing.T) {
+			h := newTestHeadless(t, text)
+			if tt.comment != "" {
+				h.athena.cfg.Languages["txt"] = config.LanguageOptions{Comment: tt.comment}
+			}
+			h.Type(tt.keys)
+			if text, _ := h.Text(); text != tt.wantText {
+				t.Errorf("Type(%q) text = %q, want %q", tt.keys, text, tt.wantText)
+			}
+			if msg := h.Message(); tt.wantErr != "" && (!msg.IsError || msg.Text != tt.wantErr) {
+				t.Errorf("Type(%q) message = %+v, want %q", tt.keys, msg, tt.wantErr)
+			}
+		})
+	}
+}
+
+func TestHeadlessSurround(t *testing.T) {
+	tests := []struct {
+		name     string
+		text     string
+		keys     string
+		wantText string
+		wantErr  string
+	}{
+		{name: "word", text: "one two\n", keys: "ysiw)", wantText: "(one) two\n"},
+		{name: "opening bracket pads", text: "one two\n", keys: "ysiw(", wantText: "( one ) two\n"},
+		{name: "alias", text: "one two\n", keys: "wysiwB", wantText: "one {two}\n"},
+		{name: "quote", text: "one two\n", keys: "ysiw\"", wantText: "\"one\" two\n"},
+		{name: "line", text: "  one two\n", keys: "yss]", wantText: "  [one two]\n"},
+		{name: "selection", text: "one two\n", keys: "<s-right><s-right><s-right>S*", wantText: "*one* two\n"},
+		{name: "delete", text: "f(a, (b))\n", keys: "lds)", wantText: "fa, (b)\n"},
+		{name: "delete padded", text: "( one )\n", keys: "wds(", wantText: "one\n"},
+		{name: "delete quotes", text: "say \"hi\" now\n", keys: "wlds\"", wantText: "say hi now\n"},
+		{name: "change", text: "say \"hi\" now\n", keys: "wlcs\"'", wantText: "say 'hi' now\n"},
+		{name: "change brackets", text: "a[1]\n", keys: "llcs]{", wantText: "a{ 1 }\n"},
+		{name: "no pair", text: "one two\n", keys: "ds)", wantText: "one two\n", wantErr: "no surrounding pair"},
+		{name: "not a delimiter", text: "one two\n", keys: "ysiwx", wantText: "one two\n", wantErr: "not a delimiter"},
+	}
+	for _, tt := range tests {
+		t.Run(tt.name, func(t *testing.T) {
+			h := newTestHeadless(t, tt.text)
+			h.Type(tt.keys)
+			if text, _ := h.Text(); text != tt.wantText {
+				t.Errorf("Type(%q) text = %q, want %q", tt.keys, text, tt.wantText)
+			}
+			if msg := h.Message(); tt.wantErr != "" && (!msg.IsError || msg.Text != tt.wantErr) {
+				t.Errorf("Type(%q) message = %+v, want %q", tt.keys, msg, tt.wantErr)
+			}
+		})
+	}
+}
+
+func TestHeadlessOperators(t *testing.T) {
+	tests := []struct {
+		name       string
+		keys       string
+		wantText   string
+		wantLine   int
+		wantColumn int
+		wantMode   state.EditorMode
+	}{
+		{name: "delete word", keys: "dw", wantText: "two  three\nfour\nfive\n"},
+		{name: "count after operator", keys: "d2w", wantText: "three\nfour\nfive\n"},
+		{name: "count before operator", keys: "2dw", wantText: "three\nfour\nfive\n"},
+		{name: "word stops at line end", keys: "wwdw", wantText: "one two  \nfour\nfive\n", wantColumn: 9},
+		{name: "change word", keys: "cwX<esc>", wantText: "X two  three\nfour\nfive\n", wantColumn: 1},
+		{name: "change still inserting", keys: "cw", wantText: " two  three\nfour\nfive\n", wantMode: state.Insert},
+		{name: "inner word", keys: "wdiw", wantText: "one   three\nfour\nfive\n", wantColumn: 4},
+		{name: "a word", keys: "wdaw", wantText: "one three\nfour\nfive\n", wantColumn: 4},
+		{name: "to line end", keys: "wdgl", wantText: "one \nfour\nfive\n", wantColumn: 4},
+		{name: "to line start", keys: "wwdgh", wantText: "three\nfour\nfive\n"},
+		{name: "lines down", keys: "dj", wantText: "five\n"},
+		{name: "lines to top", keys: "jdgg", wantText: "five\n"},
+		{name: "counted lines", keys: "2dd", wantText: "five\n"},
+		{name: "change line", keys: "jccsix<esc>", wantText: "one two  three\nsix\nfive\n", wantLine: 1, wantColumn: 3},
+		{name: "yank line", keys: "yyjp", wantText: "one two  three\nfour\none two  three\nfive\n", wantLine: 2},
+		{name: "yank word", keys: "ywP", wantText: "one one two  three\nfour\nfive\n", wantColumn: 3},
+		{name: "cancelled", keys: "d<esc>x", wantText: "ne two  three\nfour\nfive\n"},
+	}
+
+	for _, tt := range tests {
+		t.Run(tt.name, func(t *testing.T) {
+			h := newTestHeadless(t, "one two  three\nfour\nfive\n")
+			h.Type(tt.keys)
+
+			text, err := h.Text()
+			if err != nil {
+				t.Fatalf("Text() error = %v", err)
+			}
+			if text != tt.wantText {
+				t.Errorf("Type(%q) text = %q, want %q", tt.keys, text, tt.wantText)
+			}
+			line, col, err := h.Cursor()
+			if err != nil {
+				t.Fatalf("Cursor() error = %v", err)
+			}
+			if line != tt.wantLine || col != tt.wantColumn {
+				t.Errorf("Type(%q) cursor = %d:%d, want %d:%d", tt.keys, line, col, tt.wantLine, tt.wantColumn)
+			}
+			if mode := h.Mode(); mode != tt.wantMode {
+				t.Errorf("Type(%q) mode = %v, want %v", tt.keys, mode, tt.wantMode)
+			}
+		})
+	}
+}
+
+func TestHeadlessOperatorMotionFails(t *testing.T) {
+	text := "one (two)\n\nfive\n"
+	tests := []struct {
+		name     string
+		keys     string
+		wantText string
+		wantMode state.EditorMode
+		wantErr  string
+	}{
+		{name: "pair", keys: "d%", wantText: "\n\nfive\n"},
+		{name: "no pair", keys: "jjd%", wantText: text, wantErr: "buffer: no matching pair on line"},
+		{name: "change without a pair", keys: "jjc%", wantText: text, wantErr: "buffer: no matching pair on line"},
+		{name: "to line end", keys: "jjdgl", wantText: "one (two)\n\n\n"},
+		// the newline of an empty line is left, as the motion stays on it
+		{name: "to line end of an empty line", keys: "jdgl", wantText: text},
+	}
+	for _, tt := range tests {
+		t.Run(tt.name, func(t *testing.T) {
+			h := newTestHeadless(t, text)
+			h.Type(tt.keys)
+			if text, _ := h.Text(); text != tt.wantText {
+				t.Errorf("Type(%q) text = %q, want %q", tt.keys, text, tt.wantText)
+			}
+			if mode := h.Mode(); mode != tt.wantMode {
+				t.Errorf("Type(%q) mode = %v, want %v", tt.keys, mode, tt.wantMode)
+			}
+			if msg := h.Message(); tt.wantErr != "" && (!msg.IsError || msg.Text != tt.wantErr) {
+				t.Errorf("Type(%q) message = %+v, want %q", tt.keys, msg, tt.wantErr)
+			}
+		})
+	}
+}
+
+func TestHeadlessRepeat(t *testing.T) {
+	tests := []struct {
+		name     string
+		keys     string
+		wantText string
+		wantErr  error
+	}{
+		{name: "delete word", keys: "dw.", wantText: "three four\nfive six\n"},
+		{name: "elsewhere", keys: "dwj.", wantText: "two three four\nsix\n"},
+		{name: "count replaced", keys: "dw2.", wantText: "four\nfive six\n"},
+		{name: "count kept", keys: "2dwj.", wantText: "three four\n\n"},
+		{name: "change", keys: "cwX<esc>w.", wantText: "X X three four\nfive six\n"},
+		{name: "insert", keys: "ix<esc>.", wantText: "xxone two three four\nfive six\n"},
+		{name: "lines", keys: "dd.", wantText: ""},
+		{name: "yank isn't a change", keys: "dwyy.", wantText: "three four\nfive six\n"},
+		{name: "command line in between", keys: "dw:<esc>.", wantText: "three four\nfive six\n"},
+		{name: "repeat undone", keys: "dw.u", wantText: "two three four\nfive six\n"},
+		{name: "repeated change undone", keys: "cwX<esc>w.u", wantText: "X two three four\nfive six\n"},
+		{name: "nothing to repeat", keys: "yy.", wantText: "one two three four\nfive six\n", wantErr: ui.ErrNoPreviousChange},
+	}
+
+	for _, tt := range tests {
+		t.Run(tt.name, func(t *testing.T) {
+			h := newTestHeadless(t, "one two three four\nfive six\n")
+			h.Type(tt.keys)
+
+			text, err := h.Text()
+			if err != nil {
+				t.Fatalf("Text() error = %v", err)
+			}
+			if text != tt.wantText {
+				t.Errorf("Type(%q) text = %q, want %q", tt.keys, text, tt.wantText)
+			}
+			if msg := h.Message(); tt.wantErr != nil && (!msg.IsError || msg.Text != tt.wantErr.Error()) {
+				t.Errorf("Type(%q) message = %+v, want %q", tt.keys, msg, tt.wantErr)
+			}
+		})
+	}
+}
+
+func TestHeadlessUndo(t *testing.T) {
+	// a command, or an insert with all that was typed in it, is undone as one
+	original := "one two three four\nfive six\n"
+	tests := []struct {
+		name       string
+		keys       string
+		wantText   string
+		wantLine   int
+		wantColumn int
+		wantErr    error
+	}{
+		{name: "delete words", keys: "d2wu", wantText: original},
+		{name: "change", keys: "wcwX<esc>u", wantText: original, wantColumn: 4},
+		{name: "insert", keys: "inew words <esc>u", wantText: original},
+		{name: "lines", keys: "2ddu", wantText: original},
+		{name: "elsewhere", keys: "jwdwggu", wantText: original, wantLine: 1, wantColumn: 5},
+		{name: "substitute", keys: ":%s/o/0/g<cr>u", wantText: original},
+		{name: "substitute confirmed", keys: ":%s/o/0/gc<cr>ynyu", wantText: original},
+		{name: "count", keys: "xxx2u", wantText: "ne two three four\nfive six\n"},
+		{name: "redo", keys: "dwu<c-r>", wantText: "two three four\nfive six\n"},
+		{name: "redo count", keys: "xxuu2<c-r>", wantText: "e two three four\nfive six\n"},
+		{name: "change drops redo", keys: "dwux<c-r>", wantText: "ne two three four\nfive six\n", wantErr: state.ErrNewestChange},
+		{name: "undo isn't repeated", keys: "dwu.", wantText: "two three four\nfive six\n"},
+		{name: "nothing to undo", keys: "u", wantText: original, wantErr: state.ErrOldestChange},
+	}
+
+	for _, tt := range tests {
+		t.Run(tt.name, func(t *testing.T) {
+			h := newTestHeadless(t, original)
+			h.Type(tt.keys)
+
+			text, err := h.Text()
+			if err != nil {
+				t.Fatalf("Text() error = %v", err)
+			}
+			if text != tt.wantText {
+				t.Errorf("Type(%q) text = %q, want %q", tt.keys, text, tt.wantText)
+			}
+			if line, col, _ := h.Cursor(); tt.wantErr == nil && (line != tt.wantLine || col != tt.wantColumn) {
+				t.Errorf("Type(%q) cursor = %d:%d, want %d:%d", tt.keys, line, col, tt.wantLine, tt.wantColumn)
+			}
+			if msg := h.Message(); tt.wantErr != nil && (!msg.IsError || msg.Text != tt.wantErr.Error()) {
+				t.Errorf("Type(%q) message = %+v, want %q", tt.keys, msg, tt.wantErr)
+			}
+		})
+	}
+}
+
+func TestHeadlessUndoModified(t *testing.T) {
+	h := newTestHeadless(t, "one\n")
+	h.Type("x:w<cr>")
+	h.athena.editor.WaitForSaves()
+
+	// undoing and redoing back to the text saved leaves the buffer unmodified
+	for _, step := range []struct {
+		keys         string
+		wantModified bool
+	}{
+		{keys: "xu", wantModified: false},
+		{keys: "u", wantModified: true},
+		{keys: "<c-r>", wantModified: false},
+	} {
+		h.Type(step.keys)
+		if modified, _ := h.athena.editor.Modified(); modified != step.wantModified {
+			t.Errorf("Modified() after %q = %v, want %v", step.keys, modified, step.wantModified)
+		}
+	}
+}
+
+func TestHeadlessLines(t *testing.T) {
+	tests := []struct {
+		name       string
+		keys       string
+		wantText   string
+		wantLine   int
+		wantColumn int
+	}{
+		{name: "join", keys: "J", wantText: "one two\nthree\n", wantColumn: 3},
+		{name: "join count", keys: "3J", wantText: "one two three\n", wantColumn: 7},
+		{name: "join last line", keys: "jjJ", wantText: "one\n  two\nthree\n", wantLine: 2},
+		{name: "indent", keys: ">>", wantText: "    one\n  two\nthree\n", wantColumn: 4},
+		{name: "indent count", keys: "3>>", wantText: "    one\n      two\n    three\n", wantColumn: 4},
+		{name: "indent motion", keys: ">j", wantText: "    one\n      two\nthree\n", wantColumn: 4},
+		{name: "dedent", keys: "j<<", wantText: "one\ntwo\nthree\n", wantLine: 1},
+		{name: "dedent at margin", keys: "<<", wantText: "one\n  two\nthree\n"},
+		{name: "join undone", keys: "JJu", wantText: "one two\nthree\n", wantColumn: 7},
+		{name: "indent undone", keys: "3>>u", wantText: "one\n  two\nthree\n"},
+	}
+
+	for _, tt := range tests {
+		t.Run(tt.name, func(t *testing.T) {
+			h := newTestHeadless(t, "one\n  two\nthree\n")
+			h.Type(tt.keys)
+
+			text, err := h.Text()
+			if err != nil {
+				t.Fatalf("Text() error = %v", err)
+			}
+			if text != tt.wantText {
+				t.Errorf("Type(%q) text = %q, want %q", tt.keys, text, tt.wantText)
+			}
+			line, col, err := h.Cursor()
+			if err != nil {
+				t.Fatalf("Cursor() error = %v", err)
+			}
+			if line != tt.wantLine || col != tt.wantColumn {
+				t.Errorf("Type(%q) cursor = %d:%d, want %d:%d", tt.keys, line, col, tt.wantLine, tt.wantColumn)
+			}
+		})
+	}
+}
+
+func TestHeadlessAutoIndent(t *testing.T) {
+	tests := []struct {
+		name     string
+		text     string
+		keys     string
+		wantText string
+	}{
+		{name: "kept", text: "  ab\n", keys: "llli<cr>x<esc>", wantText: "  a\n  xb\n"},
+		{name: "deeper", text: "  a{b\n", keys: "lllli<cr>x<esc>", wantText: "  a{\n      xb\n"},
+		{name: "off", text: "  ab\n", keys: ":set noai<cr>llli<cr>x<esc>", wantText: "  a\nxb\n"},
+	}
+
+	for _, tt := range tests {
+		t.Run(tt.name, func(t *testing.T) {
+			h := newTestHeadless(t, tt.text)
+			h.Type(tt.keys)
+
+			text, err := h.Text()
+			if err != nil {
+				t.Fatalf("Text() error = %v", err)
+			}
+			if text != tt.wantText {
+				t.Errorf("Type(%q) text = %q, want %q", tt.keys, text, tt.wantText)
+			}
+		})
+	}
+}
+
+func TestHeadlessDiagnostics(t *testing.T) {
+	h := newTestHeadless(t, "one\ntwo\nthree\n")
+	path, _ := h.athena.editor.FilePath()
+
+	h.athena.editor.SetDiagnostics(path, []lsp.Diagnostic{
+		{Range: lsp.Range{Start: lsp.Position{Line: 1, Character: 0}, End: lsp.Position{Line: 1, Character: 3}}, Severity: lsp.SeverityWarning},
+		{Range: lsp.Range{Start: lsp.Position{Line: 1, Character: 1}, End: lsp.Position{Line: 1, Character: 2}}},
+	})
+	h.athena.render()
+	frame := h.Frame()
+	if !strings.HasPrefix(frame[0], " ") || !strings.HasPrefix(frame[1], "E") {
+		t.Errorf("Frame() rows = %q, %q, want the error sign on the second only", frame[0], frame[1])
+	}
+	if status := frame[len(frame)-2]; !strings.Contains(status, " E1 W1 ") {
+		t.Errorf("status bar = %q, want the counts of errors and warnings", status)
+	}
+	cells, width, _ := h.screen.GetContents()
+	if _, _, attrs := cells[width+h.athena.gutterWidth()].Style.Decompose(); attrs&tcell.AttrUnderline == 0 {
+		t.Errorf("diagnostic text not underlined")
+	}
+
+	h.athena.editor.SetDiagnostics(path, nil)
+	h.athena.render()
+	frame = h.Frame()
+	if strings.HasPrefix(frame[1], "E") {
+		t.Errorf("Frame() row = %q after the diagnostics were cleared", frame[1])
+	}
+	if status := frame[len(frame)-2]; strings.Contains(status, " E1") {
+		t.Errorf("status bar = %q after the diagnostics were cleared", status)
+	}
+}
+
+func TestHeadlessVersionControl(t *testing.T) {
+	h := newTestHeadless(t, "one\ntwo\nthree\n")
+	path, _ := h.athena.editor.FilePath()
+
+	h.athena.editor.SetVCS(path, "main", "one\r\nfour\r\nthree\r\n", true)
+	h.athena.render()
+	if status := h.Frame()[len(h.Frame())-2]; !strings.Contains(status, " main ") || !strings.Contains(status, " +1 -1 ") {
+		t.Errorf("status bar = %q, want the branch and the lines changed", status)
+	}
+
+	h.Type("jdd")
+	if hunks, ok := h.athena.editor.Hunks(); !ok || len(hunks) != 1 || hunks[0] != (vcs.Hunk{Start: 1, Removed: 1}) {
+		t.Errorf("Hunks() = %+v, %v, want the committed line removed", hunks, ok)
+	}
+
+	h.athena.editor.SetVCS(path, "", "", false)
+	h.athena.render()
+	if status := h.Frame()[len(h.Frame())-2]; strings.Contains(status, " main ") || strings.Contains(status, " +") {
+		t.Errorf("status bar = %q outside of a repository", status)
+	}
+}
+
+func TestHeadlessGutterClickDiagnostic(t *testing.T) {
+	tests := []struct {
+		name    string
+		row     int
+		wantMsg string
+	}{
+		{name: "most severe first", row: 1, wantMsg: "undefined: two"},
+		{name: "line without problems", row: 0, wantMsg: ""},
+	}
+	for _, tt := range tests {
+		t.Run(tt.name, func(t *testing.T) {
+			h := newTestHeadless(t, "one\ntwo\nthree\n")
+			path, _ := h.athena.editor.FilePath()
+			h.athena.editor.SetDiagnostics(path, []lsp.Diagnostic{
+				{Range: lsp.Range{Start: lsp.Position{Line: 1, Character: 0}, End: lsp.Position{Line: 1, Character: 3}}, Severity: lsp.SeverityWarning, Message: "unused: two"},
+				{Range: lsp.Range{Start: lsp.Position{Line: 1, Character: 0}, End: lsp.Position{Line: 1, Character: 3}}, Severity: lsp.SeverityError, Message: "undefined: two"},
+			})
+			h.athena.render()
+
+			// the diagnostics column is the first of the default gutters
+			h.athena.handleEvent(tcell.NewEventMouse(0, tt.row, tcell.Button1, 0))
+			h.athena.render()
+			if msg := h.Message(); msg.Text != tt.wantMsg {
+				t.Errorf("clicking the gutter of row %d message = %q, want %q", tt.row, msg.Text, tt.wantMsg)
+			}
+		})
+	}
+}
+
+func TestHeadlessLanguageServerFailed(t *testing.T) {
+	h := newTestHeadless(t, "one\n")
+	h.athena.cfg.Languages["txt"] = config.LanguageOptions{LanguageServer: []string{"athena-no-such-server"}}
+	h.athena.render()
+
+	var err error
+	for deadline := time.Now().Add(5 * time.Second); ; time.Sleep(10 * time.Millisecond) {
+		if _, err = h.athena.currentLanguageServer(); !errors.Is(err, errLanguageServerStarting) {
+			break
+		}
+		if time.Now().After(deadline) {
+			t.Fatal("language server still starting after 5s")
+		}
+	}
+	// kept, so it isn't started again, and words are still completed
+	if !errors.Is(err, ui.ErrNoLanguageServer) || !strings.Contains(err.Error(), "athena-no-such-server failed to start") {
+		t.Errorf("currentLanguageServer() error = %v, want why the server failed to start", err)
+	}
+	if _, again := h.athena.currentLanguageServer(); again != err {
+		t.Errorf("currentLanguageServer() error = %v the second time, want %v", again, err)
+	}
+}
+
+func TestHeadlessDiffSigns(t *testing.T) {
+	h := newTestHeadless(t, "one\ntwo\nthree\nfour\nfive\n")
+	h.athena.cfg.Editor.Gutters = []config.GutterOption{config.GutterDiff, config.GutterLineNumbers, config.GutterSpacer}
+	path, _ := h.athena.editor.FilePath()
+
+	h.athena.editor.SetVCS(path, "main", "one\nTWO\nthree\nfive\nsix\n", true)
+	h.athena.render()
+	frame := h.Frame()
+	for row, want := range []rune{' ', '▎', ' ', '▎', '▁'} {
+		if got := []rune(frame[row])[0]; got != want {
+			t.Errorf("Frame() row %d = %q, want the sign %q", row, frame[row], want)
+		}
+	}
+
+	for _, step := range []struct {
+		keys     string
+		wantLine int
+		wantErr  error
+	}{
+		{keys: "]c", wantLine: 1},
+		{keys: "]c", wantLine: 3},
+		{keys: "]c", wantLine: 4}, // the removed last line
+		{keys: "]c", wantLine: 4, wantErr: editor.ErrNoHunk},
+		{keys: "2[c", wantLine: 1},
+	} {
+		h.Type(step.keys)
+		if line, _, _ := h.Cursor(); line != step.wantLine {
+			t.Fatalf("Type(%q) cursor line = %d, want %d", step.keys, line, step.wantLine)
+		}
+		if msg := h.Message(); step.wantErr != nil && (!msg.IsError || msg.Text != step.wantErr.Error()) {
+			t.Errorf("Type(%q) message = %+v, want %q", step.keys, msg, step.wantErr)
+		}
+	}
+}
+
+func TestHeadlessSigns(t *testing.T) {
+	h := newTestHeadless(t, "one\ntwo\nthree\nfour\n")
+	h.athena.cfg.Editor.Gutters = []config.GutterOption{config.GutterSigns, config.GutterLineNumbers, config.GutterSpacer}
+	path, _ := h.athena.editor.FilePath()
+
+	h.Type("ma")
+	h.athena.editor.SetVCS(path, "main", "one\nTWO\nthree\nfour\n", true)
+	h.athena.editor.SetDiagnostics(path, []lsp.Diagnostic{
+		{Range: lsp.Range{Start: lsp.Position{Line: 1, Character: 0}, End: lsp.Position{Line: 1, Character: 3}}, Severity: lsp.SeverityWarning},
+	})
+	h.athena.editor.SetSigns(path, "breakpoints", []editor.Sign{{Line: 2, Kind: editor.SignInfo, Text: '●'}})
+	h.athena.render()
+	frame := h.Frame()
+	for row, want := range []rune{'a', 'W', '●', ' '} {
+		if got := []rune(frame[row])[0]; got != want {
+			t.Errorf("Frame() row %d = %q, want the sign %q", row, frame[row], want)
+		}
+	}
+
+	h.athena.editor.SetDiagnostics(path, nil)
+	h.athena.editor.SetSigns(path, "breakpoints", nil)
+	h.athena.render()
+	frame = h.Frame()
+	for row, want := range []rune{'a', '▎', ' '} {
+		if got := []rune(frame[row])[0]; got != want {
+			t.Errorf("Frame() row %d = %q after the signs were removed, want %q", row, frame[row], want)
+		}
+	}
+}
+
+func TestHeadlessCursorLine(t *testing.T) {
+	h := newTestHeadless(t, "one\ntwo\nthree\n")
+	h.athena.cfg.Editor.CursorLine = true
+	h.athena.render()
+
+	highlighted := func(x, y int) bool {
+		cells, width, _ := h.screen.GetContents()
+		_, bg, _ := cells[y*width+h.athena.gutterWidth()+x].Style.Decompose()
+		return bg == treesitter.ColorBgHighlight
+	}
+	if !highlighted(0, 0) || !highlighted(40, 0) || highlighted(0, 1) {
+		t.Errorf("cursor line not highlighted across the first row alone")
+	}
+	h.Type("j")
+	if highlighted(40, 0) || !highlighted(40, 1) {
+		t.Errorf("cursor line highlight didn't follow the cursor down")
+	}
+
+	h.athena.cfg.Editor.CursorLine = false
+	h.athena.cfg.Editor.CursorColumn = true
+	h.Type("l")
+	if !highlighted(1, 0) || !highlighted(1, 2) || highlighted(0, 0) || highlighted(2, 2) {
+		t.Errorf("cursor column not highlighted down the second column alone")
+	}
+}
+
+func TestHeadlessSoftWrapWords(t *testing.T) {
+	h := newTestHeadless(t, strings.Repeat("words ", 20)+"\n")
+	h.athena.cfg.Editor.SoftWrap = true
+	h.athena.render()
+
+	frame := h.Frame()
+	if got, want := strings.TrimSpace(frame[0]), "1 "+strings.TrimSpace(strings.Repeat("words ", 12)); got != want {
+		t.Errorf("Frame() row 0 = %q, want %q", got, want)
+	}
+	if got, want := strings.TrimSpace(frame[1]), strings.TrimSpace(strings.Repeat("words ", 8)); got != want {
+		t.Errorf("Frame() row 1 = %q, want the words that didn't fit, whole", got)
+	}
+}
+
+func TestHeadlessZenFrame(t *testing.T) {
+	h := newTestHeadless(t, strings.Repeat("words ", 10)+"\n")
+	h.athena.cfg.Editor.Zen.Width = 40
+	// the other window stays as it is
+	h.Type("<c-w>s")
+
+	// centered in 40 columns, wrapped whatever the soft-wrap setting, without gutters or status bar
+	h.Type(":zen<cr>")
+	frame := h.Frame()
+	if got, want := frame[0], strings.Repeat(" ", 20)+strings.TrimSpace(strings.Repeat("words ", 6)); got != want {
+		t.Errorf("zen Frame() row 0 = %q, want %q", got, want)
+	}
+	if got, want := frame[1], strings.Repeat(" ", 20)+strings.TrimSpace(strings.Repeat("words ", 4)); got != want {
+		t.Errorf("zen Frame() row 1 = %q, want %q", got, want)
+	}
+	if status := frame[len(frame)-2]; status != "" {
+		t.Errorf("zen status bar row = %q, want it blank", status)
+	}
+	if h.athena.cfg.Editor.SoftWrap {
+		t.Errorf("zen mode turned the soft-wrap setting on")
+	}
+
+	h.Type(":zen<cr>")
+	frame = h.Frame()
+	for _, row := range []int{0, 11} {
+		if got, want := strings.TrimSpace(frame[row]), "1 "+strings.TrimSpace(strings.Repeat("words ", 10)); got != want {
+			t.Errorf("Frame() row %d = %q after leaving zen mode, want %q", row, got, want)
+		}
+	}
+	if status := frame[len(frame)-2]; !strings.Contains(status, "test.txt") {
+		t.Errorf("status bar = %q after leaving zen mode, want it back", status)
+	}
+}
+
+func TestHeadlessDisplayRowsWrappedWords(t *testing.T) {
+	// wrapped to 10 cells, "aaaa bbbbbbb" breaks after the blank, the second row starting at column 5
+	tests := []struct {
+		name       string
+		keys       string // typed before the moves
+		moves      []int  // display rows to move, one after another
+		wantLine   int
+		wantColumn int
+	}{
+		{name: "up from a word that wrapped", keys: "lllllll", moves: []int{-1}, wantColumn: 2},
+		{name: "down onto it", keys: "ll", moves: []int{1}, wantColumn: 7},
+		{name: "there and back", keys: "lllllll", moves: []int{-1, 1}, wantColumn: 7},
+		{name: "down to the next line", keys: "lllllll", moves: []int{1}, wantLine: 1, wantColumn: 2},
+		{name: "up from the next line", keys: "jll", moves: []int{-1}, wantColumn: 7},
+	}
+
+	for _, tt := range tests {
+		t.Run(tt.name, func(t *testing.T) {
+			h := newTestHeadless(t, "aaaa bbbbbbb\nccccc\n")
+			h.Type(tt.keys)
+			for _, rows := range tt.moves {
+				if err := h.athena.editor.MoveDisplayRows(rows, 10, false); err != nil {
+					t.Fatalf("MoveDisplayRows(%d) error = %v", rows, err)
+				}
+			}
+			line, col, _ := h.Cursor()
+			if line != tt.wantLine || col != tt.wantColumn {
+				t.Errorf("cursor = %d:%d, want %d:%d", line, col, tt.wantLine, tt.wantColumn)
+			}
+		})
+	}
+}
+
+func TestHeadlessCursorAfterGraphemes(t *testing.T) {
+	tests := []struct {
+		name  string
+		text  string
+		moves int
+		want  int // cells from the start of the text
+	}{
+		{name: "wide", text: "日本x\n", moves: 2, want: 4},
+		{name: "combining mark", text: "e\u0301x\n", moves: 1, want: 1},
+		{name: "emoji sequence", text: "👩\u200d👧x\n", moves: 1, want: 2},
+		{name: "tab", text: "\tx\n", moves: 1, want: 4},
+		{name: "control character", text: "\x01x\n", moves: 1, want: 2},
+		{name: "zero width space", text: "\u200bx\n", moves: 1, want: 1},
+	}
+
+	for _, tt := range tests {
+		t.Run(tt.name, func(t *testing.T) {
+			h := newTestHeadless(t, tt.text)
+			h.Type(strings.Repeat("l", tt.moves))
+			x, _, _, ok := h.athena.focus.document.Cursor()
+			if want := h.athena.gutterWidth() + tt.want; !ok || x != want {
+				t.Errorf("Cursor() x = %d, %v, want %d", x, ok, want)
+			}
+		})
+	}
+}
+
+func TestHeadlessControlCharacters(t *testing.T) {
+	h := newTestHeadless(t, "a\x01b\x7f\n")
+	h.athena.render()
+	if row := h.Frame()[0]; !strings.HasSuffix(row, "a^Ab^?") {
+		t.Errorf("Frame() row 0 = %q, want the control characters in caret notation", row)
+	}
+}
+
+func TestHeadlessEOFMarker(t *testing.T) {
+	h := newTestHeadless(t, "one\n")
+	h.athena.render()
+	want := strings.Repeat(" ", h.athena.gutterWidth()) + "~"
+	if row := h.Frame()[3]; row != want {
+		t.Errorf("Frame() row 3 = %q, want %q", row, want)
+	}
+	cells, width, _ := h.screen.GetContents()
+	if fg, _, _ := cells[3*width+h.athena.gutterWidth()].Style.Decompose(); fg != treesitter.ColorFgGutter {
+		t.Errorf("marker drawn in %v, want it dimmed", fg)
+	}
+
+	h.athena.cfg.Editor.EOFMarker = ""
+	h.athena.render()
+	if row := h.Frame()[3]; row != "" {
+		t.Errorf("Frame() row 3 = %q without a marker, want it blank", row)
+	}
+}
+
+func TestHeadlessScrollbar(t *testing.T) {
+	h := newTestHeadless(t, strings.Repeat("line\n", 100))
+	path, _ := h.athena.editor.FilePath()
+	h.athena.editor.SetDiagnostics(path, []lsp.Diagnostic{
+		{Range: lsp.Range{Start: lsp.Position{Line: 50}, End: lsp.Position{Line: 50, Character: 4}}},
+	})
+	h.athena.render()
+	cells, width, _ := h.screen.GetContents()
+	if _, bg, _ := cells[width-1].Style.Decompose(); bg == treesitter.ColorFgGutter {
+		t.Errorf("scrollbar drawn while turned off")
+	}
+
+	h.athena.cfg.Editor.Scrollbar = true
+	h.athena.render()
+	cells, width, _ = h.screen.GetContents()
+	// 22 rows of 100 lines: the thumb takes the first 5 rows, and line 50 is on row 11
+	for row := 0; row < 22; row++ {
+		cell := cells[row*width+width-1]
+		fg, bg, _ := cell.Style.Decompose()
+		switch {
+		case row == 11:
+			if len(cell.Runes) == 0 || cell.Runes[0] != '─' || fg == tcell.ColorDefault {
+				t.Errorf("row %d = %q in %v, want the error marked", row, cell.Runes, fg)
+			}
+		case row < 5:
+			if bg != treesitter.ColorFgGutter {
+				t.Errorf("row %d background = %v, want the thumb", row, bg)
+			}
+		default:
+			if bg != treesitter.ColorBgHighlight {
+				t.Errorf("row %d background = %v, want the track", row, bg)
+			}
+		}
+	}
+
+	h.Type("ge")
+	cells, width, _ = h.screen.GetContents()
+	if _, bg, _ := cells[width-1].Style.Decompose(); bg == treesitter.ColorFgGutter {
+		t.Errorf("thumb still at the top after going to the bottom")
+	}
+}
+
+func TestHeadlessCompletion(t *testing.T) {
+	items := []editor.CompletionItem{
+		{Label: "alpha", Text: "alpha"},
+		{Label: "beta", Detail: "func()", Text: "beta()"},
+	}
+	tests := []struct {
+		name      string
+		before    string // typed before the items arrive
+		after     string // typed once they are shown
+		wantText  string
+		wantShown bool
+	}{
+		{name: "first", before: "i", after: "<cr>", wantText: "alphaone\n"},
+		{name: "next", before: "i", after: "<c-n><cr>", wantText: "beta()one\n"},
+		{name: "wraps", before: "i", after: "<c-p><c-p><cr>", wantText: "alphaone\n"},
+		{name: "filtered", before: "ib", after: "<cr>", wantText: "beta()one\n"},
+		{name: "typing on", before: "i", after: "be<cr>", wantText: "beta()one\n"},
+		{name: "left the word", before: "i", after: "x <cr>", wantText: "x \none\n"},
+		{name: "still open", before: "i", after: "b", wantText: "bone\n", wantShown: true},
+		{name: "closed by esc", before: "i", after: "<esc>i<cr>", wantText: "\none\n"},
+	}
+
+	for _, tt := range tests {
+		t.Run(tt.name, func(t *testing.T) {
+			h := newTestHeadless(t, "one\n")
+			path, _ := h.athena.editor.FilePath()
+			h.Type(tt.before)
+			h.athena.editor.SetCompletion(path, items)
+			h.athena.render()
+			if frame := h.Frame(); !strings.Contains(frame[1], "alpha") && !strings.Contains(frame[1], "beta") {
+				t.Fatalf("Frame() row = %q, want the completion menu under the cursor", frame[1])
+			}
+
+			h.Type(tt.after)
+			text, err := h.Text()
+			if err != nil {
+				t.Fatalf("Text() error = %v", err)
+			}
+			if text != tt.wantText {
+				t.Errorf("Type(%q) text = %q, want %q", tt.after, text, tt.wantText)
+			}
+			if _, shown := h.athena.editor.Completion(); shown != tt.wantShown {
+				t.Errorf("Completion() shown = %v, want %v", shown, tt.wantShown)
+			}
+		})
+	}
+}
+
+func TestHeadlessCompletionMatched(t *testing.T) {
+	h := newTestHeadless(t, "one\n")
+	path, _ := h.athena.editor.FilePath()
+	h.Type("iab")
+	h.athena.editor.SetCompletion(path, []editor.CompletionItem{
+		{Label: "alphabet", Text: "alphabet"},
+		{Label: "cab", Text: "cab"},
+	})
+	h.athena.render()
+
+	// the tightest match, and the shorter label first
+	frame := h.Frame()
+	x := strings.Index(frame[1], "cab")
+	if x < 0 || !strings.Contains(frame[2], "alphabet") {
+		t.Fatalf("Frame() rows = %q, %q, want cab then alphabet", frame[1], frame[2])
+	}
+	cells, width, _ := h.screen.GetContents()
+	for i, want := range []bool{false, true, true} {
+		if _, _, attrs := cells[width+x+i].Style.Decompose(); (attrs&tcell.AttrBold != 0) != want {
+			t.Errorf("%q in the menu bold = %v, want %v", "cab"[i], !want, want)
+		}
+	}
+}
+
+func TestHeadlessCompleteWords(t *testing.T) {
+	tests := []struct {
+		name     string
+		text     string
+		keys     string
+		wantText string
+		wantMsg  string
+	}{
+		{name: "first word", text: "alpha beta\n\n", keys: "ji<c-n><cr>", wantText: "alpha beta\nalpha\n"},
+		{name: "tab", text: "alpha beta\n\n", keys: "ji<c-n><tab><cr>", wantText: "alpha beta\nbeta\n"},
+		{name: "filtered", text: "alpha beta\n\n", keys: "jib<c-n><cr>", wantText: "alpha beta\nbeta\n"},
+		{name: "typed word left out", text: "alpha\n\n", keys: "jialp<c-n><cr>", wantText: "alpha\nalpha\n"},
+		{name: "no words", text: "\n", keys: "i<c-n>", wantText: "\n", wantMsg: "No completions"},
+	}
+
+	for _, tt := range tests {
+		t.Run(tt.name, func(t *testing.T) {
+			h := newTestHeadless(t, tt.text)
+			h.Type(tt.keys)
+			text, err := h.Text()
+			if err != nil {
+				t.Fatalf("Text() error = %v", err)
+			}
+			if text != tt.wantText {
+				t.Errorf("Type(%q) text = %q, want %q", tt.keys, text, tt.wantText)
+			}
+			if msg := h.Message(); msg.Text != tt.wantMsg {
+				t.Errorf("Type(%q) message = %q, want %q", tt.keys, msg.Text, tt.wantMsg)
+			}
+		})
+	}
+}
+
+func TestHeadlessGoToLocation(t *testing.T) {
+	h := newTestHeadless(t, "one\ntwo\nthree\n")
+	from, _ := h.athena.editor.FilePath()
+	other := filepath.Join(filepath.Dir(from), "other.txt")
+	if err := os.WriteFile(other, []byte("a\nbcd\n"), 0644); err != nil {
+		t.Fatal(err)
+	}
+
+	h.Type("jl")
+	if err := h.athena.editor.GoToLocation(from, other, lsp.Position{Line: 1, Character: 2}); err != nil {
+		t.Fatalf("GoToLocation() error = %v", err)
+	}
+	if path, _ := h.athena.editor.FilePath(); path != other {
+		t.Errorf("FilePath() = %q, want %q", path, other)
+	}
+	if line, col, _ := h.Cursor(); line != 1 || col != 2 {
+		t.Errorf("Cursor() = %d, %d, want 1, 2", line, col)
+	}
+
+	// <c-o> goes back to where the jump was made
+	h.Type("<c-o>")
+	if path, _ := h.athena.editor.FilePath(); path != from {
+		t.Errorf("FilePath() after <c-o> = %q, want %q", path, from)
+	}
+	if line, col, _ := h.Cursor(); line != 1 || col != 1 {
+		t.Errorf("Cursor() after <c-o> = %d, %d, want 1, 1", line, col)
+	}
+	h.Type("<c-o>")
+	if msg := h.Message(); !msg.IsError || msg.Text != state.ErrJumpListStart.Error() {
+		t.Errorf("Message() = %+v, want %q", msg, state.ErrJumpListStart)
+	}
+
+	// a location for a buffer that was left meanwhile is dropped
+	_ = h.athena.editor.GoToLocation(other, from, lsp.Position{Line: 2})
+	if line, _, _ := h.Cursor(); line != 1 {
+		t.Errorf("Cursor() line = %d after a stale location, want 1", line)
+	}
+}
+
+func TestHeadlessJumpList(t *testing.T) {
+	tests := []struct {
+		name     string
+		keys     string
+		wantLine int
+		wantErr  error
+	}{
+		{name: "back from gg", keys: "jgg<c-o>", wantLine: 1},
+		{name: "forward again", keys: "jjgg<c-o><tab>", wantLine: 0},
+		{name: "back from search", keys: "j/three<cr><c-o>", wantLine: 1},
+		{name: "count", keys: "j/three<cr>gg2<c-o>", wantLine: 1},
+		{name: "nothing to go back to", keys: "j<c-o>", wantLine: 1, wantErr: state.ErrJumpListStart},
+		{name: "nothing to go forward to", keys: "jgg<tab>", wantErr: state.ErrJumpListEnd},
+	}
+
+	for _, tt := range tests {
+		t.Run(tt.name, func(t *testing.T) {
+			h := newTestHeadless(t, "one\ntwo\nthree\n")
+			h.Type(tt.keys)
+
+			if line, _, _ := h.Cursor(); line != tt.wantLine {
+				t.Errorf("Type(%q) cursor line = %d, want %d", tt.keys, line, tt.wantLine)
+			}
+			msg := h.Message()
+			if tt.wantErr != nil && (!msg.IsError || msg.Text != tt.wantErr.Error()) {
+				t.Errorf("Type(%q) message = %+v, want %q", tt.keys, msg, tt.wantErr)
+			}
+		})
+	}
+}
+
+func TestHeadlessMarks(t *testing.T) {
+	tests := []struct {
+		name     string
+		keys     string
+		wantLine int
+		wantCol  int
+		wantErr  error
+	}{
+		{name: "line of mark", keys: "jlllmajj'a", wantLine: 1, wantCol: 2},
+		{name: "position of mark", keys: "jlllmajj`a", wantLine: 1, wantCol: 3},
+		{name: "moved by edits", keys: "jlllmaggi<cr><esc>`a", wantLine: 2, wantCol: 3},
+		{name: "previous context", keys: "jgg''", wantLine: 1, wantCol: 2},
+		{name: "previous context twice", keys: "jgg''``", wantLine: 0},
+		{name: "back from a mark", keys: "majj`a``", wantLine: 2},
+		{name: "not set", keys: "j'b", wantLine: 1, wantErr: state.ErrMarkNotSet},
+		{name: "invalid", keys: "mA", wantErr: state.ErrInvalidMark},
+	}
+
+	for _, tt := range tests {
+		t.Run(tt.name, func(t *testing.T) {
+			h := newTestHeadless(t, "one\n  two\nthree\n")
+			h.Type(tt.keys)
+
+			if line, col, _ := h.Cursor(); line != tt.wantLine || col != tt.wantCol {
+				t.Errorf("Type(%q) cursor = %d, %d, want %d, %d", tt.keys, line, col, tt.wantLine, tt.wantCol)
+			}
+			msg := h.Message()
+			if tt.wantErr != nil && (!msg.IsError || msg.Text != tt.wantErr.Error()) {
+				t.Errorf("Type(%q) message = %+v, want %q", tt.keys, msg, tt.wantErr)
+			}
+		})
+	}
+}
+
+func TestHeadlessHover(t *testing.T) {
+	tests := []struct {
+		name      string
+		keys      string
+		wantCol   int
+		wantShown bool
+	}{
+		{name: "closed by esc", keys: "<esc>"},
+		{name: "closed by motion", keys: "l", wantCol: 1},
+		{name: "closed by other keys", keys: "<c-g>"},
+	}
+
+	for _, tt := range tests {
+		t.Run(tt.name, func(t *testing.T) {
+			h := newTestHeadless(t, "one\ntwo\n")
+			path, _ := h.athena.editor.FilePath()
+			h.athena.editor.SetHover(path, 0, "```go\nfunc one()\n```\n\nDoes one.")
+			h.athena.render()
+			frame := h.Frame()
+			if !strings.Contains(frame[2], "func one()") || !strings.Contains(frame[4], "Does one.") {
+				t.Fatalf("Frame() rows = %q, want the hover popup under the cursor", frame[1:6])
+			}
+			if strings.Contains(strings.Join(frame, "\n"), "```") {
+				t.Errorf("Frame() shows code fences")
+			}
+
+			h.Type(tt.keys)
+			if _, shown := h.athena.editor.Hover(); shown != tt.wantShown {
+				t.Errorf("Type(%q) Hover() shown = %v, want %v", tt.keys, shown, tt.wantShown)
+			}
+			if _, col, _ := h.Cursor(); col != tt.wantCol {
+				t.Errorf("Type(%q) cursor column = %d, want %d", tt.keys, col, tt.wantCol)
+			}
+		})
+	}
+}
+
+func TestHeadlessHoverWithoutText(t *testing.T) {
+	h := newTestHeadless(t, "one\n")
+	path, _ := h.athena.editor.FilePath()
+	h.athena.editor.SetHover(path, 0, "")
+	if _, shown := h.athena.editor.Hover(); shown {
+		t.Errorf("Hover() shown with no text")
+	}
+	if msg := h.Message(); !msg.IsError || msg.Text != "No hover information" {
+		t.Errorf("Message() = %+v, want %q", msg, "No hover information")
+	}
+}
+
+func TestHeadlessKeyTimeout(t *testing.T) {
+	tests := []struct {
+		name     string
+		wait     time.Duration
+		wantLine int
+	}{
+		// "gg" goes to the top; once the first g times out, "gj" moves down a row instead
+		{name: "within timeout", wait: 100 * time.Millisecond, wantLine: 0},
+		{name: "timed out", wait: 2 * time.Second, wantLine: 2},
+	}
+
+	for _, tt := range tests {
+		t.Run(tt.name, func(t *testing.T) {
+			h := newTestHeadless(t, "one\ntwo\nthree\n")
+			h.Type("jg")
+			h.Wait(tt.wait)
+			h.Type("gj")
+
+			line, _, err := h.Cursor()
+			if err != nil {
+				t.Fatalf("Cursor() error = %v", err)
+			}
+			if line != tt.wantLine {
+				t.Errorf("cursor line = %d, want %d", line, tt.wantLine)
+			}
+		})
+	}
+}
+
+func TestHeadlessLeaderKey(t *testing.T) {
+	h := newTestHeadless(t, "one\ntwo\nthree\n")
+	h.athena.cfg.Keymap.Normal["<space>"] = map[string]interface{}{"j": "move_down"}
+
+	h.Type("<space>")
+	if pending := h.athena.editor.PendingKeys(); pending != "<space>" {
+		t.Errorf("PendingKeys() = %q, want %q", pending, "<space>")
+	}
+	h.Type("j")
+	if line, _, _ := h.Cursor(); line != 1 {
+		t.Errorf("cursor line after <space>j = %d, want 1", line)
+	}
+}
+
+func TestHeadlessNamedCommands(t *testing.T) {
+	tests := []struct {
+		name     string
+		keys     string
+		wantLine int
+		wantErr  string
+	}{
+		{name: "bound alias", keys: "x", wantLine: 1},
+		{name: "run", keys: ":run move_down<cr>", wantLine: 1},
+		{name: "run with count", keys: ":run 2 move_down<cr>", wantLine: 2},
+		{name: "run alias", keys: ":run down<cr>", wantLine: 1},
+		{name: "prompt alias", keys: ":Down<cr>", wantLine: 1},
+		{name: "prompt alias of ex command", keys: ":Rn move_down<cr>", wantLine: 1},
+		{name: "unknown", keys: ":run nope<cr>", wantErr: "not a named command: nope"},
+	}
+	for _, tt := range tests {
+		t.Run(tt.name, func(t *testing.T) {
+			h := newTestHeadless(t, "one\ntwo\nthree\n")
+			h.athena.cfg.Aliases = map[string]string{"down": "move_down", "Down": "move_down", "Rn": "run"}
+			h.athena.cfg.Keymap.Normal["x"] = "down"
+
+			h.Type(tt.keys)
+			if line, _, _ := h.Cursor(); line != tt.wantLine {
+				t.Errorf("cursor line after %q = %d, want %d", tt.keys, line, tt.wantLine)
+			}
+			if msg := h.Message(); tt.wantErr != "" && (!msg.IsError || msg.Text != tt.wantErr) {
+				t.Errorf("Message() = %q, want the error %q", msg.Text, tt.wantErr)
+			}
+		})
+	}
+}
+
+func TestHeadlessFrame(t *testing.T) {
+	h := newTestHeadless(t, "one\ntwo\n")
+
+	frame := h.Frame()
+	if len(frame) != 24 || !strings.Contains(frame[0], "one") || !strings.Contains(frame[1], "two") {
+		t.Fatalf("Frame() = %q, want the text on the first rows", frame)
+	}
+
+	h.Type(":set")
+	frame = h.Frame()
+	if last := frame[len(frame)-1]; !strings.HasPrefix(last, ":set") {
+		t.Errorf("Frame() last row = %q, want the prompt %q", last, ":set")
+	}
+}
+
+func TestHeadlessCursor(t *testing.T) {
+	h := newTestHeadless(t, "one\ntwo\n")
+	x0, y0, _ := h.screen.GetCursor()
+
+	h.Type("jl")
+	x, y, shown := h.screen.GetCursor()
+	if !shown || x != x0+1 || y != y0+1 {
+		t.Errorf("cursor after jl = %d,%d shown %v, want %d,%d shown", x, y, shown, x0+1, y0+1)
+	}
+	if _, _, style, _ := h.athena.focus.document.Cursor(); style != tcell.CursorStyleSteadyBlock {
+		t.Errorf("normal mode cursor style = %v, want steady block", style)
+	}
+
+	h.Type("i")
+	if _, _, style, _ := h.athena.focus.document.Cursor(); style != tcell.CursorStyleSteadyBar {
+		t.Errorf("insert mode cursor style = %v, want steady bar", style)
+	}
+
+	h.Type("<esc>:")
+	if _, _, shown := h.screen.GetCursor(); shown {
+		t.Errorf("cursor shown with the prompt open")
+	}
+}
+
+func TestHeadlessWindows(t *testing.T) {
+	tests := []struct {
+		name        string
+		keys        string
+		wantWindows int
+		wantLine    int
+		wantQuit    bool
+	}{
+		{name: "split", keys: "<c-w>s", wantWindows: 2},
+		{name: "vertical split", keys: ":vsplit<cr>", wantWindows: 2},
+		{name: "cursor per window", keys: "<c-w>sjj<c-w>j", wantWindows: 2},
+		{name: "cursor kept", keys: "<c-w>sjj<c-w>j<c-w>k", wantWindows: 2, wantLine: 2},
+		{name: "next window", keys: "<c-w>vj<c-w>w<c-w>w", wantWindows: 2, wantLine: 1},
+		{name: "no window there", keys: "<c-w>sj<c-w>k", wantWindows: 2, wantLine: 1},
+		{name: "close", keys: "<c-w>sj<c-w>c", wantWindows: 1},
+		{name: "close last", keys: "<c-w>c", wantWindows: 1},
+		{name: "only", keys: "<c-w>s<c-w>v<c-w>o", wantWindows: 1},
+		{name: "quit closes window", keys: "<c-w>s:q<cr>", wantWindows: 1},
+		{name: "quit last window", keys: "<c-w>s:q<cr>:q<cr>", wantWindows: 1, wantQuit: true},
+	}
+
+	for _, tt := range tests {
+		t.Run(tt.name, func(t *testing.T) {
+			h := newTestHeadless(t, "one\ntwo\nthree\n")
+			h.Type(tt.keys)
+
+			if n := len(h.athena.layout.windows()); n != tt.wantWindows {
+				t.Errorf("Type(%q) windows = %d, want %d", tt.keys, n, tt.wantWindows)
+			}
+			if line, _, _ := h.Cursor(); line != tt.wantLine {
+				t.Errorf("Type(%q) cursor line = %d, want %d", tt.keys, line, tt.wantLine)
+			}
+			if h.Quit() != tt.wantQuit {
+				t.Errorf("Type(%q) quit = %v, want %v", tt.keys, h.Quit(), tt.wantQuit)
+			}
+		})
+	}
+}
+
+func TestHeadlessFrameAfterEdits(t *testing.T) {
+	// every key is drawn, so lines left alone are drawn from the rows kept for them
+	tests := []struct {
+		name      string
+		keys      string
+		wantLines []string
+	}{
+		{name: "line added above", keys: "jOnew<esc>", wantLines: []string{"alpha", "new", "beta", "gamma"}},
+		{name: "line deleted", keys: "jdd", wantLines: []string{"alpha", "gamma"}},
+		{name: "lines joined", keys: "J", wantLines: []string{"alpha beta", "gamma"}},
+		{name: "start of a line edited", keys: "jIx<esc>k", wantLines: []string{"alpha", "xbeta", "gamma"}},
+		{name: "deleted line put back", keys: "jddkp", wantLines: []string{"alpha", "beta", "gamma"}},
+		{name: "deletion undone", keys: "jddu", wantLines: []string{"alpha", "beta", "gamma"}},
+		{name: "cursor moved away", keys: "jjkk", wantLines: []string{"alpha", "beta", "gamma"}},
+	}
+
+	for _, tt := range tests {
+		t.Run(tt.name, func(t *testing.T) {
+			h := newTestHeadless(t, "alpha\nbeta\ngamma\n")
+			h.Type(tt.keys)
+
+			frame := h.Frame()
+			for row, want := range tt.wantLines {
+				if !strings.HasSuffix(frame[row], " "+want) {
+					t.Errorf("Type(%q) row %d = %q, want the line %q", tt.keys, row, frame[row], want)
+				}
+			}
+			for _, word := range []string{"alpha", "beta", "gamma"} {
+				if row := frame[len(tt.wantLines)]; strings.Contains(row, word) {
+					t.Errorf("Type(%q) row %d = %q, want no line past the end", tt.keys, len(tt.wantLines), row)
+				}
+			}
+		})
+	}
+}
+
+func TestHeadlessSplitFrame(t *testing.T) {
+	h := newTestHeadless(t, "one\ntwo\n")
+
+	// 22 rows above the status bar, shared by two windows with a name row each
+	h.Type("<c-w>sihi<esc>")
+	frame := h.Frame()
+	for _, row := range []int{0, 11} {
+		if !strings.Contains(frame[row], "hione") {
+			t.Errorf("Frame() row %d = %q, want the edited first line", row, frame[row])
+		}
+	}
+	for _, row := range []int{10, 21} {
+		if !strings.Contains(frame[row], "test.txt [+]") {
+			t.Errorf("Frame() row %d = %q, want the window's name", row, frame[row])
+		}
+	}
+
+	h.Type("<c-w>o<c-w>v")
+	frame = h.Frame()
+	if strings.Count(frame[0], "hione") != 2 || !strings.Contains(frame[0], "│") {
+		t.Errorf("Frame() row 0 = %q, want the text twice with a divider between", frame[0])
+	}
+}
+
+func TestHeadlessWindowSizes(t *testing.T) {
+	// 22 rows above the status bar and 80 columns, less a divider, shared out first come first
+	tests := []struct {
+		name      string
+		keys      string
+		vertical  bool // sizes are widths rather than heights
+		wantSizes []int
+	}{
+		{name: "split", keys: "<c-w>s", wantSizes: []int{11, 11}},
+		{name: "grow", keys: "<c-w>s<c-w>+", wantSizes: []int{12, 10}},
+		{name: "counted", keys: "<c-w>s3<c-w>+", wantSizes: []int{14, 8}},
+		{name: "shrink", keys: "<c-w>s<c-w>-", wantSizes: []int{10, 12}},
+		{name: "last grows into the previous", keys: "<c-w>s<c-w>j<c-w>+", wantSizes: []int{10, 12}},
+		{name: "at least two rows", keys: "<c-w>s20<c-w>+", wantSizes: []int{20, 2}},
+		{name: "nothing to resize", keys: "<c-w>+", wantSizes: []int{22}},
+		{name: "equalize", keys: "<c-w>s3<c-w>+<c-w>=", wantSizes: []int{11, 11}},
+		{name: "new split shares evenly", keys: "<c-w>s3<c-w>+<c-w>s", wantSizes: []int{8, 7, 7}},
+		{name: "side by side", keys: "<c-w>v", vertical: true, wantSizes: []int{40, 39}},
+		{name: "wider", keys: "<c-w>v<c-w>>", vertical: true, wantSizes: []int{41, 38}},
+		{name: "narrower", keys: "<c-w>v<c-w><", vertical: true, wantSizes: []int{39, 40}},
+		{name: "height of side by side", keys: "<c-w>v<c-w>+", vertical: true, wantSizes: []int{40, 39}},
+		{name: "zoom", keys: "<c-w>s<c-w>z", wantSizes: []int{22}},
+		{name: "zoom back", keys: "<c-w>s<c-w>+<c-w>z<c-w>z", wantSizes: []int{12, 10}},
+		{name: "split ends zoom", keys: "<c-w>s<c-w>z<c-w>s", wantSizes: []int{8, 7, 7}},
+	}
+
+	for _, tt := range tests {
+		t.Run(tt.name, func(t *testing.T) {
+			h := newTestHeadless(t, "one\n")
+			h.Type(tt.keys)
+
+			var sizes []int
+			for _, w := range h.athena.layout.windows() {
+				if tt.vertical {
+					sizes = append(sizes, w.width)
+				} else {
+					sizes = append(sizes, w.height)
+				}
+			}
+			if !slices.Equal(sizes, tt.wantSizes) {
+				t.Errorf("Type(%q) window sizes = %v, want %v", tt.keys, sizes, tt.wantSizes)
+			}
+		})
+	}
+}
+
+func TestHeadlessTabs(t *testing.T) {
+	tests := []struct {
+		name        string
+		keys        string
+		wantTabs    int
+		wantTab     int
+		wantWindows int
+		wantLine    int
+		wantQuit    bool
+	}{
+		{name: "new tab", keys: ":tabnew<cr>", wantTabs: 2, wantTab: 1, wantWindows: 1},
+		{name: "next tab", keys: ":tabnew<cr>gt", wantTabs: 2, wantWindows: 1},
+		{name: "previous tab", keys: ":tabnew<cr>:tabn<cr>gT", wantTabs: 2, wantTab: 1, wantWindows: 1},
+		{name: "layout per tab", keys: ":tabnew<cr><c-w>sgt", wantTabs: 2, wantWindows: 1},
+		{name: "layout kept", keys: ":tabnew<cr><c-w>sgtgt", wantTabs: 2, wantTab: 1, wantWindows: 2},
+		{name: "cursor per tab", keys: ":tabnew<cr>jjgt", wantTabs: 2, wantWindows: 1},
+		{name: "cursor kept", keys: ":tabnew<cr>jjgtgt", wantTabs: 2, wantTab: 1, wantWindows: 1, wantLine: 2},
+		{name: "close tab", keys: ":tabnew<cr><c-w>v:tabc<cr>", wantTabs: 1, wantWindows: 1},
+		{name: "close last tab", keys: ":tabc<cr>", wantTabs: 1, wantWindows: 1},
+		{name: "quit closes tab", keys: ":tabnew<cr>:q<cr>", wantTabs: 1, wantWindows: 1},
+		{name: "quit last tab", keys: ":tabnew<cr>:q<cr>:q<cr>", wantTabs: 1, wantWindows: 1, wantQuit: true},
+	}
+
+	for _, tt := range tests {
+		t.Run(tt.name, func(t *testing.T) {
+			h := newTestHeadless(t, "one\ntwo\nthree\n")
+			h.Type(tt.keys)
+
+			if n := len(h.athena.tabs); n != tt.wantTabs || h.athena.tab != tt.wantTab {
+				t.Errorf("Type(%q) tab = %d of %d, want %d of %d", tt.keys, h.athena.tab, n, tt.wantTab, tt.wantTabs)
+			}
+			if n := len(h.athena.layout.windows()); n != tt.wantWindows {
+				t.Errorf("Type(%q) windows = %d, want %d", tt.keys, n, tt.wantWindows)
+			}
+			if line, _, _ := h.Cursor(); line != tt.wantLine {
+				t.Errorf("Type(%q) cursor line = %d, want %d", tt.keys, line, tt.wantLine)
+			}
+			if h.Quit() != tt.wantQuit {
+				t.Errorf("Type(%q) quit = %v, want %v", tt.keys, h.Quit(), tt.wantQuit)
+			}
+		})
+	}
+}
+
+func TestHeadlessSession(t *testing.T) {
+	sessions := t.TempDir()
+	h := newTestHeadless(t, "one\ntwo\nthree\n")
+	h.athena.sessionDir = sessions
+	path, _ := h.athena.editor.FilePath()
+	other := filepath.Join(filepath.Dir(path), "other.txt")
+	if err := os.WriteFile(other, []byte("alpha\nbeta two\n"), 0644); err != nil {
+		t.Fatal(err)
+	}
+
+	// two windows side by side on the first tab page, the cursor on a search match in the focused
+	// one, and a second tab page
+	h.Type("jj:vs " + other + "<cr>/two<cr>:tabnew<cr>gT:session save work<cr>")
+	if msg := h.Message(); msg.Text != "session work saved" {
+		t.Fatalf(":session save message = %+v", msg)
+	}
+
+	restored := newTestHeadless(t, "elsewhere\n")
+	restored.athena.sessionDir = sessions
+	restored.Type(":session load work<cr>")
+	if msg := restored.Message(); msg.Text != "session work loaded" {
+		t.Fatalf(":session load message = %+v", msg)
+	}
+
+	a := restored.athena
+	if len(a.tabs) != 2 || a.tab != 0 {
+		t.Errorf("after :session load, tab = %d of %d, want 0 of 2", a.tab, len(a.tabs))
+	}
+	if windows := a.layout.windows(); len(windows) != 2 || !a.layout.vertical || a.focus != windows[0] {
+		t.Fatalf("after :session load, %d windows, vertical %v, want the first of 2 side by side focused", len(windows), a.layout.vertical)
+	}
+	if got, _ := a.editor.FilePath(); got != other {
+		t.Errorf("focused window shows %q, want %q", got, other)
+	}
+	if line, col, _ := restored.Cursor(); line != 1 || col != 5 {
+		t.Errorf("focused window cursor = %d:%d, want 1:5", line, col)
+	}
+
+	restored.Type("<c-w>l")
+	if got, _ := a.editor.FilePath(); got != path {
+		t.Errorf("right window shows %q, want %q", got, path)
+	}
+	if line, _, _ := restored.Cursor(); line != 2 {
+		t.Errorf("right window cursor line = %d, want 2", line)
+	}
+	if got := a.views.commandLine.Histories()["/"]; !reflect.DeepEqual(got, []string{"two"}) {
+		t.Errorf("search history = %q, want [two]", got)
+	}
+
+	// without a name it is the session last loaded
+	restored.Type(":session save<cr>")
+	if msg := restored.Message(); msg.Text != "session work saved" {
+		t.Errorf(":session save message = %+v", msg)
+	}
+}
+
+func TestHeadlessTabLine(t *testing.T) {
+	h := newTestHeadless(t, "one\ntwo\n")
+	if frame := h.Frame(); !strings.Contains(frame[0], "one") {
+		t.Fatalf("Frame() row 0 = %q, want the text with a single tab page", frame[0])
+	}
+
+	h.Type(":tabnew<cr><c-w>s")
+	frame := h.Frame()
+	if want := " test.txt  2 test.txt"; !strings.HasPrefix(frame[0], want) {
+		t.Errorf("Frame() row 0 = %q, want the tab line %q", frame[0], want)
+	}
+	if !strings.Contains(frame[1], "one") {
+		t.Errorf("Frame() row 1 = %q, want the text below the tab line", frame[1])
+	}
+}
+
+func TestHeadlessWrite(t *testing.T) {
+	h := newTestHeadless(t, "one\n")
+	h.Type("ihi <esc>:w<cr>")
+	h.athena.editor.WaitForSaves()
+
+	path, err := h.athena.editor.FilePath()
+	if err != nil {
+		t.Fatalf("FilePath() error = %v", err)
+	}
+	data, err := os.ReadFile(path)
+	if err != nil {
+		t.Fatal(err)
+	}
+	if got := string(data); got != "hi one\n" {
+		t.Errorf("file after :w = %q, want %q", got, "hi one\n")
+	}
+	if modified, _ := h.athena.editor.Modified(); modified {
+		t.Errorf("Modified() = true after :w")
+	}
+}
+
+func TestHeadlessWriteSymlink(t *testing.T) {
+	tests := []struct {
+		name       string
+		replace    bool
+		wantTarget string
+		wantLink   bool // the path is still a symlink after saving
+	}{
+		{name: "write-through", wantTarget: "xtarget\n", wantLink: true},
+		{name: "replace", replace: true, wantTarget: "target\n", wantLink: false},
+	}
+
+	for _, tt := range tests {
+		t.Run(tt.name, func(t *testing.T) {
+			h := newTestHeadless(t, "one\n")
+			dir := t.TempDir()
+			target, link := filepath.Join(dir, "target.txt"), filepath.Join(dir, "link.txt")
+			if err := os.WriteFile(target, []byte("target\n"), 0644); err != nil {
+				t.Fatal(err)
+			}
+			if err := os.Symlink(target, link); err != nil {
+				t.Fatal(err)
+			}
+			h.athena.editor.SetReplaceSymlinks(tt.replace)
+
+			h.athena.OpenFile(link, 0, 0)
+			h.Type("ix<esc>:w<cr>")
+			h.athena.editor.WaitForSaves()
+
+			if data, _ := os.ReadFile(target); string(data) != tt.wantTarget {
+				t.Errorf("target after :w = %q, want %q", data, tt.wantTarget)
+			}
+			if data, _ := os.ReadFile(link); string(data) != "xtarget\n" {
+				t.Errorf("link after :w = %q, want %q", data, "xtarget\n")
+			}
+			info, err := os.Lstat(link)
+			if err != nil {
+				t.Fatal(err)
+			}
+			if isLink := info.Mode()&os.ModeSymlink != 0; isLink != tt.wantLink {
+				t.Errorf("link is a symlink = %v, want %v", isLink, tt.wantLink)
+			}
+			if modified, _ := h.athena.editor.Modified(); modified {
+				t.Errorf("Modified() = true after :w")
+			}
+
+			// saving again writes to the file now at the path
+			h.Type("iy<esc>:w<cr>")
+			h.athena.editor.WaitForSaves()
+			if data, _ := os.ReadFile(link); string(data) != "yxtarget\n" {
+				t.Errorf("link after a second :w = %q, want %q", data, "yxtarget\n")
+			}
+		})
+	}
+}
+
+func TestHeadlessOpenSymlinkReusesBuffer(t *testing.T) {
+	h := newTestHeadless(t, "one\n")
+	path, err := h.athena.editor.FilePath()
+	if err != nil {
+		t.Fatalf("FilePath() error = %v", err)
+	}
+	link := filepath.Join(t.TempDir(), "link.txt")
+	if err := os.Symlink(path, link); err != nil {
+		t.Fatal(err)
+	}
+
+	h.Type("ix<esc>")
+	h.athena.OpenFile(link, 0, 0)
+
+	if buffers := h.athena.editor.GetBufferList(); len(buffers) != 1 {
+		t.Errorf("GetBufferList() = %v, want the one buffer of the file", buffers)
+	}
+	if text, _ := h.Text(); text != "xone\n" {
+		t.Errorf("text through the link = %q, want the open buffer's %q", text, "xone\n")
+	}
+}
+
+func TestHeadlessHooks(t *testing.T) {
+	h := newTestHeadless(t, "one\n")
+	path, err := h.athena.editor.FilePath()
+	if err != nil {
+		t.Fatalf("FilePath() error = %v", err)
+	}
+
+	var changes []editor.BufferChanged
+	var modes []editor.ModeChanged
+	var saves []string
+	stop := editor.Subscribe(h.athena.editor, func(ev editor.BufferChanged) { changes = append(changes, ev) })
+	editor.Subscribe(h.athena.editor, func(ev editor.ModeChanged) { modes = append(modes, ev) })
+	editor.Subscribe(h.athena.editor, func(ev editor.BeforeSave) { saves = append(saves, "before "+ev.Path) })
+	editor.Subscribe(h.athena.editor, func(ev editor.AfterSave) {
+		if ev.Err != nil {
+			t.Errorf("AfterSave error = %v", ev.Err)
+		}
+		saves = append(saves, "after "+ev.Path)
+	})
+
+	h.Type("lix<esc>:w<cr>")
+	h.athena.editor.WaitForSaves()
+	h.Wait(0)
+
+	version, _ := h.athena.editor.Version()
+	want := []editor.BufferChanged{{Path: path, Version: version, Changes: state.ChangeSet{{Start: 1, Inserted: 1}}}}
+	if !reflect.DeepEqual(changes, want) {
+		t.Errorf("BufferChanged events = %+v, want %+v", changes, want)
+	}
+	if len(modes) < 2 || modes[0] != (editor.ModeChanged{From: state.Normal, To: state.Insert}) || modes[len(modes)-1].To != state.Normal {
+		t.Errorf("ModeChanged events = %+v, want into insert mode and back to normal", modes)
+	}
+	if want := []string{"before " + path, "after " + path}; !reflect.DeepEqual(saves, want) {
+		t.Errorf("save events = %q, want %q", saves, want)
+	}
+
+	stop()
+	h.Type("ix<esc>")
+	if len(changes) != 1 {
+		t.Errorf("BufferChanged events after unsubscribing = %d, want 1", len(changes))
+	}
+}
+
+func TestHeadlessViewRestore(t *testing.T) {
+	h := newTestHeadless(t, "one\ntwo\nthree\n")
+	// views kept in memory rather than in the user's state directory
+	a := h.athena
+	a.viewStore = &viewStore{Views: make(map[string]fileView)}
+	a.visited = make(map[string]bool)
+	a.baseEditor = a.cfg.Editor
+	editor.Subscribe(a.editor, a.forgetVisited)
+
+	path, _ := a.editor.FilePath()
+	other := filepath.Join(filepath.Dir(path), "other.txt")
+	if err := os.WriteFile(other, []byte("other\n"), 0644); err != nil {
+		t.Fatal(err)
+	}
+
+	// closed with its cursor on the last line, then opened again
+	h.Type("jjl:e " + other + "<cr>gp:bd<cr>:e " + path + "<cr>")
+	if got, _ := a.editor.FilePath(); got != path {
+		t.Fatalf("FilePath() = %q, want %q", got, path)
+	}
+	if line, col, _ := h.Cursor(); line != 2 || col != 1 {
+		t.Errorf("reopened file cursor = %d:%d, want 2:1", line, col)
+	}
+}
+
+func TestHeadlessFilter(t *testing.T) {
+	text := "two\none\nthree\n"
+	tests := []struct {
+		name     string
+		keys     string
+		wantText string
+		wantErr  string
+	}{
+		{name: "whole file", keys: ":%!sort<cr>", wantText: "one\nthree\ntwo\n"},
+		{name: "range", keys: ":1,2!sort<cr>", wantText: "one\ntwo\nthree\n"},
+		{name: "current line", keys: "j:.!tr a-z A-Z<cr>", wantText: "two\nONE\nthree\n"},
+		{name: "read command", keys: ":r !echo hi<cr>", wantText: "two\nhi\none\nthree\n"},
+		{name: "read command at range", keys: ":$r!echo hi<cr>", wantText: "two\none\nthree\nhi\n"},
+		{name: "failing", keys: ":%!exit 3<cr>", wantText: text, wantErr: "exit 3: exit status 3"},
+	}
+	for _, tt := range tests {
+		t.Run(tt.name, func(t *testing.T) {
+			h := newTestHeadless(t, text)
+			h.Type(tt.keys)
+			h.athena.editor.WaitForJobs()
+			if text, _ := h.Text(); text != tt.wantText {
+				t.Errorf("Type(%q) text = %q, want %q", tt.keys, text, tt.wantText)
+			}
+			if msg := h.Message(); tt.wantErr != "" && (!msg.IsError || msg.Text != tt.wantErr) {
+				t.Errorf("Type(%q) message = %+v, want %q", tt.keys, msg, tt.wantErr)
+			}
+		})
+	}
+}
+
+func TestHeadlessFilterUndo(t *testing.T) {
+	// the output lands after the command is typed, as a step of its own
+	for _, keys := range []string{":%!sort<cr>", ":r !echo hi<cr>"} {
+		h := newTestHeadless(t, "two\none\nthree\n")
+		h.Type(keys)
+		h.athena.editor.WaitForJobs()
+		h.Type("u")
+		if text, _ := h.Text(); text != "two\none\nthree\n" {
+			t.Errorf("Type(%q) then u text = %q, want %q", keys, text, "two\none\nthree\n")
+		}
+	}
+}
+
+func TestHeadlessShellOutput(t *testing.T) {
+	h := newTestHeadless(t, "text\n")
+	h.Type(":!printf 'one\\ttwo\\n'; echo oops >&2; exit 2<cr>")
+
+	// the command runs in the background; the panel says so until it ends
+	title := ":!printf 'one\\ttwo\\n'; echo oops >&2; exit 2 (exit status 2)"
+	var panel []string
+	for deadline := time.Now().Add(5 * time.Second); time.Now().Before(deadline); time.Sleep(10 * time.Millisecond) {
+		h.Wait(0)
+		frame := h.Frame()
+		if i := slices.IndexFunc(frame, func(row string) bool { return strings.TrimSpace(row) == title }); i >= 0 {
+			panel = frame[i+1 : len(frame)-2]
+			break
+		}
+	}
+	if panel == nil {
+		t.Fatalf("Frame() = %q, want the output panel titled %q", h.Frame(), title)
+	}
+	if want := []string{"one    two", "oops"}; len(panel) < 2 || !reflect.DeepEqual(panel[:2], want) {
+		t.Errorf("output panel rows = %q, want %q first", panel, want)
+	}
+	if text, _ := h.Text(); text != "text\n" {
+		t.Errorf(":! changed text to %q", text)
+	}
+
+	h.Type("<c-w>!")
+	for _, row := range h.Frame() {
+		if strings.Contains(row, "exit status 2") {
+			t.Errorf("Frame() row = %q after the output panel was hidden", row)
+		}
+	}
+}
+
+func TestHeadlessGrep(t *testing.T) {
+	h := newTestHeadless(t, "text\n")
+	path, _ := h.athena.editor.FilePath()
+	dir := filepath.Dir(path)
+	if err := os.WriteFile(filepath.Join(dir, "other.txt"), []byte("one needle\ntwo\nneedle three\n"), 0644); err != nil {
+		t.Fatal(err)
+	}
+	// :grep searches the working directory
+	wd, err := os.Getwd()
+	if err != nil {
+		t.Fatal(err)
+	}
+	if err := os.Chdir(dir); err != nil {
+		t.Fatal(err)
+	}
+	t.Cleanup(func() { _ = os.Chdir(wd) })
+
+	h.Type(":grep need+le<cr>")
+	h.athena.editor.WaitForJobs()
+	h.Wait(0)
+	if msg := h.Message(); msg.Text != "2 matching lines, ]q goes to the first" {
+		t.Errorf(":grep message = %+v", msg)
+	}
+	frame := strings.Join(h.Frame(), "\n")
+	for _, want := range []string{"other.txt:1:5: one needle", "other.txt:3:1: needle three"} {
+		if !strings.Contains(frame, want) {
+			t.Errorf("Frame() = %q, want the output panel to list %q", frame, want)
+		}
+	}
+
+	steps := []struct {
+		keys     string
+		wantLine int
+		wantCol  int
+		wantErr  string
+	}{
+		{keys: "]q", wantLine: 0, wantCol: 4},
+		{keys: "]q", wantLine: 2, wantCol: 0},
+		{keys: "]q", wantLine: 2, wantCol: 0, wantErr: "at end of quickfix list"},
+		{keys: "[q", wantLine: 0, wantCol: 4},
+	}
+	for _, step := range steps {
+		h.Type(step.keys)
+		if got, _ := h.athena.editor.FilePath(); filepath.Base(got) != "other.txt" {
+			t.Fatalf("after %s, file = %q, want other.txt", step.keys, got)
+		}
+		if line, col, _ := h.Cursor(); line != step.wantLine || col != step.wantCol {
+			t.Errorf("after %s, cursor = %d:%d, want %d:%d", step.keys, line, col, step.wantLine, step.wantCol)
+		}
+		if msg := h.Message(); step.wantErr != "" && msg.Text != step.wantErr {
+			t.Errorf("after %s, message = %+v, want %q", step.keys, msg, step.wantErr)
+		}
+	}
+
+	// the first jump left the file grep started from
+	h.Type("<c-o><c-o>")
+	if got, _ := h.athena.editor.FilePath(); got != path {
+		t.Errorf("<c-o><c-o> went to %q, want %q", got, path)
+	}
+}
+
+func TestHeadlessGrepAgain(t *testing.T) {
+	h := newTestHeadless(t, "text\n")
+	path, _ := h.athena.editor.FilePath()
+	dir := filepath.Dir(path)
+	if err := os.WriteFile(filepath.Join(dir, "other.txt"), []byte("one needle\ntwo\nneedle three\n"), 0644); err != nil {
+		t.Fatal(err)
+	}
+	wd, err := os.Getwd()
+	if err != nil {
+		t.Fatal(err)
+	}
+	if err := os.Chdir(dir); err != nil {
+		t.Fatal(err)
+	}
+	t.Cleanup(func() { _ = os.Chdir(wd) })
+
+	// the second cancels the first, whose matches are left out of the list and the panel
+	h.Type(":grep needle<cr>:grep three<cr>")
+	h.athena.editor.WaitForJobs()
+	h.Wait(0)
+	if msg := h.Message(); msg.Text != "1 matching lines, ]q goes to the first" {
+		t.Errorf(":grep message = %+v", msg)
+	}
+	if frame := strings.Join(h.Frame(), "\n"); strings.Contains(frame, "one needle") {
+		t.Errorf("Frame() = %q, want only the matches of the second grep", frame)
+	}
+	h.Type("]q")
+	if line, col, _ := h.Cursor(); line != 2 || col != 7 {
+		t.Errorf("]q cursor = %d:%d, want 2:7", line, col)
+	}
+}
+
+func TestHeadlessReadFile(t *testing.T) {
+	h := newTestHeadless(t, "one\ntwo\n")
+	other := filepath.Join(t.TempDir(), "other.txt")
+	if err := os.WriteFile(other, []byte("inserted\n"), 0644); err != nil {
+		t.Fatal(err)
+	}
+	h.Type(":r " + other + "<cr>")
+	if text, _ := h.Text(); text != "one\ninserted\ntwo\n" {
+		t.Errorf(":r text = %q, want %q", text, "one\ninserted\ntwo\n")
+	}
+	if line, _, _ := h.Cursor(); line != 1 {
+		t.Errorf(":r cursor line = %d, want 1", line)
+	}
+}
+
+func TestHeadlessQuit(t *testing.T) {
+	tests := []struct {
+		name     string
+		keys     string
+		wantQuit bool
+		wantFile string
+	}{
+		{name: "quit", keys: ":q<cr>", wantQuit: true, wantFile: "one\n"},
+		{name: "unsaved changes", keys: "ix<esc>:q<cr>", wantFile: "one\n"},
+		{name: "unsaved changes saved", keys: "ix<esc>:q<cr>y", wantQuit: true, wantFile: "xone\n"},
+		{name: "unsaved changes dropped", keys: "ix<esc>:q<cr>n", wantQuit: true, wantFile: "one\n"},
+		{name: "quitting cancelled", keys: "ix<esc>:q<cr>c", wantFile: "one\n"},
+		{name: "discard changes", keys: "ix<esc>:q!<cr>", wantQuit: true, wantFile: "one\n"},
+		{name: "write all and quit", keys: "ix<esc>:wqa<cr>", wantQuit: true, wantFile: "xone\n"},
+		{name: "write and quit", keys: "ix<esc>:wq<cr>", wantQuit: true, wantFile: "xone\n"},
+		{name: "exit", keys: "ix<esc>:x<cr>", wantQuit: true, wantFile: "xone\n"},
+		{name: "keys after quitting ignored", keys: ":q<cr>ix", wantQuit: true, wantFile: "one\n"},
+	}
+
+	for _, tt := range tests {
+		t.Run(tt.name, func(t *testing.T) {
+			h := newTestHeadless(t, "one\n")
+			h.Type(tt.keys)
+
+			if h.Quit() != tt.wantQuit {
+				t.Errorf("Type(%q) quit = %v, want %v (message %q)", tt.keys, h.Quit(), tt.wantQuit, h.Message().Text)
+			}
+			path, _ := h.athena.editor.FilePath()
+			data, err := os.ReadFile(path)
+			if err != nil {
+				t.Fatal(err)
+			}
+			if string(data) != tt.wantFile {
+				t.Errorf("Type(%q) file = %q, want %q", tt.keys, data, tt.wantFile)
+			}
+		})
+	}
+}
+
+func TestHeadlessCtrlC(t *testing.T) {
+	// as terminals send it, which "<c-c>" in keymap notation isn't
+	ctrlC := tcell.NewEventKey(tcell.KeyCtrlC, 0, tcell.ModCtrl)
+
+	h := newTestHeadless(t, "one\n")
+	h.Type("ix<esc>")
+	if h.athena.handleEvent(ctrlC) {
+		t.Fatal("<c-c> with unsaved changes quit without asking")
+	}
+	if !h.athena.views.commandLine.Active() {
+		t.Error("<c-c> with unsaved changes didn't ask")
+	}
+	if !h.athena.handleEvent(ctrlC) {
+		t.Error("<c-c> typed again while asking didn't quit")
+	}
+
+	h = newTestHeadless(t, "one\n")
+	if !h.athena.handleEvent(ctrlC) {
+		t.Error("<c-c> without unsaved changes didn't quit")
+	}
+}
+
+func TestHeadlessRestoreOnPanic(t *testing.T) {
+	h := newTestHeadless(t, "one\n")
+	var out bytes.Buffer
+	h.athena.title = newTerminalTitle(&out, "{name}")
+	h.athena.title.Set("test.txt", "", false)
+	out.Reset()
+
+	func() {
+		defer func() {
+			if r := recover(); r != "boom" {
+				t.Errorf("recovered %v, want the panic to go on", r)
+			}
+		}()
+		defer h.athena.restoreOnPanic()
+		panic("boom")
+	}()
+	// a second restore, as Close does, mustn't write again
+	h.athena.restoreTerminal()
+
+	if got, want := out.String(), "\x1b[23;0t"; got != want {
+		t.Errorf("output = %q, want the title restored once, %q", got, want)
+	}
+}
+
+func TestHeadlessEventLoopWakeups(t *testing.T) {
+	h := newTestHeadless(t, "one\n")
+	if h.athena.timeout() != nil {
+		t.Error("timeout() with nothing due isn't nil")
+	}
+	h.Type("g")
+	if h.athena.timeout() == nil {
+		t.Error("timeout() with a key sequence pending is nil")
+	}
+
+	// must not block however many come before the loop takes one
+	for range 3 {
+		h.athena.wakeup()
+	}
+	if got := len(h.athena.wake); got != 1 {
+		t.Errorf("pending wake-ups = %d, want 1", got)
+	}
+}
+
+func TestHeadlessGatherEvents(t *testing.T) {
+	tests := []struct {
+		name     string
+		keys     string
+		close    bool // the queue is closed after the keys, as when the screen is torn down
+		wantText string
+		wantStop bool
+	}{
+		{name: "burst", keys: "iab<esc>", wantText: "abone\n"},
+		{name: "quit", keys: ":q<enter>ix", wantText: "one\n", wantStop: true},
+		{name: "closed", keys: "ia", close: true, wantText: "aone\n", wantStop: true},
+	}
+
+	for _, tt := range tests {
+		t.Run(tt.name, func(t *testing.T) {
+			h := newTestHeadless(t, "one\n")
+			keys := ui.ParseKeys(tt.keys)
+			events := make(chan tcell.Event, len(keys))
+			for _, ev := range keys {
+				events <- ev
+			}
+			if tt.close {
+				close(events)
+			}
+
+			stop := h.athena.gatherEvents(events, time.Time{})
+			if stop != tt.wantStop {
+				t.Errorf("gatherEvents() = %v, want %v", stop, tt.wantStop)
+			}
+			if text, _ := h.Text(); text != tt.wantText {
+				t.Errorf("text = %q, want %q", text, tt.wantText)
+			}
+		})
+	}
+}
+
+func TestHeadlessSwapFiles(t *testing.T) {
+	tests := []struct {
+		name     string
+		keys     string // typed before the first write of swap files
+		thenKeys string // typed before the second
+		wantSwap string // empty when no swap file should be left
+	}{
+		{name: "unmodified", wantSwap: ""},
+		{name: "modified", keys: "ix<esc>", wantSwap: "xone\n"},
+		{name: "modified again", keys: "ix<esc>", thenKeys: "iy<esc>", wantSwap: "xyone\n"},
+		{name: "saved", keys: "ix<esc>", thenKeys: ":w<cr>", wantSwap: ""},
+		{name: "discarded", keys: "ix<esc>", thenKeys: ":e!<cr>", wantSwap: ""},
+	}
+
+	for _, tt := range tests {
+		t.Run(tt.name, func(t *testing.T) {
+			h := newTestHeadless(t, "one\n")
+			dir := t.TempDir()
+			h.athena.startAutosave(dir, time.Hour)
+			for _, keys := range []string{tt.keys, tt.thenKeys} {
+				h.Type(keys)
+				if err := h.athena.editor.WriteSwaps(); err != nil {
+					t.Fatalf("WriteSwaps() error = %v", err)
+				}
+			}
+
+			path, _ := h.athena.editor.FilePath()
+			swap := editor.SwapFile(dir, path)
+			data, err := os.ReadFile(swap)
+			if tt.wantSwap == "" {
+				if err == nil {
+					t.Errorf("Type(%q, %q) left swap file %q", tt.keys, tt.thenKeys, data)
+				}
+				return
+			}
+			if string(data) != tt.wantSwap {
+				t.Errorf("Type(%q, %q) swap file = %q, want %q (error %v)", tt.keys, tt.thenKeys, data, tt.wantSwap, err)
+			}
+
+			h.athena.stopAutosave()
+			if _, err := os.Stat(swap); err == nil {
+				t.Errorf("swap file left after stopping autosave")
+			}
+		})
+	}
+}
+
+func TestHeadlessRecover(t *testing.T) {
+	tests := []struct {
+		name      string
+		swap      string
+		wantFound bool
+		wantText  string
+	}{
+		{name: "changes left behind", swap: "one\nlost\n", wantFound: true, wantText: "one\nlost\n"},
+		{name: "same as the file", swap: "one\n", wantText: "one\n"},
+	}
+
+	for _, tt := range tests {
+		t.Run(tt.name, func(t *testing.T) {
+			h := newTestHeadless(t, "one\n")
+			dir := t.TempDir()
+			path, _ := h.athena.editor.FilePath()
+			if err := os.WriteFile(editor.SwapFile(dir, path), []byte(tt.swap), 0600); err != nil {
+				t.Fatal(err)
+			}
+			h.athena.startAutosave(dir, time.Hour)
+			h.athena.render()
+
+			found := strings.Contains(h.Message().Text, ":recover")
+			if found != tt.wantFound {
+				t.Fatalf("message = %q, want swap file found = %v", h.Message().Text, tt.wantFound)
+			}
+			if !tt.wantFound {
+				if _, err := os.Stat(editor.SwapFile(dir, path)); err == nil {
+					t.Errorf("swap file holding the file's text was kept")
+				}
+				return
+			}
+
+			h.Type(":recover<cr>")
+			if text, _ := h.Text(); text != tt.wantText {
+				t.Errorf(":recover text = %q, want %q", text, tt.wantText)
+			}
+			if modified, _ := h.athena.editor.Modified(); !modified {
+				t.Errorf("Modified() = false after :recover")
+			}
+		})
+	}
+}
+
+func TestHeadlessLineEndings(t *testing.T) {
+	tests := []struct {
+		name             string
+		file             string
+		keys             string
+		wantText         string
+		wantFile         string
+		wantLineEnding   buffer.LineEnding
+		wantFinalNewline bool
+	}{
+		{name: "lf", file: "one\ntwo\n", keys: "ix<esc>:w<cr>", wantText: "xone\ntwo\n", wantFile: "xone\ntwo\n", wantLineEnding: buffer.LF, wantFinalNewline: true},
+		{name: "crlf", file: "one\r\ntwo\r\n", keys: "jix<esc>:w<cr>", wantText: "one\nxtwo\n", wantFile: "one\r\nxtwo\r\n", wantLineEnding: buffer.CRLF, wantFinalNewline: true},
+		{name: "crlf new line", file: "one\r\n", keys: "jitwo<cr><esc>:w<cr>", wantText: "one\ntwo\n", wantFile: "one\r\ntwo\r\n", wantLineEnding: buffer.CRLF, wantFinalNewline: true},
+		{name: "no final newline", file: "one\r\ntwo", keys: "ix<esc>:w<cr>", wantText: "xone\ntwo", wantFile: "xone\r\ntwo", wantLineEnding: buffer.CRLF},
+		{name: "mostly lf", file: "one\ntwo\nthree\r\n", keys: ":w<cr>", wantText: "one\ntwo\nthree\r\n", wantFile: "one\ntwo\nthree\r\n", wantLineEnding: buffer.LF, wantFinalNewline: true},
+	}
+
+	for _, tt := range tests {
+		t.Run(tt.name, func(t *testing.T) {
+			h := newTestHeadless(t, tt.file)
+			h.Type(tt.keys)
+
+			if text, _ := h.Text(); text != tt.wantText {
+				t.Errorf("Type(%q) text = %q, want %q", tt.keys, text, tt.wantText)
+			}
+			path, _ := h.athena.editor.FilePath()
+			data, err := os.ReadFile(path)
+			if err != nil {
+				t.Fatal(err)
+			}
+			if string(data) != tt.wantFile {
+				t.Errorf("Type(%q) file = %q, want %q", tt.keys, data, tt.wantFile)
+			}
+			le, finalNewline, _ := h.athena.editor.LineEnding()
+			if le != tt.wantLineEnding || finalNewline != tt.wantFinalNewline {
+				t.Errorf("LineEnding() = %v, %v, want %v, %v", le, finalNewline, tt.wantLineEnding, tt.wantFinalNewline)
+			}
+		})
+	}
+}
+
+func TestHeadlessOverlaysOnTop(t *testing.T) {
+	// the completion menu of the left window reaches past the divider into the right one
+	h := newTestHeadless(t, "wonderfullyextravagant\n\n")
+	h.Type(":vsplit<cr>ji" + strings.Repeat("x", 25) + " <c-n>")
+
+	found := false
+	for _, row := range h.Frame()[1:] {
+		found = found || strings.Contains(row, " wonderfullyextravagant ")
+	}
+	if !found {
+		t.Errorf("completion menu covered, frame:\n%s", strings.Join(h.Frame(), "\n"))
+	}
+}
+
+func TestHeadlessSaving(t *testing.T) {
+	h := newTestHeadless(t, "one\n")
+	status := func() string { return h.Frame()[len(h.Frame())-2] }
+
+	// drawn while the content is still being written
+	var during string
+	err := h.athena.editor.SaveCurrentBufferWith(func(path string, content io.Reader) error {
+		h.athena.render()
+		during = status()
+		_, err := io.Copy(io.Discard, content)
+		return err
+	})
+	if err != nil {
+		t.Fatalf("SaveCurrentBufferWith() error = %v", err)
+	}
+	if !strings.Contains(during, "writing...") {
+		t.Errorf("status bar while saving = %q, want it to show writing...", during)
+	}
+	h.athena.render()
+	if after := status(); strings.Contains(after, "writing...") {
+		t.Errorf("status bar after saving = %q, want writing... gone", after)
+	}
+}
+
+func TestHeadlessReadOnly(t *testing.T) {
+	h := newTestHeadless(t, "one\n")
+	h.athena.cfg.Editor.StatusBar.Center = []config.StatusBarOption{config.SectionFileName, config.SectionFileModified}
+	path, _ := h.athena.editor.FilePath()
+	if err := h.athena.editor.SetReadOnly(true); err != nil {
+		t.Fatal(err)
+	}
+
+	h.Type("x")
+	if text, _ := h.Text(); text != "one\n" {
+		t.Errorf("x in a read-only buffer text = %q, want it unchanged", text)
+	}
+	if msg := h.Message(); !msg.IsError || msg.Text != buffer.ErrReadOnly.Error() {
+		t.Errorf("x message = %+v, want %q", msg, buffer.ErrReadOnly)
+	}
+	h.Type("i")
+	if mode := h.Mode(); mode != state.Normal {
+		t.Errorf("i in a read-only buffer mode = %v, want normal", mode)
+	}
+
+	// lifted to edit, then set again
+	h.Type(":set noro<cr>x:set ro<cr>:w<cr>")
+	if msg := h.Message(); !msg.IsError || msg.Text != ui.ErrReadOnly.Error() {
+		t.Errorf(":w message = %+v, want %q", msg, ui.ErrReadOnly)
+	}
+	if frame := strings.Join(h.Frame(), "\n"); !strings.Contains(frame, "[+] [RO]") {
+		t.Errorf("status bar doesn't show [RO]:\n%s", frame)
+	}
+
+	h.Type(":w!<cr>")
+	h.athena.editor.WaitForSaves()
+	if data, _ := os.ReadFile(path); string(data) != "ne\n" {
+		t.Errorf("after :w! file = %q, want %q", data, "ne\n")
+	}
+
+	// as with athena -R
+	h.athena.editor.SetOpenReadOnly(true)
+	h.athena.OpenFile(filepath.Join(filepath.Dir(path), "other.txt"), 0, 0)
+	if readOnly, _ := h.athena.editor.ReadOnly(); !readOnly {
+		t.Errorf("ReadOnly() = false for a file opened read-only")
+	}
+}
+
+func TestHeadlessOpenFile(t *testing.T) {
+	h := newTestHeadless(t, "one\n")
+	path, _ := h.athena.editor.FilePath()
+	other := filepath.Join(filepath.Dir(path), "other.txt")
+	if err := os.WriteFile(other, []byte("one\ntwo\nthree\n"), 0644); err != nil {
+		t.Fatal(err)
+	}
+
+	tests := []struct {
+		name      string
+		line, col int
+		wantLine  int
+		wantCol   int
+	}{
+		{name: "line and column", line: 2, col: 3, wantLine: 1, wantCol: 2},
+		{name: "line", line: 3, wantLine: 2, wantCol: 0},
+		{name: "past the end", line: 9, col: 1, wantLine: 2, wantCol: 0},
+	}
+	for _, tt := range tests {
+		t.Run(tt.name, func(t *testing.T) {
+			h.athena.OpenFile(other, tt.line, tt.col)
+			if got, _ := h.athena.editor.FilePath(); got != other {
+				t.Fatalf("FilePath() = %q, want %q", got, other)
+			}
+			if line, col, _ := h.Cursor(); line != tt.wantLine || col != tt.wantCol {
+				t.Errorf("OpenFile(%d, %d) cursor = %d:%d, want %d:%d", tt.line, tt.col, line, col, tt.wantLine, tt.wantCol)
+			}
+		})
+	}
+}
+
+func TestHeadlessTemplates(t *testing.T) {
+	tests := []struct {
+		name     string
+		file     string
+		existing string // content of the file before it is opened, "" for a new file
+		wantText string
+	}{
+		{name: "new file", file: "main.go", wantText: "package main\n\n"},
+		{name: "existing file", file: "main.go", existing: "package other\n", wantText: "package other\n"},
+		{name: "no template", file: "notes.md", wantText: ""},
+	}
+	for _, tt := range tests {
+		t.Run(tt.name, func(t *testing.T) {
+			h := newTestHeadless(t, "one\n")
+			dir := t.TempDir()
+			if err := os.WriteFile(filepath.Join(dir, "go.tmpl"), []byte("package {stem}\n\n{cursor}"), 0644); err != nil {
+				t.Fatal(err)
+			}
+			h.athena.fillNewFiles(dir)
+			// the file opened first isn't new
+			if text, _ := h.Text(); text != "one\n" {
+				t.Fatalf("text = %q before opening a new file, want it left alone", text)
+			}
+
+			path := filepath.Join(t.TempDir(), tt.file)
+			if tt.existing != "" {
+				if err := os.WriteFile(path, []byte(tt.existing), 0644); err != nil {
+					t.Fatal(err)
+				}
+			}
+			h.Type(":e " + path + "<cr>")
+			if text, _ := h.Text(); text != tt.wantText {
+				t.Errorf(":e %s text = %q, want %q", tt.file, text, tt.wantText)
+			}
+		})
+	}
+}
+
+func TestHeadlessChangedOnDisk(t *testing.T) {
+	tests := []struct {
+		name     string
+		keys     string // typed before the file changes on disk
+		answer   string // typed after
+		wantText string
+		wantMsg  string
+	}{
+		{name: "reloaded without changes", wantText: "changed elsewhere\n", wantMsg: `"test.txt" changed on disk, reloaded`},
+		{name: "reloaded over changes", keys: "x", answer: "y", wantText: "changed elsewhere\n", wantMsg: `"test.txt" reloaded`},
+		{name: "changes kept", keys: "x", answer: "n", wantText: "ne\n", wantMsg: `kept your changes to "test.txt"; :w saves them over the file`},
+	}
+	for _, tt := range tests {
+		t.Run(tt.name, func(t *testing.T) {
+			h := newTestHeadless(t, "one\n")
+			path, _ := h.athena.editor.FilePath()
+			h.Type(tt.keys)
+
+			if err := os.WriteFile(path, []byte("changed elsewhere\n"), 0644); err != nil {
+				t.Fatal(err)
+			}
+			h.Wait(0)
+			h.Type(tt.answer)
+			if text, _ := h.Text(); text != tt.wantText {
+				t.Errorf("text = %q, want %q", text, tt.wantText)
+			}
+			if msg := h.Message(); msg.Text != tt.wantMsg {
+				t.Errorf("message = %q, want %q", msg.Text, tt.wantMsg)
+			}
+		})
+	}
+}
+
+func TestHeadlessChangedOnDiskInOtherWindow(t *testing.T) {
+	tests := []struct {
+		name      string
+		keys      string // typed before the window is split
+		wantShown bool   // the window without the focus shows the file as changed on disk
+	}{
+		{name: "reloaded when drawn", wantShown: true},
+		{name: "asked about once current", keys: "x"},
+	}
+	for _, tt := range tests {
+		t.Run(tt.name, func(t *testing.T) {
+			h := newTestHeadless(t, "one\n")
+			path, _ := h.athena.editor.FilePath()
+			other := filepath.Join(filepath.Dir(path), "other.txt")
+			if err := os.WriteFile(other, []byte("two\n"), 0644); err != nil {
+				t.Fatal(err)
+			}
+			// the new window on top goes on with the other file
+			h.Type(tt.keys + "<c-w>s")
+			h.athena.OpenFile(other, 0, 0)
+			h.Wait(0)
+
+			if err := os.WriteFile(path, []byte("changed elsewhere\n"), 0644); err != nil {
+				t.Fatal(err)
+			}
+			if !h.athena.noticeDiskChanges() {
+				t.Fatalf("noticeDiskChanges() = false after %s changed", path)
+			}
+			if h.athena.noticeDiskChanges() {
+				t.Errorf("noticeDiskChanges() = true again for a change already noticed")
+			}
+			h.Wait(0)
+			if shown := slices.ContainsFunc(h.Frame(), func(row string) bool {
+				return strings.Contains(row, "changed elsewhere")
+			}); shown != tt.wantShown {
+				t.Errorf("frame shows the file changed on disk = %v, want %v", shown, tt.wantShown)
+			}
+			if tt.wantShown {
+				return
+			}
+
+			h.Type("<c-w>jy")
+			if text, _ := h.Text(); text != "changed elsewhere\n" {
+				t.Errorf("text = %q once current and reloaded, want %q", text, "changed elsewhere\n")
+			}
+		})
+	}
+}
+
+func TestHeadlessWriteCopy(t *testing.T) {
+	tests := []struct {
+		name     string
+		command  string // run with the path of the copy
+		existing string // content of the copy's file beforehand, "" for none
+		wantErr  error
+		wantFile string
+	}{
+		{name: "new file", command: ":w", wantFile: "ne\n"},
+		{name: "existing file", command: ":w", existing: "old\n", wantErr: editor.ErrFileExists, wantFile: "old\n"},
+		{name: "existing file forced", command: ":w!", existing: "old\n", wantFile: "ne\n"},
+	}
+	for _, tt := range tests {
+		t.Run(tt.name, func(t *testing.T) {
+			h := newTestHeadless(t, "one\n")
+			path, _ := h.athena.editor.FilePath()
+			copyPath := filepath.Join(filepath.Dir(path), "copy.txt")
+			if tt.existing != "" {
+				if err := os.WriteFile(copyPath, []byte(tt.existing), 0644); err != nil {
+					t.Fatal(err)
+				}
+			}
+
+			h.Type("x" + tt.command + " " + copyPath + "<cr>")
+			if msg := h.Message(); tt.wantErr != nil && (!msg.IsError || msg.Text != tt.wantErr.Error()) {
+				t.Errorf("%s message = %+v, want %q", tt.command, msg, tt.wantErr)
+			}
+			if data, _ := os.ReadFile(copyPath); string(data) != tt.wantFile {
+				t.Errorf("%s copy = %q, want %q", tt.command, data, tt.wantFile)
+			}
+			// the buffer stays with its own file, unsaved
+			if got, _ := h.athena.editor.FilePath(); got != path {
+				t.Errorf("%s path = %q, want %q", tt.command, got, path)
+			}
+			if modified, _ := h.athena.editor.Modified(); !modified {
+				t.Errorf("%s left the buffer unmodified, want its changes still unsaved", tt.command)
+			}
+			if data, _ := os.ReadFile(path); string(data) != "one\n" {
+				t.Errorf("%s own file = %q, want it untouched", tt.command, data)
+			}
+		})
+	}
+}
+
+func TestHeadlessSaveAs(t *testing.T) {
+	h := newTestHeadless(t, "package main\n")
+	path, _ := h.athena.editor.FilePath()
+	newPath := filepath.Join(filepath.Dir(path), "main.go")
+
+	h.Type(":saveas " + newPath + "<cr>")
+	if got, _ := h.athena.editor.FilePath(); got != newPath {
+		t.Errorf(":saveas path = %q, want %q", got, newPath)
+	}
+	if fileType, _ := h.athena.editor.FileType(); fileType != "go" {
+		t.Errorf(":saveas file type = %q, want %q", fileType, "go")
+	}
+	if data, _ := os.ReadFile(newPath); string(data) != "package main\n" {
+		t.Errorf(":saveas file = %q, want %q", data, "package main\n")
+	}
+	if buffers := h.athena.editor.GetBufferList(); !slices.Equal(buffers, []string{newPath}) {
+		t.Errorf(":saveas buffers = %q, want only %q", buffers, newPath)
+	}
+	if status := h.Frame()[len(h.Frame())-2]; !strings.Contains(status, "main.go") {
+		t.Errorf("status bar = %q, want the new file name", status)
+	}
+
+	// onto the file of another buffer
+	h.athena.OpenFile(path, 0, 0)
+	h.Type(":saveas " + newPath + "<cr>")
+	if msg := h.Message(); !msg.IsError || msg.Text != editor.ErrFileLoaded.Error() {
+		t.Errorf(":saveas onto a loaded file message = %+v, want %q", msg, editor.ErrFileLoaded)
+	}
+	if got, _ := h.athena.editor.FilePath(); got != path {
+		t.Errorf(":saveas onto a loaded file path = %q, want %q", got, path)
+	}
+
+	// a file that could only be read, copied to one that can be written
+	if os.Geteuid() == 0 {
+		t.Skip("root can write any file")
+	}
+	unwritable := filepath.Join(filepath.Dir(path), "unwritable.txt")
+	if err := os.WriteFile(unwritable, []byte("text\n"), 0444); err != nil {
+		t.Fatal(err)
+	}
+	h.athena.OpenFile(unwritable, 0, 0)
+	copyPath := filepath.Join(filepath.Dir(path), "copy.txt")
+	h.Type(":saveas " + copyPath + "<cr>ix<esc>:w<cr>")
+	h.athena.editor.WaitForSaves()
+	if msg := h.Message(); msg.IsError {
+		t.Errorf(":w after :saveas of an unwritable file message = %+v", msg)
+	}
+	if data, _ := os.ReadFile(copyPath); string(data) != "xtext\n" {
+		t.Errorf(":w after :saveas of an unwritable file = %q, want %q", data, "xtext\n")
+	}
+}
+
+func TestHeadlessCycleBuffers(t *testing.T) {
+	tests := []struct {
+		name     string
+		keys     string
+		wantFile string
+	}{
+		{name: "next wraps to the first opened", keys: "gn", wantFile: "test.txt"},
+		{name: "previous", keys: "gp", wantFile: "a.txt"},
+		{name: "previous wraps to the last opened", keys: "gpgpgp", wantFile: "b.txt"},
+		{name: "counted", keys: "2gn", wantFile: "a.txt"},
+		{name: "command", keys: ":bn<cr>", wantFile: "test.txt"},
+		{name: "command back", keys: ":bp<cr>", wantFile: "a.txt"},
+	}
+	for _, tt := range tests {
+		t.Run(tt.name, func(t *testing.T) {
+			h := newTestHeadless(t, "one\n")
+			path, _ := h.athena.editor.FilePath()
+			// opened in this order, leaving b.txt current
+			for _, name := range []string{"a.txt", "b.txt"} {
+				h.athena.OpenFile(filepath.Join(filepath.Dir(path), name), 0, 0)
+			}
+
+			h.Type(tt.keys)
+			if name, _ := h.athena.editor.FileName(); name != tt.wantFile {
+				t.Errorf("Type(%q) file = %q, want %q", tt.keys, name, tt.wantFile)
+			}
+		})
+	}
+}
+
+func TestHeadlessBufferDelete(t *testing.T) {
+	tests := []struct {
+		name        string
+		other       bool   // other.txt is opened after test.txt, which is then made current again
+		keys        string // typed in test.txt
+		wantErr     string
+		wantFile    string // current once done
+		wantBuffers int
+	}{
+		{name: "closed", other: true, keys: ":bd<cr>", wantFile: "other.txt", wantBuffers: 1},
+		{name: "changes refused", other: true, keys: "x:bd<cr>", wantErr: "unsaved changes exist (add ! to override)", wantFile: "test.txt", wantBuffers: 2},
+		{name: "changes dropped", other: true, keys: "x:bd!<cr>", wantFile: "other.txt", wantBuffers: 1},
+		{name: "last buffer", keys: ":bd<cr>", wantErr: editor.ErrLastBuffer.Error(), wantFile: "test.txt", wantBuffers: 1},
+	}
+	for _, tt := range tests {
+		t.Run(tt.name, func(t *testing.T) {
+			h := newTestHeadless(t, "one\n")
+			path, _ := h.athena.editor.FilePath()
+			if tt.other {
+				h.athena.OpenFile(filepath.Join(filepath.Dir(path), "other.txt"), 0, 0)
+				h.athena.OpenFile(path, 0, 0)
+			}
+
+			h.Type(tt.keys)
+			if msg := h.Message(); tt.wantErr != "" && (!msg.IsError || msg.Text != tt.wantErr) {
+				t.Errorf("Type(%q) message = %+v, want %q", tt.keys, msg, tt.wantErr)
+			}
+			if name, _ := h.athena.editor.FileName(); name != tt.wantFile {
+				t.Errorf("Type(%q) file = %q, want %q", tt.keys, name, tt.wantFile)
+			}
+			if buffers := h.athena.editor.GetBufferList(); len(buffers) != tt.wantBuffers {
+				t.Errorf("Type(%q) buffers = %q, want %d", tt.keys, buffers, tt.wantBuffers)
+			}
+		})
+	}
+}
+
+func TestHeadlessSaveChangedOnDisk(t *testing.T) {
+	h := newTestHeadless(t, "one\n")
+	path, _ := h.athena.editor.FilePath()
+
+	// changed between the last check and the save
+	h.Type("x:w")
+	if err := os.WriteFile(path, []byte("changed elsewhere\n"), 0644); err != nil {
+		t.Fatal(err)
+	}
+	h.Type("<cr>")
+	if msg := h.Message(); !msg.IsError || msg.Text != ui.ErrChangedOnDisk.Error() {
+		t.Errorf(":w message = %+v, want %q", msg, ui.ErrChangedOnDisk)
+	}
+
+	h.Type("n:w!<cr>")
+	h.athena.editor.WaitForSaves()
+	if data, _ := os.ReadFile(path); string(data) != "ne\n" {
+		t.Errorf("after :w! file = %q, want %q", data, "ne\n")
+	}
+}
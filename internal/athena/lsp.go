@@ -0,0 +1,263 @@
+package athena
+
+import (
+	"errors"
+	"fmt"
+	"os"
+	"slices"
+	"sync"
+
+	"github.com/lg2m/athena/internal/editor"
+	"github.com/lg2m/athena/internal/lsp"
+	"github.com/lg2m/athena/internal/ui"
+)
+
+var (
+	errLanguageServerStarting = errors.New("language server is still starting")
+	errNoDefinition           = errors.New("no definition found")
+)
+
+// languageServers are the servers configured per file type, and the documents they were sent.
+//
+//	A server starts in the background the first time a buffer of its type is shown. Documents are
+//	sent whole from the event loop whenever the buffer's version has moved on, see syncDocuments.
+type languageServers struct {
+	mu      sync.Mutex
+	clients map[string]*lsp.Client // by file type; nil while starting
+	failed  map[string]error       // by file type, why its server failed to start
+
+	documents map[string]syncedDocument // by path, touched by the event loop only
+}
+
+// syncedDocument is a buffer a server has open, and the version of it the server has.
+type syncedDocument struct {
+	client  *lsp.Client
+	version uint64
+}
+
+// languageServer returns the running server for fileType, starting it the first time. Without one
+// running, the error is ui.ErrNoLanguageServer when none is configured, errLanguageServerStarting
+// until it is ready, and why it failed to start once it did.
+//
+//	A server that failed to start isn't tried again until the editor is restarted, rather than
+//	with every frame; its error wraps ui.ErrNoLanguageServer, so words are still completed.
+func (a *Athena) languageServer(fileType string) (*lsp.Client, error) {
+	command := a.cfg.LanguageServer(fileType)
+	if len(command) == 0 {
+		return nil, ui.ErrNoLanguageServer
+	}
+
+	a.servers.mu.Lock()
+	defer a.servers.mu.Unlock()
+
+	if err, ok := a.servers.failed[fileType]; ok {
+		return nil, err
+	}
+	if client, ok := a.servers.clients[fileType]; ok {
+		if client == nil {
+			return nil, errLanguageServerStarting
+		}
+		return client, nil
+	}
+	if a.servers.clients == nil {
+		a.servers.clients = make(map[string]*lsp.Client)
+	}
+	a.servers.clients[fileType] = nil
+
+	// the workspace is where the editor was started, as for relative paths
+	root, _ := os.Getwd()
+	go func() {
+		defer a.restoreOnPanic()
+		client, err := lsp.Start(command, root, a.publishDiagnostics)
+
+		a.servers.mu.Lock()
+		if err != nil {
+			err = fmt.Errorf("%w: %s failed to start: %w", ui.ErrNoLanguageServer, command[0], err)
+			delete(a.servers.clients, fileType)
+			if a.servers.failed == nil {
+				a.servers.failed = make(map[string]error)
+			}
+			a.servers.failed[fileType] = err
+		} else {
+			a.servers.clients[fileType] = client
+		}
+		a.servers.mu.Unlock()
+
+		if err != nil {
+			a.editor.SetError(err)
+		}
+		a.wakeup()
+	}()
+	return nil, errLanguageServerStarting
+}
+
+// publishDiagnostics hands diagnostics a server published to the editor.
+func (a *Athena) publishDiagnostics(params lsp.PublishDiagnosticsParams) {
+	if path, ok := lsp.URIToPath(params.URI); ok {
+		a.editor.SetDiagnostics(path, params.Diagnostics)
+	}
+}
+
+// syncDocuments sends the current buffer to its language server when the server's copy is out
+// of date, and closes the documents of buffers that were closed or changed file type.
+func (a *Athena) syncDocuments() {
+	open := a.editor.GetBufferList()
+	for path, doc := range a.servers.documents {
+		if !slices.Contains(open, path) {
+			_ = doc.client.DidClose(path)
+			delete(a.servers.documents, path)
+		}
+	}
+
+	path, err := a.editor.FilePath()
+	if err != nil {
+		return
+	}
+	fileType, _ := a.editor.FileType()
+	client, _ := a.languageServer(fileType)
+	doc, opened := a.servers.documents[path]
+	if opened && doc.client != client {
+		_ = doc.client.DidClose(path)
+		delete(a.servers.documents, path)
+		opened = false
+	}
+	if client == nil {
+		return
+	}
+
+	version, _ := a.editor.Version()
+	if opened && doc.version == version {
+		return
+	}
+	text, _ := a.editor.Text()
+	if opened {
+		err = client.DidChange(path, int(version), text)
+	} else {
+		err = client.DidOpen(path, lsp.LanguageID(fileType), int(version), text)
+	}
+	if err != nil {
+		// the server is gone; it is left alone rather than reported with every frame
+		return
+	}
+	if a.servers.documents == nil {
+		a.servers.documents = make(map[string]syncedDocument)
+	}
+	a.servers.documents[path] = syncedDocument{client: client, version: version}
+}
+
+// languageCommand asks the current buffer's language server about the cursor, see
+// ui.DocumentView.SetLanguageCommand; the answer is handed to the editor once it arrives.
+func (a *Athena) languageCommand(action string) error {
+	client, err := a.currentLanguageServer()
+	if err != nil {
+		return err
+	}
+
+	// the server must have the text being asked about
+	a.syncDocuments()
+	path, _ := a.editor.FilePath()
+	pos, err := a.editor.LSPPosition()
+	if err != nil {
+		return err
+	}
+	switch action {
+	case "complete":
+		go func() {
+			defer a.restoreOnPanic()
+			a.complete(client, path, pos)
+		}()
+	case "go_to_definition":
+		go func() {
+			defer a.restoreOnPanic()
+			a.goToDefinition(client, path, pos)
+		}()
+	case "hover":
+		// the popup is for where the cursor was, which may move meanwhile
+		sel, err := a.editor.Selection()
+		if err != nil {
+			return err
+		}
+		go func() {
+			defer a.restoreOnPanic()
+			a.hover(client, path, pos, sel.End)
+		}()
+	}
+	return nil
+}
+
+// currentLanguageServer returns the running server for the current buffer.
+func (a *Athena) currentLanguageServer() (*lsp.Client, error) {
+	fileType, err := a.editor.FileType()
+	if err != nil {
+		return nil, err
+	}
+	return a.languageServer(fileType)
+}
+
+// complete asks for completions at pos and opens the menu with them.
+func (a *Athena) complete(client *lsp.Client, path string, pos lsp.Position) {
+	items, err := client.Completion(path, pos)
+	if err != nil {
+		a.editor.SetError(fmt.Errorf("completion: %w", err))
+		a.wakeup()
+		return
+	}
+	a.editor.SetCompletion(path, completionItems(items))
+}
+
+// goToDefinition asks where the symbol at pos is defined and jumps to the first place found.
+func (a *Athena) goToDefinition(client *lsp.Client, path string, pos lsp.Position) {
+	locations, err := client.Definition(path, pos)
+	if err == nil && len(locations) == 0 {
+		err = errNoDefinition
+	}
+	if err != nil {
+		a.editor.SetError(fmt.Errorf("definition: %w", err))
+		a.wakeup()
+		return
+	}
+
+	target, ok := lsp.URIToPath(locations[0].URI)
+	if !ok {
+		a.editor.SetError(fmt.Errorf("definition: not a file: %s", locations[0].URI))
+		a.wakeup()
+		return
+	}
+	if err := a.editor.GoToLocation(path, target, locations[0].Range.Start); err != nil {
+		a.editor.SetError(err)
+		a.wakeup()
+	}
+}
+
+// hover asks for the documentation of the symbol at pos and shows it in a popup; cursor is the
+// buffer position pos was taken at.
+func (a *Athena) hover(client *lsp.Client, path string, pos lsp.Position, cursor int) {
+	text, err := client.Hover(path, pos)
+	if err != nil {
+		a.editor.SetError(fmt.Errorf("hover: %w", err))
+		a.wakeup()
+		return
+	}
+	a.editor.SetHover(path, cursor, text)
+}
+
+// completionItems converts a server's completion items into the editor's.
+func completionItems(items []lsp.CompletionItem) []editor.CompletionItem {
+	converted := make([]editor.CompletionItem, len(items))
+	for i, item := range items {
+		converted[i] = editor.CompletionItem{Label: item.Label, Detail: item.Detail, Text: item.Text()}
+	}
+	return converted
+}
+
+// closeLanguageServers shuts the running servers down.
+func (a *Athena) closeLanguageServers() {
+	a.servers.mu.Lock()
+	defer a.servers.mu.Unlock()
+
+	for _, client := range a.servers.clients {
+		if client != nil {
+			_ = client.Close()
+		}
+	}
+}
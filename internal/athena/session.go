@@ -0,0 +1,311 @@
+package athena
+
+import (
+	"errors"
+	"fmt"
+	"os"
+	"path/filepath"
+	"slices"
+	"strings"
+
+	"github.com/BurntSushi/toml"
+)
+
+var (
+	errNoSessionName      = errors.New("no session name; none was saved or loaded yet")
+	errInvalidSessionName = errors.New("invalid session name")
+	errNoSessionDir       = errors.New("no directory to keep sessions in")
+	errEmptySession       = errors.New("session has a tab page without windows")
+)
+
+// session is what is kept of a run under a name, to be picked up again with ":session load" or
+// "athena -session": the open buffers, the windows of every tab page with their cursor and
+// scroll position, and the prompt histories, searches included.
+type session struct {
+	Buffers []string            `toml:"buffers"` // in the order they were opened
+	Tab     int                 `toml:"current-tab"`
+	Tabs    []sessionTab        `toml:"tab"`
+	History map[string][]string `toml:"history"` // by prompt, e.g. "/" for searches
+}
+
+// sessionTab is a tab page of a session.
+type sessionTab struct {
+	Layout sessionLayout `toml:"layout"`
+	Focus  int           `toml:"focus"` // index of the focused window, left to right and top to bottom
+}
+
+// sessionLayout is a layout of a session: a window, or a split of its children.
+type sessionLayout struct {
+	Window   *sessionWindow  `toml:"window,omitempty"`
+	Vertical bool            `toml:"vertical,omitempty"`
+	Children []sessionLayout `toml:"children,omitempty"`
+}
+
+// sessionWindow is a window of a session.
+type sessionWindow struct {
+	Path   string `toml:"path"`
+	Line   int    `toml:"line"`
+	Column int    `toml:"column"`
+	Offset int    `toml:"offset"` // first line shown
+}
+
+// defaultSessionDir returns where sessions are kept, next to the swap files.
+func defaultSessionDir() string {
+	homeDir, err := os.UserHomeDir()
+	if err != nil {
+		return ""
+	}
+	return filepath.Join(homeDir, ".local", "share", "athena", "sessions")
+}
+
+// sessionPath returns the file the session name is kept in under dir.
+func sessionPath(dir, name string) (string, error) {
+	if dir == "" {
+		return "", errNoSessionDir
+	}
+	if name == "" || name == "." || name == ".." || strings.ContainsAny(name, `/\`) {
+		return "", fmt.Errorf("%w: %q", errInvalidSessionName, name)
+	}
+	return filepath.Join(dir, name+".toml"), nil
+}
+
+// readSession reads the session name kept under dir.
+func readSession(dir, name string) (*session, error) {
+	path, err := sessionPath(dir, name)
+	if err != nil {
+		return nil, err
+	}
+	s := &session{}
+	if _, err := toml.DecodeFile(path, s); err != nil {
+		return nil, err
+	}
+	if len(s.Tabs) == 0 {
+		return nil, fmt.Errorf("%s: %w", path, errEmptySession)
+	}
+	for _, tab := range s.Tabs {
+		if len(tab.Layout.windows()) == 0 {
+			return nil, fmt.Errorf("%s: %w", path, errEmptySession)
+		}
+	}
+	return s, nil
+}
+
+// SessionFile returns the file shown by the focused window of the session name, for starting
+// the editor on before loading it with LoadSession.
+func SessionFile(name string) (string, error) {
+	s, err := readSession(defaultSessionDir(), name)
+	if err != nil {
+		return "", err
+	}
+	tab := s.Tabs[min(max(s.Tab, 0), len(s.Tabs)-1)]
+	windows := tab.Layout.windows()
+	return windows[min(max(tab.Focus, 0), len(windows)-1)].Path, nil
+}
+
+// windows returns the windows of the layout, left to right and top to bottom.
+func (l sessionLayout) windows() []sessionWindow {
+	if l.Window != nil {
+		return []sessionWindow{*l.Window}
+	}
+	var windows []sessionWindow
+	for _, child := range l.Children {
+		windows = append(windows, child.windows()...)
+	}
+	return windows
+}
+
+// LoadSession replaces the tab pages and windows with those of the session name, saving it
+// again on exit; failing to, it leaves them and shows why in the message line.
+func (a *Athena) LoadSession(name string) {
+	a.editor.SetError(a.sessionCommand("load", name))
+}
+
+// sessionCommand saves or loads a session for ":session", see ui.DocumentView.SetSessionCommand;
+// without a name it takes the session last saved or loaded.
+func (a *Athena) sessionCommand(action, name string) error {
+	if name == "" {
+		name = a.session
+	}
+	if name == "" {
+		return errNoSessionName
+	}
+
+	switch action {
+	case "save":
+		if err := a.saveSession(name); err != nil {
+			return err
+		}
+		a.editor.SetMessage(fmt.Sprintf("session %s saved", name))
+	case "load":
+		s, err := readSession(a.sessionDir, name)
+		if err != nil {
+			return err
+		}
+		if err := a.restoreSession(s); err != nil {
+			return err
+		}
+		a.editor.SetMessage(fmt.Sprintf("session %s loaded", name))
+	}
+	a.session = name
+	return nil
+}
+
+// saveSessionOnExit saves the session last saved or loaded, if any, as the editor exits.
+func (a *Athena) saveSessionOnExit() {
+	if a.session != "" {
+		_ = a.saveSession(a.session)
+	}
+}
+
+// saveSession keeps the open buffers, tab pages and windows and prompt histories under name.
+func (a *Athena) saveSession(name string) error {
+	path, err := sessionPath(a.sessionDir, name)
+	if err != nil {
+		return err
+	}
+	s := a.captureSession()
+	if err := os.MkdirAll(filepath.Dir(path), 0755); err != nil {
+		return err
+	}
+
+	file, err := os.Create(path)
+	if err != nil {
+		return err
+	}
+	if err := toml.NewEncoder(file).Encode(s); err != nil {
+		file.Close()
+		return err
+	}
+	return file.Close()
+}
+
+// captureSession returns the session of the editor as it is.
+func (a *Athena) captureSession() *session {
+	// the focused window and current tab page are only stored when left
+	if state, err := a.editor.SaveWindow(); err == nil {
+		a.focus.state = state
+	}
+	a.tabs[a.tab] = &tabPage{layout: a.layout, focus: a.focus}
+
+	s := &session{
+		Buffers: a.editor.GetBufferList(),
+		Tab:     a.tab,
+		History: a.views.commandLine.Histories(),
+	}
+	for _, tab := range a.tabs {
+		// a zoomed page is kept with all its windows
+		l := tab.layout.unzoom()
+		s.Tabs = append(s.Tabs, sessionTab{
+			Layout: captureLayout(l),
+			Focus:  slices.Index(l.windows(), tab.focus),
+		})
+	}
+	return s
+}
+
+// captureLayout returns l as kept in a session.
+func captureLayout(l *layout) sessionLayout {
+	if w := l.window; w != nil {
+		line, col, _ := w.state.Position()
+		return sessionLayout{Window: &sessionWindow{
+			Path:   w.state.FilePath(),
+			Line:   line,
+			Column: col,
+			Offset: w.viewport.Offset(),
+		}}
+	}
+	captured := sessionLayout{Vertical: l.vertical}
+	for _, child := range l.children {
+		captured.Children = append(captured.Children, captureLayout(child))
+	}
+	return captured
+}
+
+// restoreSession opens the buffers of s and replaces the tab pages with its own.
+//
+//	Buffers already open stay open, so nothing unsaved is lost, and a session failing to load
+//	leaves the focused window as it was.
+func (a *Athena) restoreSession(s *session) error {
+	state, err := a.editor.SaveWindow()
+	if err != nil {
+		return err
+	}
+	tabs, err := a.restoreTabs(s)
+	if err != nil {
+		_ = a.editor.RestoreWindow(state)
+		return err
+	}
+
+	a.tabs = tabs
+	a.showTab(min(max(s.Tab, 0), len(tabs)-1))
+	a.views.commandLine.SetHistories(s.History)
+	return nil
+}
+
+// restoreTabs opens the buffers of s and builds its tab pages.
+func (a *Athena) restoreTabs(s *session) ([]*tabPage, error) {
+	for _, path := range s.Buffers {
+		if err := a.editor.OpenFile(path); err != nil {
+			return nil, err
+		}
+	}
+
+	tabs := make([]*tabPage, 0, len(s.Tabs))
+	for _, st := range s.Tabs {
+		l, err := a.restoreLayout(st.Layout, nil)
+		if err != nil {
+			return nil, err
+		}
+		windows := l.windows()
+		tabs = append(tabs, &tabPage{layout: l, focus: windows[min(max(st.Focus, 0), len(windows)-1)]})
+	}
+	return tabs, nil
+}
+
+// restoreLayout builds the layout sl describes, with windows opening their files.
+func (a *Athena) restoreLayout(sl sessionLayout, parent *layout) (*layout, error) {
+	l := &layout{vertical: sl.Vertical, parent: parent}
+	if sw := sl.Window; sw != nil {
+		w, err := a.restoreWindow(*sw)
+		if err != nil {
+			return nil, err
+		}
+		l.window = w
+		return l, nil
+	}
+	for _, child := range sl.Children {
+		restored, err := a.restoreLayout(child, l)
+		if err != nil {
+			return nil, err
+		}
+		l.children = append(l.children, restored)
+	}
+	if len(l.children) == 0 {
+		return nil, errEmptySession
+	}
+	return l, nil
+}
+
+// restoreWindow creates a window over the file of sw, with the cursor and scroll position it had;
+// positions past the end of a file that has since shrunk are clamped.
+func (a *Athena) restoreWindow(sw sessionWindow) (*window, error) {
+	if err := a.editor.OpenFile(sw.Path); err != nil {
+		return nil, err
+	}
+	lines, err := a.editor.GetLineCount()
+	if err != nil {
+		return nil, err
+	}
+	start, end, err := a.editor.LineRange(min(max(sw.Line, 0), lines-1))
+	if err != nil {
+		return nil, err
+	}
+	if err := a.editor.MoveCursorTo(start+min(max(sw.Column, 0), end-start), false); err != nil {
+		return nil, err
+	}
+
+	w := a.newWindow()
+	w.viewport.SetOffset(sw.Offset)
+	w.state, err = a.editor.SaveWindow()
+	return w, err
+}
@@ -0,0 +1,87 @@
+package athena
+
+import (
+	"fmt"
+	"os"
+	"path/filepath"
+	"time"
+)
+
+// autosave is the background loop writing swap files, see startAutosave.
+type autosave struct {
+	dir     string          // where swap files go, empty when autosave is off
+	stop    chan struct{}   // closed to end the loop
+	done    chan struct{}   // closed once the loop has ended
+	checked map[string]bool // buffers already checked for a swap file left behind, touched by the event loop only
+}
+
+// defaultSwapDir returns the directory swap files are written to.
+func defaultSwapDir() string {
+	homeDir, err := os.UserHomeDir()
+	if err != nil {
+		return ""
+	}
+	return filepath.Join(homeDir, ".local", "share", "athena", "swap")
+}
+
+// startAutosave writes modified buffers to swap files in dir every interval, until stopAutosave.
+func (a *Athena) startAutosave(dir string, interval time.Duration) {
+	if dir == "" {
+		return
+	}
+	a.autosave = autosave{
+		dir:     dir,
+		stop:    make(chan struct{}),
+		done:    make(chan struct{}),
+		checked: make(map[string]bool),
+	}
+	a.editor.SetSwapDir(dir)
+
+	stop, done := a.autosave.stop, a.autosave.done
+	go func() {
+		defer a.restoreOnPanic()
+		defer close(done)
+		ticker := time.NewTicker(interval)
+		defer ticker.Stop()
+		for {
+			select {
+			case <-stop:
+				return
+			case <-ticker.C:
+				if err := a.editor.WriteSwaps(); err != nil {
+					a.editor.SetError(fmt.Errorf("writing swap file: %w", err))
+					a.wakeup()
+				}
+			}
+		}
+	}()
+}
+
+// stopAutosave ends the autosave loop and removes the swap files it wrote, which are only left
+// behind when athena doesn't exit cleanly.
+func (a *Athena) stopAutosave() {
+	if a.autosave.dir == "" {
+		return
+	}
+	close(a.autosave.stop)
+	<-a.autosave.done
+	_ = a.editor.RemoveSwaps()
+	a.autosave.dir = ""
+}
+
+// checkSwap tells about a swap file left behind for the current buffer, the first time the buffer
+// is shown.
+func (a *Athena) checkSwap() {
+	if a.autosave.dir == "" {
+		return
+	}
+	path, err := a.editor.FilePath()
+	if err != nil || a.autosave.checked[path] {
+		return
+	}
+	a.autosave.checked[path] = true
+
+	if swap, ok := a.editor.ForeignSwap(); ok {
+		a.editor.SetError(fmt.Errorf("found swap file %s, which may hold unsaved changes; :recover restores them", swap))
+	}
+}
@@ -0,0 +1,82 @@
+package athena
+
+import (
+	"fmt"
+	"slices"
+
+	"github.com/lg2m/athena/internal/ui"
+)
+
+// tabPage is a window layout of its own, shown one page at a time.
+//
+//	The current page's layout and focus live in Athena while it is shown, and are stored back
+//	when another page is switched to.
+type tabPage struct {
+	layout *layout
+	focus  *window
+}
+
+// newTab opens a tab page after the current one, with a window over the current buffer, and
+// switches to it.
+func (a *Athena) newTab() {
+	w := a.newWindow()
+	// the new window starts out as a copy of the focused one, like a split
+	w.viewport.SetOffset(a.focus.viewport.Offset())
+	a.tabs = slices.Insert(a.tabs, a.tab+1, &tabPage{layout: &layout{window: w}, focus: w})
+	a.switchTab(a.tab + 1)
+}
+
+// switchTab shows tab page i, putting the cursor of its focused window back in the editor.
+func (a *Athena) switchTab(i int) {
+	if i == a.tab {
+		return
+	}
+	if state, err := a.editor.SaveWindow(); err == nil {
+		a.focus.state = state
+	}
+	a.tabs[a.tab] = &tabPage{layout: a.layout, focus: a.focus}
+	a.showTab(i)
+}
+
+// showTab makes tab page i the current one, leaving the page shown before as it is.
+func (a *Athena) showTab(i int) {
+	a.tab = i
+	a.layout, a.focus = a.tabs[i].layout, a.tabs[i].focus
+	_ = a.editor.RestoreWindow(a.focus.state)
+}
+
+// cycleTab switches to the tab page offset places after the current one, wrapping around.
+func (a *Athena) cycleTab(offset int) {
+	n := len(a.tabs)
+	a.switchTab(((a.tab+offset)%n + n) % n)
+}
+
+// closeTab closes the current tab page and its windows, showing the page that moves into its
+// place; the last page stays.
+func (a *Athena) closeTab() error {
+	if len(a.tabs) == 1 {
+		return ui.ErrLastTab
+	}
+	a.tabs = slices.Delete(a.tabs, a.tab, a.tab+1)
+	a.showTab(min(a.tab, len(a.tabs)-1))
+	return nil
+}
+
+// tabLabels returns the label of each tab page: the name of its focused window's buffer, after
+// the number of windows when it has several.
+func (a *Athena) tabLabels() []string {
+	labels := make([]string, len(a.tabs))
+	for i, tab := range a.tabs {
+		windows, name := len(tab.layout.windows()), tab.focus.state.FileName()
+		if i == a.tab {
+			// the stored page is stale while it is shown
+			windows = len(a.layout.windows())
+			name, _ = a.editor.FileName()
+		}
+		if windows > 1 {
+			name = fmt.Sprintf("%d %s", windows, name)
+		}
+		labels[i] = name
+	}
+	return labels
+}
@@ -0,0 +1,108 @@
+package athena
+
+import (
+	"os"
+	"os/user"
+	"path/filepath"
+	"strings"
+	"time"
+
+	"github.com/lg2m/athena/internal/editor"
+)
+
+// cursorPlaceholder marks where the cursor starts in a template.
+const cursorPlaceholder = "{cursor}"
+
+// defaultTemplatesDir returns where file templates are kept, one per file type, e.g. "go.tmpl".
+func defaultTemplatesDir() string {
+	homeDir, err := os.UserHomeDir()
+	if err != nil {
+		return ""
+	}
+	return filepath.Join(homeDir, ".config", "athena", "templates")
+}
+
+// templateVars returns the placeholders a template for the file at path can use.
+func templateVars(path string, now time.Time) map[string]string {
+	name := filepath.Base(path)
+	author := ""
+	if u, err := user.Current(); err == nil {
+		author = u.Name
+		if author == "" {
+			author = u.Username
+		}
+	}
+	return map[string]string{
+		"name":   name,
+		"stem":   strings.TrimSuffix(name, filepath.Ext(name)),
+		"dir":    filepath.Base(filepath.Dir(path)),
+		"date":   now.Format(time.DateOnly),
+		"year":   now.Format("2006"),
+		"author": author,
+	}
+}
+
+// expandTemplate fills in the "{name}"-style placeholders of template, returning the text before
+// and after the cursor; without a "{cursor}" the cursor goes to the end.
+//
+//	Braces that aren't a known placeholder are kept, since templates are mostly code.
+func expandTemplate(template string, vars map[string]string) (before, after string) {
+	pairs := make([]string, 0, 2*len(vars))
+	for name, value := range vars {
+		pairs = append(pairs, "{"+name+"}", value)
+	}
+	text := strings.NewReplacer(pairs...).Replace(template)
+
+	before, after, _ = strings.Cut(text, cursorPlaceholder)
+	return before, strings.ReplaceAll(after, cursorPlaceholder, "")
+}
+
+// fillNewFiles fills the current buffer, and every file opened from then on, from the template for
+// its file type in dir when it is a new, empty file; see applyTemplate.
+//
+//	Files are filled as the hooks run, before the next frame, so they are first drawn filled.
+func (a *Athena) fillNewFiles(dir string) {
+	if dir == "" {
+		return
+	}
+	a.applyTemplate(dir)
+	editor.Subscribe(a.editor, func(ev editor.FileOpened) {
+		// a file opened and left again before the hooks ran is left as it is
+		if path, err := a.editor.FilePath(); err == nil && path == ev.Path {
+			a.applyTemplate(dir)
+		}
+	})
+}
+
+// applyTemplate fills the current buffer from the template for its file type, if there is one and
+// it is a new, empty file.
+func (a *Athena) applyTemplate(dir string) {
+	if isNew, err := a.editor.IsNewFile(); err != nil || !isNew {
+		return
+	}
+	if text, err := a.editor.Text(); err != nil || text != "" {
+		return
+	}
+	fileType, err := a.editor.FileType()
+	if err != nil || fileType == "" {
+		return
+	}
+	template, err := os.ReadFile(filepath.Join(dir, fileType+".tmpl"))
+	if err != nil {
+		return
+	}
+	path, err := a.editor.FilePath()
+	if err != nil {
+		return
+	}
+
+	before, after := expandTemplate(string(template), templateVars(path, time.Now()))
+	pos, err := a.editor.Replace(0, 0, before)
+	if err != nil {
+		return
+	}
+	if _, err := a.editor.Replace(pos, pos, after); err != nil {
+		return
+	}
+	_ = a.editor.MoveCursorTo(pos, false)
+}
@@ -0,0 +1,33 @@
+package athena
+
+import (
+	"testing"
+	"time"
+)
+
+func TestExpandTemplate(t *testing.T) {
+	vars := templateVars("/src/pkg/main.go", time.Date(2024, 3, 9, 0, 0, 0, 0, time.UTC))
+	vars["author"] = "Ada"
+
+	tests := []struct {
+		name       string
+		template   string
+		wantBefore string
+		wantAfter  string
+	}{
+		{name: "placeholders", template: "// {name} ({stem}) in {dir}, {date}", wantBefore: "// main.go (main) in pkg, 2024-03-09"},
+		{name: "license header", template: "// Copyright {year} {author}\n", wantBefore: "// Copyright 2024 Ada\n"},
+		{name: "cursor", template: "func main() {\n\t{cursor}\n}\n", wantBefore: "func main() {\n\t", wantAfter: "\n}\n"},
+		{name: "extra cursors dropped", template: "a{cursor}b{cursor}c", wantBefore: "a", wantAfter: "bc"},
+		{name: "unknown braces kept", template: "{{.Name}} {unknown}", wantBefore: "{{.Name}} {unknown}"},
+	}
+
+	for _, tt := range tests {
+		t.Run(tt.name, func(t *testing.T) {
+			before, after := expandTemplate(tt.template, vars)
+			if before != tt.wantBefore || after != tt.wantAfter {
+				t.Errorf("expandTemplate(%q) = %q, %q, want %q, %q", tt.template, before, after, tt.wantBefore, tt.wantAfter)
+			}
+		})
+	}
+}
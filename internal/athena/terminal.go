@@ -0,0 +1,32 @@
+package athena
+
+import "github.com/gdamore/tcell/v2"
+
+// Close restores the terminal, for when the editor won't Run after NewAthena, e.g. because
+// setting it up further failed; Run restores it itself on any way out.
+func (a *Athena) Close() {
+	a.restoreTerminal()
+}
+
+// restoreTerminal hands the terminal back as it was: the alternate screen left, raw mode off, the
+// default cursor shape shown and the title put back. Only the first call does anything, so it is
+// safe from whichever exit path gets there first.
+func (a *Athena) restoreTerminal() {
+	a.restoreOnce.Do(func() {
+		// the shape of the last mode would otherwise stay with the shell
+		a.screen.SetCursorStyle(tcell.CursorStyleDefault)
+		a.screen.Show()
+		a.screen.Fini()
+		// after the screen, whose teardown may set the title too
+		a.title.Restore()
+	})
+}
+
+// restoreOnPanic, deferred first thing in a goroutine, restores the terminal before a panic in it
+// ends the program, so the panic and its stack are printed to a usable shell.
+func (a *Athena) restoreOnPanic() {
+	if r := recover(); r != nil {
+		a.restoreTerminal()
+		panic(r)
+	}
+}
@@ -0,0 +1,79 @@
+package athena
+
+import (
+	"fmt"
+	"io"
+	"strings"
+	"unicode"
+)
+
+// terminalTitle keeps the terminal window title in sync with the current buffer.
+//
+//	The title from before athena started is saved on the terminal's title stack and
+//	popped again on exit, since terminals don't let programs read the title back.
+type terminalTitle struct {
+	out      io.Writer
+	template string
+	current  string
+	saved    bool
+}
+
+func newTerminalTitle(out io.Writer, template string) *terminalTitle {
+	return &terminalTitle{out: out, template: template}
+}
+
+// Set updates the title for the given buffer, writing only when it changed.
+func (t *terminalTitle) Set(name, path string, modified bool) {
+	if t.template == "" {
+		return
+	}
+
+	title := formatTitle(t.template, name, path, modified)
+	if title == t.current {
+		return
+	}
+
+	if !t.saved {
+		// XTWINOPS: push the icon and window titles
+		fmt.Fprint(t.out, "\x1b[22;0t")
+		t.saved = true
+	}
+	// OSC 0 sets both the icon name and the window title
+	fmt.Fprintf(t.out, "\x1b]0;%s\x07", title)
+	t.current = title
+}
+
+// Restore puts back the title the terminal had before the first Set.
+func (t *terminalTitle) Restore() {
+	if !t.saved {
+		return
+	}
+	fmt.Fprint(t.out, "\x1b[23;0t")
+	t.saved = false
+	t.current = ""
+}
+
+// formatTitle expands the placeholders in template: {name} and {path} of the file,
+// and {modified}, which becomes " [+]" while there are unsaved changes.
+func formatTitle(template, name, path string, modified bool) string {
+	marker := ""
+	if modified {
+		marker = " [+]"
+	}
+	if name == "" {
+		name = "[scratch]"
+	}
+
+	r := strings.NewReplacer(
+		"{name}", name,
+		"{path}", path,
+		"{modified}", marker,
+	)
+	// control characters in file names would end the escape sequence early
+	return strings.Map(func(r rune) rune {
+		if unicode.IsControl(r) {
+			return -1
+		}
+		return r
+	}, r.Replace(template))
+}
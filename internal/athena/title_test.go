@@ -0,0 +1,61 @@
+package athena
+
+import (
+	"bytes"
+	"testing"
+)
+
+func TestFormatTitle(t *testing.T) {
+	tests := []struct {
+		name     string
+		template string
+		file     string
+		path     string
+		modified bool
+		want     string
+	}{
+		{name: "name", template: "{name} - athena", file: "main.go", path: "/src/main.go", want: "main.go - athena"},
+		{name: "modified", template: "{name}{modified}", file: "main.go", modified: true, want: "main.go [+]"},
+		{name: "unmodified", template: "{name}{modified}", file: "main.go", want: "main.go"},
+		{name: "path", template: "{path}", file: "main.go", path: "/src/main.go", want: "/src/main.go"},
+		{name: "no file", template: "{name}", want: "[scratch]"},
+		{name: "control characters", template: "{name}", file: "a\x07b\x1bc", want: "abc"},
+		{name: "literal text", template: "athena", file: "main.go", want: "athena"},
+	}
+
+	for _, tt := range tests {
+		t.Run(tt.name, func(t *testing.T) {
+			got := formatTitle(tt.template, tt.file, tt.path, tt.modified)
+			if got != tt.want {
+				t.Errorf("formatTitle(%q) = %q, want %q", tt.template, got, tt.want)
+			}
+		})
+	}
+}
+
+func TestTerminalTitle(t *testing.T) {
+	var out bytes.Buffer
+	title := newTerminalTitle(&out, "{name}{modified}")
+
+	title.Set("main.go", "/src/main.go", false)
+	title.Set("main.go", "/src/main.go", false)
+	title.Set("main.go", "/src/main.go", true)
+	title.Restore()
+
+	want := "\x1b[22;0t\x1b]0;main.go\x07\x1b]0;main.go [+]\x07\x1b[23;0t"
+	if got := out.String(); got != want {
+		t.Errorf("output = %q, want %q", got, want)
+	}
+}
+
+func TestTerminalTitleDisabled(t *testing.T) {
+	var out bytes.Buffer
+	title := newTerminalTitle(&out, "")
+
+	title.Set("main.go", "/src/main.go", true)
+	title.Restore()
+
+	if out.Len() != 0 {
+		t.Errorf("output = %q, want nothing", out.String())
+	}
+}
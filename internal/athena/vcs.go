@@ -0,0 +1,48 @@
+package athena
+
+import (
+	"path/filepath"
+
+	"github.com/lg2m/athena/internal/vcs"
+)
+
+// versionControl tracks which files version control was asked about, see syncVCS; it is touched by
+// the event loop only.
+type versionControl struct {
+	loaded   map[string]bool // files whose branch and committed text were looked up
+	modified map[string]bool // whether each file's buffer was modified when last shown
+}
+
+func newVersionControl() *versionControl {
+	return &versionControl{
+		loaded:   make(map[string]bool),
+		modified: make(map[string]bool),
+	}
+}
+
+// syncVCS looks up the branch and committed text of the current buffer's file when it is first
+// shown, and again after it is saved, since a save is when a commit may have happened. The lookup
+// runs git in the background and hands its result to the editor, which wakes the UI.
+func (a *Athena) syncVCS() {
+	if a.vcs == nil {
+		return
+	}
+	path, err := a.editor.FilePath()
+	if err != nil {
+		return
+	}
+	modified, _ := a.editor.Modified()
+	saved := a.vcs.modified[path] && !modified
+	a.vcs.modified[path] = modified
+	if a.vcs.loaded[path] && !saved {
+		return
+	}
+	a.vcs.loaded[path] = true
+
+	go func() {
+		defer a.restoreOnPanic()
+		branch, _ := vcs.Branch(filepath.Dir(path))
+		base, tracked := vcs.HeadText(path)
+		a.editor.SetVCS(path, branch, base, tracked)
+	}()
+}
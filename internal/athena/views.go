@@ -0,0 +1,153 @@
+package athena
+
+import (
+	"os"
+	"path/filepath"
+	"slices"
+	"time"
+
+	"github.com/BurntSushi/toml"
+	"github.com/lg2m/athena/internal/athena/config"
+	"github.com/lg2m/athena/internal/editor"
+)
+
+// maxViews is the number of files whose view is remembered; the least recently left are dropped first.
+const maxViews = 500
+
+// fileView is what is remembered of a file between runs, like a vim view.
+//
+//	Options holds the ":set" expressions that differ from the config, so later config changes still apply.
+type fileView struct {
+	Line    int       `toml:"line"`
+	Column  int       `toml:"column"`
+	Offset  int       `toml:"offset"` // first line shown
+	Options []string  `toml:"options"`
+	Left    time.Time `toml:"left"` // when the file was last left, for pruning
+}
+
+// viewStore persists file views, keyed by the file's real path.
+type viewStore struct {
+	path  string
+	Views map[string]fileView `toml:"view"`
+}
+
+// loadViews reads the views saved at path; a missing or unreadable file yields an empty store.
+func loadViews(path string) *viewStore {
+	s := &viewStore{path: path}
+	if _, err := toml.DecodeFile(path, s); err != nil || s.Views == nil {
+		s.Views = make(map[string]fileView)
+	}
+	return s
+}
+
+// defaultViewsPath returns where views are saved, next to the config directory.
+func defaultViewsPath() string {
+	homeDir, err := os.UserHomeDir()
+	if err != nil {
+		return ""
+	}
+	return filepath.Join(homeDir, ".local", "state", "athena", "views.toml")
+}
+
+// put remembers the view of the file at path, dropping the oldest views past maxViews.
+func (s *viewStore) put(path string, view fileView) {
+	view.Left = time.Now()
+	s.Views[path] = view
+
+	if len(s.Views) <= maxViews {
+		return
+	}
+	paths := make([]string, 0, len(s.Views))
+	for p := range s.Views {
+		paths = append(paths, p)
+	}
+	slices.SortFunc(paths, func(a, b string) int {
+		return s.Views[a].Left.Compare(s.Views[b].Left)
+	})
+	for _, p := range paths[:len(paths)-maxViews] {
+		delete(s.Views, p)
+	}
+}
+
+// save writes the views to disk.
+func (s *viewStore) save() error {
+	if s.path == "" {
+		return nil
+	}
+	if err := os.MkdirAll(filepath.Dir(s.path), 0755); err != nil {
+		return err
+	}
+
+	file, err := os.Create(s.path)
+	if err != nil {
+		return err
+	}
+	if err := toml.NewEncoder(file).Encode(s); err != nil {
+		file.Close()
+		return err
+	}
+	return file.Close()
+}
+
+// captureView records the cursor, scroll position and changed options of the current buffer.
+func (a *Athena) captureView() {
+	line, col, err := a.editor.GetCurrentPosition()
+	if err != nil {
+		return
+	}
+	base := a.baseEditor
+	a.view = fileView{
+		Line:    line,
+		Column:  col,
+		Offset:  a.focus.viewport.Offset(),
+		Options: config.SyncOptions(&base, &a.cfg.Editor),
+	}
+}
+
+// saveViews remembers the view of the current file and writes all views to disk.
+func (a *Athena) saveViews() {
+	if a.viewStore == nil || a.viewPath == "" {
+		return
+	}
+	a.viewStore.put(a.viewPath, a.view)
+	_ = a.viewStore.save()
+}
+
+// forgetVisited lets a closed file's position be restored again when it is reopened.
+func (a *Athena) forgetVisited(ev editor.BufferClosed) {
+	path := ev.Path
+	if realPath, err := filepath.EvalSymlinks(path); err == nil {
+		path = realPath
+	}
+	delete(a.visited, path)
+}
+
+// trackView notices when another file becomes current, remembering the view of the one left and
+// restoring the new one's: its options every time, its position only when it was just opened.
+func (a *Athena) trackView() {
+	path, err := a.editor.RealPath()
+	if err != nil || path == a.viewPath {
+		return
+	}
+	if a.viewPath != "" {
+		a.viewStore.put(a.viewPath, a.view)
+	}
+	a.viewPath = path
+
+	saved, ok := a.viewStore.Views[path]
+	options := a.baseEditor
+	if ok {
+		for _, expr := range saved.Options {
+			_, _ = config.SetOption(&options, expr)
+		}
+	}
+	config.SyncOptions(&a.cfg.Editor, &options)
+
+	if ok && !a.visited[path] {
+		if start, end, err := a.editor.LineRange(saved.Line); err == nil {
+			_ = a.editor.MoveCursorTo(min(start+saved.Column, end), false)
+			a.focus.viewport.SetOffset(saved.Offset)
+		}
+	}
+	a.visited[path] = true
+}
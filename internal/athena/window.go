@@ -0,0 +1,454 @@
+package athena
+
+import (
+	"slices"
+
+	"github.com/lg2m/athena/internal/athena/config"
+	"github.com/lg2m/athena/internal/editor"
+	"github.com/lg2m/athena/internal/ui"
+)
+
+// window shows a buffer in part of the screen, with its own viewport and cursor.
+//
+//	Only the focused window's cursor lives in the editor; the others keep theirs in state and
+//	put it back while they are drawn.
+type window struct {
+	viewport  *ui.Viewport
+	gutters   *ui.GuttersView
+	document  *ui.DocumentView
+	scrollbar *ui.ScrollbarView // the window's last column, when the scrollbar setting is on
+	name      *ui.DividerView   // the row under the window naming its buffer, when the screen is split
+	state     editor.WindowState
+
+	x, y, width, height int // area taken, name row included
+}
+
+// layout is a tree of windows: a leaf holds one, and a split shares its area between its children,
+// side by side or stacked.
+type layout struct {
+	window   *window
+	vertical bool // children side by side, as made by ":vsplit"
+	children []*layout
+	parent   *layout
+
+	weight int     // share of the parent's area against the siblings', 0 while they share evenly
+	size   int     // cells across the parent's split arrange last gave it
+	zoomed *layout // on the root of a zoomed layout, the layout to put back, see zoom
+}
+
+// minWindowSize is the fewest rows or columns resizing leaves a window, e.g. a row of text and
+// the row naming its buffer.
+const minWindowSize = 2
+
+// find returns the leaf holding w, or nil when w isn't in the layout.
+func (l *layout) find(w *window) *layout {
+	if l.window != nil {
+		if l.window == w {
+			return l
+		}
+		return nil
+	}
+	for _, child := range l.children {
+		if leaf := child.find(w); leaf != nil {
+			return leaf
+		}
+	}
+	return nil
+}
+
+// windows returns the windows of the layout, left to right and top to bottom.
+func (l *layout) windows() []*window {
+	if l.window != nil {
+		return []*window{l.window}
+	}
+	var windows []*window
+	for _, child := range l.children {
+		windows = append(windows, child.windows()...)
+	}
+	return windows
+}
+
+// split puts added above w, or to its left when vertical, sharing the space w had.
+//
+//	Splitting the same way as w's parent adds a sibling, so repeated splits share evenly.
+func (l *layout) split(w, added *window, vertical bool) {
+	leaf := l.find(w)
+	if leaf == nil {
+		return
+	}
+	if parent := leaf.parent; parent != nil && parent.vertical == vertical {
+		idx := slices.Index(parent.children, leaf)
+		parent.children = slices.Insert(parent.children, idx, &layout{window: added, parent: parent})
+		parent.equalize()
+		return
+	}
+
+	leaf.children = []*layout{
+		{window: added, parent: leaf},
+		{window: w, parent: leaf},
+	}
+	leaf.window = nil
+	leaf.vertical = vertical
+}
+
+// remove takes w out of the layout, leaving its space to its siblings; the last window stays.
+func (l *layout) remove(w *window) bool {
+	leaf := l.find(w)
+	if leaf == nil || leaf.parent == nil {
+		return false
+	}
+	parent := leaf.parent
+	parent.children = slices.DeleteFunc(parent.children, func(child *layout) bool {
+		return child == leaf
+	})
+
+	if len(parent.children) == 1 {
+		// a split of one is its child
+		only := parent.children[0]
+		parent.window, parent.vertical, parent.children = only.window, only.vertical, only.children
+		for _, child := range parent.children {
+			child.parent = parent
+		}
+	}
+	return true
+}
+
+// resize grows the part of the layout holding w by n cells, or shrinks it for a negative n, across
+// the nearest split that is side by side when vertical, or stacked otherwise. The cells are taken
+// from or given to the next part, or the previous one for the last.
+func (l *layout) resize(w *window, n int, vertical bool) {
+	part := l.find(w)
+	for part != nil && part.parent != nil && part.parent.vertical != vertical {
+		part = part.parent
+	}
+	if part == nil || part.parent == nil {
+		return
+	}
+
+	siblings := part.parent.children
+	idx := slices.Index(siblings, part)
+	other := idx + 1
+	if other == len(siblings) {
+		other = idx - 1
+	}
+	// from now on the parts keep the sizes they have, whatever sharing evenly would give them
+	for _, sibling := range siblings {
+		sibling.weight = max(sibling.size, 1)
+	}
+
+	// neither part goes below minWindowSize
+	grow := n > 0
+	if grow {
+		n = min(n, siblings[other].weight-minWindowSize)
+	} else {
+		n = max(n, minWindowSize-part.weight)
+	}
+	if n == 0 || (n > 0) != grow {
+		return
+	}
+	part.weight += n
+	siblings[other].weight -= n
+	// resizing again before the next arrange, as a count does, starts from here
+	part.size, siblings[other].size = part.weight, siblings[other].weight
+}
+
+// equalize makes every split of the layout share its area evenly again.
+func (l *layout) equalize() {
+	for _, child := range l.children {
+		child.weight = 0
+		child.equalize()
+	}
+}
+
+// zoom shows only w's window until zoom is called again, which puts the layout back as it was;
+// it returns the layout to show.
+func (l *layout) zoom(w *window) *layout {
+	if l.zoomed != nil {
+		return l.unzoom()
+	}
+	if l.window != nil {
+		return l
+	}
+	return &layout{window: w, zoomed: l}
+}
+
+// unzoom returns the layout a zoomed one stands in for, or l itself when it isn't zoomed.
+func (l *layout) unzoom() *layout {
+	if l.zoomed == nil {
+		return l
+	}
+	return l.zoomed
+}
+
+// newWindow creates a window over the editor's current buffer; it isn't placed until it is added
+// to the layout.
+func (a *Athena) newWindow() *window {
+	w := &window{
+		viewport: ui.NewViewport(a.cfg.Editor.ScrollPadding),
+		name:     ui.NewDividerView(a.cfg, false),
+	}
+	w.gutters = ui.NewGuttersView(a.editor, a.cfg, w.viewport)
+	w.document = ui.NewDocumentView(a.editor, a.cfg, w.viewport, a.views.commandLine)
+	w.scrollbar = ui.NewScrollbarView(a.editor, a.cfg, w.viewport, w.document)
+	w.document.SetRunExternal(a.runExternal)
+	w.document.SetQuit(func() { a.quitting = true })
+	w.document.SetWindowCommand(a.windowCommand)
+	w.document.SetLanguageCommand(a.languageCommand)
+	w.document.SetSessionCommand(a.sessionCommand)
+	w.document.SetSyntaxCache(a.views.syntax)
+	w.document.SetRowCache(a.views.rows)
+	w.document.SetOutput(a.views.output)
+	if a.fedKeys {
+		w.document.SetPasteBurstGap(0)
+	}
+	return w
+}
+
+// resize places the window, keeping its last row for its name when framed and its last column for
+// the scrollbar when shown.
+func (w *window) resize(x, y, width, height, gutterWidth int, framed, scrollbar bool) {
+	w.x, w.y, w.width, w.height = x, y, width, height
+	if framed && height > 0 {
+		height--
+		w.name.Resize(x, y+height, width, 1)
+	} else {
+		w.name.Resize(0, 0, 0, 0)
+	}
+
+	// the scrollbar only takes a column the text can spare
+	if scrollbar && width > gutterWidth+1 {
+		width--
+		w.scrollbar.Resize(x+width, y, 1, height)
+	} else {
+		w.scrollbar.Resize(0, 0, 0, 0)
+	}
+
+	gutterWidth = min(gutterWidth, width)
+	w.gutters.Resize(x, y, gutterWidth, height)
+	w.document.Resize(x+gutterWidth, y, width-gutterWidth, height)
+}
+
+// draw draws the window for the editor's current buffer, which must be the one it shows.
+func (w *window) draw(surface ui.Surface) {
+	// the document lays out the rows the gutters and scrollbar follow
+	w.document.Draw(surface)
+	w.gutters.Draw(surface)
+	w.scrollbar.Draw(surface)
+	w.name.Draw(surface)
+}
+
+// windowCommand runs a window or tab page action for the focused document, see
+// ui.DocumentView.SetWindowCommand.
+func (a *Athena) windowCommand(action string) error {
+	switch action {
+	case "window_split", "window_vsplit", "window_close", "window_only":
+		// changing the windows shows them all again first
+		a.layout = a.layout.unzoom()
+	}
+
+	switch action {
+	case "window_split", "window_vsplit":
+		w := a.newWindow()
+		// the new window starts out as a copy of the focused one
+		w.viewport.SetOffset(a.focus.viewport.Offset())
+		a.layout.split(a.focus, w, action == "window_vsplit")
+		a.focusWindow(w)
+	case "window_left":
+		a.focusWindow(a.neighbour(-1, 0))
+	case "window_down":
+		a.focusWindow(a.neighbour(0, 1))
+	case "window_up":
+		a.focusWindow(a.neighbour(0, -1))
+	case "window_right":
+		a.focusWindow(a.neighbour(1, 0))
+	case "window_next":
+		windows := a.layout.windows()
+		idx := slices.Index(windows, a.focus)
+		a.focusWindow(windows[(idx+1)%len(windows)])
+	case "window_close":
+		windows := a.layout.windows()
+		idx := slices.Index(windows, a.focus)
+		if !a.layout.remove(a.focus) {
+			// the last window of a tab page closes the page
+			if len(a.tabs) > 1 {
+				return a.closeTab()
+			}
+			return ui.ErrLastWindow
+		}
+		// the window that moved into its place in the order takes the focus
+		windows = slices.Delete(windows, idx, idx+1)
+		a.focusWindow(windows[min(idx, len(windows)-1)])
+	case "window_only":
+		a.layout = &layout{window: a.focus}
+	case "window_grow":
+		a.layout.resize(a.focus, 1, false)
+	case "window_shrink":
+		a.layout.resize(a.focus, -1, false)
+	case "window_wider":
+		a.layout.resize(a.focus, 1, true)
+	case "window_narrower":
+		a.layout.resize(a.focus, -1, true)
+	case "window_equalize":
+		a.layout.equalize()
+	case "window_zoom":
+		a.layout = a.layout.zoom(a.focus)
+	case "tab_new":
+		a.newTab()
+	case "tab_next":
+		a.cycleTab(1)
+	case "tab_prev":
+		a.cycleTab(-1)
+	case "tab_close":
+		return a.closeTab()
+	}
+	return nil
+}
+
+// focusWindow sends keys to w from now on, putting its cursor back in the editor; a new window, or
+// one whose buffer was closed, goes on with the buffer of the window that had the focus.
+func (a *Athena) focusWindow(w *window) {
+	if w == nil || w == a.focus {
+		return
+	}
+	if state, err := a.editor.SaveWindow(); err == nil {
+		a.focus.state = state
+	}
+	a.focus = w
+	_ = a.editor.RestoreWindow(w.state)
+}
+
+// neighbour returns the nearest window next to the focused one in the direction of dx or dy, or
+// nil at the edge of the screen.
+func (a *Athena) neighbour(dx, dy int) *window {
+	f := a.focus
+	var best *window
+	bestDistance := 0
+	for _, w := range a.layout.windows() {
+		var distance int
+		switch {
+		case dx < 0 && w.x+w.width <= f.x && overlaps(w.y, w.height, f.y, f.height):
+			distance = f.x - (w.x + w.width)
+		case dx > 0 && w.x >= f.x+f.width && overlaps(w.y, w.height, f.y, f.height):
+			distance = w.x - (f.x + f.width)
+		case dy < 0 && w.y+w.height <= f.y && overlaps(w.x, w.width, f.x, f.width):
+			distance = f.y - (w.y + w.height)
+		case dy > 0 && w.y >= f.y+f.height && overlaps(w.x, w.width, f.x, f.width):
+			distance = w.y - (f.y + f.height)
+		default:
+			continue
+		}
+		if best == nil || distance < bestDistance {
+			best, bestDistance = w, distance
+		}
+	}
+	return best
+}
+
+// overlaps reports whether the spans starting at a and b, of lengths aLen and bLen, share a cell.
+func overlaps(a, aLen, b, bLen int) bool {
+	return a < b+bLen && b < a+aLen
+}
+
+// arrange shares the area of l out between its windows, with a divider column between side by
+// side ones and, when the screen is split, a row under each naming its buffer.
+func (a *Athena) arrange(l *layout, x, y, width, height int) {
+	if l.window != nil {
+		l.window.resize(x, y, width, height, a.gutterWidth(), a.layout.window == nil, a.cfg.Editor.Scrollbar)
+		return
+	}
+
+	n := len(l.children)
+	size := height
+	if l.vertical {
+		size = max(0, width-(n-1))
+	}
+	parts := shareOut(l.children, size)
+	for i, child := range l.children {
+		part := parts[i]
+		child.size = part
+
+		if !l.vertical {
+			a.arrange(child, x, y, width, part)
+			y += part
+			continue
+		}
+		a.arrange(child, x, y, part, height)
+		x += part
+		if i < n-1 {
+			divider := ui.NewDividerView(a.cfg, true)
+			divider.Resize(x, y, 1, height)
+			a.views.dividers = append(a.views.dividers, divider)
+			x++
+		}
+	}
+}
+
+// shareOut shares size cells out between children by their weights, evenly when none has one;
+// the first children take what doesn't share out exactly.
+func shareOut(children []*layout, size int) []int {
+	total := 0
+	for _, child := range children {
+		total += child.weight
+	}
+	parts := make([]int, len(children))
+	left := size
+	for i, child := range children {
+		parts[i] = size / len(children)
+		if total > 0 {
+			parts[i] = size * child.weight / total
+		}
+		left -= parts[i]
+	}
+	// fewer cells than children are left over
+	for i := range left {
+		parts[i]++
+	}
+	return parts
+}
+
+// drawWindows draws every window with its own buffer and cursor, then the dividers between them.
+func (a *Athena) drawWindows(surface ui.Surface) {
+	focused, err := a.editor.SaveWindow()
+	if err != nil {
+		return
+	}
+	for _, w := range a.layout.windows() {
+		if w == a.focus {
+			continue
+		}
+		if a.editor.RestoreWindow(w.state) != nil {
+			// its buffer was closed, so it shows the focused window's instead
+			w.state = focused
+			_ = a.editor.RestoreWindow(w.state)
+		}
+		a.reloadShown()
+		a.drawWindow(surface, w)
+	}
+	_ = a.editor.RestoreWindow(focused)
+	a.drawWindow(surface, a.focus)
+
+	for _, divider := range a.views.dividers {
+		divider.Draw(surface)
+	}
+}
+
+// drawWindow names the buffer w shows, which must be the editor's current one, and draws it.
+func (a *Athena) drawWindow(surface ui.Surface, w *window) {
+	name, _ := a.editor.FileName()
+	if modified, _ := a.editor.Modified(); modified {
+		name += " [+]"
+	}
+	if readOnly, _ := a.editor.ReadOnly(); readOnly {
+		name += " [RO]"
+	}
+	w.name.SetLabel(name, w == a.focus)
+	w.draw(surface)
+}
+
+// gutterWidth returns the width of the gutters beside each window.
+func (a *Athena) gutterWidth() int {
+	if a.cfg.Editor.LineNumber == config.LineNumberNone {
+		return 0
+	}
+	return 6
+}
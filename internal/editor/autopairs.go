@@ -0,0 +1,102 @@
+package editor
+
+import (
+	"unicode"
+	"unicode/utf8"
+
+	"github.com/lg2m/athena/internal/editor/state"
+)
+
+// TypeRune inserts r typed in insert mode. pairs are the brackets and quotes typed in pairs, each
+// an opening and a closing rune such as "()": typing an opening one inserts its closer too, with
+// the cursor between them, and typing a closer over one inserted that way steps over it.
+//
+//	A pair is only opened before blanks, closers or the end of the line, and quotes not right
+//	after a word, so an apostrophe in "don't" stays alone.
+func (e *Editor) TypeRune(r rune, pairs []string) error {
+	e.mu.Lock()
+	defer e.mu.Unlock()
+
+	if e.current == nil {
+		return ErrNoBuffer
+	}
+	if e.mode != state.Insert {
+		return ErrInvalidOperation
+	}
+	sel := e.current.Selection()
+	if !sel.Empty() || len(pairs) == 0 {
+		return e.current.Insert(string(r))
+	}
+
+	pos := sel.End
+	before, after := e.runeAt(pos-1), e.runeAt(pos)
+	if r == after {
+		if pair, ok := e.current.AutoPairClosedAt(pos); ok {
+			e.current.ForgetAutoPair(pair)
+			return e.current.MoveSelectionTo(pos+1, false)
+		}
+	}
+	for _, pair := range pairs {
+		open, size := utf8.DecodeRuneInString(pair)
+		close, _ := utf8.DecodeRuneInString(pair[size:])
+		if r != open {
+			continue
+		}
+		if !unicode.IsSpace(after) && after != 0 && !isCloser(after, pairs) {
+			break
+		}
+		if open == close && (isWordRune(before) || before == open) {
+			break
+		}
+		return e.current.InsertPair(string(open), string(close))
+	}
+	return e.current.Insert(string(r))
+}
+
+// Backspace deletes the selection, or the character before the cursor; between the two halves of
+// a pair typed together, with nothing else typed yet, both go.
+func (e *Editor) Backspace() error {
+	e.mu.Lock()
+	defer e.mu.Unlock()
+
+	if e.current == nil {
+		return ErrNoBuffer
+	}
+	sel := e.current.Selection()
+	if !sel.Empty() {
+		return e.current.DeleteSelection()
+	}
+	pos := sel.End
+	if pos == 0 {
+		return nil
+	}
+	end := pos
+	if pair, ok := e.current.AutoPairClosedAt(pos); ok && pair.Open.End == pos {
+		end = pos + 1
+	}
+	return e.current.Delete(pos-1, end)
+}
+
+// runeAt returns the first rune of the grapheme at pos in the current buffer, 0 outside of it.
+func (e *Editor) runeAt(pos int) rune {
+	if pos < 0 {
+		return 0
+	}
+	g, err := e.current.Substring(pos, pos+1)
+	if err != nil || g == "" {
+		return 0
+	}
+	r, _ := utf8.DecodeRuneInString(g)
+	return r
+}
+
+// isCloser reports whether r closes one of pairs.
+func isCloser(r rune, pairs []string) bool {
+	for _, pair := range pairs {
+		_, size := utf8.DecodeRuneInString(pair)
+		if close, _ := utf8.DecodeRuneInString(pair[size:]); r == close {
+			return true
+		}
+	}
+	return false
+}
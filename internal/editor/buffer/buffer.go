@@ -1,10 +1,13 @@
 package buffer
 
 import (
+	"bufio"
 	"errors"
 	"io"
+	"io/fs"
 	"os"
 	"path/filepath"
+	"slices"
 	"sync"
 	"time"
 
@@ -17,12 +20,20 @@ import (
 )
 
 var (
-	ErrInvalidRange     = errors.New("buffer: poition range exeeds document boundaries")
-	ErrInvalidPosition  = errors.New("buffer: position exceeds document boundaries")
-	ErrInvalidLineCol   = errors.New("buffer: line/column position out of bounds")
-	ErrInvalidSelection = errors.New("buffer: selection boundaries are invalid")
+	ErrInvalidRange      = errors.New("buffer: poition range exeeds document boundaries")
+	ErrInvalidPosition   = errors.New("buffer: position exceeds document boundaries")
+	ErrInvalidLineCol    = errors.New("buffer: line/column position out of bounds")
+	ErrInvalidSelection  = errors.New("buffer: selection boundaries are invalid")
+	ErrNoWordUnderCursor = errors.New("buffer: no word under cursor")
+	ErrNoMatchingPair    = errors.New("buffer: no matching pair on line")
+	ErrSaveInProgress    = errors.New("buffer: a save is in progress")
+	ErrReadOnly          = errors.New("buffer: read-only, :set noreadonly to make changes")
 )
 
+// maxShebangLength is how much of the first line of a file without an extension is read to tell
+// its type.
+const maxShebangLength = 128
+
 // Buffer represents a text buffer with support for syntax highlighting and concurrent access.
 type Buffer struct {
 	document      *rope.Rope
@@ -34,6 +45,20 @@ type Buffer struct {
 	lineCache     []int
 	highlighter   *treesitter.Highlighter
 	dirty         bool
+	version       uint64 // incremented on every edit, to tell whether a save or a language server has the latest one
+	saving        bool
+	unwritable    bool       // the file exists but could only be opened for reading, e.g. it is owned by root
+	readOnly      bool       // edits are refused with ErrReadOnly, see SetReadOnly
+	realPath      string     // filePath with symlinks resolved, identifying the file on disk
+	isLink        bool       // filePath itself is a symlink, to realPath
+	lineEnding    LineEnding // written in place of "\n", as detected when the file was read
+	encoding      Encoding   // character encoding of the file, as detected when it was read
+	stamp         fileStamp  // of the file as last read or written, see ChangedOnDisk
+	changes       state.ChangeList
+	marks         state.Marks
+	autoPairs     state.AutoPairs
+	history       state.History // what undo takes back, see Undo
+	changeHook    func(path string, version uint64, changes state.ChangeSet) // see SetChangeHook
 
 	FileUtil *util.FileUtil
 
@@ -41,47 +66,58 @@ type Buffer struct {
 	mu          sync.RWMutex
 }
 
-// NewBuffer creates a new Buffer with optional initial content.
+// NewBuffer creates a new Buffer from the content of filePath.
+//
+//	A file that does not exist yet yields an empty buffer; it is created on the first save.
 func NewBuffer(filePath string) (*Buffer, error) {
-	file, err := os.OpenFile(filePath, os.O_RDWR, 0644)
-	if err != nil {
-		return nil, err
-	}
-
-	document, err := io.ReadAll(file)
-	if err != nil {
-		file.Close()
-		return nil, err
-	}
-
 	fp, err := filepath.Abs(filePath)
 	if err != nil {
-		file.Close()
 		return nil, err
 	}
 
-	// Setup registry
-	registry := treesitter.NewRegistry()
-
-	// Register langauges
-	_ = registry.RegisterLanguage(&languages.RustProvider{})
-	_ = registry.RegisterLanguage(&languages.GoProvider{})
-
-	// Create highlighter
-	highlighter, err := treesitter.NewHighlighter(registry, filepath.Base(filePath))
-	if err != nil {
-		file.Close()
+	var document []byte
+	unwritable := false
+	file, err := os.OpenFile(fp, os.O_RDWR, 0644)
+	switch {
+	case errors.Is(err, fs.ErrNotExist):
+		file = nil
+	case errors.Is(err, fs.ErrPermission):
+		// saving needs SaveWith, e.g. through sudo, so it starts read-only
+		file = nil
+		document, err = os.ReadFile(fp)
+		if err != nil {
+			return nil, err
+		}
+		unwritable = true
+	case err != nil:
 		return nil, err
+	default:
+		document, err = io.ReadAll(file)
+		if err != nil {
+			file.Close()
+			return nil, err
+		}
 	}
 
+	stamp := statFile(fp)
+	encoding, text := decode(document)
+	lineEnding, text := detectLineEnding(text)
+	realPath, isLink := resolveLink(fp)
 	b := &Buffer{
-		document:      rope.NewRope(string(document)),
+		document:      rope.NewRope(text),
 		selection:     state.Selection{Start: 0, End: 0},
 		filePath:      fp,
 		lastSavePoint: time.Now(),
 		file:          file,
-		size:          int64(len(document)),
-		highlighter:   highlighter,
+		size:          int64(len(text)),
+		highlighter:   newHighlighter(fp),
+		unwritable:    unwritable,
+		readOnly:      unwritable,
+		realPath:      realPath,
+		isLink:        isLink,
+		lineEnding:    lineEnding,
+		encoding:      encoding,
+		stamp:         stamp,
 		FileUtil:      util.NewFileUtil(nil),
 	}
 
@@ -95,109 +131,678 @@ func (b *Buffer) Insert(s string) error {
 	b.mu.Lock()
 	defer b.mu.Unlock()
 
+	if b.readOnly {
+		return ErrReadOnly
+	}
+
 	// replace selection with new text
-	if b.selection.Start != b.selection.End {
-		if err := b.document.Delete(b.selection.Start, b.selection.End); err != nil {
+	start, end := b.selection.Range()
+	removed, err := b.document.Substring(start, end)
+	if err != nil {
+		return err
+	}
+	if start != end {
+		if err := b.document.Delete(start, end); err != nil {
 			return err
 		}
-		b.size -= int64(b.selection.End - b.selection.Start)
+		b.size -= int64(end - start)
+	}
+
+	// text that continues the grapheme before it, e.g. an accent typed after its letter or the
+	// rest of an emoji sequence from an input method, replaces that grapheme joined with it, so
+	// positions keep counting whole graphemes
+	at, text := start, s
+	if prev := b.graphemeBefore(start); prev != "" && joinsGrapheme(prev, s) {
+		if err := b.document.Delete(start-1, start); err != nil {
+			return err
+		}
+		at, text, removed = start-1, prev+s, prev+removed
 	}
 
 	// insert new text at selection start
-	if err := b.document.Insert(b.selection.Start, s); err != nil {
+	if err := b.document.Insert(at, text); err != nil {
 		return err
 	}
 
 	// update selection to new position
-	graphemeCount := countGraphemes(s)
-	newEnd := b.selection.Start + graphemeCount
+	graphemeCount := countGraphemes(text)
+	newEnd := at + graphemeCount
 	b.selection = state.Selection{Start: newEnd, End: newEnd}
 
 	b.size += int64(len(s))
 	b.dirty = true
+	b.version++
 	b.updateLineCache()
+	b.recordChange(state.ChangeSet{{Start: at, Removed: end - at, Inserted: graphemeCount}}, at+max(graphemeCount-1, 0))
+	b.history.Record(state.TextChange{Start: at, Removed: removed, Inserted: text})
 	return nil
 }
 
+// graphemeBefore returns the grapheme before pos, "" at the start of the buffer.
+func (b *Buffer) graphemeBefore(pos int) string {
+	if pos <= 0 {
+		return ""
+	}
+	g, err := b.document.GraphemeAt(pos - 1)
+	if err != nil {
+		return ""
+	}
+	return g
+}
+
 // Delete deletes text from the cursor position to position + length.
 func (b *Buffer) Delete(start, end int) error {
 	b.mu.Lock()
 	defer b.mu.Unlock()
 
+	if b.readOnly {
+		return ErrReadOnly
+	}
+
+	removed, err := b.document.Substring(start, end)
+	if err != nil {
+		return err
+	}
 	if err := b.document.Delete(start, end); err != nil {
 		return err
 	}
 
-	if b.selection.Start > start {
-		b.selection = state.Selection{Start: start, End: start}
-	}
+	changes := state.ChangeSet{{Start: start, Removed: end - start}}
+	b.selection = changes.MapSelection(b.selection)
 
 	b.size -= int64(end - start)
+	b.dirty = true
+	b.version++
 	b.updateLineCache()
+	b.recordChange(changes, start)
+	b.history.Record(state.TextChange{Start: start, Removed: removed})
 	return nil
 }
 
+// Replace replaces the text between start and end with s, returning the position after the inserted text.
+//
+//	The cursor is moved to the start of the replaced range.
+func (b *Buffer) Replace(start, end int, s string) (int, error) {
+	b.mu.Lock()
+	defer b.mu.Unlock()
+
+	if b.readOnly {
+		return 0, ErrReadOnly
+	}
+
+	removed, err := b.document.Substring(start, end)
+	if err != nil {
+		return 0, err
+	}
+	if err := b.document.Delete(start, end); err != nil {
+		return 0, err
+	}
+	if err := b.document.Insert(start, s); err != nil {
+		return 0, err
+	}
+
+	b.selection = state.Selection{Start: start, End: start}
+	b.size += int64(len(s) - len(removed))
+	b.dirty = true
+	b.version++
+	b.updateLineCache()
+	b.recordChange(state.ChangeSet{{Start: start, Removed: end - start, Inserted: countGraphemes(s)}}, start)
+	b.history.Record(state.TextChange{Start: start, Removed: removed, Inserted: s})
+	return start + countGraphemes(s), nil
+}
+
 // DeleteSelections deletes text in the current selections.
 func (b *Buffer) DeleteSelection() error {
 	b.mu.Lock()
 	defer b.mu.Unlock()
 
-	start, end := b.selection.Start, b.selection.End
+	if b.readOnly {
+		return ErrReadOnly
+	}
+
+	start, end := b.selection.Range()
+	removed, err := b.document.Substring(start, end)
+	if err != nil {
+		return err
+	}
 	if err := b.document.Delete(start, end); err != nil {
 		return err
 	}
 
 	b.selection = state.Selection{Start: start, End: start}
 	b.size -= int64(end - start)
+	b.dirty = true
+	b.version++
 	b.updateLineCache()
+	b.recordChange(state.ChangeSet{{Start: start, Removed: end - start}}, start)
+	b.history.Record(state.TextChange{Start: start, Removed: removed})
 	return nil
 }
 
+// Edit replaces the text between Start and End with Text, in positions of the text before any of
+// the edits applied with it; see Apply.
+type Edit struct {
+	Start, End int
+	Text       string
+}
+
+// Apply makes edits, which must not overlap, as one change, mapping the selection through them,
+// and returns the changes made so the caller can map the positions it holds too.
+func (b *Buffer) Apply(edits ...Edit) (state.ChangeSet, error) {
+	b.mu.Lock()
+	defer b.mu.Unlock()
+
+	if b.readOnly {
+		return nil, ErrReadOnly
+	}
+
+	edits = slices.Clone(edits)
+	slices.SortStableFunc(edits, func(x, y Edit) int { return x.Start - y.Start })
+	total := b.document.TotalGraphemes()
+	for i, edit := range edits {
+		if edit.Start < 0 || edit.Start > edit.End || edit.End > total || i > 0 && edit.Start < edits[i-1].End {
+			return nil, ErrInvalidRange
+		}
+	}
+	if len(edits) == 0 {
+		return nil, nil
+	}
+
+	// from the last one, so the positions of those before it still hold
+	var changes state.ChangeSet
+	var undo []state.TextChange
+	for i := len(edits) - 1; i >= 0; i-- {
+		edit := edits[i]
+		removed, err := b.document.Substring(edit.Start, edit.End)
+		if err != nil {
+			return nil, err
+		}
+		if err := b.document.Delete(edit.Start, edit.End); err != nil {
+			return nil, err
+		}
+		if err := b.document.Insert(edit.Start, edit.Text); err != nil {
+			return nil, err
+		}
+		b.size += int64(len(edit.Text) - len(removed))
+		changes = append(changes, state.Change{Start: edit.Start, Removed: edit.End - edit.Start, Inserted: countGraphemes(edit.Text)})
+		undo = append(undo, state.TextChange{Start: edit.Start, Removed: removed, Inserted: edit.Text})
+	}
+
+	b.selection = changes.MapSelection(b.selection)
+	b.dirty = true
+	b.version++
+	b.updateLineCache()
+	b.recordChange(changes, edits[0].Start)
+	for _, c := range undo {
+		b.history.Record(c)
+	}
+	return changes, nil
+}
+
 // GetSelectedText returns the text within the current selections.
 func (b *Buffer) GetSelectedText() (string, error) {
 	b.mu.RLock()
 	defer b.mu.RUnlock()
 
-	return b.document.Substring(b.selection.Start, b.selection.End)
+	start, end := b.selection.Range()
+	return b.document.Substring(start, end)
+}
+
+// saveJob is a save that has been started: the file to write and a snapshot of the document.
+type saveJob struct {
+	file       *os.File
+	snapshot   *rope.Rope
+	version    uint64
+	lineEnding LineEnding
+	encoding   Encoding
 }
 
 // Save writes buffer content to disk.
 func (b *Buffer) Save() error {
+	job, err := b.beginSave()
+	if err != nil {
+		return err
+	}
+	err = job.write()
+	b.endSave(job, err)
+	return err
+}
+
+// SaveInBackground writes buffer content to disk on another goroutine, calling done with the result.
+//
+//	The write works from a clone of the rope, so editing can go on meanwhile; the buffer is only
+//	marked as saved if it was not edited since the clone was taken.
+func (b *Buffer) SaveInBackground(done func(error)) error {
+	job, err := b.beginSave()
+	if err != nil {
+		return err
+	}
+
+	go func() {
+		err := job.write()
+		b.endSave(job, err)
+		done(err)
+	}()
+	return nil
+}
+
+// SaveWith saves the buffer by handing its content to write, for files that can't be opened
+// for writing directly, e.g. by piping it to "sudo tee".
+func (b *Buffer) SaveWith(write func(content io.Reader) error) error {
+	b.mu.Lock()
+	if b.saving {
+		b.mu.Unlock()
+		return ErrSaveInProgress
+	}
+	b.saving = true
+	job := &saveJob{snapshot: b.document.Clone(), version: b.version, lineEnding: b.lineEnding, encoding: b.encoding}
+	b.mu.Unlock()
+
+	// streamed, so huge files aren't copied into one string first
+	pr, pw := io.Pipe()
+	go func() {
+		content, err := encodedDocument(job.snapshot, job.lineEnding, job.encoding)
+		if err == nil {
+			_, err = content.WriteTo(pw)
+		}
+		pw.CloseWithError(err)
+	}()
+
+	err := write(pr)
+	// unblocks the writer if write stopped reading early
+	pr.Close()
+
+	b.endSave(job, err)
+	return err
+}
+
+// Saving reports whether a save is being written.
+func (b *Buffer) Saving() bool {
+	b.mu.RLock()
+	defer b.mu.RUnlock()
+
+	return b.saving
+}
+
+// beginSave creates the file if needed and snapshots the document to write.
+func (b *Buffer) beginSave() (*saveJob, error) {
 	b.mu.Lock()
 	defer b.mu.Unlock()
 
-	if err := b.file.Truncate(0); err != nil {
+	if b.saving {
+		return nil, ErrSaveInProgress
+	}
+	if b.file == nil {
+		file, err := createFile(b.filePath, true)
+		if err != nil {
+			return nil, err
+		}
+		b.file = file
+	}
+
+	b.saving = true
+	return &saveJob{file: b.file, snapshot: b.document.Clone(), version: b.version, lineEnding: b.lineEnding, encoding: b.encoding}, nil
+}
+
+// endSave records the outcome of a save started with beginSave.
+func (b *Buffer) endSave(job *saveJob, err error) {
+	b.mu.Lock()
+	defer b.mu.Unlock()
+
+	b.saving = false
+	if err != nil {
+		return
+	}
+	b.lastSavePoint = time.Now()
+	b.stamp = statFile(b.filePath)
+	if b.version == job.version {
+		b.dirty = false
+		b.history.MarkSaved()
+	}
+}
+
+// write replaces the file's content with the snapshot.
+//
+//	Only one save runs at a time and the file is not swapped or closed meanwhile, so this runs
+//	without holding the buffer lock.
+func (j *saveJob) write() error {
+	content, err := encodedDocument(j.snapshot, j.lineEnding, j.encoding)
+	if err != nil {
+		return err
+	}
+	if err := j.file.Truncate(0); err != nil {
+		return err
+	}
+	if _, err := j.file.Seek(0, 0); err != nil {
 		return err
 	}
 
-	if _, err := b.file.Seek(0, 0); err != nil {
+	w := bufio.NewWriter(j.file)
+	if _, err := content.WriteTo(w); err != nil {
 		return err
 	}
+	return w.Flush()
+}
 
-	_, err := b.file.WriteString(b.document.String())
+// WriteCopy writes the buffer content to another file without rebinding the buffer to it.
+//
+//	An existing file is only replaced when overwrite is set.
+func (b *Buffer) WriteCopy(filePath string, overwrite bool) error {
+	b.mu.RLock()
+	defer b.mu.RUnlock()
+
+	content, err := encodedDocument(b.document, b.lineEnding, b.encoding)
+	if err != nil {
+		return err
+	}
+	file, err := createFile(filePath, overwrite)
+	if err != nil {
+		return err
+	}
+	defer file.Close()
+
+	_, err = content.WriteTo(file)
+	return err
+}
+
+// WriteBackup copies the buffer's file as it is on disk to the same path with "~" appended,
+// replacing an older backup; a file that doesn't exist yet has nothing to back up.
+//
+//	The backup gets the file's permissions, so a file only its owner can read stays that way.
+func (b *Buffer) WriteBackup() error {
+	b.mu.RLock()
+	defer b.mu.RUnlock()
+
+	info, err := os.Stat(b.filePath)
+	if errors.Is(err, fs.ErrNotExist) {
+		return nil
+	}
 	if err != nil {
 		return err
 	}
+	content, err := os.ReadFile(b.filePath)
+	if err != nil {
+		return err
+	}
+	backup := b.filePath + "~"
+	if err := os.WriteFile(backup, content, info.Mode().Perm()); err != nil {
+		return err
+	}
+	// an older backup keeps the permissions it was made with otherwise
+	return os.Chmod(backup, info.Mode().Perm())
+}
+
+// SaveAs writes the buffer content to filePath and binds the buffer to it, re-detecting the language.
+//
+//	An existing file is only replaced when overwrite is set.
+func (b *Buffer) SaveAs(filePath string, overwrite bool) error {
+	b.mu.Lock()
+	defer b.mu.Unlock()
 
+	if b.saving {
+		return ErrSaveInProgress
+	}
+
+	fp, err := filepath.Abs(filePath)
+	if err != nil {
+		return err
+	}
+
+	content, err := encodedDocument(b.document, b.lineEnding, b.encoding)
+	if err != nil {
+		return err
+	}
+	file, err := createFile(fp, overwrite)
+	if err != nil {
+		return err
+	}
+	if _, err := content.WriteTo(file); err != nil {
+		file.Close()
+		return err
+	}
+
+	if b.file != nil {
+		_ = b.file.Close()
+	}
+	b.file = file
+	b.filePath = fp
+	b.realPath, b.isLink = resolveLink(fp)
+	b.clearUnwritable()
+	b.highlighter = newHighlighter(fp)
 	b.lastSavePoint = time.Now()
+	b.stamp = statFile(fp)
 	b.dirty = false
+	b.history.MarkSaved()
 	return nil
 }
 
-// Close properly closes the buffer and its resources
+// clearUnwritable marks the buffer's file as one that can be written, once it is bound to a new
+// one, ending the read-only state an unwritable file started it in.
+func (b *Buffer) clearUnwritable() {
+	if b.unwritable {
+		b.unwritable, b.readOnly = false, false
+	}
+}
+
+// RealPath returns the path of the file on disk with symlinks resolved.
+func (b *Buffer) RealPath() string {
+	b.mu.RLock()
+	defer b.mu.RUnlock()
+
+	return b.realPath
+}
+
+// LinkTarget returns the file the buffer's path links to, if the path is a symlink.
+func (b *Buffer) LinkTarget() (string, bool) {
+	b.mu.RLock()
+	defer b.mu.RUnlock()
+
+	return b.realPath, b.isLink
+}
+
+// ReplaceLink saves the buffer to a regular file put in place of the symlink at its path, so
+// saving no longer writes through to the link target; it reports whether the path was a link, and
+// so the buffer was saved.
+//
+//	The content is written to a temporary file next to the link and renamed over it, so the link
+//	is only replaced once the content is on disk, and a failed write leaves it as it was.
+func (b *Buffer) ReplaceLink() (bool, error) {
+	b.mu.Lock()
+	if !b.isLink {
+		b.mu.Unlock()
+		return false, nil
+	}
+	if b.saving {
+		b.mu.Unlock()
+		return true, ErrSaveInProgress
+	}
+	b.saving = true
+	path := b.filePath
+	job := &saveJob{snapshot: b.document.Clone(), version: b.version, lineEnding: b.lineEnding, encoding: b.encoding}
+	b.mu.Unlock()
+
+	file, err := replaceFile(path, job)
+	if err == nil {
+		b.mu.Lock()
+		if b.file != nil {
+			_ = b.file.Close()
+		}
+		b.file = file
+		b.realPath, b.isLink = resolveLink(path)
+		b.clearUnwritable()
+		b.mu.Unlock()
+	}
+	b.endSave(job, err)
+	return true, err
+}
+
+// replaceFile writes the job's snapshot to a new file renamed over path, with the permissions of
+// the file path leads to, returning the new file left open.
+func replaceFile(path string, job *saveJob) (*os.File, error) {
+	mode := fs.FileMode(0644)
+	if info, err := os.Stat(path); err == nil {
+		mode = info.Mode().Perm()
+	}
+
+	file, err := os.CreateTemp(filepath.Dir(path), "."+filepath.Base(path)+".*")
+	if err != nil {
+		return nil, err
+	}
+	job.file = file
+	err = job.write()
+	if err == nil {
+		err = file.Sync()
+	}
+	if err == nil {
+		err = file.Chmod(mode)
+	}
+	if err == nil {
+		err = os.Rename(file.Name(), path)
+	}
+	if err != nil {
+		_ = file.Close()
+		_ = os.Remove(file.Name())
+		return nil, err
+	}
+	return file, nil
+}
+
+// Close releases the file backing the buffer.
+//
+//	Unsaved changes are discarded; callers check Modified first.
 func (b *Buffer) Close() error {
 	b.mu.Lock()
 	defer b.mu.Unlock()
 
-	// Save remaining dirty content
-	if b.dirty {
-		if err := b.Save(); err != nil {
-			return err
-		}
+	if b.saving {
+		return ErrSaveInProgress
+	}
+
+	if b.file == nil {
+		return nil
 	}
 	return b.file.Close()
 }
 
+// Reload discards the buffer content and reads its file from disk again.
+//
+//	The file is opened again too, since programs often replace a file rather than write to it,
+//	which would leave saves going to the one removed.
+func (b *Buffer) Reload() error {
+	b.mu.Lock()
+	defer b.mu.Unlock()
+
+	if b.saving {
+		return ErrSaveInProgress
+	}
+
+	stamp := statFile(b.filePath)
+	document, err := os.ReadFile(b.filePath)
+	if err != nil && !errors.Is(err, fs.ErrNotExist) {
+		return err
+	}
+	if b.file != nil {
+		if file, err := os.OpenFile(b.filePath, os.O_RDWR, 0644); err == nil {
+			_ = b.file.Close()
+			b.file = file
+		}
+	}
+
+	encoding, text := decode(document)
+	lineEnding, text := detectLineEnding(text)
+	removed := b.document.TotalGraphemes()
+	b.document = rope.NewRope(text)
+	b.lineEnding = lineEnding
+	b.encoding = encoding
+	b.stamp = stamp
+	b.version++
+	pos := min(b.selection.End, b.document.TotalGraphemes())
+	b.selection = state.Selection{Start: pos, End: pos}
+	b.size = int64(len(text))
+	b.dirty = false
+	b.changes.Clear()
+	b.autoPairs.Clear()
+	b.history.Clear()
+	b.updateLineCache()
+	if b.changeHook != nil {
+		b.changeHook(b.filePath, b.version, state.ChangeSet{{Start: 0, Removed: removed, Inserted: b.document.TotalGraphemes()}})
+	}
+	return nil
+}
+
+// LineEnding returns the line ending the buffer is written with.
+func (b *Buffer) LineEnding() LineEnding {
+	b.mu.RLock()
+	defer b.mu.RUnlock()
+
+	return b.lineEnding
+}
+
+// Encoding returns the character encoding the buffer is written in.
+func (b *Buffer) Encoding() Encoding {
+	b.mu.RLock()
+	defer b.mu.RUnlock()
+
+	return b.encoding
+}
+
+// FinalNewline reports whether the document ends with a line break, as text files should; an
+// empty document counts as ending with one.
+//
+//	Files are written back as they are, so one without is never given one behind the user's back.
+func (b *Buffer) FinalNewline() bool {
+	b.mu.RLock()
+	defer b.mu.RUnlock()
+
+	total := b.document.TotalGraphemes()
+	if total == 0 {
+		return true
+	}
+	last, err := b.document.Substring(total-1, total)
+	return err == nil && last == "\n"
+}
+
+// Modified reports whether the buffer has changes that have not been saved.
+func (b *Buffer) Modified() bool {
+	b.mu.RLock()
+	defer b.mu.RUnlock()
+
+	return b.dirty
+}
+
+// Version returns a number that grows with every edit of the buffer.
+func (b *Buffer) Version() uint64 {
+	b.mu.RLock()
+	defer b.mu.RUnlock()
+
+	return b.version
+}
+
+// IsNew reports whether the buffer's file has not been created on disk yet.
+func (b *Buffer) IsNew() bool {
+	b.mu.RLock()
+	defer b.mu.RUnlock()
+
+	return b.file == nil && !b.unwritable
+}
+
+// SetReadOnly sets whether edits are refused; saving is left to the caller to refuse.
+func (b *Buffer) SetReadOnly(on bool) {
+	b.mu.Lock()
+	defer b.mu.Unlock()
+
+	b.readOnly = on
+}
+
+// ReadOnly reports whether edits are refused, as they are from the start for files that could
+// only be opened for reading.
+func (b *Buffer) ReadOnly() bool {
+	b.mu.RLock()
+	defer b.mu.RUnlock()
+
+	return b.readOnly
+}
+
 // CollapseSelectionsToCursor collapses all selections to their end positions.
 func (b *Buffer) CollapseSelectionsToCursor() {
 	b.mu.Lock()
@@ -215,6 +820,22 @@ func (b *Buffer) Selection() state.Selection {
 	return b.selection
 }
 
+// Text returns the full content of the document.
+func (b *Buffer) Text() string {
+	b.mu.RLock()
+	defer b.mu.RUnlock()
+
+	return b.document.String()
+}
+
+// Substring returns the text between the start and end positions.
+func (b *Buffer) Substring(start, end int) (string, error) {
+	b.mu.RLock()
+	defer b.mu.RUnlock()
+
+	return b.document.Substring(start, end)
+}
+
 // TotalGraphemes returns the total number of graphemes in the document.
 func (b *Buffer) TotalGraphemes() int {
 	b.mu.RLock()
@@ -232,6 +853,16 @@ func (b *Buffer) PositionToLineCol(pos int) (int, int, error) {
 		return 0, 0, ErrInvalidPosition
 	}
 
+	line := b.lineOf(pos)
+	b.lineCacheMu.RLock()
+	defer b.lineCacheMu.RUnlock()
+
+	column := pos - b.lineCache[line]
+	return line, column, nil
+}
+
+// lineOf returns the line containing pos, which must be within the document.
+func (b *Buffer) lineOf(pos int) int {
 	b.lineCacheMu.RLock()
 	defer b.lineCacheMu.RUnlock()
 
@@ -248,8 +879,7 @@ func (b *Buffer) PositionToLineCol(pos int) (int, int, error) {
 		}
 	}
 
-	column := pos - b.lineCache[line]
-	return line, column, nil
+	return line
 }
 
 // GetLine returns the content of a specific line
@@ -275,10 +905,33 @@ func (b *Buffer) GetLine(lineNum int) (string, error) {
 	return b.document.Substring(start, end)
 }
 
+// LineRange returns the start and end positions of a line, excluding its newline.
+func (b *Buffer) LineRange(lineNum int) (int, int, error) {
+	b.mu.RLock()
+	defer b.mu.RUnlock()
+
+	b.lineCacheMu.RLock()
+	defer b.lineCacheMu.RUnlock()
+
+	if lineNum < 0 || lineNum >= len(b.lineCache) {
+		return 0, 0, ErrInvalidLineCol
+	}
+
+	start := b.lineCache[lineNum]
+	end := b.document.TotalGraphemes()
+	if lineNum+1 < len(b.lineCache) {
+		end = b.lineCache[lineNum+1] - 1
+	}
+	return start, end, nil
+}
+
 func (b *Buffer) GetHighlights() ([]treesitter.Highlight, error) {
 	b.mu.RLock()
 	defer b.mu.RUnlock()
 
+	if b.highlighter == nil {
+		return nil, nil
+	}
 	return b.highlighter.GetHighlights([]byte(b.document.String()))
 }
 
@@ -301,12 +954,27 @@ func (b *Buffer) FileName() string {
 	return b.FileUtil.GetFileName(b.filePath, true)
 }
 
-// FileType returns the type of file in the buffer.
+// FileType returns the type of file in the buffer, see util.DetectFileType.
+//
+//	A file without an extension is looked at again each time, so a "#!" line typed into a new
+//	script gives it its type.
 func (b *Buffer) FileType() string {
 	b.mu.RLock()
 	defer b.mu.RUnlock()
 
-	return b.FileUtil.GetFileExt(b.filePath)
+	if ext := b.FileUtil.GetFileExt(b.filePath); ext != "" {
+		return ext
+	}
+
+	// only as much of a long first line as a "#!" line needs
+	b.lineCacheMu.RLock()
+	end := b.document.TotalGraphemes()
+	if len(b.lineCache) > 1 {
+		end = b.lineCache[1] - 1
+	}
+	b.lineCacheMu.RUnlock()
+	firstLine, _ := b.document.Substring(0, min(end, maxShebangLength))
+	return util.DetectFileType(b.filePath, firstLine)
 }
 
 // FilePath returns the path of the file related to the buffer.
@@ -317,6 +985,179 @@ func (b *Buffer) FilePath() string {
 	return b.filePath
 }
 
+// changeMergeDistance is how close, on the same line, an edit must be to the previous one to replace
+// it in the change list rather than add an entry, like vim with the default text width.
+const changeMergeDistance = 79
+
+// recordChange keeps the change list, marks and pairs typed together on the same text through the
+// changes of an edit, then records pos as the most recent change.
+func (b *Buffer) recordChange(changes state.ChangeSet, pos int) {
+	b.changes.Map(changes)
+	b.marks.Map(changes)
+	b.autoPairs.Map(changes)
+
+	merge := false
+	if last, ok := b.changes.Last(); ok {
+		merge = b.lineOf(last) == b.lineOf(pos) && max(last-pos, pos-last) < changeMergeDistance
+	}
+	b.changes.Record(pos, merge)
+	if b.changeHook != nil {
+		b.changeHook(b.filePath, b.version, changes)
+	}
+}
+
+// SetChangeHook sets the function called with the changes of every edit, reloads included. It is
+// called during the edit, so it must not call back into the buffer.
+func (b *Buffer) SetChangeHook(hook func(path string, version uint64, changes state.ChangeSet)) {
+	b.mu.Lock()
+	defer b.mu.Unlock()
+
+	b.changeHook = hook
+}
+
+// CommitUndo ends the undo step changes are going into, so the next change starts another; what
+// went in before is undone as one.
+func (b *Buffer) CommitUndo() {
+	b.mu.Lock()
+	defer b.mu.Unlock()
+
+	b.history.Commit()
+}
+
+// Undo takes back the last undo step, leaving the cursor at the start of the text it changed.
+func (b *Buffer) Undo() error {
+	return b.travel(b.history.Undo)
+}
+
+// Redo makes the last step undone again, leaving the cursor like Undo.
+func (b *Buffer) Redo() error {
+	return b.travel(b.history.Redo)
+}
+
+// travel makes the changes step takes from the undo history, as one edit.
+func (b *Buffer) travel(step func() ([]state.TextChange, error)) error {
+	b.mu.Lock()
+	defer b.mu.Unlock()
+
+	if b.readOnly {
+		return ErrReadOnly
+	}
+
+	textChanges, err := step()
+	if err != nil {
+		return err
+	}
+
+	var changes state.ChangeSet
+	pos := b.document.TotalGraphemes()
+	for _, c := range textChanges {
+		removed := countGraphemes(c.Removed)
+		if err := b.document.Delete(c.Start, c.Start+removed); err != nil {
+			return err
+		}
+		if err := b.document.Insert(c.Start, c.Inserted); err != nil {
+			return err
+		}
+		b.size += int64(len(c.Inserted) - len(c.Removed))
+		changes = append(changes, state.Change{Start: c.Start, Removed: removed, Inserted: countGraphemes(c.Inserted)})
+		pos = min(pos, c.Start)
+	}
+
+	// the text before the first change is left as it was, so pos is still where it starts
+	pos = min(pos, b.document.TotalGraphemes())
+	b.selection = state.Selection{Start: pos, End: pos}
+	b.dirty = !b.history.AtSaved()
+	b.version++
+	b.updateLineCache()
+	b.recordChange(changes, pos)
+	return nil
+}
+
+// JumpToChange moves the cursor count entries through the change list, towards older changes when
+// count is negative.
+func (b *Buffer) JumpToChange(count int) error {
+	b.mu.Lock()
+	defer b.mu.Unlock()
+
+	pos, err := b.changes.Jump(count)
+	if err != nil {
+		return err
+	}
+	pos = min(pos, b.document.TotalGraphemes())
+	b.selection = state.Selection{Start: pos, End: pos}
+	return nil
+}
+
+// SetMark puts the mark name at pos, see state.Marks.
+func (b *Buffer) SetMark(name rune, pos int) error {
+	b.mu.Lock()
+	defer b.mu.Unlock()
+
+	return b.marks.Set(name, pos)
+}
+
+// Mark returns the position of the mark name, clamped to the buffer, which a reload may have shortened.
+func (b *Buffer) Mark(name rune) (int, error) {
+	b.mu.RLock()
+	defer b.mu.RUnlock()
+
+	pos, err := b.marks.Get(name)
+	if err != nil {
+		return 0, err
+	}
+	return min(pos, b.document.TotalGraphemes()), nil
+}
+
+// InsertPair inserts open and close at the cursor and leaves the cursor between them, remembering
+// them as a pair typed together; see state.AutoPairs.
+func (b *Buffer) InsertPair(open, close string) error {
+	b.mu.Lock()
+	defer b.mu.Unlock()
+
+	if b.readOnly {
+		return ErrReadOnly
+	}
+
+	pos := b.selection.End
+	if err := b.document.Insert(pos, open+close); err != nil {
+		return err
+	}
+	b.selection = state.Selection{Start: pos + 1, End: pos + 1}
+
+	b.size += int64(len(open) + len(close))
+	b.dirty = true
+	b.version++
+	b.updateLineCache()
+	b.recordChange(state.ChangeSet{{Start: pos, Inserted: 2}}, pos)
+	b.history.Record(state.TextChange{Start: pos, Inserted: open + close})
+	b.autoPairs.Add(pos, pos+1)
+	return nil
+}
+
+// AutoPairClosedAt returns the pair typed together whose closing delimiter is at pos.
+func (b *Buffer) AutoPairClosedAt(pos int) (state.Pair, bool) {
+	b.mu.RLock()
+	defer b.mu.RUnlock()
+
+	return b.autoPairs.ClosedAt(pos)
+}
+
+// ForgetAutoPair stops treating p as a pair typed together.
+func (b *Buffer) ForgetAutoPair(p state.Pair) {
+	b.mu.Lock()
+	defer b.mu.Unlock()
+
+	b.autoPairs.Forget(p)
+}
+
+// ForgetAutoPairs stops treating any pair as typed together.
+func (b *Buffer) ForgetAutoPairs() {
+	b.mu.Lock()
+	defer b.mu.Unlock()
+
+	b.autoPairs.Clear()
+}
+
 // updateLineCache rebuilds the cache of line start positions.
 func (b *Buffer) updateLineCache() {
 	b.lineCacheMu.Lock()
@@ -333,6 +1174,51 @@ func (b *Buffer) updateLineCache() {
 	}
 }
 
+// newHighlighter returns a highlighter for the language of filePath, or nil when the language is not supported.
+func newHighlighter(filePath string) *treesitter.Highlighter {
+	registry := treesitter.NewRegistry()
+	_ = registry.RegisterLanguage(&languages.RustProvider{})
+	_ = registry.RegisterLanguage(&languages.GoProvider{})
+	_ = registry.RegisterLanguage(&languages.CProvider{})
+
+	highlighter, err := treesitter.NewHighlighter(registry, filepath.Base(filePath))
+	if err != nil {
+		return nil
+	}
+	return highlighter
+}
+
+// resolveLink returns path with symlinks resolved and whether path itself is a symlink.
+//
+//	Paths that don't exist yet resolve to themselves.
+func resolveLink(path string) (string, bool) {
+	info, err := os.Lstat(path)
+	if err != nil {
+		return path, false
+	}
+	resolved, err := filepath.EvalSymlinks(path)
+	if err != nil {
+		return path, false
+	}
+	return resolved, info.Mode()&fs.ModeSymlink != 0
+}
+
+// createFile opens filePath for writing, truncating it, and fails if it exists unless overwrite is set.
+func createFile(filePath string, overwrite bool) (*os.File, error) {
+	flags := os.O_RDWR | os.O_CREATE | os.O_TRUNC
+	if !overwrite {
+		flags |= os.O_EXCL
+	}
+	return os.OpenFile(filePath, flags, 0644)
+}
+
+// joinsGrapheme reports whether s, inserted after the grapheme prev, starts by continuing it rather
+// than a grapheme of its own.
+func joinsGrapheme(prev, s string) bool {
+	first, _, _, _ := uniseg.FirstGraphemeClusterInString(prev+s, -1)
+	return len(first) > len(prev)
+}
+
 // countGraphemes counts the grapheme clusters in a string.
 func countGraphemes(s string) int {
 	gr := uniseg.NewGraphemes(s)
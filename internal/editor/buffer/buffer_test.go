@@ -0,0 +1,50 @@
+package buffer
+
+import (
+	"path/filepath"
+	"testing"
+)
+
+func TestInsertJoiningGrapheme(t *testing.T) {
+	tests := []struct {
+		name       string
+		inserts    []string // inserted one after another at the cursor, as typed
+		wantText   string
+		wantCursor int
+	}{
+		{name: "separate", inserts: []string{"a", "b"}, wantText: "abx\n", wantCursor: 2},
+		{name: "combining accent", inserts: []string{"e", "\u0301"}, wantText: "e\u0301x\n", wantCursor: 1},
+		{name: "accent pasted with more", inserts: []string{"e", "\u0301b"}, wantText: "e\u0301bx\n", wantCursor: 2},
+		{name: "zwj sequence", inserts: []string{"\U0001F469", "\u200D", "\U0001F467"}, wantText: "\U0001F469\u200D\U0001F467x\n", wantCursor: 1},
+		{name: "flags", inserts: []string{"\U0001F1FA", "\U0001F1F8", "\U0001F1EB", "\U0001F1F7"}, wantText: "\U0001F1FA\U0001F1F8\U0001F1EB\U0001F1F7x\n", wantCursor: 2},
+		{name: "accent at the start", inserts: []string{"\u0301"}, wantText: "\u0301x\n", wantCursor: 1},
+	}
+
+	for _, tt := range tests {
+		t.Run(tt.name, func(t *testing.T) {
+			path := filepath.Join(t.TempDir(), "file.txt")
+			writeFile(t, path, "x\n")
+			b, err := NewBuffer(path)
+			if err != nil {
+				t.Fatal(err)
+			}
+			defer b.Close()
+
+			for _, s := range tt.inserts {
+				if err := b.Insert(s); err != nil {
+					t.Fatal(err)
+				}
+			}
+			if got := b.Text(); got != tt.wantText {
+				t.Errorf("text = %q, want %q", got, tt.wantText)
+			}
+			if got := b.Selection().End; got != tt.wantCursor {
+				t.Errorf("cursor = %d, want %d", got, tt.wantCursor)
+			}
+			// the document must count graphemes as if it was read from a file
+			if got, want := b.document.TotalGraphemes(), countGraphemes(tt.wantText); got != want {
+				t.Errorf("graphemes = %d, want %d", got, want)
+			}
+		})
+	}
+}
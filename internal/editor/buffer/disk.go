@@ -0,0 +1,54 @@
+package buffer
+
+import (
+	"os"
+	"time"
+)
+
+// fileStamp tells versions of a file on disk apart, to notice when another program changed it.
+type fileStamp struct {
+	modTime time.Time
+	size    int64
+}
+
+// statFile returns the stamp of the file at path, the zero stamp when there is none.
+func statFile(path string) fileStamp {
+	info, err := os.Stat(path)
+	if err != nil {
+		return fileStamp{}
+	}
+	return fileStamp{modTime: info.ModTime(), size: info.Size()}
+}
+
+// exists reports whether the stamp is of a file that exists.
+func (s fileStamp) exists() bool {
+	return !s.modTime.IsZero()
+}
+
+// equal reports whether s and o stamp the same version of a file.
+func (s fileStamp) equal(o fileStamp) bool {
+	return s.modTime.Equal(o.modTime) && s.size == o.size
+}
+
+// ChangedOnDisk reports whether the buffer's file was written by something else since the buffer
+// last read or wrote it, going by its modification time and size; a file that was removed isn't
+// reported, saving brings it back.
+func (b *Buffer) ChangedOnDisk() bool {
+	b.mu.RLock()
+	defer b.mu.RUnlock()
+
+	if b.saving {
+		return false
+	}
+	now := statFile(b.filePath)
+	return now.exists() && !now.equal(b.stamp)
+}
+
+// KeepChangedOnDisk takes the file on disk as it is now for the one last read, without reading it,
+// so ChangedOnDisk stops reporting it and the buffer's own version is kept.
+func (b *Buffer) KeepChangedOnDisk() {
+	b.mu.Lock()
+	defer b.mu.Unlock()
+
+	b.stamp = statFile(b.filePath)
+}
@@ -0,0 +1,145 @@
+package buffer
+
+import (
+	"os"
+	"path/filepath"
+	"testing"
+)
+
+func TestChangedOnDisk(t *testing.T) {
+	tests := []struct {
+		name  string
+		after func(t *testing.T, b *Buffer, path string)
+		want  bool
+	}{
+		{name: "untouched", after: func(t *testing.T, b *Buffer, path string) {}, want: false},
+		{name: "written by another program", after: func(t *testing.T, b *Buffer, path string) {
+			writeFile(t, path, "changed elsewhere\n")
+		}, want: true},
+		{name: "removed", after: func(t *testing.T, b *Buffer, path string) {
+			if err := os.Remove(path); err != nil {
+				t.Fatal(err)
+			}
+		}, want: false},
+		{name: "saved", after: func(t *testing.T, b *Buffer, path string) {
+			if err := b.Insert("more "); err != nil {
+				t.Fatal(err)
+			}
+			if err := b.Save(); err != nil {
+				t.Fatal(err)
+			}
+		}, want: false},
+		{name: "reloaded", after: func(t *testing.T, b *Buffer, path string) {
+			writeFile(t, path, "changed elsewhere\n")
+			if err := b.Reload(); err != nil {
+				t.Fatal(err)
+			}
+		}, want: false},
+		{name: "kept", after: func(t *testing.T, b *Buffer, path string) {
+			writeFile(t, path, "changed elsewhere\n")
+			b.KeepChangedOnDisk()
+		}, want: false},
+	}
+
+	for _, tt := range tests {
+		t.Run(tt.name, func(t *testing.T) {
+			path := filepath.Join(t.TempDir(), "file.txt")
+			writeFile(t, path, "text\n")
+			b, err := NewBuffer(path)
+			if err != nil {
+				t.Fatal(err)
+			}
+			defer b.Close()
+
+			tt.after(t, b, path)
+			if got := b.ChangedOnDisk(); got != tt.want {
+				t.Errorf("ChangedOnDisk() = %v, want %v", got, tt.want)
+			}
+		})
+	}
+}
+
+func TestReloadReplacedFile(t *testing.T) {
+	dir := t.TempDir()
+	path := filepath.Join(dir, "file.txt")
+	writeFile(t, path, "text\n")
+	b, err := NewBuffer(path)
+	if err != nil {
+		t.Fatal(err)
+	}
+	defer b.Close()
+
+	// replaced the way many programs write files, by renaming a new one over it
+	replacement := filepath.Join(dir, "file.txt.new")
+	writeFile(t, replacement, "new text\n")
+	if err := os.Rename(replacement, path); err != nil {
+		t.Fatal(err)
+	}
+	if err := b.Reload(); err != nil {
+		t.Fatal(err)
+	}
+	if err := b.Insert("more "); err != nil {
+		t.Fatal(err)
+	}
+	if err := b.Save(); err != nil {
+		t.Fatal(err)
+	}
+
+	if data, _ := os.ReadFile(path); string(data) != "more new text\n" {
+		t.Errorf("file after Reload and Save = %q, want %q", data, "more new text\n")
+	}
+}
+
+func TestWriteBackup(t *testing.T) {
+	tests := []struct {
+		name   string
+		perm   os.FileMode
+		before os.FileMode // of an older backup, none when 0
+	}{
+		{name: "readable by all", perm: 0644},
+		{name: "owner only", perm: 0600},
+		{name: "over an older backup", perm: 0600, before: 0644},
+	}
+
+	for _, tt := range tests {
+		t.Run(tt.name, func(t *testing.T) {
+			path := filepath.Join(t.TempDir(), "file.txt")
+			writeFile(t, path, "text\n")
+			if err := os.Chmod(path, tt.perm); err != nil {
+				t.Fatal(err)
+			}
+			if tt.before != 0 {
+				writeFile(t, path+"~", "old\n")
+				if err := os.Chmod(path+"~", tt.before); err != nil {
+					t.Fatal(err)
+				}
+			}
+			b, err := NewBuffer(path)
+			if err != nil {
+				t.Fatal(err)
+			}
+			defer b.Close()
+
+			if err := b.WriteBackup(); err != nil {
+				t.Fatalf("WriteBackup() error = %v", err)
+			}
+			if data, _ := os.ReadFile(path + "~"); string(data) != "text\n" {
+				t.Errorf("backup = %q, want %q", data, "text\n")
+			}
+			info, err := os.Stat(path + "~")
+			if err != nil {
+				t.Fatal(err)
+			}
+			if got := info.Mode().Perm(); got != tt.perm {
+				t.Errorf("backup permissions = %v, want %v", got, tt.perm)
+			}
+		})
+	}
+}
+
+func writeFile(t *testing.T, path, text string) {
+	t.Helper()
+	if err := os.WriteFile(path, []byte(text), 0644); err != nil {
+		t.Fatal(err)
+	}
+}
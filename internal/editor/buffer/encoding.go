@@ -0,0 +1,163 @@
+package buffer
+
+import (
+	"bytes"
+	"encoding/binary"
+	"errors"
+	"fmt"
+	"io"
+	"strings"
+	"unicode/utf16"
+	"unicode/utf8"
+
+	"github.com/lg2m/athena/internal/rope"
+)
+
+var ErrCannotEncode = errors.New("buffer: text can't be written in the file's encoding")
+
+// Encoding is the character encoding a file is read and written in; buffers always hold UTF-8.
+type Encoding int
+
+const (
+	UTF8    Encoding = iota // the default for new files
+	UTF8BOM                 // UTF-8 starting with a byte order mark
+	UTF16LE                 // written with a byte order mark, whether or not it was read with one
+	UTF16BE
+	Latin1 // ISO-8859-1, assumed for files that aren't valid in any of the others
+)
+
+func (enc Encoding) String() string {
+	switch enc {
+	case UTF8BOM:
+		return "UTF-8 BOM"
+	case UTF16LE:
+		return "UTF-16LE"
+	case UTF16BE:
+		return "UTF-16BE"
+	case Latin1:
+		return "ISO-8859-1"
+	default:
+		return "UTF-8"
+	}
+}
+
+var (
+	bomUTF8    = []byte{0xEF, 0xBB, 0xBF}
+	bomUTF16LE = []byte{0xFF, 0xFE}
+	bomUTF16BE = []byte{0xFE, 0xFF}
+)
+
+// decode detects the encoding of data, a file's content, by its byte order mark or else by its
+// bytes, and returns it with data decoded to UTF-8.
+//
+//	UTF-16 without a byte order mark is recognized by the zero bytes of the ASCII characters that
+//	most text is made of; anything that isn't valid UTF-8 either is read as Latin-1, which any
+//	bytes are.
+func decode(data []byte) (Encoding, string) {
+	switch {
+	case bytes.HasPrefix(data, bomUTF8):
+		return UTF8BOM, string(data[len(bomUTF8):])
+	case bytes.HasPrefix(data, bomUTF16LE):
+		return UTF16LE, decodeUTF16(data[len(bomUTF16LE):], binary.LittleEndian)
+	case bytes.HasPrefix(data, bomUTF16BE):
+		return UTF16BE, decodeUTF16(data[len(bomUTF16BE):], binary.BigEndian)
+	}
+
+	if len(data) >= 2 && len(data)%2 == 0 {
+		var zeros [2]int // at even and odd offsets
+		for i, c := range data {
+			if c == 0 {
+				zeros[i%2]++
+			}
+		}
+		switch half := len(data) / 4; {
+		case zeros[0] == 0 && zeros[1] > half:
+			return UTF16LE, decodeUTF16(data, binary.LittleEndian)
+		case zeros[1] == 0 && zeros[0] > half:
+			return UTF16BE, decodeUTF16(data, binary.BigEndian)
+		}
+	}
+
+	if utf8.Valid(data) {
+		return UTF8, string(data)
+	}
+	runes := make([]rune, len(data))
+	for i, c := range data {
+		runes[i] = rune(c)
+	}
+	return Latin1, string(runes)
+}
+
+// decodeUTF16 decodes UTF-16 in the given byte order; a trailing odd byte is dropped.
+func decodeUTF16(data []byte, order binary.ByteOrder) string {
+	units := make([]uint16, len(data)/2)
+	for i := range units {
+		units[i] = order.Uint16(data[2*i:])
+	}
+	return string(utf16.Decode(units))
+}
+
+// encodedDocument returns doc as it is written to a file, with le and in enc.
+//
+//	UTF-8 is streamed from the rope; other encodings are converted up front, so text they can't
+//	hold fails the write before the file is touched.
+func encodedDocument(doc *rope.Rope, le LineEnding, enc Encoding) (io.WriterTo, error) {
+	switch enc {
+	case UTF8:
+		return utf8Document{doc: doc, lineEnding: le}, nil
+	case UTF8BOM:
+		return utf8Document{doc: doc, lineEnding: le, bom: true}, nil
+	}
+
+	var text strings.Builder
+	if _, err := doc.WriteTo(lineEndingWriter(&text, le)); err != nil {
+		return nil, err
+	}
+	var data []byte
+	switch enc {
+	case UTF16LE:
+		data = encodeUTF16(text.String(), bomUTF16LE, binary.LittleEndian)
+	case UTF16BE:
+		data = encodeUTF16(text.String(), bomUTF16BE, binary.BigEndian)
+	case Latin1:
+		data = make([]byte, 0, text.Len())
+		for _, r := range text.String() {
+			if r > 0xFF {
+				return nil, fmt.Errorf("%w: %q in %s", ErrCannotEncode, r, enc)
+			}
+			data = append(data, byte(r))
+		}
+	}
+	return bytes.NewReader(data), nil
+}
+
+// encodeUTF16 encodes s as UTF-16 in the given byte order, after bom.
+func encodeUTF16(s string, bom []byte, order binary.ByteOrder) []byte {
+	units := utf16.Encode([]rune(s))
+	data := make([]byte, len(bom), len(bom)+2*len(units))
+	copy(data, bom)
+	for _, u := range units {
+		data = order.AppendUint16(data, u)
+	}
+	return data
+}
+
+// utf8Document writes a rope as UTF-8 with the given line ending, after a byte order mark if bom.
+type utf8Document struct {
+	doc        *rope.Rope
+	lineEnding LineEnding
+	bom        bool
+}
+
+func (d utf8Document) WriteTo(w io.Writer) (int64, error) {
+	var written int64
+	if d.bom {
+		n, err := w.Write(bomUTF8)
+		written += int64(n)
+		if err != nil {
+			return written, err
+		}
+	}
+	n, err := d.doc.WriteTo(lineEndingWriter(w, d.lineEnding))
+	return written + n, err
+}
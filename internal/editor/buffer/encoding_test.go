@@ -0,0 +1,75 @@
+package buffer
+
+import (
+	"bytes"
+	"errors"
+	"testing"
+
+	"github.com/lg2m/athena/internal/rope"
+)
+
+func TestDecode(t *testing.T) {
+	tests := []struct {
+		name     string
+		data     []byte
+		wantEnc  Encoding
+		wantText string
+	}{
+		{name: "empty", data: nil, wantEnc: UTF8, wantText: ""},
+		{name: "utf-8", data: []byte("héllo\n"), wantEnc: UTF8, wantText: "héllo\n"},
+		{name: "utf-8 bom", data: []byte("\xEF\xBB\xBFhi\n"), wantEnc: UTF8BOM, wantText: "hi\n"},
+		{name: "utf-16le bom", data: []byte("\xFF\xFEh\x00\xE9\x00\n\x00"), wantEnc: UTF16LE, wantText: "hé\n"},
+		{name: "utf-16be bom", data: []byte("\xFE\xFF\x00h\x00\xE9\x00\n"), wantEnc: UTF16BE, wantText: "hé\n"},
+		{name: "utf-16le without bom", data: []byte("h\x00i\x00\n\x00"), wantEnc: UTF16LE, wantText: "hi\n"},
+		{name: "utf-16be without bom", data: []byte("\x00h\x00i\x00\n"), wantEnc: UTF16BE, wantText: "hi\n"},
+		{name: "surrogate pair", data: []byte("\xFF\xFE\x3D\xD8\x00\xDE"), wantEnc: UTF16LE, wantText: "😀"},
+		{name: "latin-1", data: []byte("caf\xE9\n"), wantEnc: Latin1, wantText: "café\n"},
+	}
+
+	for _, tt := range tests {
+		t.Run(tt.name, func(t *testing.T) {
+			enc, text := decode(tt.data)
+			if enc != tt.wantEnc || text != tt.wantText {
+				t.Errorf("decode(%q) = %v, %q, want %v, %q", tt.data, enc, text, tt.wantEnc, tt.wantText)
+			}
+		})
+	}
+}
+
+func TestEncodedDocument(t *testing.T) {
+	tests := []struct {
+		name       string
+		text       string
+		lineEnding LineEnding
+		enc        Encoding
+		want       []byte
+		wantErr    error
+	}{
+		{name: "utf-8", text: "hé\n", enc: UTF8, want: []byte("hé\n")},
+		{name: "utf-8 crlf", text: "a\nb\n", lineEnding: CRLF, enc: UTF8, want: []byte("a\r\nb\r\n")},
+		{name: "utf-8 bom", text: "hi\n", enc: UTF8BOM, want: []byte("\xEF\xBB\xBFhi\n")},
+		{name: "utf-16le", text: "hé\n", enc: UTF16LE, want: []byte("\xFF\xFEh\x00\xE9\x00\n\x00")},
+		{name: "utf-16be crlf", text: "h\n", lineEnding: CRLF, enc: UTF16BE, want: []byte("\xFE\xFF\x00h\x00\r\x00\n")},
+		{name: "latin-1", text: "café\n", enc: Latin1, want: []byte("caf\xE9\n")},
+		{name: "not latin-1", text: "€\n", enc: Latin1, wantErr: ErrCannotEncode},
+	}
+
+	for _, tt := range tests {
+		t.Run(tt.name, func(t *testing.T) {
+			content, err := encodedDocument(rope.NewRope(tt.text), tt.lineEnding, tt.enc)
+			if !errors.Is(err, tt.wantErr) {
+				t.Fatalf("encodedDocument() error = %v, want %v", err, tt.wantErr)
+			}
+			if err != nil {
+				return
+			}
+			var got bytes.Buffer
+			if _, err := content.WriteTo(&got); err != nil {
+				t.Fatalf("WriteTo() error = %v", err)
+			}
+			if !bytes.Equal(got.Bytes(), tt.want) {
+				t.Errorf("encodedDocument() wrote %q, want %q", got.Bytes(), tt.want)
+			}
+		})
+	}
+}
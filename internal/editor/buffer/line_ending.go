@@ -0,0 +1,55 @@
+package buffer
+
+import (
+	"bytes"
+	"io"
+	"strings"
+)
+
+// LineEnding is the line break a file is written with; buffers always hold "\n" while editing.
+type LineEnding int
+
+const (
+	LF   LineEnding = iota // "\n", the default for new files
+	CRLF                   // "\r\n", as on Windows
+)
+
+func (le LineEnding) String() string {
+	if le == CRLF {
+		return "CRLF"
+	}
+	return "LF"
+}
+
+// detectLineEnding returns the line ending most lines of content end with, and content with its
+// line endings normalized to "\n".
+//
+//	Lone "\n" in a file of mostly "\r\n" become "\r\n" when it is written back; a lone "\r" is
+//	left alone, as text.
+func detectLineEnding(content string) (LineEnding, string) {
+	crlf := strings.Count(content, "\r\n")
+	if crlf == 0 || crlf < strings.Count(content, "\n")-crlf {
+		return LF, content
+	}
+	return CRLF, strings.ReplaceAll(content, "\r\n", "\n")
+}
+
+// lineEndingWriter returns a writer to w that writes le for every "\n".
+func lineEndingWriter(w io.Writer, le LineEnding) io.Writer {
+	if le == CRLF {
+		return crlfWriter{w}
+	}
+	return w
+}
+
+// crlfWriter writes to w with "\n" turned into "\r\n".
+type crlfWriter struct {
+	w io.Writer
+}
+
+func (cw crlfWriter) Write(p []byte) (int, error) {
+	if _, err := cw.w.Write(bytes.ReplaceAll(p, []byte("\n"), []byte("\r\n"))); err != nil {
+		return 0, err
+	}
+	return len(p), nil
+}
@@ -1,11 +1,15 @@
 package buffer
 
 import (
+	"sort"
+	"strings"
 	"unicode"
 	"unicode/utf8"
 
 	"github.com/lg2m/athena/internal/editor/state"
+	"github.com/lg2m/athena/internal/editor/treesitter"
 	"github.com/lg2m/athena/internal/util"
+	"github.com/rivo/uniseg"
 )
 
 // MoveSelections moves the selections by the specified offset.
@@ -27,6 +31,24 @@ func (b *Buffer) MoveSelections(offset int, extend bool) error {
 	return nil
 }
 
+// MoveSelectionTo moves the selection to a grapheme position.
+func (b *Buffer) MoveSelectionTo(pos int, extend bool) error {
+	b.mu.Lock()
+	defer b.mu.Unlock()
+
+	if pos < 0 || pos > b.document.TotalGraphemes() {
+		return ErrInvalidPosition
+	}
+
+	if extend {
+		b.selection.End = pos
+	} else {
+		b.selection = state.Selection{Start: pos, End: pos}
+	}
+
+	return nil
+}
+
 // MoveSelectionToLineCol moves the selection to a specific line and column.
 func (b *Buffer) MoveSelectionToLineCol(line, col int, extend bool) error {
 	b.mu.Lock()
@@ -103,6 +125,176 @@ func (b *Buffer) MoveToPrevWord(extend bool) error {
 	return nil
 }
 
+// WordAtCursor returns the word under the cursor, or the next word on the same line.
+func (b *Buffer) WordAtCursor() (string, error) {
+	b.mu.RLock()
+	defer b.mu.RUnlock()
+
+	total := b.document.TotalGraphemes()
+	pos := b.selection.End
+
+	// skip forward to the next word, without leaving the line
+	for ; pos < total; pos++ {
+		g, err := b.document.GraphemeAt(pos)
+		if err != nil || g == "\n" {
+			return "", ErrNoWordUnderCursor
+		}
+		if getWordType(g) == Letter {
+			break
+		}
+	}
+	if pos >= total {
+		return "", ErrNoWordUnderCursor
+	}
+
+	start, end := pos, pos
+	for start > 0 && b.wordTypeAt(start-1) == Letter {
+		start--
+	}
+	for end < total && b.wordTypeAt(end) == Letter {
+		end++
+	}
+
+	return b.document.Substring(start, end)
+}
+
+// WordObject returns the word, run of symbols or run of blanks under the cursor, like vim's "iw";
+// around also takes the blanks after it, or before it when there are none, like "aw".
+//
+//	It never spans a line break, so on an empty line it is empty.
+func (b *Buffer) WordObject(around bool) (int, int) {
+	b.mu.RLock()
+	defer b.mu.RUnlock()
+
+	total := b.document.TotalGraphemes()
+	pos := b.selection.End
+	if pos >= total || b.isLineBreak(pos) {
+		return pos, pos
+	}
+
+	kind := b.wordTypeAt(pos)
+	same := func(p int) bool {
+		if kind == Whitespace {
+			return b.isBlank(p)
+		}
+		return b.wordTypeAt(p) == kind
+	}
+	start, end := pos, pos+1
+	for start > 0 && same(start-1) {
+		start--
+	}
+	for end < total && same(end) {
+		end++
+	}
+
+	if around && kind != Whitespace {
+		after := end
+		for after < total && b.isBlank(after) {
+			after++
+		}
+		if after > end {
+			end = after
+		} else {
+			for start > 0 && b.isBlank(start-1) {
+				start--
+			}
+		}
+	}
+	return start, end
+}
+
+// isBlank reports whether the grapheme at pos is a space or tab.
+func (b *Buffer) isBlank(pos int) bool {
+	g, err := b.document.GraphemeAt(pos)
+	return err == nil && (g == " " || g == "\t")
+}
+
+// WordStartBefore returns where deleting a word back from pos stops, like <c-w> in insert mode:
+// the start of the word before it, with the blanks in between, or the line break before pos when
+// pos starts a line.
+func (b *Buffer) WordStartBefore(pos int) int {
+	b.mu.RLock()
+	defer b.mu.RUnlock()
+
+	if pos <= 0 {
+		return 0
+	}
+	if b.isLineBreak(pos - 1) {
+		return pos - 1
+	}
+	start := pos
+	for start > 0 && b.isBlank(start-1) {
+		start--
+	}
+	// punctuation and letters are separate words, so "foo.bar" takes two
+	if kind := b.wordTypeAt(start - 1); kind != Whitespace {
+		for start > 0 && b.wordTypeAt(start-1) == kind {
+			start--
+		}
+	}
+	return start
+}
+
+// WordEndAfter returns where deleting a word on from pos stops, like <a-d> in insert mode: the
+// end of the word after it, with the blanks in between, or past the line break at pos.
+func (b *Buffer) WordEndAfter(pos int) int {
+	b.mu.RLock()
+	defer b.mu.RUnlock()
+
+	total := b.document.TotalGraphemes()
+	if pos >= total {
+		return total
+	}
+	if b.isLineBreak(pos) {
+		return pos + 1
+	}
+	end := pos
+	for end < total && b.isBlank(end) {
+		end++
+	}
+	if kind := b.wordTypeAt(end); kind != Whitespace {
+		for end < total && b.wordTypeAt(end) == kind {
+			end++
+		}
+	}
+	return end
+}
+
+// LineStartBefore returns where deleting to the start of the line from pos stops, like <c-u> in
+// insert mode: the end of the line's indentation, or the start of the line when pos is within it.
+func (b *Buffer) LineStartBefore(pos int) int {
+	b.mu.RLock()
+	defer b.mu.RUnlock()
+
+	start := pos
+	for start > 0 && !b.isLineBreak(start-1) {
+		start--
+	}
+	indentEnd := start
+	for indentEnd < pos && b.isBlank(indentEnd) {
+		indentEnd++
+	}
+	if indentEnd < pos {
+		return indentEnd
+	}
+	return start
+}
+
+// isLineBreak reports whether the grapheme at pos ends a line.
+func (b *Buffer) isLineBreak(pos int) bool {
+	g, err := b.document.GraphemeAt(pos)
+	return err == nil && (g == "\n" || g == "\r\n")
+}
+
+// wordTypeAt returns the word type of the grapheme at pos.
+func (b *Buffer) wordTypeAt(pos int) WordType {
+	g, err := b.document.GraphemeAt(pos)
+	if err != nil {
+		return None
+	}
+	return getWordType(g)
+}
+
 // findNextWordBoundary finds the next word boundary position from the given position.
 // direction: 1 for forward, -1 for backward TODO make constants
 func (b *Buffer) findNextWordBoundary(pos int, direction int) int {
@@ -169,3 +361,157 @@ func getWordType(s string) WordType {
 		return Symbol
 	}
 }
+
+// PairAt returns the keyword or bracket pair with a delimiter at pos.
+func (b *Buffer) PairAt(pos int) (state.Pair, bool) {
+	b.mu.RLock()
+	defer b.mu.RUnlock()
+
+	return b.pairAt(b.keywordPairs(), b.literals(), pos)
+}
+
+// MatchPair moves the cursor to the partner of the first keyword or bracket pair at or after it
+// on its line, like vim's %.
+func (b *Buffer) MatchPair(extend bool) error {
+	b.mu.Lock()
+	defer b.mu.Unlock()
+
+	line := b.lineOf(b.selection.End)
+	b.lineCacheMu.RLock()
+	lineEnd := b.document.TotalGraphemes()
+	if line+1 < len(b.lineCache) {
+		lineEnd = b.lineCache[line+1] - 1
+	}
+	b.lineCacheMu.RUnlock()
+
+	keywords, literals := b.keywordPairs(), b.literals()
+	for pos := b.selection.End; pos < lineEnd; pos++ {
+		pair, ok := b.pairAt(keywords, literals, pos)
+		if !ok {
+			continue
+		}
+		partner, _ := pair.Partner(pos)
+		if extend {
+			b.selection.End = partner.Start
+		} else {
+			b.selection = state.Selection{Start: partner.Start, End: partner.Start}
+		}
+		return nil
+	}
+	return ErrNoMatchingPair
+}
+
+// pairAt prefers keyword pairs from the pairs query over brackets, which are matched outside of
+// literals.
+func (b *Buffer) pairAt(keywords []state.Pair, literals []state.Span, pos int) (state.Pair, bool) {
+	if pair, ok := state.PairAt(keywords, pos); ok {
+		return pair, true
+	}
+
+	return state.MatchBracket(b.graphemeReader(), b.document.TotalGraphemes(), pos, literals)
+}
+
+// EnclosingPair returns the innermost pair of the brackets open and close around pos; see
+// state.EnclosingPair.
+func (b *Buffer) EnclosingPair(open, close string, pos int) (state.Pair, bool) {
+	b.mu.RLock()
+	defer b.mu.RUnlock()
+
+	return state.EnclosingPair(b.graphemeReader(), b.document.TotalGraphemes(), pos, open, close)
+}
+
+// graphemeReader returns a function reading the grapheme at a position of the document, "" past
+// its end.
+//
+//	Searches read graphemes one after another, so the rope's leaves are split once each rather
+//	than for every grapheme.
+func (b *Buffer) graphemeReader() func(pos int) string {
+	var chunk []string
+	chunkStart := 0
+	return func(i int) string {
+		if i < chunkStart || i >= chunkStart+len(chunk) {
+			start, data, err := b.document.ChunkAt(i)
+			if err != nil {
+				return ""
+			}
+			chunk, chunkStart = chunk[:0], start
+			gr := uniseg.NewGraphemes(data)
+			for gr.Next() {
+				chunk = append(chunk, gr.Str())
+			}
+			if i-chunkStart >= len(chunk) {
+				return ""
+			}
+		}
+		return chunk[i-chunkStart]
+	}
+}
+
+// keywordPairs returns the keyword pairs of the document as grapheme positions.
+func (b *Buffer) keywordPairs() []state.Pair {
+	if b.highlighter == nil {
+		return nil
+	}
+	text := b.document.String()
+	found, err := b.highlighter.GetPairs([]byte(text))
+	if err != nil || len(found) == 0 {
+		return nil
+	}
+
+	b.lineCacheMu.RLock()
+	defer b.lineCacheMu.RUnlock()
+
+	toPos := b.syntaxPosition(text)
+	pairs := make([]state.Pair, 0, len(found))
+	for _, p := range found {
+		pairs = append(pairs, state.Pair{
+			Open:  state.Span{Start: toPos(p.OpenStart), End: toPos(p.OpenEnd)},
+			Close: state.Span{Start: toPos(p.CloseStart), End: toPos(p.CloseEnd)},
+		})
+	}
+	return pairs
+}
+
+// literals returns the strings and comments of the document as grapheme positions, sorted and
+// with those overlapping merged.
+func (b *Buffer) literals() []state.Span {
+	if b.highlighter == nil {
+		return nil
+	}
+	text := b.document.String()
+	found, err := b.highlighter.GetLiterals([]byte(text))
+	if err != nil || len(found) == 0 {
+		return nil
+	}
+
+	b.lineCacheMu.RLock()
+	defer b.lineCacheMu.RUnlock()
+
+	toPos := b.syntaxPosition(text)
+	spans := make([]state.Span, 0, len(found))
+	for _, l := range found {
+		spans = append(spans, state.Span{Start: toPos(l.Start), End: toPos(l.End)})
+	}
+	sort.Slice(spans, func(i, j int) bool { return spans[i].Start < spans[j].Start })
+
+	merged := spans[:1]
+	for _, s := range spans[1:] {
+		if last := &merged[len(merged)-1]; s.Start < last.End {
+			last.End = max(last.End, s.End)
+		} else {
+			merged = append(merged, s)
+		}
+	}
+	return merged
+}
+
+// syntaxPosition returns a function converting tree-sitter positions in text, the document, to
+// grapheme positions; the line cache must be read locked while it is used.
+func (b *Buffer) syntaxPosition(text string) func(p treesitter.Position) int {
+	// tree-sitter columns are bytes
+	lines := strings.Split(text, "\n")
+	return func(p treesitter.Position) int {
+		line := lines[p.Row]
+		return b.lineCache[p.Row] + countGraphemes(line[:min(int(p.Column), len(line))])
+	}
+}
@@ -0,0 +1,136 @@
+package editor
+
+import (
+	"errors"
+	"strings"
+	"unicode"
+)
+
+var ErrPastLineEnd = errors.New("not enough characters on the line")
+
+// DeleteChars deletes count characters from the cursor on, like x, keeping them in the register;
+// it stops at the end of the line. Characters are graphemes, so an emoji or a letter with its
+// accents goes as one.
+func (e *Editor) DeleteChars(count int) error {
+	e.mu.Lock()
+	defer e.mu.Unlock()
+
+	if e.current == nil {
+		return ErrNoBuffer
+	}
+	pos, lineStart, lineEnd, err := e.cursorLine()
+	if err != nil {
+		return err
+	}
+	end := min(pos+max(count, 1), lineEnd)
+	if err := e.deleteChars(pos, end); err != nil {
+		return err
+	}
+	// the cursor stays on the line, on its last character when the end was deleted
+	lineEnd -= end - pos
+	return e.current.MoveSelectionTo(max(min(pos, lineEnd-1), lineStart), false)
+}
+
+// DeleteCharsBefore deletes count characters before the cursor, like X, keeping them in the
+// register; it stops at the start of the line.
+func (e *Editor) DeleteCharsBefore(count int) error {
+	e.mu.Lock()
+	defer e.mu.Unlock()
+
+	if e.current == nil {
+		return ErrNoBuffer
+	}
+	pos, lineStart, _, err := e.cursorLine()
+	if err != nil {
+		return err
+	}
+	start := max(pos-max(count, 1), lineStart)
+	if err := e.deleteChars(start, pos); err != nil {
+		return err
+	}
+	return e.current.MoveSelectionTo(start, false)
+}
+
+// ReplaceChars replaces count characters from the cursor on with r, like r, leaving the cursor on
+// the last one; nothing changes when the line has fewer left.
+func (e *Editor) ReplaceChars(r rune, count int) error {
+	e.mu.Lock()
+	defer e.mu.Unlock()
+
+	if e.current == nil {
+		return ErrNoBuffer
+	}
+	pos, _, lineEnd, err := e.cursorLine()
+	if err != nil {
+		return err
+	}
+	count = max(count, 1)
+	if pos+count > lineEnd {
+		return ErrPastLineEnd
+	}
+	if _, err := e.current.Replace(pos, pos+count, strings.Repeat(string(r), count)); err != nil {
+		return err
+	}
+	e.desiredColumn = -1
+	return e.current.MoveSelectionTo(pos+count-1, false)
+}
+
+// ToggleCase switches the case of count characters from the cursor on, like ~, and moves the
+// cursor past them, stopping on the last character of the line.
+func (e *Editor) ToggleCase(count int) error {
+	e.mu.Lock()
+	defer e.mu.Unlock()
+
+	if e.current == nil {
+		return ErrNoBuffer
+	}
+	pos, _, lineEnd, err := e.cursorLine()
+	if err != nil {
+		return err
+	}
+	end := min(pos+max(count, 1), lineEnd)
+	if end == pos {
+		return nil
+	}
+	text, err := e.current.Substring(pos, end)
+	if err != nil {
+		return err
+	}
+	// rune by rune, so accents keep to their letters and the graphemes stay as many
+	toggled := strings.Map(func(r rune) rune {
+		if unicode.IsUpper(r) {
+			return unicode.ToLower(r)
+		}
+		return unicode.ToUpper(r)
+	}, text)
+	if _, err := e.current.Replace(pos, end, toggled); err != nil {
+		return err
+	}
+	e.desiredColumn = -1
+	return e.current.MoveSelectionTo(min(end, lineEnd-1), false)
+}
+
+// cursorLine returns the cursor position and the start and end of its line, newline left out.
+func (e *Editor) cursorLine() (pos, start, end int, err error) {
+	pos = e.current.Selection().End
+	line, _, err := e.current.PositionToLineCol(pos)
+	if err != nil {
+		return 0, 0, 0, err
+	}
+	start, end, err = e.current.LineRange(line)
+	return pos, start, end, err
+}
+
+// deleteChars deletes the characters between start and end into the register.
+func (e *Editor) deleteChars(start, end int) error {
+	if start >= end {
+		return nil
+	}
+	text, err := e.current.Substring(start, end)
+	if err != nil {
+		return err
+	}
+	e.setRegister(Register{Text: text})
+	e.desiredColumn = -1
+	return e.current.Delete(start, end)
+}
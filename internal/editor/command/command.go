@@ -0,0 +1,57 @@
+package command
+
+import (
+	"errors"
+	"strings"
+	"unicode"
+)
+
+var ErrMissingCommand = errors.New("command: missing command name")
+
+// Command represents a parsed command line, e.g. ":set ic", ":w!", ":%s/a/b/" or ":%!sort".
+type Command struct {
+	Range *Range // line range before the name, nil when omitted
+	Name  string // command name as typed, possibly abbreviated, or "!" for a shell command
+	Bang  bool   // the name was followed by "!"
+	Args  string // remaining text with leading whitespace removed
+}
+
+// Parse parses a command line entered at the ":" prompt, without the leading colon.
+func Parse(line string) (Command, error) {
+	line = strings.TrimLeft(line, " \t:")
+
+	rng, line, err := parseRange(line)
+	if err != nil {
+		return Command{}, err
+	}
+	line = strings.TrimLeft(line, " \t")
+
+	// a shell command takes the rest of the line, e.g. ":%!sort -u"
+	if shell, ok := strings.CutPrefix(line, "!"); ok {
+		return Command{Range: rng, Name: "!", Args: strings.TrimLeft(shell, " \t")}, nil
+	}
+
+	end := strings.IndexFunc(line, func(r rune) bool {
+		return !unicode.IsLetter(r)
+	})
+	if end == -1 {
+		end = len(line)
+	}
+	if end == 0 {
+		// a range alone goes to its line, e.g. ":42"
+		if rng != nil && line == "" {
+			return Command{Range: rng}, nil
+		}
+		return Command{}, ErrMissingCommand
+	}
+
+	cmd := Command{Range: rng, Name: line[:end]}
+	rest := line[end:]
+	if strings.HasPrefix(rest, "!") {
+		cmd.Bang = true
+		rest = rest[1:]
+	}
+	cmd.Args = strings.TrimLeft(rest, " \t")
+
+	return cmd, nil
+}
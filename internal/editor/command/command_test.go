@@ -0,0 +1,133 @@
+package command
+
+import (
+	"errors"
+	"testing"
+)
+
+func TestParse(t *testing.T) {
+	tests := []struct {
+		name      string
+		line      string
+		wantName  string
+		wantBang  bool
+		wantArgs  string
+		wantRange bool
+	}{
+		{name: "plain", line: "set ic", wantName: "set", wantArgs: "ic"},
+		{name: "bang", line: "w!", wantName: "w", wantBang: true},
+		{name: "sudo write", line: "w!!", wantName: "w", wantBang: true, wantArgs: "!"},
+		{name: "leading colon", line: ":q", wantName: "q"},
+		{name: "substitute", line: "s/a/b/", wantName: "s", wantArgs: "/a/b/"},
+		{name: "whole file", line: "%s/a/b/g", wantName: "s", wantArgs: "/a/b/g", wantRange: true},
+		{name: "line range", line: "3,$d", wantName: "d", wantRange: true},
+		{name: "range only", line: "42", wantRange: true},
+		{name: "filter", line: "%!sort -u", wantName: "!", wantArgs: "sort -u", wantRange: true},
+		{name: "shell", line: "! ls", wantName: "!", wantArgs: "ls"},
+		{name: "read shell", line: "r !ls", wantName: "r", wantArgs: "!ls"},
+	}
+
+	for _, tt := range tests {
+		t.Run(tt.name, func(t *testing.T) {
+			got, err := Parse(tt.line)
+			if err != nil {
+				t.Fatalf("Parse(%q) error: %v", tt.line, err)
+			}
+			if got.Name != tt.wantName || got.Bang != tt.wantBang || got.Args != tt.wantArgs {
+				t.Errorf("Parse(%q) = %+v", tt.line, got)
+			}
+			if (got.Range != nil) != tt.wantRange {
+				t.Errorf("Parse(%q) range = %v, want range %v", tt.line, got.Range, tt.wantRange)
+			}
+		})
+	}
+}
+
+func TestRangeResolve(t *testing.T) {
+	tests := []struct {
+		name      string
+		line      string
+		wantStart int
+		wantEnd   int
+		wantErr   error
+	}{
+		{name: "none", line: "s", wantStart: 4, wantEnd: 4},
+		{name: "all", line: "%s", wantStart: 0, wantEnd: 9},
+		{name: "absolute", line: "2,5s", wantStart: 1, wantEnd: 4},
+		{name: "current to last", line: ".,$s", wantStart: 4, wantEnd: 9},
+		{name: "offsets", line: "-1,+2s", wantStart: 3, wantEnd: 6},
+		{name: "last minus", line: "$-2s", wantStart: 7, wantEnd: 7},
+		{name: "backwards", line: "5,2s", wantStart: 1, wantEnd: 4},
+		{name: "out of bounds", line: "1,20s", wantErr: ErrInvalidRange},
+	}
+
+	for _, tt := range tests {
+		t.Run(tt.name, func(t *testing.T) {
+			cmd, err := Parse(tt.line)
+			if err != nil {
+				t.Fatalf("Parse(%q) error: %v", tt.line, err)
+			}
+			start, end, err := cmd.Range.Resolve(4, 10)
+			if !errors.Is(err, tt.wantErr) {
+				t.Fatalf("Resolve() error = %v, want %v", err, tt.wantErr)
+			}
+			if err == nil && (start != tt.wantStart || end != tt.wantEnd) {
+				t.Errorf("Resolve() = %d, %d, want %d, %d", start, end, tt.wantStart, tt.wantEnd)
+			}
+		})
+	}
+}
+
+func TestParseSubstitute(t *testing.T) {
+	tests := []struct {
+		name    string
+		args    string
+		want    Substitute
+		wantErr error
+	}{
+		{name: "basic", args: "/foo/bar/", want: Substitute{Pattern: "foo", Replacement: "bar"}},
+		{name: "no trailing delimiter", args: "/foo/bar", want: Substitute{Pattern: "foo", Replacement: "bar"}},
+		{name: "pattern only", args: "/foo", want: Substitute{Pattern: "foo"}},
+		{name: "flags", args: "/a/b/gci", want: Substitute{Pattern: "a", Replacement: "b", Global: true, Confirm: true, IgnoreCase: true}},
+		{name: "count only", args: "/a//gn", want: Substitute{Pattern: "a", Global: true, CountOnly: true}},
+		{name: "other delimiter", args: "#a/b#c#", want: Substitute{Pattern: "a/b", Replacement: "c"}},
+		{name: "escaped delimiter", args: `/a\/b/c/`, want: Substitute{Pattern: "a/b", Replacement: "c"}},
+		{name: "keeps other escapes", args: `/a/\&x/`, want: Substitute{Pattern: "a", Replacement: `\&x`}},
+		{name: "empty", args: "", wantErr: ErrMissingPattern},
+		{name: "letter delimiter", args: "afoobar", wantErr: ErrInvalidDelim},
+		{name: "unknown flag", args: "/a/b/z", wantErr: ErrInvalidFlag},
+	}
+
+	for _, tt := range tests {
+		t.Run(tt.name, func(t *testing.T) {
+			got, err := ParseSubstitute(tt.args)
+			if !errors.Is(err, tt.wantErr) {
+				t.Fatalf("ParseSubstitute(%q) error = %v, want %v", tt.args, err, tt.wantErr)
+			}
+			if err == nil && got != tt.want {
+				t.Errorf("ParseSubstitute(%q) = %+v, want %+v", tt.args, got, tt.want)
+			}
+		})
+	}
+}
+
+func TestSubstituteExpand(t *testing.T) {
+	tests := []struct {
+		replacement string
+		matched     string
+		want        string
+	}{
+		{replacement: "bar", matched: "foo", want: "bar"},
+		{replacement: "[&]", matched: "foo", want: "[foo]"},
+		{replacement: `\&`, matched: "foo", want: "&"},
+		{replacement: `a\nb`, matched: "foo", want: "a\nb"},
+		{replacement: `\\`, matched: "foo", want: `\`},
+	}
+
+	for _, tt := range tests {
+		got := Substitute{Replacement: tt.replacement}.Expand(tt.matched)
+		if got != tt.want {
+			t.Errorf("Expand(%q) with %q = %q, want %q", tt.matched, tt.replacement, got, tt.want)
+		}
+	}
+}
@@ -0,0 +1,147 @@
+package command
+
+import (
+	"errors"
+	"strconv"
+)
+
+var ErrInvalidRange = errors.New("command: invalid range")
+
+// AddressKind represents how a line address is resolved.
+type AddressKind uint8
+
+const (
+	AddressCurrent AddressKind = iota // "." or an address made of offsets only
+	AddressLine                       // an absolute line number
+	AddressLast                       // "$"
+)
+
+// Address represents a single line address, such as "12", "." or "$-1".
+type Address struct {
+	Kind   AddressKind
+	Line   int // 1-based line number for AddressLine
+	Offset int // added after resolving the base line
+}
+
+// Range represents the line range preceding a command, such as "%" or "3,$".
+type Range struct {
+	Start Address
+	End   Address
+}
+
+// Resolve converts the range into 0-based inclusive line indices.
+//
+//	A nil range resolves to the current line.
+func (r *Range) Resolve(current, lineCount int) (int, int, error) {
+	if r == nil {
+		return current, current, nil
+	}
+
+	start := r.Start.resolve(current, lineCount)
+	end := r.End.resolve(current, lineCount)
+	if start > end {
+		start, end = end, start
+	}
+	if start < 0 || end >= lineCount {
+		return 0, 0, ErrInvalidRange
+	}
+	return start, end, nil
+}
+
+// Line returns the line a range given without a command moves the cursor to, like ":42": that of
+// its last address, kept within the lineCount lines.
+func (r *Range) Line(current, lineCount int) int {
+	return max(0, min(r.End.resolve(current, lineCount), lineCount-1))
+}
+
+// resolve converts the address into a 0-based line index.
+func (a Address) resolve(current, lineCount int) int {
+	var line int
+	switch a.Kind {
+	case AddressLine:
+		line = a.Line - 1
+	case AddressLast:
+		line = lineCount - 1
+	default:
+		line = current
+	}
+	return line + a.Offset
+}
+
+// parseRange parses a range at the start of s, returning nil when there is none.
+func parseRange(s string) (*Range, string, error) {
+	if len(s) > 0 && s[0] == '%' {
+		all := &Range{
+			Start: Address{Kind: AddressLine, Line: 1},
+			End:   Address{Kind: AddressLast},
+		}
+		return all, s[1:], nil
+	}
+
+	start, rest, ok := parseAddress(s)
+	if !ok {
+		return nil, s, nil
+	}
+
+	r := &Range{Start: start, End: start}
+	if len(rest) > 0 && rest[0] == ',' {
+		end, after, ok := parseAddress(rest[1:])
+		if !ok {
+			return nil, s, ErrInvalidRange
+		}
+		r.End, rest = end, after
+	}
+	return r, rest, nil
+}
+
+// parseAddress parses a single address at the start of s.
+func parseAddress(s string) (Address, string, bool) {
+	addr := Address{Kind: AddressCurrent}
+	ok := false
+
+	switch {
+	case len(s) > 0 && s[0] == '.':
+		s, ok = s[1:], true
+	case len(s) > 0 && s[0] == '$':
+		addr.Kind = AddressLast
+		s, ok = s[1:], true
+	default:
+		if n, digits := leadingNumber(s); digits > 0 {
+			addr.Kind = AddressLine
+			addr.Line = n
+			s, ok = s[digits:], true
+		}
+	}
+
+	// trailing offsets like "+2" or "-", which also work without a base
+	for len(s) > 0 && (s[0] == '+' || s[0] == '-') {
+		step := 1
+		if s[0] == '-' {
+			step = -1
+		}
+		n, digits := leadingNumber(s[1:])
+		if digits == 0 {
+			n = 1
+		}
+		addr.Offset += step * n
+		s, ok = s[1+digits:], true
+	}
+
+	return addr, s, ok
+}
+
+// leadingNumber parses the decimal number at the start of s, returning it and its length.
+func leadingNumber(s string) (int, int) {
+	digits := 0
+	for digits < len(s) && s[digits] >= '0' && s[digits] <= '9' {
+		digits++
+	}
+	if digits == 0 {
+		return 0, 0
+	}
+	n, err := strconv.Atoi(s[:digits])
+	if err != nil {
+		return 0, 0
+	}
+	return n, digits
+}
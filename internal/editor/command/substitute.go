@@ -0,0 +1,128 @@
+package command
+
+import (
+	"errors"
+	"strings"
+	"unicode"
+)
+
+var (
+	ErrMissingPattern = errors.New("command: missing pattern")
+	ErrInvalidDelim   = errors.New("command: invalid delimiter")
+	ErrInvalidFlag    = errors.New("command: invalid flag")
+)
+
+// Substitute represents the arguments of ":s/pattern/replacement/flags".
+type Substitute struct {
+	Pattern     string
+	Replacement string
+	Global      bool // "g": replace every match on a line instead of only the first
+	Confirm     bool // "c": ask before each replacement
+	IgnoreCase  bool // "i": ignore case regardless of the search options
+	MatchCase   bool // "I": match case regardless of the search options
+	CountOnly   bool // "n": report the number of matches without replacing them
+}
+
+// ParseSubstitute parses the arguments of a substitute command.
+//
+//	The first character is the delimiter; it may be escaped inside the pattern and replacement
+//	with a backslash. A missing pattern means the last search pattern should be used.
+func ParseSubstitute(args string) (Substitute, error) {
+	if args == "" {
+		return Substitute{}, ErrMissingPattern
+	}
+
+	delim := []rune(args)[0]
+	if unicode.IsLetter(delim) || unicode.IsDigit(delim) || unicode.IsSpace(delim) ||
+		delim == '\\' || delim == '"' || delim == '|' {
+		return Substitute{}, ErrInvalidDelim
+	}
+
+	fields := splitDelimited(args[len(string(delim)):], delim, 3)
+	sub := Substitute{Pattern: fields[0]}
+	if len(fields) > 1 {
+		sub.Replacement = fields[1]
+	}
+	if len(fields) > 2 {
+		for _, flag := range strings.TrimSpace(fields[2]) {
+			switch flag {
+			case 'g':
+				sub.Global = true
+			case 'c':
+				sub.Confirm = true
+			case 'i':
+				sub.IgnoreCase = true
+			case 'I':
+				sub.MatchCase = true
+			case 'n':
+				sub.CountOnly = true
+			default:
+				return Substitute{}, ErrInvalidFlag
+			}
+		}
+	}
+
+	return sub, nil
+}
+
+// Expand returns the replacement text for a single match.
+//
+//	"&" inserts the matched text, "\&" a literal ampersand and "\n" or "\r" a line break.
+func (s Substitute) Expand(matched string) string {
+	var sb strings.Builder
+	escaped := false
+	for _, r := range s.Replacement {
+		switch {
+		case escaped:
+			switch r {
+			case 'n', 'r':
+				sb.WriteRune('\n')
+			case 't':
+				sb.WriteRune('\t')
+			default:
+				sb.WriteRune(r)
+			}
+			escaped = false
+		case r == '\\':
+			escaped = true
+		case r == '&':
+			sb.WriteString(matched)
+		default:
+			sb.WriteRune(r)
+		}
+	}
+	if escaped {
+		sb.WriteRune('\\')
+	}
+	return sb.String()
+}
+
+// splitDelimited splits s on unescaped occurrences of delim into at most n fields.
+//
+//	Escaped delimiters are unescaped; other escapes are kept for the caller to interpret.
+func splitDelimited(s string, delim rune, n int) []string {
+	var fields []string
+	var sb strings.Builder
+	escaped := false
+	for _, r := range s {
+		switch {
+		case escaped:
+			if r != delim {
+				sb.WriteRune('\\')
+			}
+			sb.WriteRune(r)
+			escaped = false
+		case r == '\\':
+			escaped = true
+		case r == delim && len(fields) < n-1:
+			fields = append(fields, sb.String())
+			sb.Reset()
+		default:
+			sb.WriteRune(r)
+		}
+	}
+	if escaped {
+		sb.WriteRune('\\')
+	}
+	return append(fields, sb.String())
+}
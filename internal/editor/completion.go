@@ -0,0 +1,276 @@
+package editor
+
+import (
+	"strings"
+	"sync"
+	"unicode"
+
+	"github.com/lg2m/athena/internal/editor/buffer"
+	"github.com/lg2m/athena/internal/editor/state"
+	"github.com/lg2m/athena/internal/util/fuzzy"
+	"github.com/rivo/uniseg"
+)
+
+// CompletionItem is a suggestion for the word before the cursor.
+type CompletionItem struct {
+	Label  string // shown in the menu and matched against the word as typed
+	Detail string // e.g. the type of the suggested symbol
+	Text   string // replaces the word when the item is accepted
+}
+
+// Completion is the menu of suggestions open for the word before the cursor, see SetCompletion.
+type Completion struct {
+	Start    int              // position of the word being completed
+	Items    []CompletionItem // suggestions matching the word as typed so far, best first
+	Matched  [][]int          // rune indexes in each item's label of the characters the word matched
+	Selected int              // index of the highlighted item
+}
+
+// completion is the open menu; its items are filtered again as the word is typed.
+type completion struct {
+	path     string // file of the buffer it was opened in
+	start    int
+	items    []CompletionItem
+	selected int
+
+	// matcher filters the labels of items by the word typed, only searching the last matches
+	// again as it grows; the menu is read with the editor's read lock, so it has its own
+	mu      sync.Mutex
+	matcher *fuzzy.Matcher
+}
+
+// SetCompletion opens the completion menu with items for the word before the cursor in the
+// buffer for the file at path, and wakes the UI to show it; it may be called from any goroutine.
+//
+//	Items arriving once the buffer was left or insert mode ended are dropped.
+func (e *Editor) SetCompletion(path string, items []CompletionItem) {
+	e.mu.Lock()
+	if e.current != nil && e.current.FilePath() == path && e.mode == state.Insert {
+		e.openCompletion(items)
+	}
+	wakeup := e.wakeup
+	e.mu.Unlock()
+
+	if wakeup != nil {
+		wakeup()
+	}
+}
+
+// CompleteWords opens the completion menu with the words of the open buffers, for the word before
+// the cursor, when no language server offers any.
+//
+//	Words of the current buffer come first, in the order they appear; the word being typed is left
+//	out.
+func (e *Editor) CompleteWords() error {
+	e.mu.Lock()
+	defer e.mu.Unlock()
+
+	if e.current == nil {
+		return ErrNoBuffer
+	}
+	if e.mode != state.Insert {
+		return ErrInvalidOperation
+	}
+
+	seen := make(map[string]bool)
+	var items []CompletionItem
+	for _, b := range e.buffersCurrentFirst() {
+		cursor := -1
+		if b == e.current {
+			cursor = b.Selection().End
+		}
+		for _, word := range wordsOf(b.Text(), cursor) {
+			if !seen[word] {
+				seen[word] = true
+				items = append(items, CompletionItem{Label: word, Text: word})
+			}
+		}
+	}
+	e.openCompletion(items)
+	return nil
+}
+
+// openCompletion opens the completion menu with items for the word before the cursor, or reports
+// there are none.
+func (e *Editor) openCompletion(items []CompletionItem) {
+	e.completion = nil
+	if len(items) == 0 {
+		e.message = Message{Text: "No completions", IsError: true}
+		return
+	}
+	labels := make([]string, len(items))
+	for i, item := range items {
+		labels[i] = item.Label
+	}
+	e.completion = &completion{
+		path:    e.current.FilePath(),
+		start:   e.wordStartBeforeCursor(),
+		items:   items,
+		matcher: fuzzy.NewMatcher(labels),
+	}
+}
+
+// buffersCurrentFirst returns the open buffers, the current one first and the others in the order
+// they were opened.
+func (e *Editor) buffersCurrentFirst() []*buffer.Buffer {
+	buffers := []*buffer.Buffer{e.current}
+	for _, path := range e.order {
+		if b := e.buffers[path]; b != nil && b != e.current {
+			buffers = append(buffers, b)
+		}
+	}
+	return buffers
+}
+
+// wordsOf returns the words in text, in order and with repeats, leaving out the one touching the
+// grapheme position cursor.
+func wordsOf(text string, cursor int) []string {
+	var words []string
+	var word strings.Builder
+	start := 0
+	end := func(pos int) {
+		if word.Len() > 0 && (cursor < start || cursor > pos) {
+			words = append(words, word.String())
+		}
+		word.Reset()
+	}
+
+	g := uniseg.NewGraphemes(text)
+	pos := 0
+	for ; g.Next(); pos++ {
+		if !isWordRune(g.Runes()[0]) {
+			end(pos)
+			continue
+		}
+		if word.Len() == 0 {
+			start = pos
+		}
+		word.WriteString(g.Str())
+	}
+	end(pos)
+	return words
+}
+
+// Completion returns the completion menu, unless none is open or the cursor has left the word it
+// was opened for.
+func (e *Editor) Completion() (Completion, bool) {
+	e.mu.RLock()
+	defer e.mu.RUnlock()
+
+	items, matched := e.completionItems()
+	if len(items) == 0 {
+		return Completion{}, false
+	}
+	return Completion{
+		Start:    e.completion.start,
+		Items:    items,
+		Matched:  matched,
+		Selected: min(e.completion.selected, len(items)-1),
+	}, true
+}
+
+// SelectCompletion highlights the item offset places after the highlighted one, wrapping around.
+func (e *Editor) SelectCompletion(offset int) {
+	e.mu.Lock()
+	defer e.mu.Unlock()
+
+	items, _ := e.completionItems()
+	if len(items) == 0 {
+		return
+	}
+	n := len(items)
+	e.completion.selected = ((min(e.completion.selected, n-1)+offset)%n + n) % n
+}
+
+// AcceptCompletion replaces the word before the cursor with the highlighted item and closes the menu.
+func (e *Editor) AcceptCompletion() error {
+	e.mu.Lock()
+	defer e.mu.Unlock()
+
+	items, _ := e.completionItems()
+	if len(items) == 0 {
+		return nil
+	}
+	item := items[min(e.completion.selected, len(items)-1)]
+	start, cursor := e.completion.start, e.current.Selection().End
+	e.completion = nil
+
+	end, err := e.current.Replace(start, cursor, item.Text)
+	if err != nil {
+		return err
+	}
+	e.desiredColumn = -1
+	return e.current.MoveSelectionTo(end, false)
+}
+
+// CloseCompletion closes the completion menu.
+func (e *Editor) CloseCompletion() {
+	e.mu.Lock()
+	defer e.mu.Unlock()
+
+	e.completion = nil
+}
+
+// completionItems returns the items of the open menu matching the word typed since it opened,
+// with the characters of their labels it matched, or none once the cursor has left that word.
+func (e *Editor) completionItems() ([]CompletionItem, [][]int) {
+	c := e.completion
+	if c == nil || e.current == nil || e.current.FilePath() != c.path {
+		return nil, nil
+	}
+	cursor := e.current.Selection().End
+	if cursor < c.start {
+		return nil, nil
+	}
+	typed, err := e.current.Substring(c.start, cursor)
+	if err != nil || strings.IndexFunc(typed, func(r rune) bool { return !isWordRune(r) }) >= 0 {
+		return nil, nil
+	}
+	if typed == "" {
+		return c.items, make([][]int, len(c.items))
+	}
+
+	c.mu.Lock()
+	defer c.mu.Unlock()
+
+	c.matcher.SetPattern(typed)
+	var items []CompletionItem
+	var matched [][]int
+	for _, m := range c.matcher.Matches() {
+		items = append(items, c.items[m.Index])
+		matched = append(matched, m.Positions)
+	}
+	return items, matched
+}
+
+// wordStartBeforeCursor returns the position of the start of the word the cursor is at the end of,
+// or the cursor's own when it doesn't follow a word.
+func (e *Editor) wordStartBeforeCursor() int {
+	cursor := e.current.Selection().End
+	line, _, err := e.current.PositionToLineCol(cursor)
+	if err != nil {
+		return cursor
+	}
+	lineStart, _, err := e.current.LineRange(line)
+	if err != nil {
+		return cursor
+	}
+	before, err := e.current.Substring(lineStart, cursor)
+	if err != nil {
+		return cursor
+	}
+
+	start := lineStart
+	g := uniseg.NewGraphemes(before)
+	for pos := lineStart; g.Next(); pos++ {
+		if !isWordRune(g.Runes()[0]) {
+			start = pos + 1
+		}
+	}
+	return start
+}
+
+// isWordRune reports whether r can be part of an identifier.
+func isWordRune(r rune) bool {
+	return unicode.IsLetter(r) || unicode.IsNumber(r) || r == '_'
+}
@@ -0,0 +1,97 @@
+package editor
+
+import "github.com/lg2m/athena/internal/lsp"
+
+// Diagnostic is a problem a language server found in the current buffer, like a compile error.
+type Diagnostic struct {
+	Line       int // line the problem starts on
+	Start, End int // positions of the text it is about, never empty
+	Severity   lsp.DiagnosticSeverity
+	Message    string
+}
+
+// SetDiagnostics replaces the diagnostics of the buffer for the file at path, which need not be
+// open, and wakes the UI to show them; it may be called from any goroutine.
+func (e *Editor) SetDiagnostics(path string, diagnostics []lsp.Diagnostic) {
+	e.mu.Lock()
+	if e.diagnostics == nil {
+		e.diagnostics = make(map[string][]lsp.Diagnostic)
+	}
+	if len(diagnostics) == 0 {
+		delete(e.diagnostics, path)
+	} else {
+		e.diagnostics[path] = diagnostics
+	}
+	wakeup := e.wakeup
+	e.mu.Unlock()
+
+	if wakeup != nil {
+		wakeup()
+	}
+}
+
+// Diagnostics returns the diagnostics of the current buffer, placed in its text as it is now.
+//
+//	Servers publish them for the text they were last sent, so after an edit they may sit a little
+//	off until the next set arrives; ranges past the end of the buffer are clamped to it.
+func (e *Editor) Diagnostics() ([]Diagnostic, error) {
+	e.mu.RLock()
+	defer e.mu.RUnlock()
+
+	if e.current == nil {
+		return nil, ErrNoBuffer
+	}
+
+	published := e.diagnostics[e.current.FilePath()]
+	diagnostics := make([]Diagnostic, 0, len(published))
+	for _, d := range published {
+		start, end := e.lspPosition(d.Range.Start), e.lspPosition(d.Range.End)
+		severity := d.Severity
+		if severity == 0 {
+			severity = lsp.SeverityError
+		}
+		line, _, _ := e.current.PositionToLineCol(start)
+		diagnostics = append(diagnostics, Diagnostic{
+			Line:     line,
+			Start:    start,
+			End:      max(end, start+1),
+			Severity: severity,
+			Message:  d.Message,
+		})
+	}
+	return diagnostics, nil
+}
+
+// lspPosition converts a language server position into a position in the current buffer.
+func (e *Editor) lspPosition(pos lsp.Position) int {
+	lines := e.current.LineCount()
+	if pos.Line >= lines {
+		_, end, _ := e.current.LineRange(lines - 1)
+		return end
+	}
+	text, err := e.current.GetLine(pos.Line)
+	if err != nil {
+		return 0
+	}
+	start, _, _ := e.current.LineRange(pos.Line)
+	return start + lsp.Column(text, pos.Character)
+}
+
+// LSPPosition returns the language server position of the cursor in the current buffer.
+func (e *Editor) LSPPosition() (lsp.Position, error) {
+	e.mu.RLock()
+	defer e.mu.RUnlock()
+
+	if e.current == nil {
+		return lsp.Position{}, ErrNoBuffer
+	}
+	line, col, err := e.current.PositionToLineCol(e.current.Selection().End)
+	if err != nil {
+		return lsp.Position{}, err
+	}
+	text, err := e.current.GetLine(line)
+	if err != nil {
+		return lsp.Position{}, err
+	}
+	return lsp.Position{Line: line, Character: lsp.Character(text, col)}, nil
+}
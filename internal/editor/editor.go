@@ -1,13 +1,20 @@
 package editor
 
 import (
+	"context"
 	"errors"
+	"fmt"
+	"io"
+	"io/fs"
 	"path/filepath"
+	"slices"
 	"sync"
 
 	"github.com/lg2m/athena/internal/editor/buffer"
 	"github.com/lg2m/athena/internal/editor/state"
 	"github.com/lg2m/athena/internal/editor/treesitter"
+	"github.com/lg2m/athena/internal/lsp"
+	"github.com/lg2m/athena/internal/util"
 )
 
 var (
@@ -16,14 +23,46 @@ var (
 	ErrBufferNotFound   = errors.New("buffer not found")
 	ErrInvalidOperation = errors.New("invalid operation for current mode")
 	ErrUnsavedChanges   = errors.New("unsaved changes exist")
+	ErrFileExists       = errors.New("file exists (add ! to override)")
+	ErrFileLoaded       = errors.New("file is loaded in another buffer")
+	ErrLastBuffer       = errors.New("cannot close the last buffer")
+	ErrReadOnlyBuffer   = errors.New("buffer is read-only")
+	ErrChangedOnDisk    = errors.New("file changed on disk since it was read")
 )
 
 // Editor represents the main editor application.
 type Editor struct {
 	buffers       map[string]*buffer.Buffer // keys by absolute file path
+	order         []string                  // buffer paths in the order they were opened
 	current       *buffer.Buffer
 	mode          state.EditorMode
-	desiredColumn int // track movement
+	desiredColumn int  // visual column to keep across vertical movement
+	desiredInRow  bool // desiredColumn is within the soft wrapped row, kept by gj and gk
+	tabWidth      int
+	replaceLinks  bool // saving a symlinked file replaces the link instead of writing through it
+	openReadOnly  bool // buffers opened start read-only, see SetOpenReadOnly
+	lastSearch    searchState
+	register      Register          // unnamed register used by yank, delete and paste
+	history       []Register        // numbered registers, see NumberedRegisters
+	macros        map[rune][]string // recorded keys by register, see StartRecording
+	recording     rune              // register being recorded into, 0 when not recording
+	pendingKeys   string            // count and keys of an unfinished command, for display
+	message       Message
+	diagnostics   map[string][]lsp.Diagnostic  // published by language servers, by file path
+	vcs           map[string]*vcsState         // version control state by file path, see SetVCS
+	signs         map[string]map[string][]Sign // placed by file path and group, see SetSigns
+	completion    *completion                  // open completion menu, see SetCompletion
+	hover         *hover                       // open hover popup, see SetHover
+	jumps         state.JumpList               // where the cursor was before jumps, for <c-o>
+	quickfix      state.QuickfixList           // locations ]q and [q go through, see Grep
+	grepCancel    context.CancelFunc           // stops the grep filling the quickfix list
+	swaps         swapFiles                    // see WriteSwaps
+	backup        bool                         // saving first copies the file on disk, see SetBackup
+	wakeup        func()                       // called when background work finishes, see SetWakeup
+	onPanic       func()                       // called before a panic in background work ends the program
+	hooks         hooks                        // subscribed to events, see Subscribe
+	saves         sync.WaitGroup               // background saves still writing
+	jobs          sync.WaitGroup               // background work still running, see ReplaceInBackground
 	mu            sync.RWMutex
 }
 
@@ -33,14 +72,47 @@ func NewEditor() *Editor {
 		buffers:       make(map[string]*buffer.Buffer),
 		mode:          state.Normal,
 		desiredColumn: -1,
+		tabWidth:      state.DefaultTabWidth,
 	}
 }
 
+// SetTabWidth sets the number of cells between tab stops used for visual columns.
+func (e *Editor) SetTabWidth(width int) {
+	e.mu.Lock()
+	defer e.mu.Unlock()
+
+	if width <= 0 {
+		width = state.DefaultTabWidth
+	}
+	e.tabWidth = width
+}
+
+// TabWidth returns the number of cells between tab stops.
+func (e *Editor) TabWidth() int {
+	e.mu.RLock()
+	defer e.mu.RUnlock()
+
+	return e.tabWidth
+}
+
+// SetOpenReadOnly sets whether buffers opened from now on start read-only, refusing edits.
+func (e *Editor) SetOpenReadOnly(on bool) {
+	e.mu.Lock()
+	defer e.mu.Unlock()
+
+	e.openReadOnly = on
+}
+
 // OpenFile opens a file and adds it to the buffer manager.
 func (e *Editor) OpenFile(filePath string) error {
 	e.mu.Lock()
 	defer e.mu.Unlock()
 
+	return e.openFile(filePath)
+}
+
+// openFile opens a file, switching to its buffer if it is already open.
+func (e *Editor) openFile(filePath string) error {
 	absPath, err := filepath.Abs(filePath)
 	if err != nil {
 		return err
@@ -49,20 +121,183 @@ func (e *Editor) OpenFile(filePath string) error {
 	// check if buffer exists
 	if b, exists := e.buffers[absPath]; exists {
 		e.current = b
+		e.desiredColumn = -1
 		return nil
 	}
+	// or the same file is open through a symlink
+	if realPath, err := filepath.EvalSymlinks(absPath); err == nil {
+		for _, b := range e.buffers {
+			if b.RealPath() == realPath {
+				e.current = b
+				e.desiredColumn = -1
+				return nil
+			}
+		}
+	}
 
 	// create new buffer
 	b, err := buffer.NewBuffer(absPath)
 	if err != nil {
 		return err
 	}
+	b.SetChangeHook(func(path string, version uint64, changes state.ChangeSet) {
+		e.emit(BufferChanged{Path: path, Version: version, Changes: changes})
+	})
+	if e.openReadOnly {
+		b.SetReadOnly(true)
+	}
 
 	e.buffers[absPath] = b
+	e.order = append(e.order, absPath)
 	e.current = b
+	e.desiredColumn = -1
+	e.emit(FileOpened{Path: absPath})
+	return nil
+}
+
+// ReloadCurrentBuffer rereads the current buffer from disk, refusing to drop unsaved changes unless force is set.
+func (e *Editor) ReloadCurrentBuffer(force bool) error {
+	e.mu.Lock()
+	defer e.mu.Unlock()
+
+	if e.current == nil {
+		return ErrNoBuffer
+	}
+	if e.current.Modified() && !force {
+		return ErrUnsavedChanges
+	}
+	e.desiredColumn = -1
+	return e.current.Reload()
+}
+
+// ChangedOnDisk reports whether the current buffer's file was written by another program since the
+// buffer last read or wrote it.
+func (e *Editor) ChangedOnDisk() (bool, error) {
+	e.mu.RLock()
+	defer e.mu.RUnlock()
+
+	if e.current == nil {
+		return false, ErrNoBuffer
+	}
+	return e.current.ChangedOnDisk(), nil
+}
+
+// KeepChangedOnDisk keeps the current buffer as it is rather than reloading its file changed on
+// disk, which ChangedOnDisk stops reporting until it changes again.
+func (e *Editor) KeepChangedOnDisk() error {
+	e.mu.Lock()
+	defer e.mu.Unlock()
+
+	if e.current == nil {
+		return ErrNoBuffer
+	}
+	e.current.KeepChangedOnDisk()
+	return nil
+}
+
+// BufferChangedOnDisk reports whether the file of the open buffer at path was written by another
+// program since the buffer last read or wrote it, as ChangedOnDisk does for the current buffer.
+func (e *Editor) BufferChangedOnDisk(path string) (bool, error) {
+	e.mu.RLock()
+	defer e.mu.RUnlock()
+
+	b, err := e.getBuffer(path)
+	if err != nil {
+		return false, err
+	}
+	return b.ChangedOnDisk(), nil
+}
+
+// Modified reports whether the current buffer has unsaved changes.
+func (e *Editor) Modified() (bool, error) {
+	e.mu.RLock()
+	defer e.mu.RUnlock()
+
+	if e.current == nil {
+		return false, ErrNoBuffer
+	}
+	return e.current.Modified(), nil
+}
+
+// ReadOnly reports whether the current buffer refuses edits.
+func (e *Editor) ReadOnly() (bool, error) {
+	e.mu.RLock()
+	defer e.mu.RUnlock()
+
+	if e.current == nil {
+		return false, ErrNoBuffer
+	}
+	return e.current.ReadOnly(), nil
+}
+
+// Saving reports whether a save of the current buffer is being written, e.g. in the background.
+func (e *Editor) Saving() (bool, error) {
+	e.mu.RLock()
+	defer e.mu.RUnlock()
+
+	if e.current == nil {
+		return false, ErrNoBuffer
+	}
+	return e.current.Saving(), nil
+}
+
+// SetReadOnly sets whether the current buffer refuses edits.
+func (e *Editor) SetReadOnly(on bool) error {
+	e.mu.Lock()
+	defer e.mu.Unlock()
+
+	if e.current == nil {
+		return ErrNoBuffer
+	}
+	e.current.SetReadOnly(on)
 	return nil
 }
 
+// Version returns a number that grows with every edit of the current buffer.
+func (e *Editor) Version() (uint64, error) {
+	e.mu.RLock()
+	defer e.mu.RUnlock()
+
+	if e.current == nil {
+		return 0, ErrNoBuffer
+	}
+	return e.current.Version(), nil
+}
+
+// IsNewFile reports whether the current buffer's file has not been created on disk yet.
+func (e *Editor) IsNewFile() (bool, error) {
+	e.mu.RLock()
+	defer e.mu.RUnlock()
+
+	if e.current == nil {
+		return false, ErrNoBuffer
+	}
+	return e.current.IsNew(), nil
+}
+
+// Encoding returns the character encoding the current buffer is written in.
+func (e *Editor) Encoding() (buffer.Encoding, error) {
+	e.mu.RLock()
+	defer e.mu.RUnlock()
+
+	if e.current == nil {
+		return buffer.UTF8, ErrNoBuffer
+	}
+	return e.current.Encoding(), nil
+}
+
+// LineEnding returns the line ending the current buffer is written with, and whether its text
+// ends with a line break.
+func (e *Editor) LineEnding() (le buffer.LineEnding, finalNewline bool, err error) {
+	e.mu.RLock()
+	defer e.mu.RUnlock()
+
+	if e.current == nil {
+		return buffer.LF, false, ErrNoBuffer
+	}
+	return e.current.LineEnding(), e.current.FinalNewline(), nil
+}
+
 // FileName returns the file name related to the current active buffer.
 func (e *Editor) FileName() (string, error) {
 	if e.current == nil {
@@ -76,7 +311,18 @@ func (e *Editor) FileType() (string, error) {
 	if e.current == nil {
 		return "", ErrNoBuffer
 	}
-	return e.current.FileName(), nil
+	return e.current.FileType(), nil
+}
+
+// RealPath returns the path of the current buffer's file with symlinks resolved.
+func (e *Editor) RealPath() (string, error) {
+	e.mu.RLock()
+	defer e.mu.RUnlock()
+
+	if e.current == nil {
+		return "", ErrNoBuffer
+	}
+	return e.current.RealPath(), nil
 }
 
 // FilePath returns the path of the file related to the current active buffer.
@@ -101,17 +347,44 @@ func (e *Editor) SwitchBuffer(filePath string) error {
 	return nil
 }
 
+// ModifiedBuffers returns the paths of the open buffers with unsaved changes, in the order they
+// were opened.
+func (e *Editor) ModifiedBuffers() []string {
+	e.mu.RLock()
+	defer e.mu.RUnlock()
+
+	var paths []string
+	for _, path := range e.order {
+		if e.buffers[path].Modified() {
+			paths = append(paths, path)
+		}
+	}
+	return paths
+}
+
 // GetBufferList returns a list of all open buffer file paths
 func (e *Editor) GetBufferList() []string {
 	e.mu.RLock()
 	defer e.mu.RUnlock()
 
-	paths := make([]string, 0, len(e.buffers))
-	for path := range e.buffers {
-		paths = append(paths, path)
+	return slices.Clone(e.order)
+}
+
+// CycleBuffer switches to the buffer offset places after the current one in opening order, wrapping around.
+func (e *Editor) CycleBuffer(offset int) error {
+	e.mu.Lock()
+	defer e.mu.Unlock()
+
+	if e.current == nil {
+		return ErrNoBuffer
 	}
 
-	return paths
+	idx := slices.Index(e.order, e.current.FilePath())
+	n := len(e.order)
+	idx = ((idx+offset)%n + n) % n
+	e.current = e.buffers[e.order[idx]]
+	e.desiredColumn = -1
+	return nil
 }
 
 // GetMode returns the current mode state.
@@ -119,9 +392,26 @@ func (e *Editor) GetMode() state.EditorMode {
 	return e.mode
 }
 
-// SetMode sets the current editor mode state.
+// SetMode sets the current editor mode state; leaving insert mode closes the completion menu and
+// forgets the pairs typed together, and a read-only buffer refuses to enter it with a message.
 func (e *Editor) SetMode(mode state.EditorMode) {
+	e.mu.Lock()
+	defer e.mu.Unlock()
+
+	if mode == state.Insert && e.current != nil && e.current.ReadOnly() {
+		e.message = Message{Text: buffer.ErrReadOnly.Error(), IsError: true}
+		return
+	}
+	if mode != e.mode {
+		e.emit(ModeChanged{From: e.mode, To: mode})
+	}
 	e.mode = mode
+	if mode != state.Insert {
+		e.completion = nil
+		if e.current != nil {
+			e.current.ForgetAutoPairs()
+		}
+	}
 }
 
 // InsertText inserts text at the cursor position in the current buffer.
@@ -138,8 +428,7 @@ func (e *Editor) InsertText(text string) error {
 		return ErrInvalidOperation
 	}
 
-	e.current.CollapseSelectionsToCursor()
-
+	// typing over a selection replaces it
 	return e.current.Insert(text)
 }
 
@@ -154,6 +443,18 @@ func (e *Editor) DeleteSelection() error {
 	return e.current.DeleteSelection()
 }
 
+// CollapseSelection reduces the selection to just the cursor.
+func (e *Editor) CollapseSelection() error {
+	e.mu.Lock()
+	defer e.mu.Unlock()
+
+	if e.current == nil {
+		return ErrNoBuffer
+	}
+	e.current.CollapseSelectionsToCursor()
+	return nil
+}
+
 // DeleteText deletes text of specified length from the cursor position.
 func (e *Editor) DeleteText(length int) error {
 	e.mu.Lock()
@@ -164,6 +465,9 @@ func (e *Editor) DeleteText(length int) error {
 	}
 
 	selection := e.current.Selection()
+	if !selection.Empty() {
+		return e.current.DeleteSelection()
+	}
 	pos := selection.End
 
 	if length < 0 {
@@ -175,6 +479,80 @@ func (e *Editor) DeleteText(length int) error {
 	return e.current.Delete(pos, pos+length)
 }
 
+// DeleteWordBefore deletes the word before the cursor and the blanks after it, or joins the line
+// to the one above at its start; see buffer.WordStartBefore.
+func (e *Editor) DeleteWordBefore() error {
+	return e.deleteFromCursor((*buffer.Buffer).WordStartBefore)
+}
+
+// DeleteWordAfter deletes the blanks after the cursor and the word after them, or joins the line
+// below at the end of the line; see buffer.WordEndAfter.
+func (e *Editor) DeleteWordAfter() error {
+	return e.deleteFromCursor((*buffer.Buffer).WordEndAfter)
+}
+
+// DeleteToLineStart deletes the text before the cursor back to the indentation of its line, or
+// the indentation too when there is none; see buffer.LineStartBefore.
+func (e *Editor) DeleteToLineStart() error {
+	return e.deleteFromCursor((*buffer.Buffer).LineStartBefore)
+}
+
+// deleteFromCursor deletes between the cursor and where edge says the deletion stops, as a
+// single change.
+func (e *Editor) deleteFromCursor(edge func(b *buffer.Buffer, pos int) int) error {
+	e.mu.Lock()
+	defer e.mu.Unlock()
+
+	if e.current == nil {
+		return ErrNoBuffer
+	}
+	pos := e.current.Selection().End
+	other := edge(e.current, pos)
+	start, end := min(pos, other), max(pos, other)
+	if start == end {
+		return nil
+	}
+	e.desiredColumn = -1
+	if err := e.current.Delete(start, end); err != nil {
+		return err
+	}
+	return e.current.MoveSelectionTo(start, false)
+}
+
+// Replace replaces the text between start and end in the current buffer, returning the position after the new text.
+func (e *Editor) Replace(start, end int, text string) (int, error) {
+	e.mu.Lock()
+	defer e.mu.Unlock()
+
+	if e.current == nil {
+		return 0, ErrNoBuffer
+	}
+	return e.current.Replace(start, end, text)
+}
+
+// Substring returns the text between start and end in the current buffer.
+func (e *Editor) Substring(start, end int) (string, error) {
+	e.mu.RLock()
+	defer e.mu.RUnlock()
+
+	if e.current == nil {
+		return "", ErrNoBuffer
+	}
+	return e.current.Substring(start, end)
+}
+
+// MoveCursorTo moves the cursor to a position in the current buffer.
+func (e *Editor) MoveCursorTo(pos int, extend bool) error {
+	e.mu.Lock()
+	defer e.mu.Unlock()
+
+	if e.current == nil {
+		return ErrNoBuffer
+	}
+	e.desiredColumn = -1
+	return e.current.MoveSelectionTo(pos, extend)
+}
+
 // GetCurrentPosition retrieves the current line and column of the cursor.
 func (e *Editor) GetCurrentPosition() (int, int, error) {
 	selection := e.current.Selection()
@@ -182,6 +560,22 @@ func (e *Editor) GetCurrentPosition() (int, int, error) {
 	return e.current.PositionToLineCol(pos)
 }
 
+// GetVisualPosition retrieves the current line and display column of the cursor, expanding tabs and wide graphemes.
+func (e *Editor) GetVisualPosition() (int, int, error) {
+	e.mu.RLock()
+	defer e.mu.RUnlock()
+
+	if e.current == nil {
+		return 0, 0, ErrNoBuffer
+	}
+
+	line, col, err := e.current.PositionToLineCol(e.current.Selection().End)
+	if err != nil {
+		return 0, 0, err
+	}
+	return line, e.visualColumn(line, col), nil
+}
+
 // LineCol retrieves the current line and column of a position.
 func (e *Editor) LineCol(pos int) (int, int, error) {
 	e.mu.RLock()
@@ -204,6 +598,26 @@ func (e *Editor) Selection() (state.Selection, error) {
 	return e.current.Selection(), nil
 }
 
+// TextStats counts the lines, words, characters and bytes of the current selection, or of the
+// whole buffer when nothing is selected; selected reports which was counted.
+func (e *Editor) TextStats() (stats util.TextStats, selected bool, err error) {
+	e.mu.RLock()
+	defer e.mu.RUnlock()
+
+	if e.current == nil {
+		return util.TextStats{}, false, ErrNoBuffer
+	}
+	if e.current.Selection().Empty() {
+		return util.CountText(e.current.Text()), false, nil
+	}
+
+	text, err := e.current.GetSelectedText()
+	if err != nil {
+		return util.TextStats{}, true, err
+	}
+	return util.CountText(text), true, nil
+}
+
 // MoveCursorHorizontal moves the cursor horizontally in the current buffer.
 func (e *Editor) MoveCursorHorizontal(offset int, extend bool) error {
 	e.mu.Lock()
@@ -221,12 +635,12 @@ func (e *Editor) MoveCursorHorizontal(offset int, extend bool) error {
 	selection := e.current.Selection()
 
 	pos := selection.End
-	_, col, err := e.current.PositionToLineCol(pos)
+	line, col, err := e.current.PositionToLineCol(pos)
 	if err != nil {
 		return err
 	}
 
-	e.desiredColumn = col
+	e.desiredColumn, e.desiredInRow = e.visualColumn(line, col), false
 	return nil
 }
 
@@ -258,14 +672,88 @@ func (e *Editor) JumpFromCursor(offset int, extend bool) error {
 		targetLine = totalLines - 1
 	}
 
-	if e.desiredColumn == -1 {
-		e.desiredColumn = currCol
+	if e.desiredColumn == -1 || e.desiredInRow {
+		e.desiredColumn, e.desiredInRow = e.visualColumn(currLine, currCol), false
+	}
+
+	return e.current.MoveSelectionToLineCol(targetLine, e.graphemeColumn(targetLine, e.desiredColumn), extend)
+}
+
+// MoveDisplayRows moves the cursor a number of display rows when lines are soft wrapped to width cells.
+//
+//	A width of zero means lines are not wrapped, so this behaves like JumpFromCursor.
+func (e *Editor) MoveDisplayRows(offset, width int, extend bool) error {
+	if width <= 0 {
+		return e.JumpFromCursor(offset, extend)
+	}
+
+	e.mu.Lock()
+	defer e.mu.Unlock()
+
+	if e.current == nil {
+		return ErrNoBuffer
+	}
+
+	line, col, err := e.current.PositionToLineCol(e.current.Selection().End)
+	if err != nil {
+		return err
+	}
+
+	text, err := e.current.GetLine(line)
+	if err != nil {
+		return err
+	}
+
+	rows := state.WrapLine(text, width, e.tabWidth)
+	row := state.RowOf(rows, col)
+	totalLines := e.current.LineCount()
+
+	// rows don't all start at multiples of the width, as they break between words and before
+	// wide graphemes, so the column kept is the one within the row
+	if e.desiredColumn == -1 || !e.desiredInRow {
+		e.desiredColumn = state.VisualColumn(text, col, e.tabWidth) - state.VisualColumn(text, rows[row], e.tabWidth)
+		e.desiredInRow = true
+	}
+
+rowLoop:
+	for ; offset != 0; offset -= sign(offset) {
+		switch {
+		case offset > 0 && row+1 < len(rows):
+			row++
+			continue
+		case offset < 0 && row > 0:
+			row--
+			continue
+		case offset > 0 && line+1 < totalLines:
+			line++
+		case offset < 0 && line > 0:
+			line--
+		default:
+			break rowLoop // reached the edge of the buffer
+		}
+
+		if text, err = e.current.GetLine(line); err != nil {
+			return err
+		}
+		rows = state.WrapLine(text, width, e.tabWidth)
+		row = 0
+		if offset < 0 {
+			row = len(rows) - 1
+		}
+	}
+
+	// keep the desired column relative to the start of the row, without spilling onto the next one
+	rowStart := state.VisualColumn(text, rows[row], e.tabWidth)
+	target := state.ColumnAtVisual(text, rowStart+e.desiredColumn, e.tabWidth)
+	if row+1 < len(rows) && target >= rows[row+1] {
+		target = rows[row+1] - 1
 	}
 
-	return e.current.MoveSelectionToLineCol(targetLine, e.desiredColumn, extend)
+	return e.current.MoveSelectionToLineCol(line, target, extend)
 }
 
-// JumpToLine moves the cursor to a specific line number (0-based) and attempts to retain column position (when possible).
+// JumpToLine moves the cursor to a specific line number (0-based) and attempts to retain column position (when possible),
+// remembering where it was in the jump list.
 func (e *Editor) JumpToLine(lineNum int, extend bool) error {
 	e.mu.Lock()
 	defer e.mu.Unlock()
@@ -285,29 +773,33 @@ func (e *Editor) JumpToLine(lineNum int, extend bool) error {
 
 	// current column for maintaining desired column
 	selection := e.current.Selection()
-	_, currCol, err := e.current.PositionToLineCol(selection.End)
+	currLine, currCol, err := e.current.PositionToLineCol(selection.End)
 	if err != nil {
 		return err
 	}
 
-	if e.desiredColumn == -1 {
-		e.desiredColumn = currCol
+	if e.desiredColumn == -1 || e.desiredInRow {
+		e.desiredColumn, e.desiredInRow = e.visualColumn(currLine, currCol), false
 	}
 
-	return e.current.MoveSelectionToLineCol(lineNum, e.desiredColumn, extend)
+	return e.jumpTo(func() error {
+		return e.current.MoveSelectionToLineCol(lineNum, e.graphemeColumn(lineNum, e.desiredColumn), extend)
+	})
 }
 
-// JumpToTop moves the cursor to the beginning of the document.
+// JumpToTop moves the cursor to the beginning of the document, remembering where it was in the jump list.
 func (e *Editor) JumpToTop(extend bool) error {
 	e.mu.Lock()
 	defer e.mu.Unlock()
 	if e.current == nil {
 		return ErrNoBuffer
 	}
-	return e.current.MoveSelectionToLineCol(0, 0, extend)
+	return e.jumpTo(func() error {
+		return e.current.MoveSelectionToLineCol(0, 0, extend)
+	})
 }
 
-// JumpToBottom moves the cursor to the end of the document.
+// JumpToBottom moves the cursor to the end of the document, remembering where it was in the jump list.
 func (e *Editor) JumpToBottom(extend bool) error {
 	e.mu.Lock()
 	defer e.mu.Unlock()
@@ -315,7 +807,91 @@ func (e *Editor) JumpToBottom(extend bool) error {
 		return ErrNoBuffer
 	}
 	lastLine := e.current.LineCount() - 1
-	return e.current.MoveSelectionToLineCol(lastLine, 0, extend)
+	return e.jumpTo(func() error {
+		return e.current.MoveSelectionToLineCol(lastLine, 0, extend)
+	})
+}
+
+// JumpToChange moves the cursor through the current buffer's recent edits, towards older ones when
+// count is negative, like g; and g,.
+func (e *Editor) JumpToChange(count int) error {
+	e.mu.Lock()
+	defer e.mu.Unlock()
+
+	if e.current == nil {
+		return ErrNoBuffer
+	}
+	e.desiredColumn = -1
+	return e.current.JumpToChange(count)
+}
+
+// Undo takes back the last count changes to the current buffer, each a command or an insert with
+// everything typed in it.
+func (e *Editor) Undo(count int) error {
+	e.mu.Lock()
+	defer e.mu.Unlock()
+
+	if e.current == nil {
+		return ErrNoBuffer
+	}
+	e.desiredColumn = -1
+	for range count {
+		if err := e.current.Undo(); err != nil {
+			return err
+		}
+	}
+	return nil
+}
+
+// Redo makes the last count changes undone in the current buffer again.
+func (e *Editor) Redo(count int) error {
+	e.mu.Lock()
+	defer e.mu.Unlock()
+
+	if e.current == nil {
+		return ErrNoBuffer
+	}
+	e.desiredColumn = -1
+	for range count {
+		if err := e.current.Redo(); err != nil {
+			return err
+		}
+	}
+	return nil
+}
+
+// CommitUndo ends the undo step of every buffer, so the changes made so far are undone apart from
+// the next ones.
+func (e *Editor) CommitUndo() {
+	e.mu.Lock()
+	defer e.mu.Unlock()
+
+	for _, b := range e.buffers {
+		b.CommitUndo()
+	}
+}
+
+// MatchPair moves the cursor to the partner of the first keyword or bracket pair at or after it on its line.
+func (e *Editor) MatchPair(extend bool) error {
+	e.mu.Lock()
+	defer e.mu.Unlock()
+
+	if e.current == nil {
+		return ErrNoBuffer
+	}
+	e.desiredColumn = -1
+	return e.current.MatchPair(extend)
+}
+
+// PairAtCursor returns the keyword or bracket pair with a delimiter under the cursor, to highlight it.
+func (e *Editor) PairAtCursor() (state.Pair, bool) {
+	e.mu.RLock()
+	defer e.mu.RUnlock()
+
+	if e.current == nil {
+		return state.Pair{}, false
+	}
+	return e.current.PairAt(e.current.Selection().End)
 }
 
 // MoveToNextWord moves the cursor to the beginning of the next word boundary.
@@ -328,6 +904,18 @@ func (e *Editor) MoveToNextWord(extend bool) error {
 	return e.current.MoveToNextWord(extend)
 }
 
+// WordObject returns the range of the word under the cursor, with the blanks around it when around is set.
+func (e *Editor) WordObject(around bool) (int, int, error) {
+	e.mu.RLock()
+	defer e.mu.RUnlock()
+
+	if e.current == nil {
+		return 0, 0, ErrNoBuffer
+	}
+	start, end := e.current.WordObject(around)
+	return start, end, nil
+}
+
 // MoveToPrevWord moves the cursor to the beginning of the previous word boundary.
 func (e *Editor) MoveToPrevWord(extend bool) error {
 	e.mu.Lock()
@@ -340,43 +928,281 @@ func (e *Editor) MoveToPrevWord(extend bool) error {
 
 // SaveCurrentBuffer saves the current buffer.
 func (e *Editor) SaveCurrentBuffer() error {
+	e.mu.RLock()
+	b := e.current
+	e.mu.RUnlock()
+
+	if b == nil {
+		return ErrNoBuffer
+	}
+	return e.saveBuffer(b)
+}
+
+// SaveModifiedBuffers saves every buffer with unsaved changes, in the order they were opened,
+// stopping at the first that fails. Unless force is set, read-only buffers and files changed on
+// disk since they were read aren't saved over.
+func (e *Editor) SaveModifiedBuffers(force bool) error {
+	e.mu.RLock()
+	var modified []*buffer.Buffer
+	for _, path := range e.order {
+		if b := e.buffers[path]; b.Modified() {
+			modified = append(modified, b)
+		}
+	}
+	e.mu.RUnlock()
+
+	for _, b := range modified {
+		var err error
+		switch {
+		case !force && b.ReadOnly():
+			err = ErrReadOnlyBuffer
+		case !force && b.ChangedOnDisk():
+			err = ErrChangedOnDisk
+		default:
+			err = e.saveBuffer(b)
+		}
+		if err != nil {
+			return fmt.Errorf("%q: %w", b.FilePath(), err)
+		}
+	}
+	return nil
+}
+
+// saveBuffer saves b in the foreground.
+func (e *Editor) saveBuffer(b *buffer.Buffer) error {
+	path := b.FilePath()
+	e.beforeSave(path)
+
+	e.mu.Lock()
+	defer e.mu.Unlock()
+
+	if e.backup {
+		if err := b.WriteBackup(); err != nil {
+			return err
+		}
+	}
+	var err error
+	replaced := false
+	if e.replaceLinks {
+		replaced, err = b.ReplaceLink()
+	}
+	if !replaced {
+		err = b.Save()
+	}
+	e.emit(AfterSave{Path: path, Err: err})
+	return err
+}
+
+// SetBackup sets whether saving first copies the file as it is on disk to a backup, see
+// buffer.WriteBackup.
+func (e *Editor) SetBackup(backup bool) {
+	e.mu.Lock()
+	defer e.mu.Unlock()
+
+	e.backup = backup
+}
+
+// SetReplaceSymlinks sets whether saving a file opened through a symlink replaces the link with a
+// regular file; by default saves write through the link to its target.
+func (e *Editor) SetReplaceSymlinks(replace bool) {
 	e.mu.Lock()
 	defer e.mu.Unlock()
 
+	e.replaceLinks = replace
+}
+
+// LinkTarget returns the file the current buffer's path links to, if it is a symlink.
+func (e *Editor) LinkTarget() (string, bool) {
+	e.mu.RLock()
+	defer e.mu.RUnlock()
+
 	if e.current == nil {
+		return "", false
+	}
+	return e.current.LinkTarget()
+}
+
+// SaveCurrentBufferInBackground starts saving the current buffer without waiting for the write,
+// so huge files don't block the caller. done is called from the writing goroutine.
+func (e *Editor) SaveCurrentBufferInBackground(done func(error)) error {
+	path, _ := e.FilePath()
+	e.beforeSave(path)
+
+	e.mu.RLock()
+	buf, wakeup, replaceLinks, backup := e.current, e.wakeup, e.replaceLinks, e.backup
+	e.mu.RUnlock()
+
+	if buf == nil {
+		return ErrNoBuffer
+	}
+	if backup {
+		if err := buf.WriteBackup(); err != nil {
+			return err
+		}
+	}
+	// a link is replaced in the foreground, by saving to the file put in its place
+	if replaceLinks {
+		if replaced, err := buf.ReplaceLink(); replaced {
+			done(err)
+			e.emit(AfterSave{Path: path, Err: err})
+			return nil
+		}
+	}
+
+	e.saves.Add(1)
+	err := buf.SaveInBackground(func(err error) {
+		defer e.recoverPanic()
+		defer e.saves.Done()
+		done(err)
+		e.emit(AfterSave{Path: path, Err: err})
+		if wakeup != nil {
+			wakeup()
+		}
+	})
+	if err != nil {
+		e.saves.Done()
+	}
+	return err
+}
+
+// SaveCurrentBufferWith saves the current buffer by handing its content to write, see buffer.SaveWith.
+func (e *Editor) SaveCurrentBufferWith(write func(path string, content io.Reader) error) error {
+	path, _ := e.FilePath()
+	e.beforeSave(path)
+
+	e.mu.RLock()
+	buf := e.current
+	e.mu.RUnlock()
+
+	if buf == nil {
 		return ErrNoBuffer
 	}
-	return e.current.Save()
+	path = buf.FilePath()
+	err := buf.SaveWith(func(content io.Reader) error {
+		return write(path, content)
+	})
+	e.emit(AfterSave{Path: path, Err: err})
+	return err
+}
+
+// WaitForSaves blocks until background saves have finished writing.
+func (e *Editor) WaitForSaves() {
+	e.saves.Wait()
 }
 
-// CloseCurrentBuffer closes the current buffer.
-func (e *Editor) CloseCurrentBuffer() error {
+// SetWakeup sets the function called when background work finishes, so the UI can redraw.
+func (e *Editor) SetWakeup(wakeup func()) {
 	e.mu.Lock()
 	defer e.mu.Unlock()
 
+	e.wakeup = wakeup
+}
+
+// SetPanicHandler sets the function called when background work panics, before the panic goes on
+// to end the program, e.g. to restore the terminal so it can be read.
+func (e *Editor) SetPanicHandler(onPanic func()) {
+	e.mu.Lock()
+	defer e.mu.Unlock()
+
+	e.onPanic = onPanic
+}
+
+// recoverPanic, deferred first thing in a background goroutine, calls the panic handler on a panic
+// and then panics again.
+func (e *Editor) recoverPanic() {
+	if r := recover(); r != nil {
+		e.mu.RLock()
+		onPanic := e.onPanic
+		e.mu.RUnlock()
+		if onPanic != nil {
+			onPanic()
+		}
+		panic(r)
+	}
+}
+
+// WriteCurrentBufferTo writes a copy of the current buffer to filePath, leaving the buffer bound to its file.
+func (e *Editor) WriteCurrentBufferTo(filePath string, overwrite bool) error {
+	e.mu.RLock()
+	defer e.mu.RUnlock()
+
 	if e.current == nil {
 		return ErrNoBuffer
 	}
+	if err := e.current.WriteCopy(filePath, overwrite); err != nil {
+		if errors.Is(err, fs.ErrExist) {
+			return ErrFileExists
+		}
+		return err
+	}
+	return nil
+}
 
-	if err := e.current.Close(); err != nil {
+// SaveCurrentBufferAs writes the current buffer to filePath and rebinds the buffer to it.
+func (e *Editor) SaveCurrentBufferAs(filePath string, overwrite bool) error {
+	absPath, err := filepath.Abs(filePath)
+	if err != nil {
 		return err
 	}
+	e.beforeSave(absPath)
 
-	for path, b := range e.buffers {
-		if b == e.current {
-			delete(e.buffers, path)
-			break
-		}
+	e.mu.Lock()
+	defer e.mu.Unlock()
+
+	if e.current == nil {
+		return ErrNoBuffer
+	}
+	if b, exists := e.buffers[absPath]; exists && b != e.current {
+		return ErrFileLoaded
 	}
 
-	for _, buf := range e.buffers {
-		if buf != nil {
-			e.current = buf
-			return nil
+	oldPath := e.current.FilePath()
+	if err := e.current.SaveAs(absPath, overwrite); err != nil {
+		e.emit(AfterSave{Path: absPath, Err: err})
+		if errors.Is(err, fs.ErrExist) {
+			return ErrFileExists
 		}
+		return err
+	}
+
+	delete(e.buffers, oldPath)
+	e.buffers[absPath] = e.current
+	if idx := slices.Index(e.order, oldPath); idx != -1 {
+		e.order[idx] = absPath
+	}
+	e.emit(AfterSave{Path: absPath})
+	return nil
+}
+
+// CloseCurrentBuffer closes the current buffer and switches to the one opened after it (or before, if it was last).
+//
+//	Unsaved changes are only discarded when force is set.
+func (e *Editor) CloseCurrentBuffer(force bool) error {
+	e.mu.Lock()
+	defer e.mu.Unlock()
+
+	if e.current == nil {
+		return ErrNoBuffer
+	}
+	if e.current.Modified() && !force {
+		return ErrUnsavedChanges
+	}
+	if len(e.order) == 1 {
+		return ErrLastBuffer
+	}
+
+	if err := e.current.Close(); err != nil {
+		return err
 	}
 
-	e.current = nil
+	path := e.current.FilePath()
+	idx := slices.Index(e.order, path)
+	e.order = slices.Delete(e.order, idx, idx+1)
+	delete(e.buffers, path)
+
+	e.current = e.buffers[e.order[min(idx, len(e.order)-1)]]
+	e.desiredColumn = -1
+	e.emit(BufferClosed{Path: path})
 	return nil
 }
 
@@ -391,6 +1217,28 @@ func (e *Editor) GetLine(lineNum int) (string, error) {
 	return e.current.GetLine(lineNum)
 }
 
+// Text returns the whole content of the current buffer.
+func (e *Editor) Text() (string, error) {
+	e.mu.RLock()
+	defer e.mu.RUnlock()
+
+	if e.current == nil {
+		return "", ErrNoBuffer
+	}
+	return e.current.Text(), nil
+}
+
+// LineRange returns the start and end positions of a line in the current buffer, excluding its newline.
+func (e *Editor) LineRange(lineNum int) (int, int, error) {
+	e.mu.RLock()
+	defer e.mu.RUnlock()
+
+	if e.current == nil {
+		return 0, 0, ErrNoBuffer
+	}
+	return e.current.LineRange(lineNum)
+}
+
 func (e *Editor) GetHighlights() ([]treesitter.Highlight, error) {
 	e.mu.RLock()
 	defer e.mu.RUnlock()
@@ -412,6 +1260,36 @@ func (e *Editor) GetLineCount() (int, error) {
 	return e.current.LineCount(), nil
 }
 
+// visualColumn converts a grapheme column on a line of the current buffer into a display column.
+func (e *Editor) visualColumn(line, col int) int {
+	text, err := e.current.GetLine(line)
+	if err != nil {
+		return col
+	}
+	return state.VisualColumn(text, col, e.tabWidth)
+}
+
+// graphemeColumn converts a display column on a line of the current buffer into a grapheme column.
+func (e *Editor) graphemeColumn(line, vcol int) int {
+	text, err := e.current.GetLine(line)
+	if err != nil {
+		return vcol
+	}
+	return state.ColumnAtVisual(text, vcol, e.tabWidth)
+}
+
+// sign returns -1, 0 or 1 depending on the sign of n.
+func sign(n int) int {
+	switch {
+	case n > 0:
+		return 1
+	case n < 0:
+		return -1
+	default:
+		return 0
+	}
+}
+
 // getBuffer returns a buffer by file path
 func (e *Editor) getBuffer(filePath string) (*buffer.Buffer, error) {
 	absPath, err := filepath.Abs(filePath)
@@ -0,0 +1,67 @@
+package editor
+
+import "errors"
+
+var ErrBufferChanged = errors.New("buffer changed while the command ran")
+
+// ReplaceInBackground replaces the text between start and end in the current buffer with what
+// replace makes of it, worked out in the background so a slow external command doesn't freeze
+// the screen. done gets the outcome, from the background goroutine.
+//
+//	The buffer may be edited or closed meanwhile; then it is left alone and done gets
+//	ErrBufferChanged.
+func (e *Editor) ReplaceInBackground(start, end int, replace func(text string) (string, error), done func(error)) error {
+	e.mu.RLock()
+	buf, wakeup := e.current, e.wakeup
+	e.mu.RUnlock()
+
+	if buf == nil {
+		return ErrNoBuffer
+	}
+	path, version := buf.FilePath(), buf.Version()
+	text, err := buf.Substring(start, end)
+	if err != nil {
+		return err
+	}
+
+	e.jobs.Add(1)
+	go func() {
+		defer e.recoverPanic()
+		defer e.jobs.Done()
+		done(e.replaceIfUnchanged(path, version, start, end, text, replace))
+		if wakeup != nil {
+			wakeup()
+		}
+	}()
+	return nil
+}
+
+// replaceIfUnchanged replaces the text between start and end in the buffer of path with what
+// replace makes of it, as long as the buffer is still at version.
+func (e *Editor) replaceIfUnchanged(path string, version uint64, start, end int, text string, replace func(text string) (string, error)) error {
+	replacement, err := replace(text)
+	if err != nil {
+		return err
+	}
+
+	e.mu.Lock()
+	defer e.mu.Unlock()
+
+	buf, ok := e.buffers[path]
+	if !ok || buf.Version() != version {
+		return ErrBufferChanged
+	}
+	if _, err := buf.Replace(start, end, replacement); err != nil {
+		return err
+	}
+	if buf == e.current {
+		e.desiredColumn = -1
+	}
+	return nil
+}
+
+// WaitForJobs blocks until the work started in the background is done, see ReplaceInBackground
+// and Grep.
+func (e *Editor) WaitForJobs() {
+	e.jobs.Wait()
+}
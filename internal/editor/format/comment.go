@@ -0,0 +1,112 @@
+package format
+
+import "strings"
+
+// Comment holds the strings a language writes comments with.
+type Comment struct {
+	Line       string // starts a comment running to the end of the line, e.g. "//"
+	BlockStart string // starts a comment ended by BlockEnd, e.g. "/*"
+	BlockEnd   string
+}
+
+// IsZero reports whether c has neither a line comment nor both ends of a block comment.
+func (c Comment) IsZero() bool {
+	return c.Line == "" && (c.BlockStart == "" || c.BlockEnd == "")
+}
+
+// CommentFor returns the comment strings of fileType, keyed like [language.<type>], if athena
+// knows them.
+func CommentFor(fileType string) (Comment, bool) {
+	switch fileType {
+	case "go", "c", "h", "cc", "cpp", "hpp", "rs", "js", "jsx", "ts", "tsx", "java", "kt", "swift",
+		"cs", "scala", "dart", "php", "proto":
+		return Comment{Line: "//", BlockStart: "/*", BlockEnd: "*/"}, true
+	case "zig":
+		return Comment{Line: "//"}, true
+	case "py", "sh", "bash", "zsh", "fish", "rb", "pl", "r", "toml", "yaml", "yml", "nix", "conf",
+		"mk":
+		return Comment{Line: "#"}, true
+	case "lua":
+		return Comment{Line: "--", BlockStart: "--[[", BlockEnd: "]]"}, true
+	case "hs":
+		return Comment{Line: "--", BlockStart: "{-", BlockEnd: "-}"}, true
+	case "sql", "elm":
+		return Comment{Line: "--"}, true
+	case "lisp", "el", "clj", "scm", "asm", "ini":
+		return Comment{Line: ";"}, true
+	case "tex", "erl":
+		return Comment{Line: "%"}, true
+	case "vim":
+		return Comment{Line: "\""}, true
+	case "css":
+		return Comment{BlockStart: "/*", BlockEnd: "*/"}, true
+	case "html", "xml", "svg", "md":
+		return Comment{BlockStart: "<!--", BlockEnd: "-->"}, true
+	}
+	return Comment{}, false
+}
+
+// ToggleComment comments out the lines of text, or uncomments them when every non-blank one is
+// commented already; blank lines are left alone.
+//
+//	Lines are commented after the indentation they all share, so they stay aligned, with c.Line
+//	or, for languages without line comments, wrapped in c.BlockStart and c.BlockEnd.
+func ToggleComment(text string, c Comment) string {
+	trailing := strings.HasSuffix(text, "\n")
+	lines := strings.Split(strings.TrimSuffix(text, "\n"), "\n")
+
+	commented, shared := true, -1
+	for _, line := range lines {
+		if strings.TrimSpace(line) == "" {
+			continue
+		}
+		if lead := len(Leading(line)); shared < 0 || lead < shared {
+			shared = lead
+		}
+		if _, ok := c.uncomment(line); !ok {
+			commented = false
+		}
+	}
+	if shared < 0 {
+		return text
+	}
+
+	for i, line := range lines {
+		switch {
+		case strings.TrimSpace(line) == "":
+		case commented:
+			lines[i], _ = c.uncomment(line)
+		case c.Line != "":
+			lines[i] = line[:shared] + c.Line + " " + line[shared:]
+		default:
+			lines[i] = line[:shared] + c.BlockStart + " " + line[shared:] + " " + c.BlockEnd
+		}
+	}
+
+	result := strings.Join(lines, "\n")
+	if trailing {
+		result += "\n"
+	}
+	return result
+}
+
+// uncomment returns line without the comment strings around its text, and whether it had them;
+// the space after the start and before the end go too.
+func (c Comment) uncomment(line string) (string, bool) {
+	lead := Leading(line)
+	body := line[len(lead):]
+	if c.Line != "" {
+		if !strings.HasPrefix(body, c.Line) {
+			return line, false
+		}
+		return lead + strings.TrimPrefix(body[len(c.Line):], " "), true
+	}
+
+	body = strings.TrimRight(body, " \t")
+	if len(body) < len(c.BlockStart)+len(c.BlockEnd) ||
+		!strings.HasPrefix(body, c.BlockStart) || !strings.HasSuffix(body, c.BlockEnd) {
+		return line, false
+	}
+	body = body[len(c.BlockStart) : len(body)-len(c.BlockEnd)]
+	return lead + strings.TrimSuffix(strings.TrimPrefix(body, " "), " "), true
+}
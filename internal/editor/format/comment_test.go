@@ -0,0 +1,48 @@
+package format
+
+import "testing"
+
+func TestToggleComment(t *testing.T) {
+	slashes := Comment{Line: "//", BlockStart: "/*", BlockEnd: "*/"}
+	markup := Comment{BlockStart: "<!--", BlockEnd: "-->"}
+
+	tests := []struct {
+		name    string
+		text    string
+		comment Comment
+		want    string
+	}{
+		{name: "comment line", text: "x := 1\n", comment: slashes, want: "// x := 1\n"},
+		{name: "uncomment line", text: "// x := 1\n", comment: slashes, want: "x := 1\n"},
+		{name: "uncomment without space", text: "//x := 1", comment: slashes, want: "x := 1"},
+		{
+			name:    "shared indentation",
+			text:    "\tif ok {\n\t\treturn\n\n\t}\n",
+			comment: slashes,
+			want:    "\t// if ok {\n\t// \treturn\n\n\t// }\n",
+		},
+		{
+			name:    "uncomment keeps indentation",
+			text:    "\t// if ok {\n\t// \treturn\n\t// }\n",
+			comment: slashes,
+			want:    "\tif ok {\n\t\treturn\n\t}\n",
+		},
+		{
+			name:    "some commented",
+			text:    "// one\ntwo\n",
+			comment: slashes,
+			want:    "// // one\n// two\n",
+		},
+		{name: "block only", text: "  <p>hi</p>\n", comment: markup, want: "  <!-- <p>hi</p> -->\n"},
+		{name: "uncomment block", text: "  <!-- <p>hi</p> -->\n", comment: markup, want: "  <p>hi</p>\n"},
+		{name: "blank lines only", text: "\n  \n", comment: slashes, want: "\n  \n"},
+	}
+
+	for _, tt := range tests {
+		t.Run(tt.name, func(t *testing.T) {
+			if got := ToggleComment(tt.text, tt.comment); got != tt.want {
+				t.Errorf("ToggleComment(%q) = %q, want %q", tt.text, got, tt.want)
+			}
+		})
+	}
+}
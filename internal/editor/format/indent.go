@@ -0,0 +1,87 @@
+package format
+
+import "strings"
+
+// Indent describes how a language writes indentation.
+type Indent struct {
+	Tabs     bool // indent with tabs rather than spaces
+	TabWidth int  // display columns per tab
+}
+
+// String renders an indentation covering width display columns.
+func (in Indent) String(width int) string {
+	if width <= 0 {
+		return ""
+	}
+	if !in.Tabs || in.TabWidth <= 0 {
+		return strings.Repeat(" ", width)
+	}
+	return strings.Repeat("\t", width/in.TabWidth) + strings.Repeat(" ", width%in.TabWidth)
+}
+
+// Width returns the display width of the leading whitespace of line.
+func (in Indent) Width(line string) int {
+	width := 0
+	for _, r := range Leading(line) {
+		if r == '\t' && in.TabWidth > 0 {
+			width += in.TabWidth - width%in.TabWidth
+		} else {
+			width++
+		}
+	}
+	return width
+}
+
+// Leading returns the leading spaces and tabs of line.
+func Leading(line string) string {
+	return line[:len(line)-len(strings.TrimLeft(line, " \t"))]
+}
+
+// NextIndent returns the indentation for a line opened after line: the same as line's, or a level
+// deeper when line ends with one of after, e.g. "{".
+func NextIndent(line string, after []string, in Indent) string {
+	trimmed := strings.TrimRight(line, " \t")
+	for _, end := range after {
+		if end != "" && strings.HasSuffix(trimmed, end) {
+			return in.String(in.Width(line) + in.TabWidth)
+		}
+	}
+	return Leading(line)
+}
+
+// Reindent shifts the lines of text so the least indented non-blank line starts at column
+// target, keeping the indentation of the other lines relative to it.
+//
+//	Blank lines are emptied and a trailing newline is preserved.
+func Reindent(text string, target int, in Indent) string {
+	trailing := strings.HasSuffix(text, "\n")
+	lines := strings.Split(strings.TrimSuffix(text, "\n"), "\n")
+
+	base := -1
+	for _, line := range lines {
+		if strings.TrimSpace(line) == "" {
+			continue
+		}
+		if w := in.Width(line); base == -1 || w < base {
+			base = w
+		}
+	}
+	if base == -1 {
+		return text
+	}
+
+	for i, line := range lines {
+		if strings.TrimSpace(line) == "" {
+			lines[i] = ""
+			continue
+		}
+		width := target + in.Width(line) - base
+		lines[i] = in.String(width) + strings.TrimLeft(line, " \t")
+	}
+
+	out := strings.Join(lines, "\n")
+	if trailing {
+		out += "\n"
+	}
+	return out
+}
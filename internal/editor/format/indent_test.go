@@ -0,0 +1,107 @@
+package format
+
+import "testing"
+
+func TestIndentString(t *testing.T) {
+	tests := []struct {
+		name  string
+		in    Indent
+		width int
+		want  string
+	}{
+		{name: "spaces", in: Indent{TabWidth: 4}, width: 6, want: "      "},
+		{name: "tabs", in: Indent{Tabs: true, TabWidth: 4}, width: 8, want: "\t\t"},
+		{name: "tabs with remainder", in: Indent{Tabs: true, TabWidth: 4}, width: 6, want: "\t  "},
+		{name: "zero", in: Indent{Tabs: true, TabWidth: 4}, width: 0, want: ""},
+	}
+
+	for _, tt := range tests {
+		t.Run(tt.name, func(t *testing.T) {
+			if got := tt.in.String(tt.width); got != tt.want {
+				t.Errorf("String(%d) = %q, want %q", tt.width, got, tt.want)
+			}
+		})
+	}
+}
+
+func TestNextIndent(t *testing.T) {
+	spaces := Indent{TabWidth: 4}
+	tabs := Indent{Tabs: true, TabWidth: 4}
+	tests := []struct {
+		name  string
+		line  string
+		after []string
+		in    Indent
+		want  string
+	}{
+		{name: "copied", line: "    foo()", after: []string{"{"}, in: spaces, want: "    "},
+		{name: "copied as is", line: " \tfoo", in: spaces, want: " \t"},
+		{name: "deeper", line: "\tif x {", after: []string{"{"}, in: tabs, want: "\t\t"},
+		{name: "trailing blanks", line: "if x {  ", after: []string{"{"}, in: spaces, want: "    "},
+		{name: "colon", line: "  def f():", after: []string{":"}, in: spaces, want: "      "},
+		{name: "no level", line: "x = {}", after: []string{"{"}, in: spaces, want: ""},
+		{name: "empty", line: "", after: []string{"{"}, in: spaces, want: ""},
+	}
+
+	for _, tt := range tests {
+		t.Run(tt.name, func(t *testing.T) {
+			if got := NextIndent(tt.line, tt.after, tt.in); got != tt.want {
+				t.Errorf("NextIndent(%q, %q) = %q, want %q", tt.line, tt.after, got, tt.want)
+			}
+		})
+	}
+}
+
+func TestReindent(t *testing.T) {
+	tests := []struct {
+		name   string
+		text   string
+		target int
+		in     Indent
+		want   string
+	}{
+		{
+			name:   "deeper",
+			text:   "if x {\n    y()\n}\n",
+			target: 4,
+			in:     Indent{TabWidth: 4},
+			want:   "    if x {\n        y()\n    }\n",
+		},
+		{
+			name:   "shallower",
+			text:   "\t\tfoo\n\t\t\tbar\n",
+			target: 0,
+			in:     Indent{Tabs: true, TabWidth: 4},
+			want:   "foo\n\tbar\n",
+		},
+		{
+			name:   "spaces to tabs",
+			text:   "  a\n    b\n",
+			target: 4,
+			in:     Indent{Tabs: true, TabWidth: 4},
+			want:   "\ta\n\t  b\n",
+		},
+		{
+			name:   "blank lines",
+			text:   "a\n   \nb",
+			target: 2,
+			in:     Indent{TabWidth: 4},
+			want:   "  a\n\n  b",
+		},
+		{
+			name:   "only blank",
+			text:   "\n\n",
+			target: 4,
+			in:     Indent{TabWidth: 4},
+			want:   "\n\n",
+		},
+	}
+
+	for _, tt := range tests {
+		t.Run(tt.name, func(t *testing.T) {
+			if got := Reindent(tt.text, tt.target, tt.in); got != tt.want {
+				t.Errorf("Reindent(%q, %d) = %q, want %q", tt.text, tt.target, got, tt.want)
+			}
+		})
+	}
+}
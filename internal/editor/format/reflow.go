@@ -0,0 +1,94 @@
+package format
+
+import (
+	"regexp"
+	"strings"
+
+	"github.com/rivo/uniseg"
+)
+
+// DefaultTextWidth is the width paragraphs are reflowed at when no text width is set.
+const DefaultTextWidth = 79
+
+// linePrefix matches the indentation and comment leader kept at the start of reflowed lines.
+var linePrefix = regexp.MustCompile(`^[ \t]*(?:(?://[/!]?|#+|--|;+|\*|>)[ \t]?)?`)
+
+// Reflow rewraps each paragraph of text so lines fit within width display columns.
+//
+//	Paragraphs are separated by blank lines. Every line of a paragraph is given the
+//	indentation and comment leader of its first line, so comments stay comments.
+//	Words longer than the width are left on a line of their own.
+func Reflow(text string, width int, in Indent) string {
+	if width <= 0 {
+		width = DefaultTextWidth
+	}
+
+	trailing := strings.HasSuffix(text, "\n")
+	lines := strings.Split(strings.TrimSuffix(text, "\n"), "\n")
+
+	var out, paragraph []string
+	flush := func() {
+		if len(paragraph) > 0 {
+			out = append(out, reflowParagraph(paragraph, width, in)...)
+			paragraph = nil
+		}
+	}
+	for _, line := range lines {
+		if isBlank(line) {
+			flush()
+			out = append(out, line)
+			continue
+		}
+		paragraph = append(paragraph, line)
+	}
+	flush()
+
+	result := strings.Join(out, "\n")
+	if trailing {
+		result += "\n"
+	}
+	return result
+}
+
+// reflowParagraph greedily fills lines with the words of a paragraph.
+func reflowParagraph(lines []string, width int, in Indent) []string {
+	prefix := linePrefix.FindString(lines[0])
+	leader := strings.TrimSpace(prefix)
+
+	var words []string
+	for _, line := range lines {
+		body := strings.TrimLeft(line, " \t")
+		if p := linePrefix.FindString(line); strings.TrimSpace(p) == leader {
+			body = line[len(p):]
+		}
+		words = append(words, strings.Fields(body)...)
+	}
+	if len(words) == 0 {
+		return []string{strings.TrimRight(prefix, " \t")}
+	}
+
+	prefixWidth := in.Width(prefix) + uniseg.StringWidth(strings.TrimLeft(prefix, " \t"))
+	var out []string
+	var sb strings.Builder
+	lineWidth := 0
+	for _, word := range words {
+		w := uniseg.StringWidth(word)
+		if lineWidth > 0 && prefixWidth+lineWidth+1+w > width {
+			out = append(out, prefix+sb.String())
+			sb.Reset()
+			lineWidth = 0
+		}
+		if lineWidth > 0 {
+			sb.WriteByte(' ')
+			lineWidth++
+		}
+		sb.WriteString(word)
+		lineWidth += w
+	}
+	return append(out, prefix+sb.String())
+}
+
+// isBlank reports whether line has no text besides indentation and a comment leader.
+func isBlank(line string) bool {
+	return strings.TrimSpace(line[len(linePrefix.FindString(line)):]) == ""
+}
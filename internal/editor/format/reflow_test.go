@@ -0,0 +1,70 @@
+package format
+
+import "testing"
+
+func TestReflow(t *testing.T) {
+	tests := []struct {
+		name  string
+		text  string
+		width int
+		want  string
+	}{
+		{
+			name:  "wrap long line",
+			text:  "the quick brown fox jumps over the lazy dog\n",
+			width: 16,
+			want:  "the quick brown\nfox jumps over\nthe lazy dog\n",
+		},
+		{
+			name:  "join short lines",
+			text:  "one\ntwo\nthree",
+			width: 20,
+			want:  "one two three",
+		},
+		{
+			name:  "keeps indentation",
+			text:  "    alpha beta gamma delta",
+			width: 16,
+			want:  "    alpha beta\n    gamma delta",
+		},
+		{
+			name:  "comment prefix",
+			text:  "// alpha beta gamma\n// delta epsilon",
+			width: 20,
+			want:  "// alpha beta gamma\n// delta epsilon",
+		},
+		{
+			name:  "rewraps comment",
+			text:  "\t# one two three four five six",
+			width: 16,
+			want:  "\t# one two\n\t# three four\n\t# five six",
+		},
+		{
+			name:  "paragraphs",
+			text:  "a b\nc\n\nd e\nf\n",
+			width: 80,
+			want:  "a b c\n\nd e f\n",
+		},
+		{
+			name:  "blank comment separates paragraphs",
+			text:  "// a\n// b\n//\n// c",
+			width: 80,
+			want:  "// a b\n//\n// c",
+		},
+		{
+			name:  "long word",
+			text:  "a supercalifragilistic b",
+			width: 10,
+			want:  "a\nsupercalifragilistic\nb",
+		},
+	}
+
+	for _, tt := range tests {
+		t.Run(tt.name, func(t *testing.T) {
+			got := Reflow(tt.text, tt.width, Indent{TabWidth: 4})
+			if got != tt.want {
+				t.Errorf("Reflow(%q, %d) = %q, want %q", tt.text, tt.width, got, tt.want)
+			}
+		})
+	}
+}
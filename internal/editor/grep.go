@@ -0,0 +1,108 @@
+package editor
+
+import (
+	"context"
+	"errors"
+	"path/filepath"
+	"regexp"
+
+	"github.com/lg2m/athena/internal/editor/grep"
+	"github.com/lg2m/athena/internal/editor/state"
+)
+
+// QuickfixResult describes the quickfix entry a jump moved the cursor to.
+type QuickfixResult struct {
+	Entry state.QuickfixEntry
+	Index int // 1-based index of the entry
+	Total int // number of entries in the list
+}
+
+// Grep searches the files under root for re in the background, filling the quickfix list with
+// the lines matching it, titled title, and passing each to found as well; done gets the outcome.
+// Both are called from the background goroutine, found with the editor's lock held, so it mustn't
+// call the editor.
+//
+//	Starting another grep cancels this one; found isn't called after, nor done unless the search
+//	had already finished.
+func (e *Editor) Grep(title, root string, re *regexp.Regexp, found func(grep.Match), done func(error)) error {
+	root, err := filepath.Abs(root)
+	if err != nil {
+		return err
+	}
+	ctx, cancel := context.WithCancel(context.Background())
+
+	e.mu.Lock()
+	if e.grepCancel != nil {
+		e.grepCancel()
+	}
+	e.grepCancel = cancel
+	e.quickfix.Reset(title)
+	wakeup := e.wakeup
+	e.mu.Unlock()
+
+	e.jobs.Add(1)
+	go func() {
+		defer e.recoverPanic()
+		defer e.jobs.Done()
+		defer cancel()
+
+		err := grep.Search(ctx, root, re, func(m grep.Match) {
+			// the list is only filled while this is the latest grep, checked with the lock held
+			// as another grep cancels this one with it held
+			e.mu.Lock()
+			if ctx.Err() != nil {
+				e.mu.Unlock()
+				return
+			}
+			e.quickfix.Add(state.QuickfixEntry{
+				Path: filepath.Join(root, filepath.FromSlash(m.Path)),
+				Line: m.Line,
+				Col:  m.Col,
+				Text: m.Text,
+			})
+			found(m)
+			e.mu.Unlock()
+		})
+		if errors.Is(err, context.Canceled) {
+			return
+		}
+		done(err)
+		if wakeup != nil {
+			wakeup()
+		}
+	}()
+	return nil
+}
+
+// JumpToQuickfix moves the cursor through the quickfix list, backwards when count is negative,
+// opening the file of the entry landed on; it is a jump, so <c-o> comes back.
+//
+//	Entries aren't kept up to date with edits, so they are clamped to the buffer.
+func (e *Editor) JumpToQuickfix(count int) (QuickfixResult, error) {
+	e.mu.Lock()
+	defer e.mu.Unlock()
+
+	if e.current == nil {
+		return QuickfixResult{}, ErrNoBuffer
+	}
+	entry, index, err := e.quickfix.Jump(count)
+	if err != nil {
+		return QuickfixResult{}, err
+	}
+
+	before, here := e.current, e.cursorLocation()
+	if err := e.openFile(entry.Path); err != nil {
+		return QuickfixResult{}, err
+	}
+	e.pushJump(before, here)
+	e.desiredColumn = -1
+
+	start, end, err := e.current.LineRange(min(entry.Line, e.current.LineCount()-1))
+	if err != nil {
+		return QuickfixResult{}, err
+	}
+	if err := e.current.MoveSelectionTo(start+min(entry.Col, end-start), false); err != nil {
+		return QuickfixResult{}, err
+	}
+	return QuickfixResult{Entry: entry, Index: index + 1, Total: e.quickfix.Len()}, nil
+}
@@ -0,0 +1,141 @@
+package grep
+
+import (
+	"bytes"
+	"context"
+	"io/fs"
+	"os"
+	"path/filepath"
+	"regexp"
+	"runtime"
+	"strings"
+	"sync"
+
+	"github.com/rivo/uniseg"
+)
+
+// binaryPrefix is how many bytes of a file are checked for a NUL byte, which marks it binary.
+const binaryPrefix = 8000
+
+// Match is a line of a file with a match of the pattern searched for.
+type Match struct {
+	Path string // relative to the directory searched
+	Line int    // 0-based
+	Col  int    // grapheme column of the first match in the line, 0-based
+	Text string // the whole line, without its line break
+}
+
+// Search looks for re in the files under root, calling found with every line matching it, the
+// lines of a file together and in order; files are searched by several goroutines at once, but
+// found is only called by one at a time.
+//
+//	Like ripgrep, hidden files and directories, binary files and files the .gitignore files
+//	ignore are skipped. Files that can't be read are skipped too; only failing to walk root is an
+//	error. The search stops early once ctx is done, returning its error.
+func Search(ctx context.Context, root string, re *regexp.Regexp, found func(Match)) error {
+	paths := make(chan string)
+	var mu sync.Mutex // held while calling found
+	var wg sync.WaitGroup
+	for range runtime.NumCPU() {
+		wg.Add(1)
+		go func() {
+			defer wg.Done()
+			for path := range paths {
+				matches := searchFile(root, path, re)
+				if len(matches) == 0 || ctx.Err() != nil {
+					continue
+				}
+				mu.Lock()
+				for _, m := range matches {
+					found(m)
+				}
+				mu.Unlock()
+			}
+		}()
+	}
+
+	err := walk(ctx, root, paths)
+	close(paths)
+	wg.Wait()
+	if err != nil {
+		return err
+	}
+	return ctx.Err()
+}
+
+// walk sends the path of every file under root to search, relative to it, until ctx is done.
+func walk(ctx context.Context, root string, paths chan<- string) error {
+	ignores := map[string]*ignoreList{} // by directory, relative to root
+	return filepath.WalkDir(root, func(path string, d fs.DirEntry, err error) error {
+		if err != nil {
+			if path == root {
+				return err
+			}
+			return nil
+		}
+		rel, err := filepath.Rel(root, path)
+		if err != nil {
+			return err
+		}
+		rel = filepath.ToSlash(rel)
+		if rel == "." {
+			ignores[rel] = readIgnoreList(nil, path, "")
+			return nil
+		}
+
+		dir := pathDir(rel)
+		if strings.HasPrefix(d.Name(), ".") || ignores[dir].Ignored(rel, d.IsDir()) {
+			if d.IsDir() {
+				return filepath.SkipDir
+			}
+			return nil
+		}
+		if d.IsDir() {
+			ignores[rel] = readIgnoreList(ignores[dir], path, rel)
+			return nil
+		}
+		if !d.Type().IsRegular() {
+			return nil
+		}
+
+		select {
+		case paths <- rel:
+			return nil
+		case <-ctx.Done():
+			return ctx.Err()
+		}
+	})
+}
+
+// pathDir returns the directory of the slash-separated path rel, "." for the root.
+func pathDir(rel string) string {
+	if i := strings.LastIndexByte(rel, '/'); i >= 0 {
+		return rel[:i]
+	}
+	return "."
+}
+
+// searchFile returns the lines of the file at rel, under root, matching re, none when the file
+// is binary or can't be read.
+func searchFile(root, rel string, re *regexp.Regexp) []Match {
+	data, err := os.ReadFile(filepath.Join(root, filepath.FromSlash(rel)))
+	if err != nil || bytes.IndexByte(data[:min(len(data), binaryPrefix)], 0) >= 0 {
+		return nil
+	}
+
+	var matches []Match
+	for i, line := range strings.Split(strings.TrimSuffix(string(data), "\n"), "\n") {
+		line = strings.TrimSuffix(line, "\r")
+		loc := re.FindStringIndex(line)
+		if loc == nil {
+			continue
+		}
+		matches = append(matches, Match{
+			Path: rel,
+			Line: i,
+			Col:  uniseg.GraphemeClusterCount(line[:loc[0]]),
+			Text: line,
+		})
+	}
+	return matches
+}
@@ -0,0 +1,99 @@
+package grep
+
+import (
+	"context"
+	"os"
+	"path/filepath"
+	"reflect"
+	"regexp"
+	"slices"
+	"strings"
+	"testing"
+)
+
+func TestIgnoreList(t *testing.T) {
+	root := parseIgnoreList(nil, "# build output\n*.log\n/bin\nout/\n!keep.log\n", "")
+	sub := parseIgnoreList(root, "gen/*.go\n", "pkg")
+
+	tests := []struct {
+		list  *ignoreList
+		rel   string
+		isDir bool
+		want  bool
+	}{
+		{list: root, rel: "main.go", want: false},
+		{list: root, rel: "debug.log", want: true},
+		{list: root, rel: "pkg/debug.log", want: true},
+		{list: root, rel: "keep.log", want: false},
+		{list: root, rel: "bin", isDir: true, want: true},
+		{list: root, rel: "pkg/bin", isDir: true, want: false},
+		{list: root, rel: "out", isDir: true, want: true},
+		{list: root, rel: "out", want: false},
+		{list: sub, rel: "pkg/gen/a.go", want: true},
+		{list: sub, rel: "pkg/a.go", want: false},
+		{list: sub, rel: "pkg/trace.log", want: true},
+		{list: root, rel: "gen/a.go", want: false},
+	}
+	for _, tt := range tests {
+		if got := tt.list.Ignored(tt.rel, tt.isDir); got != tt.want {
+			t.Errorf("Ignored(%q, %v) = %v, want %v", tt.rel, tt.isDir, got, tt.want)
+		}
+	}
+}
+
+func TestSearch(t *testing.T) {
+	root := t.TempDir()
+	files := map[string]string{
+		".gitignore":     "ignored.txt\n",
+		"a.txt":          "one\ntwo needle\nthree\r\nneedle four\n",
+		"dir/b.txt":      "héllo needle\n",
+		"ignored.txt":    "needle\n",
+		".hidden/c.txt":  "needle\n",
+		"binary.bin":     "needle\x00\n",
+		"dir/nomatch.go": "package dir\n",
+	}
+	for name, text := range files {
+		path := filepath.Join(root, filepath.FromSlash(name))
+		if err := os.MkdirAll(filepath.Dir(path), 0755); err != nil {
+			t.Fatal(err)
+		}
+		if err := os.WriteFile(path, []byte(text), 0644); err != nil {
+			t.Fatal(err)
+		}
+	}
+
+	var got []Match
+	err := Search(context.Background(), root, regexp.MustCompile("needle"), func(m Match) {
+		got = append(got, m)
+	})
+	if err != nil {
+		t.Fatalf("Search() error = %v", err)
+	}
+	// files are searched in parallel, in no particular order
+	slices.SortStableFunc(got, func(a, b Match) int { return strings.Compare(a.Path, b.Path) })
+
+	want := []Match{
+		{Path: "a.txt", Line: 1, Col: 4, Text: "two needle"},
+		{Path: "a.txt", Line: 3, Col: 0, Text: "needle four"},
+		{Path: "dir/b.txt", Line: 0, Col: 6, Text: "héllo needle"},
+	}
+	if !reflect.DeepEqual(got, want) {
+		t.Errorf("Search() found %+v, want %+v", got, want)
+	}
+}
+
+func TestSearchCanceled(t *testing.T) {
+	root := t.TempDir()
+	if err := os.WriteFile(filepath.Join(root, "a.txt"), []byte("needle\n"), 0644); err != nil {
+		t.Fatal(err)
+	}
+	ctx, cancel := context.WithCancel(context.Background())
+	cancel()
+
+	err := Search(ctx, root, regexp.MustCompile("needle"), func(m Match) {
+		t.Errorf("Search() found %+v after being canceled", m)
+	})
+	if err != context.Canceled {
+		t.Errorf("Search() error = %v, want %v", err, context.Canceled)
+	}
+}
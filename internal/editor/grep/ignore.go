@@ -0,0 +1,91 @@
+package grep
+
+import (
+	"os"
+	"path"
+	"path/filepath"
+	"strings"
+)
+
+// ignoreList holds the patterns of the .gitignore files from the directory searched down to one
+// under it.
+//
+//	Patterns follow gitignore: one without a slash matches a name at any depth, one with a slash
+//	matches the path from the directory of its .gitignore, a trailing slash only matches
+//	directories and a leading "!" takes a path back in. Later patterns win; "**" is only
+//	understood as a leading "**/".
+type ignoreList struct {
+	parent   *ignoreList
+	patterns []ignorePattern
+}
+
+// ignorePattern is a line of a .gitignore file in the directory base, relative to the directory
+// searched.
+type ignorePattern struct {
+	base     string
+	glob     string
+	anchored bool // the glob is matched against the path from base rather than the name
+	dirOnly  bool
+	negated  bool
+}
+
+// readIgnoreList returns the ignore list of the directory at path, rel under the directory
+// searched: parent with the patterns of its .gitignore file, if it has one.
+func readIgnoreList(parent *ignoreList, path, rel string) *ignoreList {
+	data, err := os.ReadFile(filepath.Join(path, ".gitignore"))
+	if err != nil {
+		return parent
+	}
+	return parseIgnoreList(parent, string(data), rel)
+}
+
+// parseIgnoreList returns parent with the patterns of a .gitignore file in the directory rel.
+func parseIgnoreList(parent *ignoreList, text, rel string) *ignoreList {
+	list := &ignoreList{parent: parent}
+	for _, line := range strings.Split(text, "\n") {
+		line = strings.TrimSpace(line)
+		if line == "" || strings.HasPrefix(line, "#") {
+			continue
+		}
+
+		p := ignorePattern{base: rel}
+		line, p.negated = strings.CutPrefix(line, "!")
+		line, p.dirOnly = strings.CutSuffix(line, "/")
+		line, _ = strings.CutPrefix(line, "**/")
+		p.anchored = strings.Contains(line, "/")
+		p.glob = strings.TrimPrefix(line, "/")
+		list.patterns = append(list.patterns, p)
+	}
+	return list
+}
+
+// Ignored reports whether the path rel, relative to the directory searched, is ignored.
+func (l *ignoreList) Ignored(rel string, isDir bool) bool {
+	for ; l != nil; l = l.parent {
+		for i := len(l.patterns) - 1; i >= 0; i-- {
+			if p := l.patterns[i]; p.matches(rel, isDir) {
+				return !p.negated
+			}
+		}
+	}
+	return false
+}
+
+// matches reports whether the pattern matches the path rel, relative to the directory searched.
+func (p ignorePattern) matches(rel string, isDir bool) bool {
+	if p.dirOnly && !isDir {
+		return false
+	}
+	if p.base != "" {
+		var ok bool
+		if rel, ok = strings.CutPrefix(rel, p.base+"/"); !ok {
+			return false
+		}
+	}
+	name := rel
+	if !p.anchored {
+		name = path.Base(rel)
+	}
+	ok, _ := path.Match(p.glob, name)
+	return ok
+}
@@ -0,0 +1,140 @@
+package editor
+
+import (
+	"sync"
+
+	"github.com/lg2m/athena/internal/editor/state"
+)
+
+// Event is something that happened in the editor, passed to the hooks subscribed to its type; see
+// Subscribe.
+type Event interface {
+	event()
+}
+
+// BufferChanged is sent after an edit to the buffer of Path, with the changes it made and the
+// version it left the buffer at.
+type BufferChanged struct {
+	Path    string
+	Version uint64
+	Changes state.ChangeSet
+}
+
+// ModeChanged is sent when the editor switches modes.
+type ModeChanged struct {
+	From state.EditorMode
+	To   state.EditorMode
+}
+
+// FileOpened is sent when a file is first loaded into a buffer.
+type FileOpened struct {
+	Path string
+}
+
+// BufferClosed is sent when the buffer of Path is closed.
+type BufferClosed struct {
+	Path string
+}
+
+// BeforeSave is sent before the buffer of Path is written, and handed to the hooks before the
+// write starts, so they can still edit it.
+type BeforeSave struct {
+	Path string
+}
+
+// AfterSave is sent once the buffer of Path has been written, or failed to be with Err.
+type AfterSave struct {
+	Path string
+	Err  error
+}
+
+func (BufferChanged) event() {}
+func (ModeChanged) event()   {}
+func (FileOpened) event()    {}
+func (BufferClosed) event()  {}
+func (BeforeSave) event()    {}
+func (AfterSave) event()     {}
+
+// hooks holds the subscribed hooks and the events waiting to be handed to them.
+//
+//	Events are queued where they happen, often with the editor locked, and handed over by
+//	DispatchEvents, so hooks are free to call back into the editor.
+type hooks struct {
+	subscribers []subscriber
+	queue       []Event
+	nextID      int
+	mu          sync.Mutex
+}
+
+// subscriber is a hook subscribed to one type of event.
+type subscriber struct {
+	id   int
+	hook func(Event) // ignores events of other types
+}
+
+// Subscribe calls hook with every event of type T from then on, until the returned function is
+// called, e.g.
+//
+//	stop := editor.Subscribe(e, func(ev editor.AfterSave) { ... })
+func Subscribe[T Event](e *Editor, hook func(T)) (unsubscribe func()) {
+	h := &e.hooks
+	h.mu.Lock()
+	defer h.mu.Unlock()
+
+	h.nextID++
+	id := h.nextID
+	h.subscribers = append(h.subscribers, subscriber{id: id, hook: func(ev Event) {
+		if ev, ok := ev.(T); ok {
+			hook(ev)
+		}
+	}})
+	return func() {
+		h.mu.Lock()
+		defer h.mu.Unlock()
+
+		for i, s := range h.subscribers {
+			if s.id == id {
+				h.subscribers = append(h.subscribers[:i:i], h.subscribers[i+1:]...)
+				return
+			}
+		}
+	}
+}
+
+// emit queues ev for the hooks; it may be called with the editor locked.
+func (e *Editor) emit(ev Event) {
+	e.hooks.mu.Lock()
+	defer e.hooks.mu.Unlock()
+
+	if len(e.hooks.subscribers) > 0 {
+		e.hooks.queue = append(e.hooks.queue, ev)
+	}
+}
+
+// DispatchEvents hands the queued events to the hooks subscribed to them, in the order they
+// happened; the event loop calls it, with the editor unlocked.
+//
+//	Events the hooks cause wait for the next call, so a hook editing the buffer on BufferChanged
+//	can't keep one going.
+func (e *Editor) DispatchEvents() {
+	e.hooks.mu.Lock()
+	queue, subscribers := e.hooks.queue, e.hooks.subscribers
+	e.hooks.queue = nil
+	e.hooks.mu.Unlock()
+
+	for _, ev := range queue {
+		for _, s := range subscribers {
+			s.hook(ev)
+		}
+	}
+}
+
+// beforeSave hands BeforeSave for path to the hooks, after the events queued before it; saves
+// call it before locking the editor.
+func (e *Editor) beforeSave(path string) {
+	if path == "" {
+		return
+	}
+	e.emit(BeforeSave{Path: path})
+	e.DispatchEvents()
+}
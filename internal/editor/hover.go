@@ -0,0 +1,51 @@
+package editor
+
+// hover is the open hover popup: documentation of the symbol at pos in the buffer for path.
+type hover struct {
+	path string
+	pos  int
+	text string
+}
+
+// SetHover opens the hover popup with text, the documentation of the symbol at the cursor in the
+// buffer for the file at path, and wakes the UI to show it; it may be called from any goroutine.
+//
+//	Text arriving once the cursor moved or the buffer was left is dropped.
+func (e *Editor) SetHover(path string, pos int, text string) {
+	e.mu.Lock()
+	if e.current != nil && e.current.FilePath() == path && e.current.Selection().End == pos {
+		e.hover = nil
+		if text == "" {
+			e.message = Message{Text: "No hover information", IsError: true}
+		} else {
+			e.hover = &hover{path: path, pos: pos, text: text}
+		}
+	}
+	wakeup := e.wakeup
+	e.mu.Unlock()
+
+	if wakeup != nil {
+		wakeup()
+	}
+}
+
+// Hover returns the text of the hover popup, unless none is open or the cursor has left the
+// symbol it was opened for.
+func (e *Editor) Hover() (string, bool) {
+	e.mu.RLock()
+	defer e.mu.RUnlock()
+
+	h := e.hover
+	if h == nil || e.current == nil || e.current.FilePath() != h.path || e.current.Selection().End != h.pos {
+		return "", false
+	}
+	return h.text, true
+}
+
+// CloseHover closes the hover popup.
+func (e *Editor) CloseHover() {
+	e.mu.Lock()
+	defer e.mu.Unlock()
+
+	e.hover = nil
+}
@@ -0,0 +1,125 @@
+package editor
+
+import (
+	"github.com/lg2m/athena/internal/editor/buffer"
+	"github.com/lg2m/athena/internal/editor/state"
+	"github.com/lg2m/athena/internal/lsp"
+)
+
+// GoToLocation opens the file at path, if it isn't already, and moves the cursor to pos, remembering
+// where it was in the jump list; from is the file of the buffer the jump was asked from, and the
+// UI is woken to show the result. It may be called from any goroutine.
+//
+//	A location arriving once the buffer it was asked from was left is dropped.
+func (e *Editor) GoToLocation(from, path string, pos lsp.Position) error {
+	e.mu.Lock()
+	err := e.goToLocation(from, path, pos)
+	wakeup := e.wakeup
+	e.mu.Unlock()
+
+	if wakeup != nil {
+		wakeup()
+	}
+	return err
+}
+
+// goToLocation is GoToLocation with the lock held.
+func (e *Editor) goToLocation(from, path string, pos lsp.Position) error {
+	if e.current == nil || e.current.FilePath() != from {
+		return nil
+	}
+
+	before, here := e.current, e.cursorLocation()
+	if err := e.openFile(path); err != nil {
+		return err
+	}
+	e.pushJump(before, here)
+	e.desiredColumn = -1
+	return e.current.MoveSelectionTo(e.lspPosition(pos), false)
+}
+
+// JumpToJump moves the cursor through the jump list, towards older locations when count is
+// negative, opening their files again when they were closed.
+//
+//	Locations aren't kept up to date with edits, so they are clamped to the buffer.
+func (e *Editor) JumpToJump(count int) error {
+	e.mu.Lock()
+	defer e.mu.Unlock()
+
+	if e.current == nil {
+		return ErrNoBuffer
+	}
+	loc, err := e.jumps.Jump(count, e.cursorLocation())
+	if err != nil {
+		return err
+	}
+	if err := e.openFile(loc.Path); err != nil {
+		return err
+	}
+	e.desiredColumn = -1
+	return e.current.MoveSelectionTo(min(loc.Pos, e.current.TotalGraphemes()), false)
+}
+
+// jumpTo runs move, which moves the cursor in the current buffer, and remembers where the cursor
+// was in the jump list when it succeeds.
+func (e *Editor) jumpTo(move func() error) error {
+	here := e.cursorLocation()
+	if err := move(); err != nil {
+		return err
+	}
+	e.pushJump(e.current, here)
+	return nil
+}
+
+// pushJump remembers here, a location in b, in the jump list and as b's previous context mark.
+func (e *Editor) pushJump(b *buffer.Buffer, here state.Location) {
+	e.jumps.Push(here)
+	_ = b.SetMark(state.PreviousContextMark, here.Pos)
+}
+
+// cursorLocation returns where the cursor is in the current buffer.
+func (e *Editor) cursorLocation() state.Location {
+	return state.Location{Path: e.current.FilePath(), Pos: e.current.Selection().End}
+}
+
+// SetMark puts the mark name, a-z, at the cursor in the current buffer, like ma.
+func (e *Editor) SetMark(name rune) error {
+	e.mu.Lock()
+	defer e.mu.Unlock()
+
+	if e.current == nil {
+		return ErrNoBuffer
+	}
+	return e.current.SetMark(name, e.current.Selection().End)
+}
+
+// JumpToMark moves the cursor to the mark name in the current buffer, to the first non-blank
+// character of its line when linewise is set, like 'a, or to the mark itself, like `a.
+//
+//	It is a jump, so '' and `` come back.
+func (e *Editor) JumpToMark(name rune, linewise bool) error {
+	e.mu.Lock()
+	defer e.mu.Unlock()
+
+	if e.current == nil {
+		return ErrNoBuffer
+	}
+	pos, err := e.current.Mark(name)
+	if err != nil {
+		return err
+	}
+	move := func() error {
+		return e.current.MoveSelectionTo(pos, false)
+	}
+	if linewise {
+		line, _, err := e.current.PositionToLineCol(pos)
+		if err != nil {
+			return err
+		}
+		move = func() error {
+			return e.moveToFirstNonBlank(line)
+		}
+	}
+	e.desiredColumn = -1
+	return e.jumpTo(move)
+}
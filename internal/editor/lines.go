@@ -0,0 +1,135 @@
+package editor
+
+import (
+	"errors"
+	"strings"
+
+	"github.com/lg2m/athena/internal/editor/format"
+)
+
+var ErrNoComment = errors.New("no comment string for this file type")
+
+// JoinLines joins lines first through last into one, like vim's J, leaving the cursor where the
+// last two met.
+//
+//	The indentation of each joined line becomes a single space, or nothing when the line before
+//	ends in a blank, the joined line is blank or it starts with ")".
+func (e *Editor) JoinLines(first, last int) error {
+	e.mu.Lock()
+	defer e.mu.Unlock()
+
+	if e.current == nil {
+		return ErrNoBuffer
+	}
+
+	lines := e.current.LineCount()
+	if text, err := e.current.GetLine(lines - 1); err == nil && text == "" && lines > 1 {
+		// the file's final newline ends the last line rather than starting another
+		lines--
+	}
+	last = min(last, lines-1)
+	pos := -1
+	for ; first < last; last-- {
+		text, err := e.current.GetLine(first)
+		if err != nil {
+			return err
+		}
+		next, err := e.current.GetLine(first + 1)
+		if err != nil {
+			return err
+		}
+		_, end, err := e.current.LineRange(first)
+		if err != nil {
+			return err
+		}
+
+		rest := strings.TrimLeft(next, " \t")
+		sep := " "
+		if text == "" || strings.HasSuffix(text, " ") || strings.HasSuffix(text, "\t") ||
+			rest == "" || strings.HasPrefix(rest, ")") {
+			sep = ""
+		}
+		// the newline and the indentation after it, both single-byte graphemes
+		if _, err := e.current.Replace(end, end+1+len(next)-len(rest), sep); err != nil {
+			return err
+		}
+		pos = end
+	}
+	if pos < 0 {
+		// nothing below to join
+		return nil
+	}
+
+	e.desiredColumn = -1
+	return e.current.MoveSelectionTo(pos, false)
+}
+
+// ShiftLines indents lines first through last by levels indentation levels, or dedents them when
+// levels is negative, like vim's >> and <<, leaving the cursor on the first line's first non-blank.
+//
+//	A level is a tab stop wide; blank lines are left alone.
+func (e *Editor) ShiftLines(first, last, levels int, in format.Indent) error {
+	e.mu.Lock()
+	defer e.mu.Unlock()
+
+	if e.current == nil {
+		return ErrNoBuffer
+	}
+
+	last = min(last, e.current.LineCount()-1)
+	for line := first; line <= last; line++ {
+		text, err := e.current.GetLine(line)
+		if err != nil {
+			return err
+		}
+		if strings.TrimSpace(text) == "" {
+			continue
+		}
+		start, _, err := e.current.LineRange(line)
+		if err != nil {
+			return err
+		}
+
+		width := max(0, in.Width(text)+levels*in.TabWidth)
+		if _, err := e.current.Replace(start, start+len(format.Leading(text)), in.String(width)); err != nil {
+			return err
+		}
+	}
+	return e.moveToFirstNonBlank(first)
+}
+
+// ToggleComment comments out lines first through last, or uncomments them when they all are
+// commented, leaving the cursor on the first line's first non-blank; see format.ToggleComment.
+func (e *Editor) ToggleComment(first, last int, c format.Comment) error {
+	e.mu.Lock()
+	defer e.mu.Unlock()
+
+	if e.current == nil {
+		return ErrNoBuffer
+	}
+	if c.IsZero() {
+		return ErrNoComment
+	}
+
+	last = min(last, e.current.LineCount()-1)
+	start, _, err := e.current.LineRange(first)
+	if err != nil {
+		return err
+	}
+	_, end, err := e.current.LineRange(last)
+	if err != nil {
+		return err
+	}
+	text, err := e.current.Substring(start, end)
+	if err != nil {
+		return err
+	}
+
+	toggled := format.ToggleComment(text, c)
+	if toggled != text {
+		if _, err := e.current.Replace(start, end, toggled); err != nil {
+			return err
+		}
+	}
+	return e.moveToFirstNonBlank(first)
+}
@@ -0,0 +1,105 @@
+package editor
+
+import (
+	"errors"
+	"slices"
+	"unicode"
+)
+
+var (
+	ErrInvalidRegister = errors.New("invalid register")
+	ErrEmptyMacro      = errors.New("nothing recorded in register")
+)
+
+// StartRecording begins recording typed keys into register a-z; an uppercase register
+// appends to the lowercase one, like vim.
+func (e *Editor) StartRecording(register rune) error {
+	reg, ok := macroRegister(register)
+	if !ok {
+		return ErrInvalidRegister
+	}
+
+	e.mu.Lock()
+	defer e.mu.Unlock()
+
+	if e.macros == nil {
+		e.macros = make(map[rune][]string)
+	}
+	if !unicode.IsUpper(register) {
+		e.macros[reg] = nil
+	}
+	e.recording = reg
+	return nil
+}
+
+// StopRecording ends the recording, dropping the key that stopped it.
+func (e *Editor) StopRecording() {
+	e.mu.Lock()
+	defer e.mu.Unlock()
+
+	if e.recording == 0 {
+		return
+	}
+	if keys := e.macros[e.recording]; len(keys) > 0 {
+		e.macros[e.recording] = keys[:len(keys)-1]
+	}
+	e.recording = 0
+}
+
+// Recording returns the register keys are being recorded into, if any.
+func (e *Editor) Recording() (rune, bool) {
+	e.mu.RLock()
+	defer e.mu.RUnlock()
+
+	return e.recording, e.recording != 0
+}
+
+// RecordKey appends a key in keymap notation to the register being recorded.
+func (e *Editor) RecordKey(key string) {
+	e.mu.Lock()
+	defer e.mu.Unlock()
+
+	if e.recording == 0 {
+		return
+	}
+	e.macros[e.recording] = append(e.macros[e.recording], key)
+}
+
+// Macro returns the keys recorded in register.
+func (e *Editor) Macro(register rune) ([]string, error) {
+	reg, ok := macroRegister(register)
+	if !ok {
+		return nil, ErrInvalidRegister
+	}
+
+	e.mu.RLock()
+	defer e.mu.RUnlock()
+
+	keys := e.macros[reg]
+	if len(keys) == 0 {
+		return nil, ErrEmptyMacro
+	}
+	return slices.Clone(keys), nil
+}
+
+// SetPendingKeys records the count and keys typed so far towards an unfinished command.
+func (e *Editor) SetPendingKeys(keys string) {
+	e.mu.Lock()
+	defer e.mu.Unlock()
+
+	e.pendingKeys = keys
+}
+
+// PendingKeys returns the count and keys typed towards an unfinished command.
+func (e *Editor) PendingKeys() string {
+	e.mu.RLock()
+	defer e.mu.RUnlock()
+
+	return e.pendingKeys
+}
+
+// macroRegister maps a register name to the lowercase register macros are stored under.
+func macroRegister(register rune) (rune, bool) {
+	register = unicode.ToLower(register)
+	return register, register >= 'a' && register <= 'z'
+}
@@ -0,0 +1,43 @@
+package editor
+
+// Message represents a notification shown to the user below the status bar.
+type Message struct {
+	Text    string
+	IsError bool
+}
+
+// SetMessage shows an informational message to the user.
+func (e *Editor) SetMessage(text string) {
+	e.mu.Lock()
+	defer e.mu.Unlock()
+
+	e.message = Message{Text: text}
+}
+
+// SetError shows an error to the user. A nil error is ignored.
+func (e *Editor) SetError(err error) {
+	if err == nil {
+		return
+	}
+
+	e.mu.Lock()
+	defer e.mu.Unlock()
+
+	e.message = Message{Text: err.Error(), IsError: true}
+}
+
+// Message returns the message currently shown to the user.
+func (e *Editor) Message() Message {
+	e.mu.RLock()
+	defer e.mu.RUnlock()
+
+	return e.message
+}
+
+// ClearMessage removes the message shown to the user.
+func (e *Editor) ClearMessage() {
+	e.mu.Lock()
+	defer e.mu.Unlock()
+
+	e.message = Message{}
+}
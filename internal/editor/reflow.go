@@ -0,0 +1,69 @@
+package editor
+
+import (
+	"strings"
+
+	"github.com/lg2m/athena/internal/editor/format"
+)
+
+// ParagraphAt returns the first and last line of the paragraph containing line.
+//
+//	Paragraphs are separated by blank lines; on a blank line the range is just that line.
+func (e *Editor) ParagraphAt(line int) (int, int, error) {
+	e.mu.RLock()
+	defer e.mu.RUnlock()
+
+	if e.current == nil {
+		return 0, 0, ErrNoBuffer
+	}
+
+	blank := func(l int) bool {
+		text, err := e.current.GetLine(l)
+		return err != nil || strings.TrimSpace(text) == ""
+	}
+	if blank(line) {
+		return line, line, nil
+	}
+
+	first, last := line, line
+	for first > 0 && !blank(first-1) {
+		first--
+	}
+	for last+1 < e.current.LineCount() && !blank(last+1) {
+		last++
+	}
+	return first, last, nil
+}
+
+// Reflow rewraps lines first through last at width, keeping indentation and comment leaders.
+//
+//	The cursor is left on the first non-blank of the last reflowed line.
+func (e *Editor) Reflow(first, last, width int, in format.Indent) error {
+	e.mu.Lock()
+	defer e.mu.Unlock()
+
+	if e.current == nil {
+		return ErrNoBuffer
+	}
+
+	start, _, err := e.current.LineRange(first)
+	if err != nil {
+		return err
+	}
+	_, end, err := e.current.LineRange(last)
+	if err != nil {
+		return err
+	}
+	text, err := e.current.Substring(start, end)
+	if err != nil {
+		return err
+	}
+
+	reflowed := format.Reflow(text, width, in)
+	if reflowed != text {
+		if _, err := e.current.Replace(start, end, reflowed); err != nil {
+			return err
+		}
+	}
+	return e.moveToFirstNonBlank(first + strings.Count(reflowed, "\n"))
+}
@@ -0,0 +1,339 @@
+package editor
+
+import (
+	"errors"
+	"slices"
+	"strings"
+
+	"github.com/lg2m/athena/internal/editor/format"
+)
+
+var ErrEmptyRegister = errors.New("nothing in register")
+
+const (
+	UnnamedRegister   = '"' // what was last yanked or deleted
+	numberedRegisters = 9   // "1 to "9, see NumberedRegisters
+)
+
+// Register holds text yanked or deleted for pasting.
+type Register struct {
+	Text     string
+	Linewise bool // whole lines, pasted above or below the cursor line
+}
+
+// Register returns the contents of the unnamed register.
+func (e *Editor) Register() Register {
+	e.mu.RLock()
+	defer e.mu.RUnlock()
+
+	return e.register
+}
+
+// NumberedRegisters returns the contents of "1 to "9, the last texts yanked or deleted, most
+// recent first; "1 is the unnamed register, and older ones move down as text is put in it.
+//
+//	They are a history of the unnamed register, so a yank overwritten by a delete can still be
+//	pasted with "2p.
+func (e *Editor) NumberedRegisters() []Register {
+	e.mu.RLock()
+	defer e.mu.RUnlock()
+
+	return slices.Clone(e.history)
+}
+
+// YankLines copies count lines starting at the cursor line into the register.
+func (e *Editor) YankLines(count int) error {
+	e.mu.Lock()
+	defer e.mu.Unlock()
+
+	if e.current == nil {
+		return ErrNoBuffer
+	}
+
+	first, last, err := e.countedLines(count)
+	if err != nil {
+		return err
+	}
+	return e.yankLineRange(first, last)
+}
+
+// YankLineRange copies lines first through last into the register.
+func (e *Editor) YankLineRange(first, last int) error {
+	e.mu.Lock()
+	defer e.mu.Unlock()
+
+	if e.current == nil {
+		return ErrNoBuffer
+	}
+	return e.yankLineRange(first, last)
+}
+
+// YankRange copies the text between start and end into the register.
+func (e *Editor) YankRange(start, end int) error {
+	e.mu.Lock()
+	defer e.mu.Unlock()
+
+	if e.current == nil {
+		return ErrNoBuffer
+	}
+
+	text, err := e.current.Substring(start, end)
+	if err != nil {
+		return err
+	}
+	e.setRegister(Register{Text: text})
+	return nil
+}
+
+// DeleteRange removes the text between start and end, keeping it in the register.
+func (e *Editor) DeleteRange(start, end int) error {
+	e.mu.Lock()
+	defer e.mu.Unlock()
+
+	if e.current == nil {
+		return ErrNoBuffer
+	}
+
+	text, err := e.current.Substring(start, end)
+	if err != nil {
+		return err
+	}
+	if text == "" {
+		return nil
+	}
+	e.setRegister(Register{Text: text})
+	e.desiredColumn = -1
+	if _, err := e.current.Replace(start, end, ""); err != nil {
+		return err
+	}
+	return e.current.MoveSelectionTo(start, false)
+}
+
+// DeleteLines removes count lines starting at the cursor line, keeping them in the register.
+func (e *Editor) DeleteLines(count int) error {
+	e.mu.Lock()
+	defer e.mu.Unlock()
+
+	if e.current == nil {
+		return ErrNoBuffer
+	}
+
+	first, last, err := e.countedLines(count)
+	if err != nil {
+		return err
+	}
+	return e.deleteLineRange(first, last)
+}
+
+// DeleteLineRange removes lines first through last, keeping them in the register.
+func (e *Editor) DeleteLineRange(first, last int) error {
+	e.mu.Lock()
+	defer e.mu.Unlock()
+
+	if e.current == nil {
+		return ErrNoBuffer
+	}
+	return e.deleteLineRange(first, last)
+}
+
+// ChangeLines empties lines first through last into a single line holding their indentation,
+// keeping them in the register, and leaves the cursor at its end for typing the replacement.
+func (e *Editor) ChangeLines(first, last int) error {
+	e.mu.Lock()
+	defer e.mu.Unlock()
+
+	if e.current == nil {
+		return ErrNoBuffer
+	}
+
+	if err := e.yankLineRange(first, last); err != nil {
+		return err
+	}
+	start, _, err := e.current.LineRange(first)
+	if err != nil {
+		return err
+	}
+	_, end, err := e.current.LineRange(last)
+	if err != nil {
+		return err
+	}
+	text, err := e.current.GetLine(first)
+	if err != nil {
+		return err
+	}
+
+	indent := format.Leading(text)
+	pos, err := e.current.Replace(start, end, indent)
+	if err != nil {
+		return err
+	}
+	e.desiredColumn = -1
+	return e.current.MoveSelectionTo(pos, false)
+}
+
+// deleteLineRange removes lines first through last after yanking them.
+func (e *Editor) deleteLineRange(first, last int) error {
+	if err := e.yankLineRange(first, last); err != nil {
+		return err
+	}
+
+	start, _, err := e.current.LineRange(first)
+	if err != nil {
+		return err
+	}
+	_, end, err := e.current.LineRange(last)
+	if err != nil {
+		return err
+	}
+
+	// take a newline with the lines, from after them or else from before them
+	switch {
+	case last+1 < e.current.LineCount():
+		end++
+	case first > 0:
+		start--
+		first--
+	}
+	if err := e.current.Delete(start, end); err != nil {
+		return err
+	}
+
+	return e.moveToFirstNonBlank(min(first, e.current.LineCount()-1))
+}
+
+// Paste inserts register, the unnamed one or "1 to "9, after the cursor, or before it when
+// before is set.
+//
+//	Linewise text goes below (or above) the cursor line. When indent is non-nil it is
+//	reindented to match the cursor line first, like vim's ]p and [p.
+func (e *Editor) Paste(register rune, before bool, indent *format.Indent) error {
+	e.mu.Lock()
+	defer e.mu.Unlock()
+
+	if e.current == nil {
+		return ErrNoBuffer
+	}
+	reg, err := e.readRegister(register)
+	if err != nil {
+		return err
+	}
+
+	pos := e.current.Selection().End
+	line, _, err := e.current.PositionToLineCol(pos)
+	if err != nil {
+		return err
+	}
+
+	if !reg.Linewise {
+		if !before {
+			_, lineEnd, err := e.current.LineRange(line)
+			if err != nil {
+				return err
+			}
+			pos = min(pos+1, lineEnd)
+		}
+		end, err := e.current.Replace(pos, pos, reg.Text)
+		if err != nil {
+			return err
+		}
+		e.desiredColumn = -1
+		return e.current.MoveSelectionTo(max(pos, end-1), false)
+	}
+
+	text := reg.Text
+	if indent != nil {
+		current, err := e.current.GetLine(line)
+		if err != nil {
+			return err
+		}
+		text = format.Reindent(text, indent.Width(current), *indent)
+	}
+
+	target := line
+	switch {
+	case before:
+		pos, _, err = e.current.LineRange(line)
+	case line+1 < e.current.LineCount():
+		target = line + 1
+		pos, _, err = e.current.LineRange(target)
+	default:
+		// below the last line, which has no newline to insert after
+		target = line + 1
+		_, pos, err = e.current.LineRange(line)
+		text = "\n" + strings.TrimSuffix(text, "\n")
+	}
+	if err != nil {
+		return err
+	}
+
+	if _, err := e.current.Replace(pos, pos, text); err != nil {
+		return err
+	}
+	return e.moveToFirstNonBlank(target)
+}
+
+// countedLines returns the count lines from the cursor line, stopping at the last line.
+func (e *Editor) countedLines(count int) (int, int, error) {
+	line, _, err := e.current.PositionToLineCol(e.current.Selection().End)
+	if err != nil {
+		return 0, 0, err
+	}
+	return line, min(line+max(count, 1)-1, e.current.LineCount()-1), nil
+}
+
+// yankLineRange copies lines first through last into the register.
+func (e *Editor) yankLineRange(first, last int) error {
+	start, _, err := e.current.LineRange(first)
+	if err != nil {
+		return err
+	}
+	_, end, err := e.current.LineRange(last)
+	if err != nil {
+		return err
+	}
+	text, err := e.current.Substring(start, end)
+	if err != nil {
+		return err
+	}
+
+	e.setRegister(Register{Text: text + "\n", Linewise: true})
+	return nil
+}
+
+// setRegister puts r in the unnamed register, moving what the numbered registers held down one.
+func (e *Editor) setRegister(r Register) {
+	e.register = r
+	e.history = slices.Insert(e.history, 0, r)
+	if len(e.history) > numberedRegisters {
+		e.history = e.history[:numberedRegisters]
+	}
+}
+
+// readRegister returns the contents of the unnamed register or of "1 to "9.
+func (e *Editor) readRegister(register rune) (Register, error) {
+	reg := e.register
+	switch {
+	case register >= '1' && register <= '9':
+		if n := int(register - '1'); n < len(e.history) {
+			reg = e.history[n]
+		} else {
+			reg = Register{}
+		}
+	case register != UnnamedRegister:
+		return Register{}, ErrInvalidRegister
+	}
+	if reg.Text == "" {
+		return Register{}, ErrEmptyRegister
+	}
+	return reg, nil
+}
+
+// moveToFirstNonBlank moves the cursor to the first non-blank character of line.
+func (e *Editor) moveToFirstNonBlank(line int) error {
+	text, err := e.current.GetLine(line)
+	if err != nil {
+		return err
+	}
+	e.desiredColumn = -1
+	return e.current.MoveSelectionToLineCol(line, len(format.Leading(text)), false)
+}
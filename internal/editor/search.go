@@ -0,0 +1,177 @@
+package editor
+
+import (
+	"errors"
+	"fmt"
+
+	"github.com/lg2m/athena/internal/editor/search"
+)
+
+var (
+	ErrNoPreviousPattern = errors.New("no previous search pattern")
+	ErrPatternNotFound   = errors.New("pattern not found")
+	ErrSearchHitBottom   = errors.New("search hit BOTTOM without match")
+	ErrSearchHitTop      = errors.New("search hit TOP without match")
+)
+
+// searchState holds the last search so it can be repeated with n and N.
+type searchState struct {
+	pattern   string
+	forward   bool
+	wholeWord bool
+	regex     bool
+}
+
+// SearchResult describes the match a search moved the cursor to.
+type SearchResult struct {
+	Pattern string
+	Forward bool
+	Index   int  // 1-based index of the match
+	Total   int  // number of matches in the buffer
+	Wrapped bool // the search continued from the other end of the buffer
+}
+
+// Search moves the cursor to the next match of pattern and remembers it for SearchNext.
+func (e *Editor) Search(pattern string, forward bool, opts search.Options) (SearchResult, error) {
+	e.mu.Lock()
+	defer e.mu.Unlock()
+
+	if e.current == nil {
+		return SearchResult{}, ErrNoBuffer
+	}
+
+	e.lastSearch = searchState{pattern: pattern, forward: forward, wholeWord: opts.WholeWord, regex: opts.Regex}
+	return e.searchFrom(e.lastSearch.pattern, forward, opts)
+}
+
+// SearchWordUnderCursor searches for the whole word under the cursor, like vim's * and #.
+func (e *Editor) SearchWordUnderCursor(forward bool, opts search.Options) (SearchResult, error) {
+	e.mu.RLock()
+	if e.current == nil {
+		e.mu.RUnlock()
+		return SearchResult{}, ErrNoBuffer
+	}
+	word, err := e.current.WordAtCursor()
+	e.mu.RUnlock()
+	if err != nil {
+		return SearchResult{}, err
+	}
+
+	opts.WholeWord, opts.Regex = true, false
+	return e.Search(word, forward, opts)
+}
+
+// SearchNext repeats the last search, in the opposite direction when reverse is set.
+func (e *Editor) SearchNext(reverse bool, opts search.Options) (SearchResult, error) {
+	e.mu.Lock()
+	defer e.mu.Unlock()
+
+	if e.current == nil {
+		return SearchResult{}, ErrNoBuffer
+	}
+	if e.lastSearch.pattern == "" {
+		return SearchResult{}, ErrNoPreviousPattern
+	}
+
+	// repeated as typed, even if the regex option changed since
+	opts.WholeWord, opts.Regex = e.lastSearch.wholeWord, e.lastSearch.regex
+	return e.searchFrom(e.lastSearch.pattern, e.lastSearch.forward != reverse, opts)
+}
+
+// searchFrom moves the cursor to the match of pattern after (or before) the cursor.
+func (e *Editor) searchFrom(pattern string, forward bool, opts search.Options) (SearchResult, error) {
+	matches, err := search.Find(e.current.Text(), pattern, opts)
+	if err != nil {
+		return SearchResult{}, err
+	}
+	if len(matches) == 0 {
+		return SearchResult{}, fmt.Errorf("%w: %s", ErrPatternNotFound, pattern)
+	}
+
+	index, wrapped, ok := search.Next(matches, e.current.Selection().End, forward, opts.WrapScan)
+	if !ok {
+		if forward {
+			return SearchResult{}, fmt.Errorf("%w for: %s", ErrSearchHitBottom, pattern)
+		}
+		return SearchResult{}, fmt.Errorf("%w for: %s", ErrSearchHitTop, pattern)
+	}
+
+	err = e.jumpTo(func() error {
+		return e.current.MoveSelectionTo(matches[index].Start, false)
+	})
+	if err != nil {
+		return SearchResult{}, err
+	}
+	e.desiredColumn = -1
+
+	return SearchResult{
+		Pattern: pattern,
+		Forward: forward,
+		Index:   index + 1,
+		Total:   len(matches),
+		Wrapped: wrapped,
+	}, nil
+}
+
+// LastSearchPattern returns the pattern of the last search, or an empty string if there was none.
+func (e *Editor) LastSearchPattern() string {
+	e.mu.RLock()
+	defer e.mu.RUnlock()
+
+	return e.lastSearch.pattern
+}
+
+// FindAll returns every match of pattern in the current buffer, e.g. to highlight them while a search is typed.
+func (e *Editor) FindAll(pattern string, opts search.Options) ([]search.Match, error) {
+	e.mu.RLock()
+	defer e.mu.RUnlock()
+
+	if e.current == nil {
+		return nil, ErrNoBuffer
+	}
+	return search.Find(e.current.Text(), pattern, opts)
+}
+
+// FindInLines returns the matches of pattern starting within lines startLine through endLine.
+//
+//	Only the first match on each line is returned unless all is set, as for ":s" without "g".
+func (e *Editor) FindInLines(startLine, endLine int, pattern string, all bool, opts search.Options) ([]search.Match, error) {
+	e.mu.RLock()
+	defer e.mu.RUnlock()
+
+	if e.current == nil {
+		return nil, ErrNoBuffer
+	}
+
+	from, _, err := e.current.LineRange(startLine)
+	if err != nil {
+		return nil, err
+	}
+	_, to, err := e.current.LineRange(endLine)
+	if err != nil {
+		return nil, err
+	}
+
+	matches, err := search.Find(e.current.Text(), pattern, opts)
+	if err != nil {
+		return nil, err
+	}
+
+	var found []search.Match
+	lastLine := -1
+	for _, m := range matches {
+		if m.Start < from || m.Start > to {
+			continue
+		}
+		line, _, err := e.current.PositionToLineCol(m.Start)
+		if err != nil {
+			return nil, err
+		}
+		if !all && line == lastLine {
+			continue
+		}
+		lastLine = line
+		found = append(found, m)
+	}
+	return found, nil
+}
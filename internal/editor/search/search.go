@@ -0,0 +1,146 @@
+package search
+
+import (
+	"regexp"
+	"unicode"
+
+	"github.com/rivo/uniseg"
+)
+
+// Options controls how a pattern matches against text.
+type Options struct {
+	IgnoreCase bool // match regardless of case
+	SmartCase  bool // with IgnoreCase, match case-sensitively when the pattern has uppercase
+	WrapScan   bool // searches wrap around the ends of the buffer
+	WholeWord  bool // only match whole words, as used by "*" and "#"
+	Regex      bool // the pattern is a regular expression (RE2 syntax) rather than literal text
+}
+
+// Match represents a match as a range of grapheme positions [Start, End).
+type Match struct {
+	Start int
+	End   int
+}
+
+// CaseSensitive reports whether pattern should be matched case-sensitively.
+func (o Options) CaseSensitive(pattern string) bool {
+	if !o.IgnoreCase {
+		return true
+	}
+	if o.SmartCase {
+		escaped := false
+		for _, r := range pattern {
+			// classes like \S and \W in a regex aren't uppercase text
+			if unicode.IsUpper(r) && !escaped {
+				return true
+			}
+			escaped = o.Regex && r == '\\' && !escaped
+		}
+	}
+	return false
+}
+
+// Find returns every non-overlapping occurrence of pattern in text.
+func Find(text, pattern string, opts Options) ([]Match, error) {
+	if pattern == "" {
+		return nil, nil
+	}
+	re, err := Compile(pattern, opts)
+	if err != nil {
+		return nil, err
+	}
+
+	var locs [][]int
+	for _, loc := range re.FindAllStringIndex(text, -1) {
+		if loc[0] < loc[1] {
+			locs = append(locs, loc)
+		}
+	}
+	if len(locs) == 0 {
+		return nil, nil
+	}
+
+	return toGraphemeRanges(text, locs), nil
+}
+
+// Compile returns the regular expression matching pattern as opts ask.
+func Compile(pattern string, opts Options) (*regexp.Regexp, error) {
+	expr := regexp.QuoteMeta(pattern)
+	if opts.Regex {
+		// ^ and $ match at line breaks, as the text is a whole buffer
+		expr = "(?m:" + pattern + ")"
+	}
+	if opts.WholeWord {
+		expr = `\b` + expr + `\b`
+	}
+	if !opts.CaseSensitive(pattern) {
+		expr = "(?i)" + expr
+	}
+	return regexp.Compile(expr)
+}
+
+// Next returns the index of the match after (or before) pos.
+//
+//	When the end of the buffer is reached and wrap is set the search continues from the other end,
+//	which is reported by wrapped.
+func Next(matches []Match, pos int, forward, wrap bool) (index int, wrapped bool, ok bool) {
+	if len(matches) == 0 {
+		return 0, false, false
+	}
+
+	if forward {
+		for i, m := range matches {
+			if m.Start > pos {
+				return i, false, true
+			}
+		}
+		if wrap {
+			return 0, true, true
+		}
+		return 0, false, false
+	}
+
+	for i := len(matches) - 1; i >= 0; i-- {
+		if matches[i].Start < pos {
+			return i, false, true
+		}
+	}
+	if wrap {
+		return len(matches) - 1, true, true
+	}
+	return 0, false, false
+}
+
+// toGraphemeRanges converts non-empty byte offset pairs into grapheme ranges, widening partial graphemes.
+func toGraphemeRanges(text string, locs [][]int) []Match {
+	matches := make([]Match, 0, len(locs))
+
+	gr := uniseg.NewGraphemes(text)
+	index := 0 // grapheme index of the current cluster
+	hasNext := gr.Next()
+	for _, loc := range locs {
+		// advance to the cluster containing the start of the match
+		for hasNext {
+			_, to := gr.Positions()
+			if to > loc[0] {
+				break
+			}
+			hasNext = gr.Next()
+			index++
+		}
+		start := index
+
+		// advance past the cluster containing the last byte of the match
+		for hasNext {
+			from, _ := gr.Positions()
+			if from >= loc[1] {
+				break
+			}
+			hasNext = gr.Next()
+			index++
+		}
+		matches = append(matches, Match{Start: start, End: index})
+	}
+
+	return matches
+}
@@ -0,0 +1,99 @@
+package search
+
+import (
+	"reflect"
+	"testing"
+)
+
+func TestCaseSensitive(t *testing.T) {
+	tests := []struct {
+		name    string
+		opts    Options
+		pattern string
+		want    bool
+	}{
+		{name: "default", opts: Options{}, pattern: "foo", want: true},
+		{name: "ignore case", opts: Options{IgnoreCase: true}, pattern: "Foo", want: false},
+		{name: "smart case lower", opts: Options{IgnoreCase: true, SmartCase: true}, pattern: "foo", want: false},
+		{name: "smart case upper", opts: Options{IgnoreCase: true, SmartCase: true}, pattern: "Foo", want: true},
+		{name: "smart case alone", opts: Options{SmartCase: true}, pattern: "foo", want: true},
+		{name: "smart case regex class", opts: Options{IgnoreCase: true, SmartCase: true, Regex: true}, pattern: `\S+`, want: false},
+		{name: "smart case regex upper", opts: Options{IgnoreCase: true, SmartCase: true, Regex: true}, pattern: `\\F`, want: true},
+		{name: "smart case literal backslash", opts: Options{IgnoreCase: true, SmartCase: true}, pattern: `\S`, want: true},
+	}
+
+	for _, tt := range tests {
+		t.Run(tt.name, func(t *testing.T) {
+			if got := tt.opts.CaseSensitive(tt.pattern); got != tt.want {
+				t.Errorf("CaseSensitive(%q) = %v, want %v", tt.pattern, got, tt.want)
+			}
+		})
+	}
+}
+
+func TestFind(t *testing.T) {
+	tests := []struct {
+		name    string
+		text    string
+		pattern string
+		opts    Options
+		want    []Match
+	}{
+		{name: "literal", text: "foo bar foo", pattern: "foo", want: []Match{{0, 3}, {8, 11}}},
+		{name: "case sensitive", text: "Foo foo", pattern: "foo", want: []Match{{4, 7}}},
+		{name: "ignore case", text: "Foo foo", pattern: "foo", opts: Options{IgnoreCase: true}, want: []Match{{0, 3}, {4, 7}}},
+		{name: "metacharacters", text: "a.b axb", pattern: "a.b", want: []Match{{0, 3}}},
+		{name: "graphemes", text: "👋 hi 👋 hi", pattern: "hi", want: []Match{{2, 4}, {7, 9}}},
+		{name: "whole word", text: "foo foobar foo", pattern: "foo", opts: Options{WholeWord: true}, want: []Match{{0, 3}, {11, 14}}},
+		{name: "no match", text: "foo", pattern: "bar", want: nil},
+		{name: "regex", text: "a.b axb", pattern: "a.b", opts: Options{Regex: true}, want: []Match{{0, 3}, {4, 7}}},
+		{name: "regex lines", text: "foo\nbar foo", pattern: "^foo|bar$", opts: Options{Regex: true}, want: []Match{{0, 3}}},
+		{name: "regex ignore case", text: "Foo foo", pattern: "f.o", opts: Options{Regex: true, IgnoreCase: true}, want: []Match{{0, 3}, {4, 7}}},
+		{name: "regex whole word", text: "foo foobar", pattern: "fo+", opts: Options{Regex: true, WholeWord: true}, want: []Match{{0, 3}}},
+		{name: "regex empty matches skipped", text: "ab", pattern: "x*", opts: Options{Regex: true}, want: nil},
+	}
+
+	for _, tt := range tests {
+		t.Run(tt.name, func(t *testing.T) {
+			got, err := Find(tt.text, tt.pattern, tt.opts)
+			if err != nil {
+				t.Fatalf("Find() error = %v", err)
+			}
+			if !reflect.DeepEqual(got, tt.want) {
+				t.Errorf("Find(%q, %q) = %v, want %v", tt.text, tt.pattern, got, tt.want)
+			}
+		})
+	}
+}
+
+func TestNext(t *testing.T) {
+	matches := []Match{{2, 4}, {10, 12}}
+
+	tests := []struct {
+		name        string
+		pos         int
+		forward     bool
+		wrap        bool
+		wantIndex   int
+		wantWrapped bool
+		wantOk      bool
+	}{
+		{name: "forward", pos: 0, forward: true, wantIndex: 0, wantOk: true},
+		{name: "forward from match", pos: 2, forward: true, wantIndex: 1, wantOk: true},
+		{name: "forward wraps", pos: 10, forward: true, wrap: true, wantIndex: 0, wantWrapped: true, wantOk: true},
+		{name: "forward hits bottom", pos: 10, forward: true, wantOk: false},
+		{name: "backward", pos: 11, forward: false, wantIndex: 1, wantOk: true},
+		{name: "backward wraps", pos: 2, forward: false, wrap: true, wantIndex: 1, wantWrapped: true, wantOk: true},
+		{name: "backward hits top", pos: 2, forward: false, wantOk: false},
+	}
+
+	for _, tt := range tests {
+		t.Run(tt.name, func(t *testing.T) {
+			index, wrapped, ok := Next(matches, tt.pos, tt.forward, tt.wrap)
+			if ok != tt.wantOk || (ok && (index != tt.wantIndex || wrapped != tt.wantWrapped)) {
+				t.Errorf("Next(%d) = (%d, %v, %v), want (%d, %v, %v)",
+					tt.pos, index, wrapped, ok, tt.wantIndex, tt.wantWrapped, tt.wantOk)
+			}
+		})
+	}
+}
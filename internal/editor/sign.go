@@ -0,0 +1,106 @@
+package editor
+
+import "github.com/lg2m/athena/internal/lsp"
+
+// SignKind is what a sign marks, which decides how it is drawn; of the signs on a line, the one
+// of the highest kind is shown.
+type SignKind int
+
+const (
+	SignMark     SignKind = iota // a mark set with m, drawn as its name
+	SignAdded                    // the line was added since the last commit
+	SignModified                 // the line was changed since the last commit
+	SignRemoved                  // lines were removed below it since the last commit, or above the first line
+	SignHint
+	SignInfo
+	SignWarning
+	SignError
+)
+
+// Sign marks a line of a buffer in the gutter.
+type Sign struct {
+	Line int
+	Kind SignKind
+	Text rune // drawn in the gutter, 0 for the theme's sign of its kind
+}
+
+// SetSigns replaces the signs group placed on the file at path, which need not be open, and wakes
+// the UI to show them; no signs remove the group. It may be called from any goroutine.
+//
+//	Groups keep the signs of different sources apart, so each can update its own.
+func (e *Editor) SetSigns(path, group string, signs []Sign) {
+	e.mu.Lock()
+	if e.signs == nil {
+		e.signs = make(map[string]map[string][]Sign)
+	}
+	if len(signs) == 0 {
+		delete(e.signs[path], group)
+	} else {
+		if e.signs[path] == nil {
+			e.signs[path] = make(map[string][]Sign)
+		}
+		e.signs[path][group] = signs
+	}
+	wakeup := e.wakeup
+	e.mu.Unlock()
+
+	if wakeup != nil {
+		wakeup()
+	}
+}
+
+// Signs returns the signs of the current buffer: those placed with SetSigns and those of its
+// diagnostics, changes since the last commit and marks.
+func (e *Editor) Signs() []Sign {
+	diagnostics, _ := e.Diagnostics()
+	hunks, _ := e.Hunks()
+
+	e.mu.RLock()
+	defer e.mu.RUnlock()
+
+	if e.current == nil {
+		return nil
+	}
+
+	var signs []Sign
+	for _, group := range e.signs[e.current.FilePath()] {
+		signs = append(signs, group...)
+	}
+	for _, d := range diagnostics {
+		signs = append(signs, Sign{Line: d.Line, Kind: diagnosticSignKind(d.Severity)})
+	}
+	for _, h := range hunks {
+		if h.Count == 0 {
+			signs = append(signs, Sign{Line: max(h.Start-1, 0), Kind: SignRemoved})
+			continue
+		}
+		kind := SignAdded
+		if h.Removed > 0 {
+			kind = SignModified
+		}
+		for line := h.Start; line < h.Start+h.Count; line++ {
+			signs = append(signs, Sign{Line: line, Kind: kind})
+		}
+	}
+	for name := 'a'; name <= 'z'; name++ {
+		if pos, err := e.current.Mark(name); err == nil {
+			line, _, _ := e.current.PositionToLineCol(pos)
+			signs = append(signs, Sign{Line: line, Kind: SignMark, Text: name})
+		}
+	}
+	return signs
+}
+
+// diagnosticSignKind returns the kind of sign marking a diagnostic of severity.
+func diagnosticSignKind(severity lsp.DiagnosticSeverity) SignKind {
+	switch severity {
+	case lsp.SeverityWarning:
+		return SignWarning
+	case lsp.SeverityInformation:
+		return SignInfo
+	case lsp.SeverityHint:
+		return SignHint
+	default:
+		return SignError
+	}
+}
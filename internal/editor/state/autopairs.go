@@ -0,0 +1,58 @@
+package state
+
+// AutoPairs are the brackets and quotes inserted as pairs by typing the opening one, so typing the
+// closing one can step over it and a backspace between them can delete both.
+//
+//	Like marks, they are kept on the same text as it is edited; a pair is forgotten once either of
+//	its delimiters is deleted.
+type AutoPairs struct {
+	pairs []Pair
+}
+
+// Add remembers the pair opened at open and closed at close, one grapheme each.
+func (a *AutoPairs) Add(open, close int) {
+	a.pairs = append(a.pairs, Pair{Open: Span{Start: open, End: open + 1}, Close: Span{Start: close, End: close + 1}})
+}
+
+// ClosedAt returns the pair whose closing delimiter is at pos.
+func (a *AutoPairs) ClosedAt(pos int) (Pair, bool) {
+	for _, p := range a.pairs {
+		if p.Close.Start == pos {
+			return p, true
+		}
+	}
+	return Pair{}, false
+}
+
+// Forget drops the pair p.
+func (a *AutoPairs) Forget(p Pair) {
+	for i, q := range a.pairs {
+		if q == p {
+			a.pairs = append(a.pairs[:i], a.pairs[i+1:]...)
+			return
+		}
+	}
+}
+
+// Clear drops all pairs, as leaving insert mode does.
+func (a *AutoPairs) Clear() {
+	a.pairs = nil
+}
+
+// Map keeps the pairs on the same text after changes, forgetting those with a delimiter in removed
+// text.
+func (a *AutoPairs) Map(changes ChangeSet) {
+	kept := a.pairs[:0]
+	for _, p := range a.pairs {
+		open, ok := changes.MapSpan(p.Open)
+		if !ok {
+			continue
+		}
+		close, ok := changes.MapSpan(p.Close)
+		if !ok {
+			continue
+		}
+		kept = append(kept, Pair{Open: open, Close: close})
+	}
+	a.pairs = kept
+}
@@ -0,0 +1,58 @@
+package state
+
+import "testing"
+
+func TestAutoPairsMap(t *testing.T) {
+	// "f(x)" with the parentheses typed as a pair
+	pair := Pair{Open: Span{1, 2}, Close: Span{3, 4}}
+
+	tests := []struct {
+		name                     string
+		start, removed, inserted int
+		want                     Pair
+		wantOk                   bool
+	}{
+		{name: "insert before", start: 0, inserted: 2, want: Pair{Open: Span{3, 4}, Close: Span{5, 6}}, wantOk: true},
+		{name: "insert inside", start: 2, inserted: 3, want: Pair{Open: Span{1, 2}, Close: Span{6, 7}}, wantOk: true},
+		{name: "insert at closer", start: 3, inserted: 1, want: Pair{Open: Span{1, 2}, Close: Span{4, 5}}, wantOk: true},
+		{name: "insert after", start: 4, inserted: 1, want: pair, wantOk: true},
+		{name: "delete inside", start: 2, removed: 1, want: Pair{Open: Span{1, 2}, Close: Span{2, 3}}, wantOk: true},
+		{name: "delete opener", start: 1, removed: 1},
+		{name: "delete closer", start: 3, removed: 1},
+	}
+
+	for _, tt := range tests {
+		t.Run(tt.name, func(t *testing.T) {
+			var a AutoPairs
+			a.Add(1, 3)
+			a.Map(ChangeSet{{Start: tt.start, Removed: tt.removed, Inserted: tt.inserted}})
+			got, ok := a.ClosedAt(tt.want.Close.Start)
+			if ok != tt.wantOk || ok && got != tt.want {
+				t.Errorf("Map(%d, %d, %d) left %v, %v, want %v, %v", tt.start, tt.removed, tt.inserted, got, ok, tt.want, tt.wantOk)
+			}
+		})
+	}
+}
+
+func TestAutoPairsForget(t *testing.T) {
+	var a AutoPairs
+	a.Add(0, 1)
+	a.Add(4, 5)
+
+	p, ok := a.ClosedAt(5)
+	if !ok {
+		t.Fatal("ClosedAt(5) found nothing")
+	}
+	a.Forget(p)
+	if _, ok := a.ClosedAt(5); ok {
+		t.Errorf("ClosedAt(5) found a forgotten pair")
+	}
+	if _, ok := a.ClosedAt(1); !ok {
+		t.Errorf("ClosedAt(1) lost the other pair")
+	}
+
+	a.Clear()
+	if _, ok := a.ClosedAt(1); ok {
+		t.Errorf("ClosedAt(1) found a pair after Clear")
+	}
+}
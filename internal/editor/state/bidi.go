@@ -0,0 +1,79 @@
+package state
+
+import "unicode"
+
+// Cell is where a rune of a line is drawn: its column within its display row, width and row.
+type Cell struct {
+	Col   int
+	Width int
+	Row   int
+}
+
+// ReorderRTL moves the cells of right-to-left runs so they display in visual order.
+//
+//	Lines are treated as left-to-right paragraphs: each run of Hebrew or Arabic text, including
+//	the spaces, punctuation and numbers between and after its letters, is mirrored within its
+//	display row. Numbers keep reading left to right and combining marks stay with their base
+//	character. Runs never continue across rows.
+func ReorderRTL(runes []rune, cells []Cell) {
+	for i := 0; i < len(runes); {
+		if !isRTL(runes[i]) {
+			i++
+			continue
+		}
+
+		last := i
+		for k := i + 1; k < len(runes) && cells[k].Row == cells[i].Row && !isLTR(runes[k]); k++ {
+			// numbers next to right-to-left text join its run
+			if isRTL(runes[k]) || unicode.IsDigit(runes[k]) || (isMark(runes[k]) && last == k-1) {
+				last = k
+			}
+		}
+		mirror(runes, cells, i, last+1)
+		i = last + 1
+	}
+}
+
+// mirror reverses the order of the units in runes[start:end], where a unit is a character
+// with its combining marks or a whole number.
+func mirror(runes []rune, cells []Cell, start, end int) {
+	left := cells[start].Col
+	right := cells[end-1].Col + cells[end-1].Width
+
+	for u := start; u < end; {
+		next := u + 1
+		if unicode.IsDigit(runes[u]) {
+			for next < end && unicode.IsDigit(runes[next]) {
+				next++
+			}
+		}
+		for next < end && isMark(runes[next]) {
+			next++
+		}
+
+		// the unit spans [from, to) and moves to the mirrored position
+		from, to := cells[u].Col, cells[next-1].Col+cells[next-1].Width
+		shift := left + right - to - from
+		for k := u; k < next; k++ {
+			cells[k].Col += shift
+		}
+		u = next
+	}
+}
+
+// isRTL reports whether r is a letter of a right-to-left script.
+func isRTL(r rune) bool {
+	if unicode.IsDigit(r) || isMark(r) {
+		return false
+	}
+	return unicode.In(r, unicode.Hebrew, unicode.Arabic, unicode.Syriac, unicode.Thaana)
+}
+
+// isLTR reports whether r is a letter that ends a right-to-left run.
+func isLTR(r rune) bool {
+	return unicode.IsLetter(r) && !isRTL(r)
+}
+
+func isMark(r rune) bool {
+	return unicode.In(r, unicode.Mn, unicode.Me)
+}
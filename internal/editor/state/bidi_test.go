@@ -0,0 +1,51 @@
+package state
+
+import (
+	"reflect"
+	"testing"
+)
+
+func TestReorderRTL(t *testing.T) {
+	tests := []struct {
+		name  string
+		line  string
+		width int // wrap width, 0 for a single row
+		want  []int
+	}{
+		{name: "left to right", line: "abc def", want: []int{0, 1, 2, 3, 4, 5, 6}},
+		{name: "hebrew word", line: "אבג", want: []int{2, 1, 0}},
+		{name: "embedded run", line: "ab אבג cd", want: []int{0, 1, 2, 5, 4, 3, 6, 7, 8}},
+		{name: "spaces inside run", line: "א ב", want: []int{2, 1, 0}},
+		{name: "trailing space stays", line: "אב x", want: []int{1, 0, 2, 3}},
+		{name: "number keeps order", line: "אב 12", want: []int{4, 3, 2, 0, 1}},
+		{name: "combining mark", line: "بَت", want: []int{1, 2, 0}},
+		{name: "mirrored per row", line: "אבגד", width: 2, want: []int{1, 0, 1, 0}},
+	}
+
+	for _, tt := range tests {
+		t.Run(tt.name, func(t *testing.T) {
+			runes := []rune(tt.line)
+			cells := make([]Cell, len(runes))
+			vx, rowStart, row := 0, 0, 0
+			for i, r := range runes {
+				w := GraphemeWidth(string(r), vx, 4)
+				if tt.width > 0 && vx > rowStart && vx-rowStart+w > tt.width {
+					row++
+					rowStart = vx
+				}
+				cells[i] = Cell{Col: vx - rowStart, Width: w, Row: row}
+				vx += w
+			}
+
+			ReorderRTL(runes, cells)
+
+			got := make([]int, len(cells))
+			for i, c := range cells {
+				got[i] = c.Col
+			}
+			if !reflect.DeepEqual(got, tt.want) {
+				t.Errorf("ReorderRTL(%q) columns = %v, want %v", tt.line, got, tt.want)
+			}
+		})
+	}
+}
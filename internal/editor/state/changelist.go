@@ -0,0 +1,82 @@
+package state
+
+import "errors"
+
+var (
+	ErrChangeListEmpty = errors.New("change list is empty")
+	ErrChangeListStart = errors.New("at start of change list")
+	ErrChangeListEnd   = errors.New("at end of change list")
+)
+
+// MaxChanges is the number of edit positions a change list remembers.
+const MaxChanges = 100
+
+// ChangeList remembers the positions of recent edits in a buffer, oldest first, for g; and g,.
+//
+//	It is separate from the jumplist: moving around never adds to it, only editing does.
+type ChangeList struct {
+	positions []int
+	index     int // entry the last jump landed on; len(positions) when not navigating
+}
+
+// Record adds pos as the most recent change, or moves the most recent one there when merge is set,
+// so a run of typing on one line is remembered once.
+func (c *ChangeList) Record(pos int, merge bool) {
+	if merge && len(c.positions) > 0 {
+		c.positions[len(c.positions)-1] = pos
+	} else {
+		c.positions = append(c.positions, pos)
+		if len(c.positions) > MaxChanges {
+			c.positions = c.positions[1:]
+		}
+	}
+	c.index = len(c.positions)
+}
+
+// Last returns the position of the most recent change.
+func (c *ChangeList) Last() (int, bool) {
+	if len(c.positions) == 0 {
+		return 0, false
+	}
+	return c.positions[len(c.positions)-1], true
+}
+
+// Map keeps the positions on the same text after changes.
+func (c *ChangeList) Map(changes ChangeSet) {
+	for i, pos := range c.positions {
+		c.positions[i] = changes.MapPos(pos, AssocAfter)
+	}
+}
+
+// Jump moves count entries through the list, towards older changes when count is negative, returning
+// the position landed on.
+//
+//	Like vim, a count past either end stops at the last entry, and only fails when already there.
+func (c *ChangeList) Jump(count int) (int, error) {
+	if len(c.positions) == 0 {
+		return 0, ErrChangeListEmpty
+	}
+
+	target := c.index + count
+	switch {
+	case target < 0:
+		if c.index == 0 {
+			return 0, ErrChangeListStart
+		}
+		target = 0
+	case target >= len(c.positions):
+		if c.index >= len(c.positions)-1 {
+			return 0, ErrChangeListEnd
+		}
+		target = len(c.positions) - 1
+	}
+
+	c.index = target
+	return c.positions[target], nil
+}
+
+// Clear forgets all changes, e.g. when the buffer is reloaded from disk.
+func (c *ChangeList) Clear() {
+	c.positions = nil
+	c.index = 0
+}
@@ -0,0 +1,101 @@
+package state
+
+import (
+	"errors"
+	"reflect"
+	"testing"
+)
+
+func TestChangeListJump(t *testing.T) {
+	tests := []struct {
+		name    string
+		changes []int
+		jumps   []int
+		want    int
+		wantErr error
+	}{
+		{name: "empty", jumps: []int{-1}, wantErr: ErrChangeListEmpty},
+		{name: "most recent", changes: []int{3, 10, 20}, jumps: []int{-1}, want: 20},
+		{name: "older", changes: []int{3, 10, 20}, jumps: []int{-1, -1}, want: 10},
+		{name: "count", changes: []int{3, 10, 20}, jumps: []int{-2}, want: 10},
+		{name: "count past start", changes: []int{3, 10, 20}, jumps: []int{-9}, want: 3},
+		{name: "at start", changes: []int{3, 10, 20}, jumps: []int{-3, -1}, wantErr: ErrChangeListStart},
+		{name: "newer", changes: []int{3, 10, 20}, jumps: []int{-3, 1}, want: 10},
+		{name: "count past end", changes: []int{3, 10, 20}, jumps: []int{-3, 9}, want: 20},
+		{name: "at end", changes: []int{3, 10, 20}, jumps: []int{-1, 1}, wantErr: ErrChangeListEnd},
+		{name: "newer without jumping back", changes: []int{3}, jumps: []int{1}, wantErr: ErrChangeListEnd},
+	}
+
+	for _, tt := range tests {
+		t.Run(tt.name, func(t *testing.T) {
+			var c ChangeList
+			for _, pos := range tt.changes {
+				c.Record(pos, false)
+			}
+
+			var got int
+			var err error
+			for _, count := range tt.jumps {
+				got, err = c.Jump(count)
+			}
+			if !errors.Is(err, tt.wantErr) {
+				t.Fatalf("Jump() error = %v, want %v", err, tt.wantErr)
+			}
+			if err == nil && got != tt.want {
+				t.Errorf("Jump() = %d, want %d", got, tt.want)
+			}
+		})
+	}
+}
+
+func TestChangeListRecord(t *testing.T) {
+	var c ChangeList
+	c.Record(1, false)
+	c.Record(2, true)
+	c.Record(8, false)
+	if want := []int{2, 8}; !reflect.DeepEqual(c.positions, want) {
+		t.Errorf("positions = %v, want %v", c.positions, want)
+	}
+
+	// a new change ends navigation, so g; starts from it again
+	_, _ = c.Jump(-2)
+	c.Record(5, false)
+	if got, _ := c.Jump(-1); got != 5 {
+		t.Errorf("Jump(-1) after Record = %d, want 5", got)
+	}
+
+	for i := range MaxChanges + 10 {
+		c.Record(i, false)
+	}
+	if len(c.positions) != MaxChanges {
+		t.Errorf("len(positions) = %d, want %d", len(c.positions), MaxChanges)
+	}
+}
+
+func TestChangeListMap(t *testing.T) {
+	tests := []struct {
+		name                     string
+		start, removed, inserted int
+		want                     []int
+	}{
+		{name: "insert before", start: 0, inserted: 3, want: []int{8, 13, 23}},
+		{name: "insert at position", start: 10, inserted: 3, want: []int{5, 13, 23}},
+		{name: "insert after", start: 25, inserted: 3, want: []int{5, 10, 20}},
+		{name: "delete around", start: 8, removed: 4, want: []int{5, 8, 16}},
+		{name: "delete ending at position", start: 7, removed: 3, want: []int{5, 7, 17}},
+		{name: "replace", start: 9, removed: 2, inserted: 5, want: []int{5, 9, 23}},
+	}
+
+	for _, tt := range tests {
+		t.Run(tt.name, func(t *testing.T) {
+			var c ChangeList
+			for _, pos := range []int{5, 10, 20} {
+				c.Record(pos, false)
+			}
+			c.Map(ChangeSet{{Start: tt.start, Removed: tt.removed, Inserted: tt.inserted}})
+			if !reflect.DeepEqual(c.positions, tt.want) {
+				t.Errorf("Map(%d, %d, %d) = %v, want %v", tt.start, tt.removed, tt.inserted, c.positions, tt.want)
+			}
+		})
+	}
+}
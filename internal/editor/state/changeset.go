@@ -0,0 +1,80 @@
+package state
+
+// Assoc tells which side of text inserted right at a position the position ends up on.
+type Assoc uint8
+
+const (
+	AssocAfter  Assoc = iota // past the inserted text, like a cursor typing it
+	AssocBefore              // before the inserted text, like the end of a selection it is typed after
+)
+
+// Change replaces Removed graphemes at Start with Inserted ones.
+type Change struct {
+	Start    int
+	Removed  int
+	Inserted int
+}
+
+// MapPos returns where pos is after the change; see ChangeSet.MapPos.
+func (c Change) MapPos(pos int, assoc Assoc) int {
+	end := c.Start + c.Removed
+	switch {
+	case pos < c.Start:
+		return pos
+	case pos > end, pos == end && c.Removed > 0:
+		return pos + c.Inserted - c.Removed
+	case pos > c.Start:
+		return c.Start
+	case c.Removed == 0 && assoc == AssocAfter:
+		return pos + c.Inserted
+	}
+	return pos
+}
+
+// removes reports whether the change removes any of the text in s.
+func (c Change) removes(s Span) bool {
+	return c.Removed > 0 && c.Start < s.End && s.Start < c.Start+c.Removed
+}
+
+// ChangeSet describes an edit as the changes it made, in the order they were made, so anything
+// holding a position in the text before it, such as a mark, a selection or a remembered pair, can
+// be kept on the same text after it.
+//
+//	Each change is in positions of the text the changes before it left. Positions inside removed
+//	text move to where it was, and positions right at an insertion go by their Assoc.
+type ChangeSet []Change
+
+// MapPos returns where pos is after the changes.
+func (cs ChangeSet) MapPos(pos int, assoc Assoc) int {
+	for _, c := range cs {
+		pos = c.MapPos(pos, assoc)
+	}
+	return pos
+}
+
+// MapSpan returns where s is after the changes, or false once any of its text is removed.
+//
+//	Text inserted at either end stays outside of it.
+func (cs ChangeSet) MapSpan(s Span) (Span, bool) {
+	for _, c := range cs {
+		if c.removes(s) {
+			return Span{}, false
+		}
+		start := c.MapPos(s.Start, AssocAfter)
+		s = Span{Start: start, End: max(c.MapPos(s.End, AssocBefore), start)}
+	}
+	return s, true
+}
+
+// MapSelection returns where sel is after the changes: a cursor moves past text inserted at it,
+// and a selection does not take in text inserted at either of its ends.
+func (cs ChangeSet) MapSelection(sel Selection) Selection {
+	if sel.Empty() {
+		pos := cs.MapPos(sel.End, AssocAfter)
+		return Selection{Start: pos, End: pos}
+	}
+	if sel.Start < sel.End {
+		return Selection{Start: cs.MapPos(sel.Start, AssocAfter), End: cs.MapPos(sel.End, AssocBefore)}
+	}
+	return Selection{Start: cs.MapPos(sel.Start, AssocBefore), End: cs.MapPos(sel.End, AssocAfter)}
+}
@@ -0,0 +1,97 @@
+package state
+
+import "testing"
+
+func TestChangeSetMapPos(t *testing.T) {
+	// "one two" with "two" replaced by "three", then "one " deleted
+	changes := ChangeSet{{Start: 4, Removed: 3, Inserted: 5}, {Start: 0, Removed: 4}}
+
+	tests := []struct {
+		name  string
+		pos   int
+		assoc Assoc
+		want  int
+	}{
+		{name: "in deleted text", pos: 2, want: 0},
+		{name: "at deleted text", pos: 0, want: 0},
+		{name: "at replaced text", pos: 4, want: 0},
+		{name: "in replaced text", pos: 5, want: 0},
+		{name: "after replaced text", pos: 7, want: 5},
+		{name: "after replaced text before", pos: 7, assoc: AssocBefore, want: 5},
+	}
+	for _, tt := range tests {
+		t.Run(tt.name, func(t *testing.T) {
+			if got := changes.MapPos(tt.pos, tt.assoc); got != tt.want {
+				t.Errorf("MapPos(%d, %d) = %d, want %d", tt.pos, tt.assoc, got, tt.want)
+			}
+		})
+	}
+}
+
+func TestChangeMapPosInsert(t *testing.T) {
+	insert := Change{Start: 3, Inserted: 2}
+	tests := []struct {
+		pos   int
+		assoc Assoc
+		want  int
+	}{
+		{pos: 2, want: 2},
+		{pos: 3, assoc: AssocAfter, want: 5},
+		{pos: 3, assoc: AssocBefore, want: 3},
+		{pos: 4, want: 6},
+	}
+	for _, tt := range tests {
+		if got := insert.MapPos(tt.pos, tt.assoc); got != tt.want {
+			t.Errorf("MapPos(%d, %d) = %d, want %d", tt.pos, tt.assoc, got, tt.want)
+		}
+	}
+}
+
+func TestChangeSetMapSpan(t *testing.T) {
+	span := Span{Start: 2, End: 4}
+	tests := []struct {
+		name    string
+		changes ChangeSet
+		want    Span
+		wantOk  bool
+	}{
+		{name: "insert before", changes: ChangeSet{{Start: 0, Inserted: 1}}, want: Span{3, 5}, wantOk: true},
+		{name: "insert at start", changes: ChangeSet{{Start: 2, Inserted: 1}}, want: Span{3, 5}, wantOk: true},
+		{name: "insert inside", changes: ChangeSet{{Start: 3, Inserted: 1}}, want: Span{2, 5}, wantOk: true},
+		{name: "insert at end", changes: ChangeSet{{Start: 4, Inserted: 1}}, want: span, wantOk: true},
+		{name: "delete before", changes: ChangeSet{{Start: 0, Removed: 2}}, want: Span{0, 2}, wantOk: true},
+		{name: "delete after", changes: ChangeSet{{Start: 4, Removed: 2}}, want: span, wantOk: true},
+		{name: "delete inside", changes: ChangeSet{{Start: 3, Removed: 1}}},
+		{name: "later change", changes: ChangeSet{{Start: 0, Inserted: 1}, {Start: 3, Removed: 1}}},
+	}
+	for _, tt := range tests {
+		t.Run(tt.name, func(t *testing.T) {
+			got, ok := tt.changes.MapSpan(span)
+			if ok != tt.wantOk || ok && got != tt.want {
+				t.Errorf("MapSpan(%v) = %v, %v, want %v, %v", span, got, ok, tt.want, tt.wantOk)
+			}
+		})
+	}
+}
+
+func TestChangeSetMapSelection(t *testing.T) {
+	insert := ChangeSet{{Start: 2, Inserted: 3}}
+	tests := []struct {
+		name string
+		sel  Selection
+		want Selection
+	}{
+		{name: "cursor", sel: Selection{2, 2}, want: Selection{5, 5}},
+		{name: "selection starting there", sel: Selection{2, 4}, want: Selection{5, 7}},
+		{name: "selection ending there", sel: Selection{0, 2}, want: Selection{0, 2}},
+		{name: "backwards selection ending there", sel: Selection{2, 0}, want: Selection{2, 0}},
+		{name: "across", sel: Selection{1, 3}, want: Selection{1, 6}},
+	}
+	for _, tt := range tests {
+		t.Run(tt.name, func(t *testing.T) {
+			if got := insert.MapSelection(tt.sel); got != tt.want {
+				t.Errorf("MapSelection(%v) = %v, want %v", tt.sel, got, tt.want)
+			}
+		})
+	}
+}
@@ -0,0 +1,135 @@
+package state
+
+import (
+	"unicode/utf8"
+
+	"github.com/rivo/uniseg"
+)
+
+// DefaultTabWidth is the number of cells between tab stops when none is configured.
+const DefaultTabWidth = 4
+
+// GraphemeWidth returns the number of display cells a grapheme occupies when drawn at visual column vcol.
+//
+//	Tabs advance to the next tab stop and control characters take two cells, drawn like ^A;
+//	everything else uses its unicode width, but takes a cell at least so the cursor can sit on
+//	graphemes that draw nothing, like a zero width space.
+func GraphemeWidth(g string, vcol, tabWidth int) int {
+	switch {
+	case g == "\t":
+		if tabWidth <= 0 {
+			tabWidth = DefaultTabWidth
+		}
+		return tabWidth - vcol%tabWidth
+	case IsControl(g):
+		return 2
+	}
+	return max(uniseg.StringWidth(g), 1)
+}
+
+// IsControl reports whether g, a grapheme, is a control character other than tab.
+func IsControl(g string) bool {
+	r, size := utf8.DecodeRuneInString(g)
+	return size == len(g) && r != '\t' && (r < 0x20 || r == 0x7f)
+}
+
+// IsInvisible reports whether g, a grapheme, draws nothing by itself, like a zero width space or
+// a combining mark without a letter to sit on.
+func IsInvisible(g string) bool {
+	return g != "\t" && !IsControl(g) && uniseg.StringWidth(g) == 0
+}
+
+// VisualColumn converts a grapheme column within line into the display column it is drawn at.
+func VisualColumn(line string, col, tabWidth int) int {
+	gr := uniseg.NewGraphemes(line)
+	vcol := 0
+	for i := 0; i < col && gr.Next(); i++ {
+		vcol += GraphemeWidth(gr.Str(), vcol, tabWidth)
+	}
+	return vcol
+}
+
+// ColumnAtVisual converts a display column into the grapheme column covering it.
+//
+//	Columns past the end of the line are clamped to the line length.
+func ColumnAtVisual(line string, vcol, tabWidth int) int {
+	gr := uniseg.NewGraphemes(line)
+	col, curr := 0, 0
+	for gr.Next() {
+		width := GraphemeWidth(gr.Str(), curr, tabWidth)
+		if curr+width > vcol {
+			return col
+		}
+		curr += width
+		col++
+	}
+	return col
+}
+
+// RuneWidths returns the display cells each rune of line takes, the line drawn from column 0:
+// each grapheme's width goes to its first rune and the rest take none, so a cluster drawn rune by
+// rune lines up with VisualColumn.
+func RuneWidths(line string, tabWidth int) []int {
+	widths := make([]int, 0, len(line))
+	gr := uniseg.NewGraphemes(line)
+	vcol := 0
+	for gr.Next() {
+		w := GraphemeWidth(gr.Str(), vcol, tabWidth)
+		widths = append(widths, w)
+		for range len(gr.Runes()) - 1 {
+			widths = append(widths, 0)
+		}
+		vcol += w
+	}
+	return widths
+}
+
+// WrapLine returns the grapheme column each display row of line starts at when soft wrapped to width cells.
+//
+//	A width of zero or less disables wrapping, yielding a single row. Rows break after the last
+//	blank that fits, so words go down whole; words longer than a row break where the row ends.
+func WrapLine(line string, width, tabWidth int) []int {
+	rows := []int{0}
+	if width <= 0 {
+		return rows
+	}
+
+	gr := uniseg.NewGraphemes(line)
+	col, vcol, rowStart := 0, 0, 0
+	breakCol, breakVcol := 0, 0 // just after the last blank
+	for gr.Next() {
+		g := gr.Str()
+		w := GraphemeWidth(g, vcol, tabWidth)
+		if vcol > rowStart && vcol-rowStart+w > width && breakCol > rows[len(rows)-1] && !IsBlank(g) {
+			rows = append(rows, breakCol)
+			rowStart = breakVcol
+		}
+		if vcol > rowStart && vcol-rowStart+w > width {
+			rows = append(rows, col)
+			rowStart = vcol
+		}
+		vcol += w
+		col++
+		if IsBlank(g) {
+			breakCol, breakVcol = col, vcol
+		}
+	}
+	return rows
+}
+
+// IsBlank reports whether g, a grapheme, is a space or tab, after which soft wrapped rows break.
+func IsBlank(g string) bool {
+	return g == " " || g == "\t"
+}
+
+// RowOf returns the index of the display row containing grapheme column col.
+func RowOf(rows []int, col int) int {
+	row := 0
+	for i, start := range rows {
+		if start > col {
+			break
+		}
+		row = i
+	}
+	return row
+}
@@ -0,0 +1,114 @@
+package state
+
+import (
+	"reflect"
+	"testing"
+)
+
+func TestVisualColumn(t *testing.T) {
+	tests := []struct {
+		name     string
+		line     string
+		col      int
+		tabWidth int
+		want     int
+	}{
+		{name: "ascii", line: "hello", col: 3, tabWidth: 4, want: 3},
+		{name: "leading tab", line: "\tfoo", col: 1, tabWidth: 4, want: 4},
+		{name: "tab after text", line: "ab\tc", col: 3, tabWidth: 4, want: 4},
+		{name: "two tabs", line: "\t\tx", col: 2, tabWidth: 8, want: 16},
+		{name: "wide graphemes", line: "日本語", col: 2, tabWidth: 4, want: 4},
+		{name: "emoji", line: "👋x", col: 1, tabWidth: 4, want: 2},
+		{name: "control character", line: "\x01x", col: 1, tabWidth: 4, want: 2},
+		{name: "zero width space", line: "\u200bx", col: 1, tabWidth: 4, want: 1},
+		{name: "past end", line: "ab", col: 5, tabWidth: 4, want: 2},
+	}
+
+	for _, tt := range tests {
+		t.Run(tt.name, func(t *testing.T) {
+			got := VisualColumn(tt.line, tt.col, tt.tabWidth)
+			if got != tt.want {
+				t.Errorf("VisualColumn(%q, %d) = %d, want %d", tt.line, tt.col, got, tt.want)
+			}
+		})
+	}
+}
+
+func TestColumnAtVisual(t *testing.T) {
+	tests := []struct {
+		name     string
+		line     string
+		vcol     int
+		tabWidth int
+		want     int
+	}{
+		{name: "ascii", line: "hello", vcol: 3, tabWidth: 4, want: 3},
+		{name: "inside tab", line: "\tfoo", vcol: 2, tabWidth: 4, want: 0},
+		{name: "after tab", line: "\tfoo", vcol: 5, tabWidth: 4, want: 2},
+		{name: "inside wide grapheme", line: "日本語", vcol: 3, tabWidth: 4, want: 1},
+		{name: "past end", line: "ab", vcol: 10, tabWidth: 4, want: 2},
+	}
+
+	for _, tt := range tests {
+		t.Run(tt.name, func(t *testing.T) {
+			got := ColumnAtVisual(tt.line, tt.vcol, tt.tabWidth)
+			if got != tt.want {
+				t.Errorf("ColumnAtVisual(%q, %d) = %d, want %d", tt.line, tt.vcol, got, tt.want)
+			}
+		})
+	}
+}
+
+func TestRuneWidths(t *testing.T) {
+	tests := []struct {
+		name string
+		line string
+		want []int
+	}{
+		{name: "ascii", line: "ab", want: []int{1, 1}},
+		{name: "tab", line: "a\tb", want: []int{1, 3, 1}},
+		{name: "wide", line: "日x", want: []int{2, 1}},
+		{name: "combining mark", line: "e\u0301x", want: []int{1, 0, 1}},
+		{name: "emoji sequence", line: "👩\u200d👧x", want: []int{2, 0, 0, 1}},
+		{name: "flag", line: "🇫🇷", want: []int{2, 0}},
+		{name: "control character", line: "a\x1b", want: []int{1, 2}},
+		{name: "lone combining mark", line: "\u0301a", want: []int{1, 1}},
+	}
+
+	for _, tt := range tests {
+		t.Run(tt.name, func(t *testing.T) {
+			got := RuneWidths(tt.line, 4)
+			if !reflect.DeepEqual(got, tt.want) {
+				t.Errorf("RuneWidths(%q) = %v, want %v", tt.line, got, tt.want)
+			}
+		})
+	}
+}
+
+func TestWrapLine(t *testing.T) {
+	tests := []struct {
+		name  string
+		line  string
+		width int
+		want  []int
+	}{
+		{name: "no wrap", line: "hello world", width: 0, want: []int{0}},
+		{name: "fits", line: "hello", width: 10, want: []int{0}},
+		{name: "exact rows", line: "abcdefgh", width: 4, want: []int{0, 4}},
+		{name: "partial row", line: "abcdefghij", width: 4, want: []int{0, 4, 8}},
+		{name: "wide grapheme moves down", line: "ab日本", width: 3, want: []int{0, 2, 3}},
+		{name: "words move down whole", line: "hello big world", width: 10, want: []int{0, 10}},
+		{name: "word fits after blank", line: "hello world", width: 8, want: []int{0, 6}},
+		{name: "long word breaks", line: "a abcdefghij", width: 4, want: []int{0, 2, 6, 10}},
+		{name: "blank at row end", line: "abcd efg", width: 4, want: []int{0, 4}},
+	}
+
+	for _, tt := range tests {
+		t.Run(tt.name, func(t *testing.T) {
+			got := WrapLine(tt.line, tt.width, 4)
+			if !reflect.DeepEqual(got, tt.want) {
+				t.Errorf("WrapLine(%q, %d) = %v, want %v", tt.line, tt.width, got, tt.want)
+			}
+		})
+	}
+}
@@ -0,0 +1,112 @@
+package state
+
+import "errors"
+
+var (
+	ErrOldestChange = errors.New("already at oldest change")
+	ErrNewestChange = errors.New("already at newest change")
+)
+
+// MaxUndoSteps is the number of steps an undo history remembers.
+const MaxUndoSteps = 1000
+
+// TextChange is a change with the text it removed and the text it inserted in its place, which is
+// what undoing and redoing it take.
+type TextChange struct {
+	Start    int
+	Removed  string
+	Inserted string
+}
+
+// inverse returns the change that puts back what c replaced.
+func (c TextChange) inverse() TextChange {
+	return TextChange{Start: c.Start, Removed: c.Inserted, Inserted: c.Removed}
+}
+
+// undoStep is changes undone together, in the order they were made.
+type undoStep struct {
+	changes []TextChange
+}
+
+// History is the undo history of a buffer: the steps of changes made to it, which undo takes back
+// and redo makes again, one step at a time.
+//
+//	Changes go into the open step until Commit ends it, so a command, or an insert with all that
+//	was typed in it, is undone as one. A change made after undoing drops the steps undone, as in
+//	vim without undo branches.
+type History struct {
+	steps []undoStep
+	done  int  // steps[:done] are made, the rest undone
+	open  bool // steps[done-1] still takes changes
+	saved int  // done when the buffer was last saved, -1 once there is no way back to it
+}
+
+// Record adds a change to the open step, starting one when none is.
+func (h *History) Record(c TextChange) {
+	switch {
+	case !h.open:
+		if h.saved > h.done {
+			h.saved = -1
+		}
+		h.steps = append(h.steps[:h.done], undoStep{})
+		h.done++
+		h.open = true
+		if len(h.steps) > MaxUndoSteps {
+			h.steps = h.steps[1:]
+			h.done--
+			h.saved = max(h.saved-1, -1)
+		}
+	case h.saved == h.done:
+		// saved partway through the open step, which goes on past it
+		h.saved = -1
+	}
+	step := &h.steps[h.done-1]
+	step.changes = append(step.changes, c)
+}
+
+// Commit ends the open step, so the next change starts another.
+func (h *History) Commit() {
+	h.open = false
+}
+
+// Undo takes back the last step made, returning the changes that do it, in the order to make them.
+func (h *History) Undo() ([]TextChange, error) {
+	h.open = false
+	if h.done == 0 {
+		return nil, ErrOldestChange
+	}
+	h.done--
+	step := h.steps[h.done]
+	changes := make([]TextChange, len(step.changes))
+	for i, c := range step.changes {
+		changes[len(changes)-1-i] = c.inverse()
+	}
+	return changes, nil
+}
+
+// Redo makes the last step undone again, returning its changes.
+func (h *History) Redo() ([]TextChange, error) {
+	h.open = false
+	if h.done == len(h.steps) {
+		return nil, ErrNewestChange
+	}
+	step := h.steps[h.done]
+	h.done++
+	return step.changes, nil
+}
+
+// MarkSaved records that the buffer was saved as it is now.
+func (h *History) MarkSaved() {
+	h.saved = h.done
+}
+
+// AtSaved reports whether the buffer is as it was last saved, as far as its steps go: undoing and
+// redoing back to where it was saved does.
+func (h *History) AtSaved() bool {
+	return h.saved == h.done
+}
+
+// Clear forgets every step, with the buffer as saved, e.g. when it is reloaded from disk.
+func (h *History) Clear() {
+	*h = History{}
+}
@@ -0,0 +1,73 @@
+package state
+
+import (
+	"errors"
+	"reflect"
+	"testing"
+)
+
+func TestHistory(t *testing.T) {
+	// one change per step, or two for the second step when grouped
+	first := TextChange{Start: 0, Removed: "a", Inserted: "b"}
+	second := TextChange{Start: 2, Inserted: "cd"}
+	third := TextChange{Start: 4, Removed: "cd"}
+
+	tests := []struct {
+		name        string
+		grouped     bool // second and third made in one step
+		ops         string
+		wantChanges []TextChange // of the last undo or redo
+		wantErr     error
+		wantSaved   bool
+	}{
+		{name: "undo", ops: "u", wantChanges: []TextChange{{Start: 4, Inserted: "cd"}}},
+		{name: "undo a step", grouped: true, ops: "u", wantChanges: []TextChange{{Start: 4, Inserted: "cd"}, {Start: 2, Removed: "cd"}}},
+		{name: "redo", ops: "uur", wantChanges: []TextChange{second}},
+		{name: "oldest", ops: "uuuu", wantErr: ErrOldestChange, wantSaved: true},
+		{name: "newest", ops: "r", wantErr: ErrNewestChange},
+		{name: "change drops the undone", ops: "uxr", wantErr: ErrNewestChange},
+		{name: "back to saved", ops: "suurr", wantSaved: true, wantChanges: []TextChange{third}},
+		{name: "saved then changed", ops: "sx", wantSaved: false},
+		{name: "saved state dropped", ops: "usux", wantSaved: false},
+	}
+
+	for _, tt := range tests {
+		t.Run(tt.name, func(t *testing.T) {
+			var h History
+			h.Record(first)
+			h.Commit()
+			h.Record(second)
+			if !tt.grouped {
+				h.Commit()
+			}
+			h.Record(third)
+			h.Commit()
+
+			var changes []TextChange
+			var err error
+			for _, op := range tt.ops {
+				switch op {
+				case 'u':
+					changes, err = h.Undo()
+				case 'r':
+					changes, err = h.Redo()
+				case 's':
+					h.MarkSaved()
+				case 'x':
+					h.Record(TextChange{Start: 0, Inserted: "x"})
+					h.Commit()
+				}
+			}
+
+			if !errors.Is(err, tt.wantErr) {
+				t.Fatalf("%s error = %v, want %v", tt.ops, err, tt.wantErr)
+			}
+			if tt.wantErr == nil && !reflect.DeepEqual(changes, tt.wantChanges) {
+				t.Errorf("%s = %v, want %v", tt.ops, changes, tt.wantChanges)
+			}
+			if got := h.AtSaved(); got != tt.wantSaved {
+				t.Errorf("%s AtSaved() = %v, want %v", tt.ops, got, tt.wantSaved)
+			}
+		})
+	}
+}
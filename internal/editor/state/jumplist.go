@@ -0,0 +1,69 @@
+package state
+
+import (
+	"errors"
+	"slices"
+)
+
+var (
+	ErrJumpListStart = errors.New("at start of jump list")
+	ErrJumpListEnd   = errors.New("at end of jump list")
+)
+
+// MaxJumps is the number of locations a jump list remembers.
+const MaxJumps = 100
+
+// Location is a position in the file at Path.
+type Location struct {
+	Path string
+	Pos  int
+}
+
+// JumpList remembers where the cursor was before jumps that may leave the screen or the buffer,
+// oldest first, for <c-o> and <c-i>.
+//
+//	Unlike the change list it spans buffers, and a location is only kept once: pushing it again moves
+//	it to the end.
+type JumpList struct {
+	locations []Location
+	index     int // entry the last jump landed on; len(locations) when not navigating
+}
+
+// Push adds loc as the most recent location and ends navigation.
+func (j *JumpList) Push(loc Location) {
+	j.locations = slices.DeleteFunc(j.locations, func(l Location) bool { return l == loc })
+	j.locations = append(j.locations, loc)
+	if len(j.locations) > MaxJumps {
+		j.locations = j.locations[1:]
+	}
+	j.index = len(j.locations)
+}
+
+// Jump moves count entries through the list, towards older locations when count is negative,
+// returning the location landed on; from is where the cursor is now.
+//
+//	Going back from outside the list first remembers from, so <c-i> can return to it. Like the
+//	change list, a count past either end stops at the last entry, and only fails when already there.
+func (j *JumpList) Jump(count int, from Location) (Location, error) {
+	if count < 0 && j.index == len(j.locations) {
+		j.Push(from)
+		j.index = len(j.locations) - 1
+	}
+
+	target := j.index + count
+	switch {
+	case target < 0:
+		if j.index == 0 {
+			return Location{}, ErrJumpListStart
+		}
+		target = 0
+	case target >= len(j.locations):
+		if j.index >= len(j.locations)-1 {
+			return Location{}, ErrJumpListEnd
+		}
+		target = len(j.locations) - 1
+	}
+
+	j.index = target
+	return j.locations[target], nil
+}
@@ -0,0 +1,50 @@
+package state
+
+import (
+	"errors"
+	"testing"
+)
+
+func TestJumpListJump(t *testing.T) {
+	a, b, c := Location{Path: "a", Pos: 1}, Location{Path: "a", Pos: 9}, Location{Path: "b", Pos: 4}
+	here := Location{Path: "c", Pos: 2}
+	tests := []struct {
+		name    string
+		pushed  []Location
+		from    Location
+		jumps   []int
+		want    Location
+		wantErr error
+	}{
+		{name: "empty", from: here, jumps: []int{-1}, wantErr: ErrJumpListStart},
+		{name: "back", pushed: []Location{a, b, c}, from: here, jumps: []int{-1}, want: c},
+		{name: "back twice", pushed: []Location{a, b, c}, from: here, jumps: []int{-1, -1}, want: b},
+		{name: "count past start", pushed: []Location{a, b, c}, from: here, jumps: []int{-9}, want: a},
+		{name: "at start", pushed: []Location{a, b, c}, from: here, jumps: []int{-9, -1}, wantErr: ErrJumpListStart},
+		{name: "back and forward", pushed: []Location{a, b, c}, from: here, jumps: []int{-1, 1}, want: here},
+		{name: "forward without going back", pushed: []Location{a, b, c}, from: here, jumps: []int{1}, wantErr: ErrJumpListEnd},
+		{name: "from already pushed", pushed: []Location{a, b, c}, from: b, jumps: []int{-1}, want: c},
+		{name: "pushed twice", pushed: []Location{a, b, a}, from: here, jumps: []int{-2}, want: b},
+	}
+
+	for _, tt := range tests {
+		t.Run(tt.name, func(t *testing.T) {
+			var j JumpList
+			for _, loc := range tt.pushed {
+				j.Push(loc)
+			}
+
+			var got Location
+			var err error
+			for _, count := range tt.jumps {
+				got, err = j.Jump(count, tt.from)
+			}
+			if !errors.Is(err, tt.wantErr) {
+				t.Fatalf("Jump() error = %v, want %v", err, tt.wantErr)
+			}
+			if err == nil && got != tt.want {
+				t.Errorf("Jump() = %+v, want %+v", got, tt.want)
+			}
+		})
+	}
+}
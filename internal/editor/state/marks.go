@@ -0,0 +1,64 @@
+package state
+
+import "errors"
+
+var (
+	ErrInvalidMark = errors.New("invalid mark")
+	ErrMarkNotSet  = errors.New("mark not set")
+)
+
+// PreviousContextMark names where the cursor was before the latest jump, set automatically and
+// reached with '' or ``.
+const PreviousContextMark = '\''
+
+// Marks are the named positions of a buffer: a-z set with m, and the previous context mark.
+//
+//	Like the change list, they are kept on the same text as it is edited.
+type Marks struct {
+	positions map[rune]int
+}
+
+// ValidMark reports whether name can be set and jumped to; ` is another name for the previous
+// context mark.
+func ValidMark(name rune) bool {
+	return name >= 'a' && name <= 'z' || name == PreviousContextMark || name == '`'
+}
+
+// Set puts the mark name at pos.
+func (m *Marks) Set(name rune, pos int) error {
+	if !ValidMark(name) {
+		return ErrInvalidMark
+	}
+	if m.positions == nil {
+		m.positions = make(map[rune]int)
+	}
+	m.positions[markKey(name)] = pos
+	return nil
+}
+
+// Get returns the position of the mark name.
+func (m *Marks) Get(name rune) (int, error) {
+	if !ValidMark(name) {
+		return 0, ErrInvalidMark
+	}
+	pos, ok := m.positions[markKey(name)]
+	if !ok {
+		return 0, ErrMarkNotSet
+	}
+	return pos, nil
+}
+
+// Map keeps the marks on the same text after changes.
+func (m *Marks) Map(changes ChangeSet) {
+	for name, pos := range m.positions {
+		m.positions[name] = changes.MapPos(pos, AssocAfter)
+	}
+}
+
+// markKey returns the name a mark is stored under.
+func markKey(name rune) rune {
+	if name == '`' {
+		return PreviousContextMark
+	}
+	return name
+}
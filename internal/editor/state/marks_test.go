@@ -0,0 +1,61 @@
+package state
+
+import (
+	"errors"
+	"testing"
+)
+
+func TestMarks(t *testing.T) {
+	tests := []struct {
+		name    string
+		set     rune
+		get     rune
+		want    int
+		wantErr error
+	}{
+		{name: "letter", set: 'a', get: 'a', want: 10},
+		{name: "not set", set: 'a', get: 'b', wantErr: ErrMarkNotSet},
+		{name: "previous context", set: '\'', get: '`', want: 10},
+		{name: "invalid", set: 'a', get: 'A', wantErr: ErrInvalidMark},
+	}
+
+	for _, tt := range tests {
+		t.Run(tt.name, func(t *testing.T) {
+			var m Marks
+			if err := m.Set(tt.set, 10); err != nil {
+				t.Fatalf("Set(%q) error = %v", tt.set, err)
+			}
+			got, err := m.Get(tt.get)
+			if !errors.Is(err, tt.wantErr) {
+				t.Fatalf("Get(%q) error = %v, want %v", tt.get, err, tt.wantErr)
+			}
+			if err == nil && got != tt.want {
+				t.Errorf("Get(%q) = %d, want %d", tt.get, got, tt.want)
+			}
+		})
+	}
+}
+
+func TestMarksMap(t *testing.T) {
+	tests := []struct {
+		name                     string
+		start, removed, inserted int
+		want                     int
+	}{
+		{name: "insert before", start: 0, inserted: 3, want: 13},
+		{name: "insert after", start: 12, inserted: 3, want: 10},
+		{name: "delete around", start: 8, removed: 4, want: 8},
+		{name: "delete before", start: 2, removed: 4, want: 6},
+	}
+
+	for _, tt := range tests {
+		t.Run(tt.name, func(t *testing.T) {
+			var m Marks
+			_ = m.Set('a', 10)
+			m.Map(ChangeSet{{Start: tt.start, Removed: tt.removed, Inserted: tt.inserted}})
+			if got, _ := m.Get('a'); got != tt.want {
+				t.Errorf("Map(%d, %d, %d) mark = %d, want %d", tt.start, tt.removed, tt.inserted, got, tt.want)
+			}
+		})
+	}
+}
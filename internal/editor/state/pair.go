@@ -0,0 +1,180 @@
+package state
+
+import "sort"
+
+// Span is a range of grapheme positions, End exclusive.
+type Span struct {
+	Start int
+	End   int
+}
+
+// Contains reports whether pos is within the span.
+func (s Span) Contains(pos int) bool {
+	return pos >= s.Start && pos < s.End
+}
+
+// Pair is a matching pair of delimiters, such as brackets or the keywords "#if" and "#endif".
+type Pair struct {
+	Open  Span
+	Close Span
+}
+
+// Partner returns the delimiter matching the one containing pos.
+func (p Pair) Partner(pos int) (Span, bool) {
+	switch {
+	case p.Open.Contains(pos):
+		return p.Close, true
+	case p.Close.Contains(pos):
+		return p.Open, true
+	}
+	return Span{}, false
+}
+
+// brackets maps each opening bracket to its closing one.
+var brackets = map[string]string{"(": ")", "[": "]", "{": "}"}
+
+// MatchBracket returns the pair formed by the bracket at pos and its partner, skipping nested pairs
+// of the same kind; graphemeAt returns the grapheme at a position below total.
+//
+//	Literals are the strings and comments, sorted and apart: brackets inside them are skipped,
+//	unless pos is inside one, when only the brackets of that literal count.
+func MatchBracket(graphemeAt func(pos int) string, total, pos int, literals []Span) (Pair, bool) {
+	if pos < 0 || pos >= total {
+		return Pair{}, false
+	}
+
+	// same is the bracket at pos, other the one closing it in the direction searched
+	same, other := graphemeAt(pos), ""
+	step := 1
+	if match, ok := brackets[same]; ok {
+		other = match
+	} else {
+		for o, c := range brackets {
+			if c == same {
+				other, step = o, -1
+				break
+			}
+		}
+		if other == "" {
+			return Pair{}, false
+		}
+	}
+
+	within, inLiteral := literalAt(literals, pos)
+	depth := 0
+	for i := pos + step; i >= 0 && i < total; i += step {
+		if inLiteral && !within.Contains(i) {
+			break
+		}
+		if !inLiteral {
+			if literal, ok := literalAt(literals, i); ok {
+				// jump to the edge of the literal; the loop steps past it
+				i = literal.Start
+				if step > 0 {
+					i = literal.End - 1
+				}
+				continue
+			}
+		}
+		switch graphemeAt(i) {
+		case same:
+			depth++
+		case other:
+			if depth == 0 {
+				from, to := Span{Start: pos, End: pos + 1}, Span{Start: i, End: i + 1}
+				if step < 0 {
+					from, to = to, from
+				}
+				return Pair{Open: from, Close: to}, true
+			}
+			depth--
+		}
+	}
+	return Pair{}, false
+}
+
+// EnclosingPair returns the innermost pair of open and close around pos, skipping the pairs nested
+// in it; a delimiter at pos counts as around it. graphemeAt returns the grapheme at a position
+// below total.
+func EnclosingPair(graphemeAt func(pos int) string, total, pos int, open, close string) (Pair, bool) {
+	if pos < 0 || pos >= total {
+		return Pair{}, false
+	}
+
+	start, depth := -1, 0
+	for i := pos; i >= 0 && start < 0; i-- {
+		switch graphemeAt(i) {
+		case close:
+			if i != pos {
+				depth++
+			}
+		case open:
+			if depth == 0 {
+				start = i
+			} else {
+				depth--
+			}
+		}
+	}
+	if start < 0 {
+		return Pair{}, false
+	}
+
+	depth = 0
+	for i := start + 1; i < total; i++ {
+		switch graphemeAt(i) {
+		case open:
+			depth++
+		case close:
+			if depth == 0 {
+				return Pair{Open: Span{Start: start, End: start + 1}, Close: Span{Start: i, End: i + 1}}, true
+			}
+			depth--
+		}
+	}
+	return Pair{}, false
+}
+
+// EnclosingQuotes returns the pair of quote around pos within graphemes, a line: quotes pair up
+// from the start of the line, and one at pos counts as around it.
+func EnclosingQuotes(graphemes []string, pos int, quote string) (Pair, bool) {
+	open := -1
+	for i, g := range graphemes {
+		if g != quote {
+			continue
+		}
+		if open < 0 {
+			open = i
+			continue
+		}
+		if open <= pos && pos <= i {
+			return Pair{Open: Span{Start: open, End: open + 1}, Close: Span{Start: i, End: i + 1}}, true
+		}
+		open = -1
+	}
+	return Pair{}, false
+}
+
+// literalAt returns the literal containing pos.
+func literalAt(literals []Span, pos int) (Span, bool) {
+	i := sort.Search(len(literals), func(i int) bool { return literals[i].End > pos })
+	if i < len(literals) && literals[i].Contains(pos) {
+		return literals[i], true
+	}
+	return Span{}, false
+}
+
+// PairAt returns the innermost of pairs with a delimiter containing pos.
+func PairAt(pairs []Pair, pos int) (Pair, bool) {
+	var found Pair
+	ok := false
+	for _, p := range pairs {
+		if _, hit := p.Partner(pos); !hit {
+			continue
+		}
+		if !ok || p.Open.Start > found.Open.Start {
+			found, ok = p, true
+		}
+	}
+	return found, ok
+}
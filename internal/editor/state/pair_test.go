@@ -0,0 +1,127 @@
+package state
+
+import "testing"
+
+func TestMatchBracket(t *testing.T) {
+	tests := []struct {
+		name     string
+		text     string
+		pos      int
+		literals []Span
+		want     Pair
+		wantOk   bool
+	}{
+		{name: "open paren", text: "f(a)", pos: 1, want: Pair{Open: Span{1, 2}, Close: Span{3, 4}}, wantOk: true},
+		{name: "close paren", text: "f(a)", pos: 3, want: Pair{Open: Span{1, 2}, Close: Span{3, 4}}, wantOk: true},
+		{name: "nested", text: "{[{}]}", pos: 0, want: Pair{Open: Span{0, 1}, Close: Span{5, 6}}, wantOk: true},
+		{name: "nested backward", text: "{{}}", pos: 2, want: Pair{Open: Span{1, 2}, Close: Span{2, 3}}, wantOk: true},
+		{name: "other kinds ignored", text: "(])", pos: 0, want: Pair{Open: Span{0, 1}, Close: Span{2, 3}}, wantOk: true},
+		{name: "unbalanced", text: "((a)", pos: 0},
+		{name: "not a bracket", text: "abc", pos: 1},
+		{name: "past end", text: "()", pos: 2},
+		{name: "string skipped", text: `f(")")`, pos: 1, literals: []Span{{2, 5}}, want: Pair{Open: Span{1, 2}, Close: Span{5, 6}}, wantOk: true},
+		{name: "string skipped backward", text: `f(")")`, pos: 5, literals: []Span{{2, 5}}, want: Pair{Open: Span{1, 2}, Close: Span{5, 6}}, wantOk: true},
+		{name: "comment skipped", text: "{ // }\n}", pos: 0, literals: []Span{{2, 6}}, want: Pair{Open: Span{0, 1}, Close: Span{7, 8}}, wantOk: true},
+		{name: "within string", text: `("()")`, pos: 2, literals: []Span{{1, 5}}, want: Pair{Open: Span{2, 3}, Close: Span{3, 4}}, wantOk: true},
+		{name: "not past string", text: `("(")`, pos: 2, literals: []Span{{1, 4}}},
+	}
+
+	for _, tt := range tests {
+		t.Run(tt.name, func(t *testing.T) {
+			graphemes := []rune(tt.text)
+			at := func(pos int) string { return string(graphemes[pos]) }
+			got, ok := MatchBracket(at, len(graphemes), tt.pos, tt.literals)
+			if ok != tt.wantOk || got != tt.want {
+				t.Errorf("MatchBracket(%q, %d) = %v, %v, want %v, %v", tt.text, tt.pos, got, ok, tt.want, tt.wantOk)
+			}
+		})
+	}
+}
+
+func TestEnclosingPair(t *testing.T) {
+	tests := []struct {
+		name   string
+		text   string
+		pos    int
+		want   Pair
+		wantOk bool
+	}{
+		{name: "inside", text: "f(a, b)", pos: 3, want: Pair{Open: Span{1, 2}, Close: Span{6, 7}}, wantOk: true},
+		{name: "on open", text: "f(a)", pos: 1, want: Pair{Open: Span{1, 2}, Close: Span{3, 4}}, wantOk: true},
+		{name: "on close", text: "f(a)", pos: 3, want: Pair{Open: Span{1, 2}, Close: Span{3, 4}}, wantOk: true},
+		{name: "skips nested before", text: "(a(b)c)", pos: 5, want: Pair{Open: Span{0, 1}, Close: Span{6, 7}}, wantOk: true},
+		{name: "skips nested after", text: "(a(b)c)", pos: 1, want: Pair{Open: Span{0, 1}, Close: Span{6, 7}}, wantOk: true},
+		{name: "innermost", text: "(a(b)c)", pos: 3, want: Pair{Open: Span{2, 3}, Close: Span{4, 5}}, wantOk: true},
+		{name: "outside", text: "(a) b", pos: 4},
+		{name: "unclosed", text: "(a", pos: 1},
+	}
+
+	for _, tt := range tests {
+		t.Run(tt.name, func(t *testing.T) {
+			graphemes := []rune(tt.text)
+			at := func(pos int) string { return string(graphemes[pos]) }
+			got, ok := EnclosingPair(at, len(graphemes), tt.pos, "(", ")")
+			if ok != tt.wantOk || got != tt.want {
+				t.Errorf("EnclosingPair(%q, %d) = %v, %v, want %v, %v", tt.text, tt.pos, got, ok, tt.want, tt.wantOk)
+			}
+		})
+	}
+}
+
+func TestEnclosingQuotes(t *testing.T) {
+	tests := []struct {
+		name   string
+		text   string
+		pos    int
+		want   Pair
+		wantOk bool
+	}{
+		{name: "inside", text: `a "b" c`, pos: 3, want: Pair{Open: Span{2, 3}, Close: Span{4, 5}}, wantOk: true},
+		{name: "on quote", text: `a "b" c`, pos: 4, want: Pair{Open: Span{2, 3}, Close: Span{4, 5}}, wantOk: true},
+		{name: "second string", text: `"a" "b"`, pos: 5, want: Pair{Open: Span{4, 5}, Close: Span{6, 7}}, wantOk: true},
+		{name: "between strings", text: `"a" "b"`, pos: 3},
+		{name: "unclosed", text: `a "b`, pos: 3},
+	}
+
+	for _, tt := range tests {
+		t.Run(tt.name, func(t *testing.T) {
+			var graphemes []string
+			for _, r := range tt.text {
+				graphemes = append(graphemes, string(r))
+			}
+			got, ok := EnclosingQuotes(graphemes, tt.pos, `"`)
+			if ok != tt.wantOk || got != tt.want {
+				t.Errorf("EnclosingQuotes(%q, %d) = %v, %v, want %v, %v", tt.text, tt.pos, got, ok, tt.want, tt.wantOk)
+			}
+		})
+	}
+}
+
+func TestPairAt(t *testing.T) {
+	// "#if A #if B #endif #endif"
+	outer := Pair{Open: Span{0, 3}, Close: Span{19, 25}}
+	inner := Pair{Open: Span{6, 9}, Close: Span{12, 18}}
+	pairs := []Pair{outer, inner}
+
+	tests := []struct {
+		name   string
+		pos    int
+		want   Pair
+		wantOk bool
+	}{
+		{name: "outer open", pos: 1, want: outer, wantOk: true},
+		{name: "inner close", pos: 12, want: inner, wantOk: true},
+		{name: "outer close", pos: 24, want: outer, wantOk: true},
+		{name: "between keywords", pos: 4},
+		{name: "end of keyword", pos: 3},
+	}
+
+	for _, tt := range tests {
+		t.Run(tt.name, func(t *testing.T) {
+			got, ok := PairAt(pairs, tt.pos)
+			if ok != tt.wantOk || got != tt.want {
+				t.Errorf("PairAt(%d) = %v, %v, want %v, %v", tt.pos, got, ok, tt.want, tt.wantOk)
+			}
+		})
+	}
+}
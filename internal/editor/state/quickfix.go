@@ -0,0 +1,74 @@
+package state
+
+import "errors"
+
+var (
+	ErrQuickfixEmpty = errors.New("no entries in the quickfix list")
+	ErrQuickfixStart = errors.New("at start of quickfix list")
+	ErrQuickfixEnd   = errors.New("at end of quickfix list")
+)
+
+// QuickfixEntry is a line of a file listed to be gone through, e.g. a match of ":grep".
+type QuickfixEntry struct {
+	Path string // absolute
+	Line int    // 0-based
+	Col  int    // grapheme column, 0-based
+	Text string // the line, to show what is there
+}
+
+// QuickfixList is the list of locations across files that ]q and [q go through, filled by the last
+// ":grep".
+type QuickfixList struct {
+	title   string // what filled the list, e.g. the ":grep" command
+	entries []QuickfixEntry
+	jumped  int // 1 + the entry the last jump landed on, 0 before the first
+}
+
+// Reset empties the list, for what title describes to fill it again.
+func (q *QuickfixList) Reset(title string) {
+	q.title, q.entries, q.jumped = title, nil, 0
+}
+
+// Add appends entries to the list.
+func (q *QuickfixList) Add(entries ...QuickfixEntry) {
+	q.entries = append(q.entries, entries...)
+}
+
+// Title returns what filled the list.
+func (q *QuickfixList) Title() string {
+	return q.title
+}
+
+// Len returns the number of entries.
+func (q *QuickfixList) Len() int {
+	return len(q.entries)
+}
+
+// Jump moves count entries through the list, backwards when count is negative, returning the
+// entry landed on and its index.
+//
+//	Before the first jump, going forward starts at the first entry. Like the jump list, a count past
+//	either end stops at the last entry, and only fails when already there.
+func (q *QuickfixList) Jump(count int) (QuickfixEntry, int, error) {
+	if len(q.entries) == 0 {
+		return QuickfixEntry{}, 0, ErrQuickfixEmpty
+	}
+
+	index := q.jumped - 1
+	target := index + count
+	switch {
+	case target < 0:
+		if index <= 0 {
+			return QuickfixEntry{}, 0, ErrQuickfixStart
+		}
+		target = 0
+	case target >= len(q.entries):
+		if index >= len(q.entries)-1 {
+			return QuickfixEntry{}, 0, ErrQuickfixEnd
+		}
+		target = len(q.entries) - 1
+	}
+
+	q.jumped = target + 1
+	return q.entries[target], target, nil
+}
@@ -0,0 +1,67 @@
+package state
+
+import (
+	"errors"
+	"testing"
+)
+
+func TestQuickfixListJump(t *testing.T) {
+	entries := []QuickfixEntry{{Path: "a", Line: 1}, {Path: "a", Line: 7}, {Path: "b", Line: 3}}
+	tests := []struct {
+		name      string
+		entries   []QuickfixEntry
+		jumps     []int
+		wantIndex int
+		wantErr   error
+	}{
+		{name: "empty", jumps: []int{1}, wantErr: ErrQuickfixEmpty},
+		{name: "first", entries: entries, jumps: []int{1}, wantIndex: 0},
+		{name: "next", entries: entries, jumps: []int{1, 1}, wantIndex: 1},
+		{name: "count", entries: entries, jumps: []int{3}, wantIndex: 2},
+		{name: "count past end", entries: entries, jumps: []int{9}, wantIndex: 2},
+		{name: "at end", entries: entries, jumps: []int{9, 1}, wantErr: ErrQuickfixEnd},
+		{name: "back before first", entries: entries, jumps: []int{-1}, wantErr: ErrQuickfixStart},
+		{name: "back", entries: entries, jumps: []int{3, -1}, wantIndex: 1},
+		{name: "count past start", entries: entries, jumps: []int{3, -9}, wantIndex: 0},
+		{name: "at start", entries: entries, jumps: []int{1, -1}, wantErr: ErrQuickfixStart},
+	}
+
+	for _, tt := range tests {
+		t.Run(tt.name, func(t *testing.T) {
+			var q QuickfixList
+			q.Reset(":grep x")
+			q.Add(tt.entries...)
+
+			var got QuickfixEntry
+			var index int
+			var err error
+			for _, count := range tt.jumps {
+				got, index, err = q.Jump(count)
+			}
+			if !errors.Is(err, tt.wantErr) {
+				t.Fatalf("Jump() error = %v, want %v", err, tt.wantErr)
+			}
+			if err == nil && (index != tt.wantIndex || got != tt.entries[tt.wantIndex]) {
+				t.Errorf("Jump() = %+v, %d, want %+v, %d", got, index, tt.entries[tt.wantIndex], tt.wantIndex)
+			}
+		})
+	}
+}
+
+func TestQuickfixListReset(t *testing.T) {
+	var q QuickfixList
+	q.Reset(":grep one")
+	q.Add(QuickfixEntry{Path: "a"}, QuickfixEntry{Path: "b"})
+	if _, _, err := q.Jump(1); err != nil {
+		t.Fatalf("Jump(1) error = %v", err)
+	}
+
+	q.Reset(":grep two")
+	q.Add(QuickfixEntry{Path: "c"})
+	if q.Title() != ":grep two" || q.Len() != 1 {
+		t.Errorf("after Reset, Title() = %q, Len() = %d, want %q, 1", q.Title(), q.Len(), ":grep two")
+	}
+	if got, _, err := q.Jump(1); err != nil || got.Path != "c" {
+		t.Errorf("Jump(1) after Reset = %+v, %v, want the first entry", got, err)
+	}
+}
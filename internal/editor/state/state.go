@@ -8,10 +8,23 @@ type EditorMode uint8
 const (
 	Normal EditorMode = iota
 	Insert
+	Command
 )
 
 // Selection represents the cursor and the text being selected.
+//
+//	Start is the anchor and End the cursor, so End may come before Start.
 type Selection struct {
 	Start int
 	End   int
 }
+
+// Range returns the bounds of the selection in ascending order.
+func (s Selection) Range() (int, int) {
+	return min(s.Start, s.End), max(s.Start, s.End)
+}
+
+// Empty reports whether nothing is selected, just a cursor.
+func (s Selection) Empty() bool {
+	return s.Start == s.End
+}
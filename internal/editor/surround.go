@@ -0,0 +1,163 @@
+package editor
+
+import (
+	"errors"
+	"unicode"
+
+	"github.com/lg2m/athena/internal/editor/buffer"
+	"github.com/lg2m/athena/internal/editor/state"
+	"github.com/rivo/uniseg"
+)
+
+var (
+	ErrNotDelimiter  = errors.New("not a delimiter")
+	ErrNoSurrounding = errors.New("no surrounding pair")
+)
+
+// surroundPair returns the delimiters r stands for: a bracket pair by either half, or by "b", "B",
+// "r" and "a" for (), {}, [] and <> like vim-surround, and other punctuation as itself on both
+// sides. spaced is set for an opening bracket, which ys pads with a space inside.
+func surroundPair(r rune) (open, close string, spaced bool, err error) {
+	brackets := []struct {
+		open, close rune
+		alias       rune
+	}{
+		{'(', ')', 'b'},
+		{'{', '}', 'B'},
+		{'[', ']', 'r'},
+		{'<', '>', 'a'},
+	}
+	for _, b := range brackets {
+		if r == b.open || r == b.close || r == b.alias {
+			return string(b.open), string(b.close), r == b.open, nil
+		}
+	}
+	if unicode.IsPunct(r) || unicode.IsSymbol(r) {
+		return string(r), string(r), false, nil
+	}
+	return "", "", false, ErrNotDelimiter
+}
+
+// Surround puts the delimiters r stands for around the text between start and end, like ys,
+// leaving the cursor on the opening one; see surroundPair.
+func (e *Editor) Surround(start, end int, r rune) error {
+	e.mu.Lock()
+	defer e.mu.Unlock()
+
+	if e.current == nil {
+		return ErrNoBuffer
+	}
+	open, close, spaced, err := surroundPair(r)
+	if err != nil {
+		return err
+	}
+	if spaced {
+		open, close = open+" ", " "+close
+	}
+
+	if _, err := e.current.Apply(
+		buffer.Edit{Start: start, End: start, Text: open},
+		buffer.Edit{Start: end, End: end, Text: close},
+	); err != nil {
+		return err
+	}
+	e.desiredColumn = -1
+	return e.current.MoveSelectionTo(start, false)
+}
+
+// DeleteSurround deletes the delimiters r stands for around the cursor, like ds, leaving the
+// cursor where the opening one was; an opening bracket takes a space inside each with it.
+//
+//	Brackets are looked for across lines, skipping the pairs nested inside; quotes and other
+//	punctuation only on the cursor line, pairing up from its start.
+func (e *Editor) DeleteSurround(r rune) error {
+	e.mu.Lock()
+	defer e.mu.Unlock()
+
+	return e.replaceSurround(r, "", "")
+}
+
+// ChangeSurround replaces the delimiters from stands for around the cursor with those to stands
+// for, like cs; opening brackets take and pad with a space inside as for DeleteSurround and
+// Surround.
+func (e *Editor) ChangeSurround(from, to rune) error {
+	e.mu.Lock()
+	defer e.mu.Unlock()
+
+	open, close, spaced, err := surroundPair(to)
+	if err != nil {
+		return err
+	}
+	if spaced {
+		open, close = open+" ", " "+close
+	}
+	return e.replaceSurround(from, open, close)
+}
+
+// replaceSurround replaces the delimiters r stands for around the cursor with open and close.
+func (e *Editor) replaceSurround(r rune, open, close string) error {
+	if e.current == nil {
+		return ErrNoBuffer
+	}
+	oldOpen, oldClose, spaced, err := surroundPair(r)
+	if err != nil {
+		return err
+	}
+	pair, err := e.surroundingPair(oldOpen, oldClose)
+	if err != nil {
+		return err
+	}
+	if spaced && pair.Close.Start-pair.Open.End >= 2 &&
+		e.graphemeAt(pair.Open.End) == " " && e.graphemeAt(pair.Close.Start-1) == " " {
+		pair.Open.End++
+		pair.Close.Start--
+	}
+
+	if _, err := e.current.Apply(
+		buffer.Edit{Start: pair.Open.Start, End: pair.Open.End, Text: open},
+		buffer.Edit{Start: pair.Close.Start, End: pair.Close.End, Text: close},
+	); err != nil {
+		return err
+	}
+	e.desiredColumn = -1
+	return e.current.MoveSelectionTo(pair.Open.Start, false)
+}
+
+// graphemeAt returns the grapheme at pos in the current buffer, "" past its end.
+func (e *Editor) graphemeAt(pos int) string {
+	g, _ := e.current.Substring(pos, pos+1)
+	return g
+}
+
+// surroundingPair finds the pair of open and close around the cursor.
+func (e *Editor) surroundingPair(open, close string) (state.Pair, error) {
+	pos := e.current.Selection().End
+	if open != close {
+		pair, ok := e.current.EnclosingPair(open, close, pos)
+		if !ok {
+			return state.Pair{}, ErrNoSurrounding
+		}
+		return pair, nil
+	}
+
+	_, lineStart, lineEnd, err := e.cursorLine()
+	if err != nil {
+		return state.Pair{}, err
+	}
+	text, err := e.current.Substring(lineStart, lineEnd)
+	if err != nil {
+		return state.Pair{}, err
+	}
+	var graphemes []string
+	for g := uniseg.NewGraphemes(text); g.Next(); {
+		graphemes = append(graphemes, g.Str())
+	}
+	pair, ok := state.EnclosingQuotes(graphemes, pos-lineStart, open)
+	if !ok {
+		return state.Pair{}, ErrNoSurrounding
+	}
+	shift := func(s state.Span) state.Span {
+		return state.Span{Start: s.Start + lineStart, End: s.End + lineStart}
+	}
+	return state.Pair{Open: shift(pair.Open), Close: shift(pair.Close)}, nil
+}
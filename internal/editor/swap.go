@@ -0,0 +1,180 @@
+package editor
+
+import (
+	"errors"
+	"io/fs"
+	"maps"
+	"os"
+	"path/filepath"
+	"strings"
+	"sync"
+
+	"github.com/lg2m/athena/internal/editor/buffer"
+)
+
+var ErrNoSwapFile = errors.New("no swap file for this buffer")
+
+// swapFiles are the swap files the editor wrote, and the version of the buffer each holds.
+//
+//	The lock also keeps writes of the same file from overlapping; it is never taken while holding
+//	the editor's.
+type swapFiles struct {
+	mu       sync.Mutex
+	dir      string            // where swap files go, empty when they are disabled
+	versions map[string]uint64 // by buffer path
+}
+
+// SwapFile returns the swap file of the file at path in dir: its path with separators turned
+// into "%", so files of the same name in different directories don't collide.
+func SwapFile(dir, path string) string {
+	return filepath.Join(dir, strings.ReplaceAll(path, string(filepath.Separator), "%")+".swp")
+}
+
+// SetSwapDir sets the directory swap files are written to, see WriteSwaps; empty disables them.
+func (e *Editor) SetSwapDir(dir string) {
+	e.swaps.mu.Lock()
+	defer e.swaps.mu.Unlock()
+
+	e.swaps.dir = dir
+}
+
+// WriteSwaps writes every buffer edited since its last swap file write to its swap file, and
+// removes the swap files of buffers that were saved or closed since. It may be called from any
+// goroutine.
+//
+//	Buffers are read through their own locks, so typing isn't held up by the writes.
+func (e *Editor) WriteSwaps() error {
+	e.mu.RLock()
+	buffers := maps.Clone(e.buffers)
+	e.mu.RUnlock()
+
+	e.swaps.mu.Lock()
+	defer e.swaps.mu.Unlock()
+
+	if e.swaps.dir == "" {
+		return nil
+	}
+	if e.swaps.versions == nil {
+		e.swaps.versions = make(map[string]uint64)
+	}
+
+	var errs []error
+	for path := range e.swaps.versions {
+		if b, open := buffers[path]; !open || !b.Modified() {
+			errs = append(errs, e.removeSwap(path))
+			delete(e.swaps.versions, path)
+		}
+	}
+	for path, b := range buffers {
+		if !b.Modified() {
+			continue
+		}
+		version := b.Version()
+		if written, ok := e.swaps.versions[path]; ok && written == version {
+			continue
+		}
+		if err := writeSwap(e.swaps.dir, path, b); err != nil {
+			errs = append(errs, err)
+			continue
+		}
+		e.swaps.versions[path] = version
+	}
+	return errors.Join(errs...)
+}
+
+// writeSwap writes the content of b, the buffer for path, to its swap file in dir.
+func writeSwap(dir, path string, b *buffer.Buffer) error {
+	if err := os.MkdirAll(dir, 0700); err != nil {
+		return err
+	}
+	// written aside and renamed, so a crash mid-write leaves the previous swap file whole
+	swap := SwapFile(dir, path)
+	if err := os.WriteFile(swap+".tmp", []byte(b.Text()), 0600); err != nil {
+		return err
+	}
+	return os.Rename(swap+".tmp", swap)
+}
+
+// RemoveSwaps removes every swap file the editor wrote, e.g. when exiting.
+func (e *Editor) RemoveSwaps() error {
+	e.swaps.mu.Lock()
+	defer e.swaps.mu.Unlock()
+
+	var errs []error
+	for path := range e.swaps.versions {
+		errs = append(errs, e.removeSwap(path))
+		delete(e.swaps.versions, path)
+	}
+	return errors.Join(errs...)
+}
+
+// removeSwap removes the swap file of the buffer for path, with the swap lock held.
+func (e *Editor) removeSwap(path string) error {
+	err := os.Remove(SwapFile(e.swaps.dir, path))
+	if errors.Is(err, fs.ErrNotExist) {
+		return nil
+	}
+	return err
+}
+
+// ForeignSwap returns the swap file of the current buffer when one exists that the editor didn't
+// write, left behind by a run that didn't exit cleanly, or by one still going.
+//
+//	A swap file holding the same text as the buffer has nothing to recover and is removed.
+func (e *Editor) ForeignSwap() (string, bool) {
+	e.mu.RLock()
+	b := e.current
+	e.mu.RUnlock()
+
+	e.swaps.mu.Lock()
+	defer e.swaps.mu.Unlock()
+
+	if b == nil || e.swaps.dir == "" {
+		return "", false
+	}
+	if _, written := e.swaps.versions[b.FilePath()]; written {
+		return "", false
+	}
+	swap := SwapFile(e.swaps.dir, b.FilePath())
+	content, err := os.ReadFile(swap)
+	if err != nil {
+		return "", false
+	}
+	if string(content) == b.Text() {
+		_ = os.Remove(swap)
+		return "", false
+	}
+	return swap, true
+}
+
+// RecoverSwap replaces the content of the current buffer with that of its swap file, leaving it
+// modified; the swap file is taken over by the next WriteSwaps.
+func (e *Editor) RecoverSwap() error {
+	e.swaps.mu.Lock()
+	dir := e.swaps.dir
+	e.swaps.mu.Unlock()
+
+	e.mu.Lock()
+	defer e.mu.Unlock()
+
+	if e.current == nil {
+		return ErrNoBuffer
+	}
+	if dir == "" {
+		return ErrNoSwapFile
+	}
+	content, err := os.ReadFile(SwapFile(dir, e.current.FilePath()))
+	if errors.Is(err, fs.ErrNotExist) {
+		return ErrNoSwapFile
+	}
+	if err != nil {
+		return err
+	}
+
+	pos := e.current.Selection().End
+	if _, err := e.current.Replace(0, e.current.TotalGraphemes(), string(content)); err != nil {
+		return err
+	}
+	e.desiredColumn = -1
+	return e.current.MoveSelectionTo(min(pos, e.current.TotalGraphemes()), false)
+}
@@ -0,0 +1,45 @@
+package treesitter
+
+import (
+	"strings"
+
+	"github.com/gdamore/tcell/v2"
+)
+
+// HighContrastStyles sticks to bright basic colors and bold, avoiding italic and dim text.
+//
+//	Captures without an entry fall back to their parent, e.g. "keyword.control" to "keyword".
+var HighContrastStyles = StyleMap{
+	"keyword":   tcell.StyleDefault.Foreground(tcell.ColorYellow).Bold(true),
+	"type":      tcell.StyleDefault.Foreground(tcell.ColorAqua),
+	"function":  tcell.StyleDefault.Foreground(tcell.ColorLime),
+	"operator":  tcell.StyleDefault.Foreground(tcell.ColorWhite),
+	"variable":  tcell.StyleDefault.Foreground(tcell.ColorWhite),
+	"constant":  tcell.StyleDefault.Foreground(tcell.ColorFuchsia),
+	"string":    tcell.StyleDefault.Foreground(tcell.ColorLime),
+	"comment":   tcell.StyleDefault.Foreground(tcell.ColorSilver),
+	"label":     tcell.StyleDefault.Foreground(tcell.ColorAqua),
+	"tag":       tcell.StyleDefault.Foreground(tcell.ColorYellow),
+	"attribute": tcell.StyleDefault.Foreground(tcell.ColorAqua),
+	"namespace": tcell.StyleDefault.Foreground(tcell.ColorAqua).Bold(true),
+
+	"error":   tcell.StyleDefault.Foreground(tcell.ColorRed).Bold(true),
+	"warning": tcell.StyleDefault.Foreground(tcell.ColorYellow).Bold(true),
+	"info":    tcell.StyleDefault.Foreground(tcell.ColorAqua),
+	"hint":    tcell.StyleDefault.Foreground(tcell.ColorWhite),
+}
+
+// Lookup returns the style for capture, trying its parent captures when it has no entry of its own.
+func (m StyleMap) Lookup(capture string) (tcell.Style, bool) {
+	for capture != "" {
+		if style, ok := m[capture]; ok {
+			return style, true
+		}
+		i := strings.LastIndex(capture, ".")
+		if i < 0 {
+			break
+		}
+		capture = capture[:i]
+	}
+	return tcell.StyleDefault, false
+}
@@ -24,6 +24,7 @@ var (
 	ColorFgGutter    = tcell.NewHexColor(0x3b4261)
 	ColorBg          = tcell.NewHexColor(0x1a1b26)
 	ColorBgSelection = tcell.NewHexColor(0x283457)
+	ColorBgHighlight = tcell.NewHexColor(0x292e42)
 )
 
 // Universal styles that work well across languages.
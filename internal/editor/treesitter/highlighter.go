@@ -9,9 +9,10 @@ import (
 
 // Highlight represents a syntax highlighting span.
 type Highlight struct {
-	Start Position
-	End   Position
-	Style tcell.Style
+	Start   Position
+	End     Position
+	Style   tcell.Style
+	Capture string // capture name the style was chosen for, e.g. "keyword.control"
 }
 
 // Position represents a position in the source code.
@@ -83,7 +84,8 @@ func (h *Highlighter) GetHighlights(code []byte) ([]Highlight, error) {
 						Row:    uint32(endPos.Row),
 						Column: uint32(endPos.Column),
 					},
-					Style: style,
+					Style:   style,
+					Capture: captureName,
 				}
 				highlights = append(highlights, highlight)
 			}
@@ -0,0 +1,24 @@
+package languages
+
+import (
+	sitter "github.com/tree-sitter/go-tree-sitter"
+	tree_sitter_c "github.com/tree-sitter/tree-sitter-c/bindings/go"
+)
+
+// CProvider implements the LanguageProvider interface for C.
+type CProvider struct{}
+
+// Language returns the Tree-sitter C language implementation.
+func (c CProvider) Language() *sitter.Language {
+	return sitter.NewLanguage(tree_sitter_c.Language())
+}
+
+// Name returns the name of the C language.
+func (c CProvider) Name() string {
+	return "c"
+}
+
+// Extensions returns the file extensions associated with C.
+func (c CProvider) Extensions() []string {
+	return []string{"c", "h"}
+}
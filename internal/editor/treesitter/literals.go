@@ -0,0 +1,57 @@
+package treesitter
+
+import (
+	"fmt"
+	"strings"
+
+	sitter "github.com/tree-sitter/go-tree-sitter"
+)
+
+// Literal is a string or comment in the code, found with the highlights query whether or not the
+// theme styles it; positions are rows and byte columns.
+type Literal struct {
+	Start Position
+	End   Position
+}
+
+// GetLiterals returns the strings and comments in code, those captured as "string" or "comment"
+// or a name under them, such as "string.special".
+func (h *Highlighter) GetLiterals(code []byte) ([]Literal, error) {
+	query := h.registry.queries[h.language.Name()][QueryHighlights]
+	if query == nil {
+		return nil, fmt.Errorf("hl: no highlights query available for %s", h.language.Name())
+	}
+
+	tree := h.parser.Parse(code, nil)
+	defer tree.Close()
+
+	qc := sitter.NewQueryCursor()
+	defer qc.Close()
+
+	matches := qc.Matches(query, tree.RootNode(), code)
+
+	var literals []Literal
+	for match := matches.Next(); match != nil; match = matches.Next() {
+		for _, capture := range match.Captures {
+			if !isLiteral(query.CaptureNames()[capture.Index]) {
+				continue
+			}
+			literals = append(literals, Literal{
+				Start: position(capture.Node.StartPosition()),
+				End:   position(capture.Node.EndPosition()),
+			})
+		}
+	}
+
+	return literals, nil
+}
+
+// isLiteral reports whether a capture name is "string" or "comment" or one under them.
+func isLiteral(capture string) bool {
+	for _, name := range []string{"string", "comment"} {
+		if capture == name || strings.HasPrefix(capture, name+".") {
+			return true
+		}
+	}
+	return false
+}
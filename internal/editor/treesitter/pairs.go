@@ -0,0 +1,56 @@
+package treesitter
+
+import sitter "github.com/tree-sitter/go-tree-sitter"
+
+// Pair is a matching pair of keywords found with a language's pairs query, such as "#if" and "#endif".
+//
+//	The query captures the keywords as @open and @close; positions are rows and byte columns.
+type Pair struct {
+	OpenStart  Position
+	OpenEnd    Position
+	CloseStart Position
+	CloseEnd   Position
+}
+
+// GetPairs returns the keyword pairs in code, or nil when the language has no pairs query.
+func (h *Highlighter) GetPairs(code []byte) ([]Pair, error) {
+	query := h.registry.queries[h.language.Name()][QueryPairs]
+	if query == nil {
+		return nil, nil
+	}
+
+	tree := h.parser.Parse(code, nil)
+	defer tree.Close()
+
+	qc := sitter.NewQueryCursor()
+	defer qc.Close()
+
+	matches := qc.Matches(query, tree.RootNode(), code)
+
+	var pairs []Pair
+	for match := matches.Next(); match != nil; match = matches.Next() {
+		var pair Pair
+		hasOpen, hasClose := false, false
+		for _, capture := range match.Captures {
+			start, end := position(capture.Node.StartPosition()), position(capture.Node.EndPosition())
+			switch query.CaptureNames()[capture.Index] {
+			case "open":
+				pair.OpenStart, pair.OpenEnd = start, end
+				hasOpen = true
+			case "close":
+				pair.CloseStart, pair.CloseEnd = start, end
+				hasClose = true
+			}
+		}
+		if hasOpen && hasClose {
+			pairs = append(pairs, pair)
+		}
+	}
+
+	return pairs, nil
+}
+
+// position converts a tree-sitter point.
+func position(p sitter.Point) Position {
+	return Position{Row: uint32(p.Row), Column: uint32(p.Column)}
+}
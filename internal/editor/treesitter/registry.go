@@ -26,6 +26,7 @@ const (
 	QueryInjections QueryType = "injections"
 	QueryLocals     QueryType = "locals"
 	QueryOutline    QueryType = "outline"
+	QueryPairs      QueryType = "pairs" // keywords % jumps between, captured as @open and @close
 )
 
 // LanguageProvider defines an interface for language support.
@@ -59,7 +60,7 @@ func (r *Registry) RegisterLanguage(provider LanguageProvider) error {
 	}
 
 	// Load queries for this language
-	queryTypes := []QueryType{QueryHighlights, QueryInjections, QueryLocals, QueryOutline}
+	queryTypes := []QueryType{QueryHighlights, QueryInjections, QueryLocals, QueryOutline, QueryPairs}
 	queryMap := make(map[QueryType]*sitter.Query)
 
 	for _, queryType := range queryTypes {
@@ -0,0 +1,9 @@
+; Keywords % jumps between
+
+(preproc_if
+  "#if" @open
+  "#endif" @close)
+
+(preproc_ifdef
+  ["#ifdef" "#ifndef"] @open
+  "#endif" @close)
@@ -0,0 +1,142 @@
+package editor
+
+import (
+	"errors"
+	"strings"
+
+	"github.com/lg2m/athena/internal/vcs"
+)
+
+var (
+	ErrNotTracked = errors.New("file isn't tracked by git")
+	ErrNoHunk     = errors.New("no more changes since the last commit")
+)
+
+// vcsState is what version control knows of a file, see SetVCS.
+type vcsState struct {
+	branch  string     // checked out in the file's repository, empty outside of one
+	base    string     // the file as last committed
+	tracked bool       // whether base was found, so the buffer can be compared to it
+	version uint64     // version of the buffer hunks were computed for
+	hunks   []vcs.Hunk // valid while the buffer is at version
+	diffed  bool       // whether hunks were computed at all
+}
+
+// SetVCS records the branch checked out for the file at path, which need not be open, and the
+// file as last committed, base, when tracked; it wakes the UI to show them and may be called from
+// any goroutine.
+func (e *Editor) SetVCS(path, branch, base string, tracked bool) {
+	e.mu.Lock()
+	if e.vcs == nil {
+		e.vcs = make(map[string]*vcsState)
+	}
+	e.vcs[path] = &vcsState{
+		branch:  branch,
+		base:    strings.ReplaceAll(base, "\r\n", "\n"), // buffers hold "\n" whatever the file uses
+		tracked: tracked,
+	}
+	wakeup := e.wakeup
+	e.mu.Unlock()
+
+	if wakeup != nil {
+		wakeup()
+	}
+}
+
+// Branch returns the branch checked out in the repository of the current buffer's file; false
+// when none is known.
+func (e *Editor) Branch() (string, bool) {
+	e.mu.RLock()
+	defer e.mu.RUnlock()
+
+	if e.current == nil {
+		return "", false
+	}
+	s := e.vcs[e.current.FilePath()]
+	if s == nil || s.branch == "" {
+		return "", false
+	}
+	return s.branch, true
+}
+
+// Hunks returns the lines of the current buffer changed since its file was last committed; false
+// when the file isn't tracked.
+//
+//	The diff is kept until the buffer is edited again, so asking on every frame costs nothing.
+func (e *Editor) Hunks() ([]vcs.Hunk, bool) {
+	e.mu.Lock()
+	defer e.mu.Unlock()
+
+	return e.hunks()
+}
+
+// hunks is Hunks with the lock held.
+func (e *Editor) hunks() ([]vcs.Hunk, bool) {
+	if e.current == nil {
+		return nil, false
+	}
+	s := e.vcs[e.current.FilePath()]
+	if s == nil || !s.tracked {
+		return nil, false
+	}
+	if version := e.current.Version(); !s.diffed || s.version != version {
+		s.hunks = vcs.Diff(s.base, e.current.Text())
+		s.version = version
+		s.diffed = true
+	}
+	return s.hunks, true
+}
+
+// JumpToHunk moves the cursor to the first line of the count-th hunk after the cursor line, or
+// before it when count is negative, remembering where it was in the jump list.
+func (e *Editor) JumpToHunk(count int) error {
+	e.mu.Lock()
+	defer e.mu.Unlock()
+
+	if e.current == nil {
+		return ErrNoBuffer
+	}
+	hunks, ok := e.hunks()
+	if !ok {
+		return ErrNotTracked
+	}
+	line, _, err := e.current.PositionToLineCol(e.current.Selection().End)
+	if err != nil {
+		return err
+	}
+
+	// lines only removed at the end of the buffer sit before a line that isn't there
+	last := e.current.LineCount() - 1
+	target := -1
+	if count > 0 {
+		for _, h := range hunks {
+			if start := min(h.Start, last); start > line {
+				if count--; count == 0 {
+					target = start
+					break
+				}
+			}
+		}
+	} else {
+		for i := len(hunks) - 1; i >= 0; i-- {
+			if start := min(hunks[i].Start, last); start < line {
+				if count++; count == 0 {
+					target = start
+					break
+				}
+			}
+		}
+	}
+	if target < 0 {
+		return ErrNoHunk
+	}
+
+	e.desiredColumn = -1
+	return e.jumpTo(func() error {
+		start, _, err := e.current.LineRange(target)
+		if err != nil {
+			return err
+		}
+		return e.current.MoveSelectionTo(start, false)
+	})
+}
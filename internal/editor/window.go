@@ -0,0 +1,78 @@
+package editor
+
+import (
+	"github.com/lg2m/athena/internal/editor/buffer"
+	"github.com/lg2m/athena/internal/editor/state"
+)
+
+// WindowState is what a window remembers of the buffer it shows while another window is current:
+// which buffer, and where its cursor is.
+type WindowState struct {
+	buf           *buffer.Buffer // kept rather than its path, which ":saveas" can change
+	selection     state.Selection
+	desiredColumn int
+	desiredInRow  bool
+}
+
+// SaveWindow returns the state of the current buffer, to be restored when its window is current again.
+func (e *Editor) SaveWindow() (WindowState, error) {
+	e.mu.RLock()
+	defer e.mu.RUnlock()
+
+	if e.current == nil {
+		return WindowState{}, ErrNoBuffer
+	}
+	return WindowState{
+		buf:           e.current,
+		selection:     e.current.Selection(),
+		desiredColumn: e.desiredColumn,
+		desiredInRow:  e.desiredInRow,
+	}, nil
+}
+
+// RestoreWindow makes the buffer of w current again with the cursor where w left it.
+//
+//	The selection is clamped to the buffer, which another window may have shortened meanwhile.
+//	A buffer closed since is not found.
+func (e *Editor) RestoreWindow(w WindowState) error {
+	e.mu.Lock()
+	defer e.mu.Unlock()
+
+	b := w.buf
+	if b == nil || e.buffers[b.FilePath()] != b {
+		return ErrBufferNotFound
+	}
+	e.current = b
+	e.desiredColumn, e.desiredInRow = w.desiredColumn, w.desiredInRow
+
+	total := b.TotalGraphemes()
+	start, end := min(w.selection.Start, total), min(w.selection.End, total)
+	if err := b.MoveSelectionTo(start, false); err != nil {
+		return err
+	}
+	return b.MoveSelectionTo(end, true)
+}
+
+// FileName returns the name of the file the window shows.
+func (w WindowState) FileName() string {
+	if w.buf == nil {
+		return ""
+	}
+	return w.buf.FileName()
+}
+
+// FilePath returns the path of the file the window shows.
+func (w WindowState) FilePath() string {
+	if w.buf == nil {
+		return ""
+	}
+	return w.buf.FilePath()
+}
+
+// Position returns the line and column of the window's cursor, both 0-based.
+func (w WindowState) Position() (int, int, error) {
+	if w.buf == nil {
+		return 0, 0, ErrNoBuffer
+	}
+	return w.buf.PositionToLineCol(min(w.selection.End, w.buf.TotalGraphemes()))
+}
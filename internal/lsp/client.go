@@ -0,0 +1,238 @@
+package lsp
+
+import (
+	"bytes"
+	"encoding/json"
+	"errors"
+	"io"
+	"os"
+	"os/exec"
+	"slices"
+	"strings"
+	"time"
+)
+
+// closeTimeout is how long a server gets to exit after being asked to before it is killed.
+const closeTimeout = 2 * time.Second
+
+// Client is a connection to a language server, which sends it documents as they change and
+// hears the diagnostics it publishes for them.
+//
+//	Documents are always sent whole; methods may be called from any goroutine, and those waiting
+//	for a response block until it arrives.
+type Client struct {
+	conn *Conn
+	cmd  *exec.Cmd // the server process, nil when connected to something else
+}
+
+// Start runs the server command in root, the workspace folder, and initializes it;
+// diagnostics is called with every set the server publishes, on the connection's reading goroutine.
+func Start(command []string, root string, diagnostics func(PublishDiagnosticsParams)) (*Client, error) {
+	if len(command) == 0 {
+		return nil, errors.New("no language server command")
+	}
+
+	cmd := exec.Command(command[0], command[1:]...)
+	cmd.Dir = root
+	stdin, err := cmd.StdinPipe()
+	if err != nil {
+		return nil, err
+	}
+	stdout, err := cmd.StdoutPipe()
+	if err != nil {
+		return nil, err
+	}
+	if err := cmd.Start(); err != nil {
+		return nil, err
+	}
+
+	c, err := initialize(stdout, stdin, root, diagnostics)
+	if err != nil {
+		_ = cmd.Process.Kill()
+		_ = cmd.Wait()
+		return nil, err
+	}
+	c.cmd = cmd
+	return c, nil
+}
+
+// initialize connects to a server reading from r and writing to w, and runs the initialize handshake.
+func initialize(r io.Reader, w io.Writer, root string, diagnostics func(PublishDiagnosticsParams)) (*Client, error) {
+	c := &Client{}
+	c.conn = NewConn(r, w, func(method string, params json.RawMessage) {
+		if method != "textDocument/publishDiagnostics" || diagnostics == nil {
+			return
+		}
+		var p PublishDiagnosticsParams
+		if json.Unmarshal(params, &p) == nil {
+			diagnostics(p)
+		}
+	})
+
+	params := initializeParams{
+		ProcessID:    os.Getpid(),
+		RootURI:      PathToURI(root),
+		Capabilities: clientCapabilities,
+	}
+	if err := c.conn.Call("initialize", params, nil); err != nil {
+		return nil, err
+	}
+	if err := c.conn.Notify("initialized", struct{}{}); err != nil {
+		return nil, err
+	}
+	return c, nil
+}
+
+// DidOpen tells the server the document at path is open, with text as its content.
+func (c *Client) DidOpen(path, languageID string, version int, text string) error {
+	return c.conn.Notify("textDocument/didOpen", didOpenTextDocumentParams{
+		TextDocument: textDocumentItem{URI: PathToURI(path), LanguageID: languageID, Version: version, Text: text},
+	})
+}
+
+// DidChange sends the new content of an open document; version must grow with every change.
+func (c *Client) DidChange(path string, version int, text string) error {
+	return c.conn.Notify("textDocument/didChange", didChangeTextDocumentParams{
+		TextDocument:   versionedTextDocumentIdentifier{URI: PathToURI(path), Version: version},
+		ContentChanges: []textDocumentContentChangeEvent{{Text: text}},
+	})
+}
+
+// DidClose tells the server the document at path is no longer open.
+func (c *Client) DidClose(path string) error {
+	return c.conn.Notify("textDocument/didClose", didCloseTextDocumentParams{
+		TextDocument: textDocumentIdentifier{URI: PathToURI(path)},
+	})
+}
+
+// Completion asks for suggestions at pos in the document at path, in the order the server ranks them.
+func (c *Client) Completion(path string, pos Position) ([]CompletionItem, error) {
+	var result json.RawMessage
+	err := c.conn.Call("textDocument/completion", textDocumentPositionParams{
+		TextDocument: textDocumentIdentifier{URI: PathToURI(path)},
+		Position:     pos,
+	}, &result)
+	if err != nil {
+		return nil, err
+	}
+
+	// either a list of items, a completion list holding them, or null
+	var items []CompletionItem
+	if trimmed := bytes.TrimSpace(result); len(trimmed) > 0 && trimmed[0] == '[' {
+		err = json.Unmarshal(trimmed, &items)
+	} else if len(trimmed) > 0 && !bytes.Equal(trimmed, []byte("null")) {
+		var list completionList
+		err = json.Unmarshal(trimmed, &list)
+		items = list.Items
+	}
+	if err != nil {
+		return nil, err
+	}
+
+	slices.SortStableFunc(items, func(a, b CompletionItem) int {
+		return strings.Compare(sortKey(a), sortKey(b))
+	})
+	return items, nil
+}
+
+// Definition asks where the symbol at pos in the document at path is defined; there may be several
+// places, or none.
+func (c *Client) Definition(path string, pos Position) ([]Location, error) {
+	var result json.RawMessage
+	err := c.conn.Call("textDocument/definition", textDocumentPositionParams{
+		TextDocument: textDocumentIdentifier{URI: PathToURI(path)},
+		Position:     pos,
+	}, &result)
+	if err != nil {
+		return nil, err
+	}
+
+	// either a location, a list of locations or links, or null
+	var found []locationOrLink
+	if trimmed := bytes.TrimSpace(result); len(trimmed) > 0 && trimmed[0] == '[' {
+		err = json.Unmarshal(trimmed, &found)
+	} else if len(trimmed) > 0 && !bytes.Equal(trimmed, []byte("null")) {
+		var l locationOrLink
+		err = json.Unmarshal(trimmed, &l)
+		found = []locationOrLink{l}
+	}
+	if err != nil {
+		return nil, err
+	}
+
+	locations := make([]Location, len(found))
+	for i, l := range found {
+		locations[i] = l.location()
+	}
+	return locations, nil
+}
+
+// Hover asks for documentation of the symbol at pos in the document at path, returning its text,
+// which may be markdown, or nothing when the server has none.
+func (c *Client) Hover(path string, pos Position) (string, error) {
+	var result *hover
+	err := c.conn.Call("textDocument/hover", textDocumentPositionParams{
+		TextDocument: textDocumentIdentifier{URI: PathToURI(path)},
+		Position:     pos,
+	}, &result)
+	if err != nil || result == nil {
+		return "", err
+	}
+
+	trimmed := bytes.TrimSpace(result.Contents)
+	if len(trimmed) == 0 {
+		return "", nil
+	}
+	parts := []json.RawMessage{trimmed}
+	if trimmed[0] == '[' {
+		if err := json.Unmarshal(trimmed, &parts); err != nil {
+			return "", err
+		}
+	}
+
+	var texts []string
+	for _, part := range parts {
+		var text string
+		if json.Unmarshal(part, &text) != nil {
+			var marked markedString
+			if err := json.Unmarshal(part, &marked); err != nil {
+				return "", err
+			}
+			text = marked.Value
+		}
+		if text = strings.TrimSpace(text); text != "" {
+			texts = append(texts, text)
+		}
+	}
+	return strings.Join(texts, "\n\n"), nil
+}
+
+// sortKey returns what items are ordered by: their sort text, falling back to their label.
+func sortKey(item CompletionItem) string {
+	if item.SortText != "" {
+		return item.SortText
+	}
+	return item.Label
+}
+
+// Close asks the server to shut down and exit, killing it when it takes too long.
+func (c *Client) Close() error {
+	if c.cmd == nil {
+		return nil
+	}
+
+	exited := make(chan error, 1)
+	go func() {
+		_ = c.conn.Call("shutdown", nil, nil)
+		_ = c.conn.Notify("exit", nil)
+		exited <- c.cmd.Wait()
+	}()
+
+	select {
+	case err := <-exited:
+		return err
+	case <-time.After(closeTimeout):
+		_ = c.cmd.Process.Kill()
+		return <-exited
+	}
+}
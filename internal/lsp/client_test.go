@@ -0,0 +1,231 @@
+package lsp
+
+import (
+	"bufio"
+	"encoding/json"
+	"io"
+	"reflect"
+	"testing"
+)
+
+// fakeServer answers the requests of a client connected through pipes, calling respond with
+// each one; notifications are handed to notified.
+type fakeServer struct {
+	r        *bufio.Reader
+	w        io.Writer
+	respond  func(method string, params json.RawMessage) any
+	notified chan string
+}
+
+// newFakeServer returns a client initialized against a fake server.
+func newFakeServer(t *testing.T, respond func(method string, params json.RawMessage) any, diagnostics func(PublishDiagnosticsParams)) (*Client, *fakeServer) {
+	t.Helper()
+	clientR, serverW := io.Pipe()
+	serverR, clientW := io.Pipe()
+	t.Cleanup(func() {
+		_ = serverW.Close()
+		_ = serverR.Close()
+	})
+
+	s := &fakeServer{r: bufio.NewReader(serverR), w: serverW, respond: respond, notified: make(chan string, 16)}
+	go s.serve()
+
+	c, err := initialize(clientR, clientW, "/work", diagnostics)
+	if err != nil {
+		t.Fatalf("initialize() error = %v", err)
+	}
+	if method := <-s.notified; method != "initialized" {
+		t.Fatalf("first notification = %q, want %q", method, "initialized")
+	}
+	return c, s
+}
+
+func (s *fakeServer) serve() {
+	conn := &Conn{w: s.w}
+	for {
+		msg, err := readMessage(s.r)
+		if err != nil {
+			return
+		}
+		if msg.ID == nil {
+			s.notified <- msg.Method
+			continue
+		}
+		var result any
+		if s.respond != nil {
+			result = s.respond(msg.Method, msg.Params)
+		}
+		data, _ := json.Marshal(result)
+		_ = conn.write(&message{ID: msg.ID, Result: data})
+	}
+}
+
+// publish sends a notification from the server to the client.
+func (s *fakeServer) publish(method string, params any) {
+	conn := &Conn{w: s.w}
+	_ = conn.Notify(method, params)
+}
+
+func TestClientCompletion(t *testing.T) {
+	tests := []struct {
+		name   string
+		result string
+		want   []string
+	}{
+		{name: "items", result: `[{"label":"b"},{"label":"a"}]`, want: []string{"a", "b"}},
+		{name: "list", result: `{"isIncomplete":false,"items":[{"label":"x","sortText":"2"},{"label":"y","sortText":"1"}]}`, want: []string{"y", "x"}},
+		{name: "null", result: `null`, want: nil},
+	}
+
+	for _, tt := range tests {
+		t.Run(tt.name, func(t *testing.T) {
+			var asked textDocumentPositionParams
+			c, _ := newFakeServer(t, func(method string, params json.RawMessage) any {
+				if method != "textDocument/completion" {
+					return nil
+				}
+				_ = json.Unmarshal(params, &asked)
+				return json.RawMessage(tt.result)
+			}, nil)
+
+			items, err := c.Completion("/work/main.go", Position{Line: 2, Character: 4})
+			if err != nil {
+				t.Fatalf("Completion() error = %v", err)
+			}
+			var labels []string
+			for _, item := range items {
+				labels = append(labels, item.Label)
+			}
+			if !reflect.DeepEqual(labels, tt.want) {
+				t.Errorf("Completion() labels = %v, want %v", labels, tt.want)
+			}
+			if asked.TextDocument.URI != "file:///work/main.go" || asked.Position != (Position{Line: 2, Character: 4}) {
+				t.Errorf("Completion() asked for %+v", asked)
+			}
+		})
+	}
+}
+
+func TestClientDefinition(t *testing.T) {
+	at := Range{Start: Position{Line: 3, Character: 5}, End: Position{Line: 3, Character: 8}}
+	tests := []struct {
+		name   string
+		result string
+		want   []Location
+	}{
+		{name: "location", result: `{"uri":"file:///work/a.go","range":{"start":{"line":3,"character":5},"end":{"line":3,"character":8}}}`, want: []Location{{URI: "file:///work/a.go", Range: at}}},
+		{name: "locations", result: `[{"uri":"file:///work/a.go","range":{"start":{"line":3,"character":5},"end":{"line":3,"character":8}}}]`, want: []Location{{URI: "file:///work/a.go", Range: at}}},
+		{name: "links", result: `[{"targetUri":"file:///work/b.go","targetRange":{"start":{"line":0,"character":0},"end":{"line":9,"character":0}},"targetSelectionRange":{"start":{"line":3,"character":5},"end":{"line":3,"character":8}}}]`, want: []Location{{URI: "file:///work/b.go", Range: at}}},
+		{name: "null", result: `null`, want: []Location{}},
+	}
+
+	for _, tt := range tests {
+		t.Run(tt.name, func(t *testing.T) {
+			c, _ := newFakeServer(t, func(method string, params json.RawMessage) any {
+				if method != "textDocument/definition" {
+					return nil
+				}
+				return json.RawMessage(tt.result)
+			}, nil)
+
+			got, err := c.Definition("/work/main.go", Position{})
+			if err != nil {
+				t.Fatalf("Definition() error = %v", err)
+			}
+			if !reflect.DeepEqual(got, tt.want) {
+				t.Errorf("Definition() = %+v, want %+v", got, tt.want)
+			}
+		})
+	}
+}
+
+func TestClientHover(t *testing.T) {
+	tests := []struct {
+		name   string
+		result string
+		want   string
+	}{
+		{name: "markup", result: `{"contents":{"kind":"markdown","value":"func f()"}}`, want: "func f()"},
+		{name: "string", result: `{"contents":"plain"}`, want: "plain"},
+		{name: "marked strings", result: `{"contents":[{"language":"go","value":"func f()"},"Does f."]}`, want: "func f()\n\nDoes f."},
+		{name: "empty", result: `{"contents":""}`, want: ""},
+		{name: "null", result: `null`, want: ""},
+	}
+
+	for _, tt := range tests {
+		t.Run(tt.name, func(t *testing.T) {
+			c, _ := newFakeServer(t, func(method string, params json.RawMessage) any {
+				if method != "textDocument/hover" {
+					return nil
+				}
+				return json.RawMessage(tt.result)
+			}, nil)
+
+			got, err := c.Hover("/work/main.go", Position{})
+			if err != nil {
+				t.Fatalf("Hover() error = %v", err)
+			}
+			if got != tt.want {
+				t.Errorf("Hover() = %q, want %q", got, tt.want)
+			}
+		})
+	}
+}
+
+func TestClientDocuments(t *testing.T) {
+	c, s := newFakeServer(t, nil, nil)
+
+	if err := c.DidOpen("/work/main.go", "go", 1, "package main\n"); err != nil {
+		t.Fatalf("DidOpen() error = %v", err)
+	}
+	if err := c.DidChange("/work/main.go", 2, "package main\n\n"); err != nil {
+		t.Fatalf("DidChange() error = %v", err)
+	}
+	if err := c.DidClose("/work/main.go"); err != nil {
+		t.Fatalf("DidClose() error = %v", err)
+	}
+	for _, want := range []string{"textDocument/didOpen", "textDocument/didChange", "textDocument/didClose"} {
+		if got := <-s.notified; got != want {
+			t.Errorf("notification = %q, want %q", got, want)
+		}
+	}
+}
+
+func TestClientDiagnostics(t *testing.T) {
+	published := make(chan PublishDiagnosticsParams, 1)
+	_, s := newFakeServer(t, nil, func(p PublishDiagnosticsParams) {
+		published <- p
+	})
+
+	want := PublishDiagnosticsParams{
+		URI: "file:///work/main.go",
+		Diagnostics: []Diagnostic{{
+			Range:    Range{Start: Position{Line: 1, Character: 2}, End: Position{Line: 1, Character: 5}},
+			Severity: SeverityWarning,
+			Message:  "unused",
+		}},
+	}
+	s.publish("textDocument/publishDiagnostics", want)
+	if got := <-published; !reflect.DeepEqual(got, want) {
+		t.Errorf("published %+v, want %+v", got, want)
+	}
+}
+
+func TestConnServerRequest(t *testing.T) {
+	clientR, serverW := io.Pipe()
+	serverR, clientW := io.Pipe()
+	defer serverW.Close()
+	NewConn(clientR, clientW, nil)
+
+	server := &Conn{w: serverW}
+	go func() {
+		_ = server.write(&message{ID: json.RawMessage(`"a"`), Method: "workspace/configuration"})
+	}()
+	msg, err := readMessage(bufio.NewReader(serverR))
+	if err != nil {
+		t.Fatalf("readMessage() error = %v", err)
+	}
+	if string(msg.ID) != `"a"` || msg.Error == nil || msg.Error.Code != codeMethodNotFound {
+		t.Errorf("reply = %+v, want method not found for id \"a\"", msg)
+	}
+}
@@ -0,0 +1,208 @@
+package lsp
+
+import (
+	"bufio"
+	"encoding/json"
+	"errors"
+	"fmt"
+	"io"
+	"net/textproto"
+	"strconv"
+	"sync"
+)
+
+var ErrClosed = errors.New("connection closed")
+
+// ResponseError is the error a request was answered with.
+type ResponseError struct {
+	Code    int    `json:"code"`
+	Message string `json:"message"`
+}
+
+func (e *ResponseError) Error() string {
+	return fmt.Sprintf("%s (%d)", e.Message, e.Code)
+}
+
+// codeMethodNotFound answers requests for methods the client doesn't implement.
+const codeMethodNotFound = -32601
+
+// message is a JSON-RPC request, notification or response; requests and responses carry an id,
+// notifications don't.
+type message struct {
+	JSONRPC string          `json:"jsonrpc"`
+	ID      json.RawMessage `json:"id,omitempty"`
+	Method  string          `json:"method,omitempty"`
+	Params  json.RawMessage `json:"params,omitempty"`
+	Result  json.RawMessage `json:"result,omitempty"`
+	Error   *ResponseError  `json:"error,omitempty"`
+}
+
+// Conn speaks JSON-RPC 2.0 over a stream, each message framed by a Content-Length header.
+//
+//	Responses are matched to calls by id on a reading goroutine, which also passes notifications
+//	to the handler; requests from the other side are answered on their own, see reply.
+type Conn struct {
+	w       io.Writer
+	writeMu sync.Mutex
+	handle  func(method string, params json.RawMessage)
+
+	mu      sync.Mutex
+	nextID  int64
+	pending map[string]chan *message // calls waiting for a response, by id
+	done    chan struct{}            // closed once reading stops
+	err     error                    // why reading stopped
+}
+
+// NewConn starts reading messages from r, writing to w; handle is called with every notification,
+// on the reading goroutine.
+func NewConn(r io.Reader, w io.Writer, handle func(method string, params json.RawMessage)) *Conn {
+	c := &Conn{
+		w:       w,
+		handle:  handle,
+		pending: make(map[string]chan *message),
+		done:    make(chan struct{}),
+	}
+	go c.read(bufio.NewReader(r))
+	return c
+}
+
+// Call sends a request and waits for its response, decoding the result into result unless it is nil.
+func (c *Conn) Call(method string, params, result any) error {
+	c.mu.Lock()
+	c.nextID++
+	id := strconv.FormatInt(c.nextID, 10)
+	ch := make(chan *message, 1)
+	c.pending[id] = ch
+	c.mu.Unlock()
+
+	defer func() {
+		c.mu.Lock()
+		delete(c.pending, id)
+		c.mu.Unlock()
+	}()
+
+	if err := c.send(&message{ID: json.RawMessage(id), Method: method}, params); err != nil {
+		return err
+	}
+
+	select {
+	case msg := <-ch:
+		if msg.Error != nil {
+			return msg.Error
+		}
+		if result == nil || len(msg.Result) == 0 {
+			return nil
+		}
+		return json.Unmarshal(msg.Result, result)
+	case <-c.done:
+		return c.err
+	}
+}
+
+// Notify sends a notification, which gets no response.
+func (c *Conn) Notify(method string, params any) error {
+	return c.send(&message{Method: method}, params)
+}
+
+// Done is closed once the other side has closed the stream or sent something unreadable.
+func (c *Conn) Done() <-chan struct{} {
+	return c.done
+}
+
+// send writes msg with params encoded into it.
+func (c *Conn) send(msg *message, params any) error {
+	if params != nil {
+		data, err := json.Marshal(params)
+		if err != nil {
+			return err
+		}
+		msg.Params = data
+	}
+	return c.write(msg)
+}
+
+// write frames and writes msg.
+func (c *Conn) write(msg *message) error {
+	msg.JSONRPC = "2.0"
+	data, err := json.Marshal(msg)
+	if err != nil {
+		return err
+	}
+
+	c.writeMu.Lock()
+	defer c.writeMu.Unlock()
+
+	if _, err := fmt.Fprintf(c.w, "Content-Length: %d\r\n\r\n", len(data)); err != nil {
+		return err
+	}
+	_, err = c.w.Write(data)
+	return err
+}
+
+// read dispatches messages until the stream ends, then fails the calls still waiting.
+func (c *Conn) read(r *bufio.Reader) {
+	var err error
+	for {
+		var msg *message
+		if msg, err = readMessage(r); err != nil {
+			break
+		}
+
+		switch {
+		case msg.Method != "" && msg.ID != nil:
+			go c.reply(msg)
+		case msg.Method != "":
+			if c.handle != nil {
+				c.handle(msg.Method, msg.Params)
+			}
+		default:
+			c.mu.Lock()
+			ch, ok := c.pending[string(msg.ID)]
+			c.mu.Unlock()
+			if ok {
+				ch <- msg
+			}
+		}
+	}
+
+	if errors.Is(err, io.EOF) {
+		err = ErrClosed
+	}
+	c.err = err
+	close(c.done)
+}
+
+// reply answers a request from the other side: registrations and progress tokens are accepted
+// with a null result, anything else isn't implemented.
+func (c *Conn) reply(req *message) {
+	resp := &message{ID: req.ID}
+	switch req.Method {
+	case "client/registerCapability", "window/workDoneProgress/create":
+		resp.Result = json.RawMessage("null")
+	default:
+		resp.Error = &ResponseError{Code: codeMethodNotFound, Message: "method not found: " + req.Method}
+	}
+	_ = c.write(resp)
+}
+
+// readMessage reads the headers and content of one message.
+func readMessage(r *bufio.Reader) (*message, error) {
+	header, err := textproto.NewReader(r).ReadMIMEHeader()
+	if err != nil {
+		return nil, err
+	}
+	length, err := strconv.Atoi(header.Get("Content-Length"))
+	if err != nil || length < 0 {
+		return nil, fmt.Errorf("invalid Content-Length %q", header.Get("Content-Length"))
+	}
+
+	data := make([]byte, length)
+	if _, err := io.ReadFull(r, data); err != nil {
+		return nil, err
+	}
+	msg := &message{}
+	if err := json.Unmarshal(data, msg); err != nil {
+		return nil, err
+	}
+	return msg, nil
+}
@@ -0,0 +1,78 @@
+package lsp
+
+import (
+	"net/url"
+	"path/filepath"
+	"unicode/utf16"
+
+	"github.com/rivo/uniseg"
+)
+
+// Character returns the UTF-16 offset of grapheme column col in line, which positions are sent in.
+func Character(line string, col int) int {
+	character := 0
+	g := uniseg.NewGraphemes(line)
+	for i := 0; i < col && g.Next(); i++ {
+		for _, r := range g.Runes() {
+			character += utf16.RuneLen(r)
+		}
+	}
+	return character
+}
+
+// Column returns the grapheme column of line at UTF-16 offset character, counting an offset
+// inside a grapheme as its start and one past the end as the end.
+func Column(line string, character int) int {
+	col := 0
+	g := uniseg.NewGraphemes(line)
+	for g.Next() {
+		for _, r := range g.Runes() {
+			character -= utf16.RuneLen(r)
+		}
+		if character < 0 {
+			break
+		}
+		col++
+	}
+	return col
+}
+
+// PathToURI returns the file URI documents at path are known by.
+func PathToURI(path string) string {
+	return (&url.URL{Scheme: "file", Path: filepath.ToSlash(path)}).String()
+}
+
+// URIToPath returns the path of a file URI; other schemes have none.
+func URIToPath(uri string) (string, bool) {
+	u, err := url.Parse(uri)
+	if err != nil || u.Scheme != "file" {
+		return "", false
+	}
+	return filepath.FromSlash(u.Path), true
+}
+
+// languageIDs maps file types to the language identifiers servers expect, where they differ.
+var languageIDs = map[string]string{
+	"rs":  "rust",
+	"py":  "python",
+	"js":  "javascript",
+	"jsx": "javascriptreact",
+	"ts":  "typescript",
+	"tsx": "typescriptreact",
+	"h":   "c",
+	"cc":  "cpp",
+	"cxx": "cpp",
+	"hpp": "cpp",
+	"rb":  "ruby",
+	"sh":  "shellscript",
+	"md":  "markdown",
+	"yml": "yaml",
+}
+
+// LanguageID returns the language identifier of documents of fileType.
+func LanguageID(fileType string) string {
+	if id, ok := languageIDs[fileType]; ok {
+		return id
+	}
+	return fileType
+}
@@ -0,0 +1,64 @@
+package lsp
+
+import "testing"
+
+func TestCharacterColumn(t *testing.T) {
+	tests := []struct {
+		name      string
+		line      string
+		col       int
+		character int
+	}{
+		{name: "ascii", line: "hello", col: 3, character: 3},
+		{name: "line end", line: "hello", col: 5, character: 5},
+		{name: "two byte", line: "café x", col: 5, character: 5},
+		{name: "astral", line: "a😀b", col: 2, character: 3},
+		{name: "combining", line: "e\u0301x", col: 1, character: 2},
+		{name: "flag", line: "🇫🇷!", col: 1, character: 4},
+	}
+
+	for _, tt := range tests {
+		t.Run(tt.name, func(t *testing.T) {
+			if got := Character(tt.line, tt.col); got != tt.character {
+				t.Errorf("Character(%q, %d) = %d, want %d", tt.line, tt.col, got, tt.character)
+			}
+			if got := Column(tt.line, tt.character); got != tt.col {
+				t.Errorf("Column(%q, %d) = %d, want %d", tt.line, tt.character, got, tt.col)
+			}
+		})
+	}
+}
+
+func TestColumnOutside(t *testing.T) {
+	tests := []struct {
+		name      string
+		line      string
+		character int
+		want      int
+	}{
+		{name: "inside surrogate pair", line: "a😀b", character: 2, want: 1},
+		{name: "past the end", line: "ab", character: 10, want: 2},
+		{name: "empty", line: "", character: 1, want: 0},
+	}
+
+	for _, tt := range tests {
+		t.Run(tt.name, func(t *testing.T) {
+			if got := Column(tt.line, tt.character); got != tt.want {
+				t.Errorf("Column(%q, %d) = %d, want %d", tt.line, tt.character, got, tt.want)
+			}
+		})
+	}
+}
+
+func TestURI(t *testing.T) {
+	for _, path := range []string{"/home/me/main.go", "/tmp/with space/a#b.rs"} {
+		uri := PathToURI(path)
+		got, ok := URIToPath(uri)
+		if !ok || got != path {
+			t.Errorf("URIToPath(%q) = %q, %v, want %q, true", uri, got, ok, path)
+		}
+	}
+	if _, ok := URIToPath("untitled:1"); ok {
+		t.Errorf("URIToPath(%q) ok = true, want false", "untitled:1")
+	}
+}
@@ -0,0 +1,165 @@
+package lsp
+
+import "encoding/json"
+
+// Position is a place in a document: a 0-based line and a character offset into it, counted in
+// UTF-16 code units, see Character and Column.
+type Position struct {
+	Line      int `json:"line"`
+	Character int `json:"character"`
+}
+
+// Range is the text between two positions, end excluded.
+type Range struct {
+	Start Position `json:"start"`
+	End   Position `json:"end"`
+}
+
+// DiagnosticSeverity says how bad a diagnostic is; servers may leave it out, meaning an error.
+type DiagnosticSeverity int
+
+const (
+	SeverityError DiagnosticSeverity = iota + 1
+	SeverityWarning
+	SeverityInformation
+	SeverityHint
+)
+
+// Diagnostic is a problem a server found in a document, like a compile error.
+type Diagnostic struct {
+	Range    Range              `json:"range"`
+	Severity DiagnosticSeverity `json:"severity,omitempty"`
+	Source   string             `json:"source,omitempty"`
+	Message  string             `json:"message"`
+}
+
+// PublishDiagnosticsParams replaces the diagnostics of a document.
+type PublishDiagnosticsParams struct {
+	URI         string       `json:"uri"`
+	Diagnostics []Diagnostic `json:"diagnostics"`
+}
+
+// CompletionItem is a suggestion for the text at a position.
+type CompletionItem struct {
+	Label      string    `json:"label"`
+	Detail     string    `json:"detail,omitempty"`
+	SortText   string    `json:"sortText,omitempty"`
+	InsertText string    `json:"insertText,omitempty"`
+	TextEdit   *TextEdit `json:"textEdit,omitempty"`
+}
+
+// Text returns the text the item inserts.
+func (item CompletionItem) Text() string {
+	switch {
+	case item.TextEdit != nil:
+		return item.TextEdit.NewText
+	case item.InsertText != "":
+		return item.InsertText
+	}
+	return item.Label
+}
+
+// TextEdit replaces text with NewText; only the new text is read, which an InsertReplaceEdit
+// shares.
+type TextEdit struct {
+	NewText string `json:"newText"`
+}
+
+// Location is a range in a document, e.g. where a symbol is defined.
+type Location struct {
+	URI   string `json:"uri"`
+	Range Range  `json:"range"`
+}
+
+// locationOrLink decodes either a Location or a LocationLink, which names the same place with
+// other fields.
+type locationOrLink struct {
+	URI                  string `json:"uri"`
+	Range                Range  `json:"range"`
+	TargetURI            string `json:"targetUri"`
+	TargetSelectionRange Range  `json:"targetSelectionRange"`
+}
+
+// location returns the place l names.
+func (l locationOrLink) location() Location {
+	if l.TargetURI != "" {
+		return Location{URI: l.TargetURI, Range: l.TargetSelectionRange}
+	}
+	return Location{URI: l.URI, Range: l.Range}
+}
+
+// hover is the result of a hover request; its contents are a string, a MarkedString with a
+// language, a list of either, or MarkupContent.
+type hover struct {
+	Contents json.RawMessage `json:"contents"`
+}
+
+// markedString is a MarkedString or MarkupContent; both hold their text in value.
+type markedString struct {
+	Value string `json:"value"`
+}
+
+// completionList is the result of a completion request when it isn't a plain list of items.
+type completionList struct {
+	IsIncomplete bool             `json:"isIncomplete"`
+	Items        []CompletionItem `json:"items"`
+}
+
+type textDocumentIdentifier struct {
+	URI string `json:"uri"`
+}
+
+type versionedTextDocumentIdentifier struct {
+	URI     string `json:"uri"`
+	Version int    `json:"version"`
+}
+
+type textDocumentItem struct {
+	URI        string `json:"uri"`
+	LanguageID string `json:"languageId"`
+	Version    int    `json:"version"`
+	Text       string `json:"text"`
+}
+
+// textDocumentContentChangeEvent replaces the whole document; ranged changes aren't sent.
+type textDocumentContentChangeEvent struct {
+	Text string `json:"text"`
+}
+
+type didOpenTextDocumentParams struct {
+	TextDocument textDocumentItem `json:"textDocument"`
+}
+
+type didChangeTextDocumentParams struct {
+	TextDocument   versionedTextDocumentIdentifier  `json:"textDocument"`
+	ContentChanges []textDocumentContentChangeEvent `json:"contentChanges"`
+}
+
+type didCloseTextDocumentParams struct {
+	TextDocument textDocumentIdentifier `json:"textDocument"`
+}
+
+type textDocumentPositionParams struct {
+	TextDocument textDocumentIdentifier `json:"textDocument"`
+	Position     Position               `json:"position"`
+}
+
+type initializeParams struct {
+	ProcessID    int            `json:"processId"`
+	RootURI      string         `json:"rootUri"`
+	Capabilities map[string]any `json:"capabilities"`
+}
+
+// clientCapabilities tells servers what the client understands: whole-document sync, diagnostics,
+// plain-text completion items, snippets being left out, definitions and hovers.
+var clientCapabilities = map[string]any{
+	"textDocument": map[string]any{
+		"synchronization":    map[string]any{"dynamicRegistration": false},
+		"publishDiagnostics": map[string]any{"relatedInformation": false},
+		"completion": map[string]any{
+			"completionItem": map[string]any{"snippetSupport": false},
+		},
+		"definition": map[string]any{"linkSupport": true},
+		"hover":      map[string]any{"contentFormat": []string{"plaintext", "markdown"}},
+	},
+}
@@ -3,6 +3,7 @@ package rope
 import (
 	"errors"
 	"fmt"
+	"io"
 	"strings"
 	"sync"
 
@@ -101,6 +102,26 @@ func (r *Rope) String() string {
 	return sb.String()
 }
 
+// WriteTo writes the text of the rope to w leaf by leaf, without building it as one string.
+func (r *Rope) WriteTo(w io.Writer) (int64, error) {
+	r.mu.RLock()
+	defer r.mu.RUnlock()
+
+	var written int64
+	err := r.root.writeTo(w, &written)
+	return written, err
+}
+
+// Clone returns a copy of the rope in constant time.
+//
+//	Nodes are never changed once built, edits create new ones, so the copy can share all of them.
+func (r *Rope) Clone() *Rope {
+	r.mu.RLock()
+	defer r.mu.RUnlock()
+
+	return &Rope{root: r.root}
+}
+
 // Substring retrieves text from start to end grapheme indices (exclusive).
 func (r *Rope) Substring(start, end int) (string, error) {
 	r.mu.RLock()
@@ -125,6 +146,28 @@ func (r *Rope) GraphemeAt(index int) (string, error) {
 	return r.root.graphemeAt(index)
 }
 
+// ChunkAt returns the leaf holding the grapheme at index and the index of its first grapheme, so
+// neighbouring graphemes can be read without walking the tree for each.
+func (r *Rope) ChunkAt(index int) (start int, chunk string, err error) {
+	r.mu.RLock()
+	defer r.mu.RUnlock()
+
+	if index < 0 || index >= r.root.totalGraphemes() {
+		return 0, "", fmt.Errorf("%w: index %d", ErrOutOfBounds, index)
+	}
+	n := r.root
+	for n.left != nil || n.right != nil {
+		if index < n.weight {
+			n = n.left
+		} else {
+			start += n.weight
+			index -= n.weight
+			n = n.right
+		}
+	}
+	return start, n.data, nil
+}
+
 func (r *Rope) TotalGraphemes() int {
 	r.mu.RLock()
 	defer r.mu.RUnlock()
@@ -194,6 +237,22 @@ func (n *RopeNode) writeToString(sb *strings.Builder) {
 	n.right.writeToString(sb)
 }
 
+// writeTo writes the node's data to w, adding the number of bytes written to written.
+func (n *RopeNode) writeTo(w io.Writer, written *int64) error {
+	if n == nil {
+		return nil
+	}
+	if n.left == nil && n.right == nil {
+		m, err := io.WriteString(w, n.data)
+		*written += int64(m)
+		return err
+	}
+	if err := n.left.writeTo(w, written); err != nil {
+		return err
+	}
+	return n.right.writeTo(w, written)
+}
+
 // appendTextRange appends text within a range to the provided StringBuilder.
 func (n *RopeNode) appendTextRange(sb *strings.Builder, start, end int) {
 	if n == nil || start >= end {
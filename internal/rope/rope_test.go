@@ -2,6 +2,7 @@ package rope
 
 import (
 	"reflect"
+	"strings"
 	"testing"
 
 	"github.com/rivo/uniseg"
@@ -246,3 +247,73 @@ func TestTotalGraphemes(t *testing.T) {
 		}
 	}
 }
+
+func TestWriteTo(t *testing.T) {
+	tests := []struct {
+		input string
+	}{
+		{"Hello, World!"},
+		{"こんにちは世界"},
+		{strings.Repeat("spans several leaves ", MaxLeafSize)},
+		{""},
+	}
+
+	for _, tt := range tests {
+		var sb strings.Builder
+		n, err := NewRope(tt.input).WriteTo(&sb)
+		if err != nil {
+			t.Fatalf("WriteTo failed for input %q: %v", tt.input, err)
+		}
+		if sb.String() != tt.input || n != int64(len(tt.input)) {
+			t.Errorf("WriteTo mismatch: expected %d bytes, got %d bytes %q", len(tt.input), n, sb.String())
+		}
+	}
+}
+
+func TestClone(t *testing.T) {
+	rope := NewRope(strings.Repeat("abc\n", MaxLeafSize))
+	clone := rope.Clone()
+	want := rope.String()
+
+	if err := rope.Insert(5, "inserted"); err != nil {
+		t.Fatal(err)
+	}
+	if err := rope.Delete(0, 3); err != nil {
+		t.Fatal(err)
+	}
+
+	if clone.String() != want {
+		t.Errorf("Clone changed by edits to the original")
+	}
+	if rope.String() == want {
+		t.Errorf("original not edited")
+	}
+}
+
+func TestChunkAt(t *testing.T) {
+	input := strings.Repeat("ab👋\n", MaxLeafSize)
+	rope := NewRope(input)
+	if err := rope.Insert(300, "inserted"); err != nil {
+		t.Fatal(err)
+	}
+
+	for _, index := range []int{0, 255, 256, 300, 307, rope.TotalGraphemes() - 1} {
+		start, chunk, err := rope.ChunkAt(index)
+		if err != nil {
+			t.Fatalf("ChunkAt(%d) failed: %v", index, err)
+		}
+		want, _ := rope.GraphemeAt(index)
+		gr := uniseg.NewGraphemes(chunk)
+		got := ""
+		for i := start; i <= index && gr.Next(); i++ {
+			got = gr.Str()
+		}
+		if got != want {
+			t.Errorf("ChunkAt(%d) = %d, chunk holding %q there, want %q", index, start, got, want)
+		}
+	}
+
+	if _, _, err := rope.ChunkAt(rope.TotalGraphemes()); err == nil {
+		t.Errorf("ChunkAt past the end succeeded")
+	}
+}
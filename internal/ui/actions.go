@@ -0,0 +1,320 @@
+package ui
+
+import (
+	"errors"
+	"fmt"
+	"slices"
+	"strconv"
+	"strings"
+
+	"github.com/lg2m/athena/internal/athena/config"
+	"github.com/lg2m/athena/internal/editor"
+	"github.com/lg2m/athena/internal/editor/command"
+	"github.com/lg2m/athena/internal/editor/state"
+)
+
+var ErrUnknownAction = errors.New("not a named command")
+
+// actions returns the named commands key bindings, ":run" and aliases refer to, keyed by name.
+//
+//	A command takes the count typed before it from numericPrefix, which is cleared once it ran.
+func (v *DocumentView) actions() map[string]func() {
+	return map[string]func(){
+		"enter_insert_mode": func() { v.editor.SetMode(state.Insert) },
+		"enter_normal_mode": func() { v.editor.SetMode(state.Normal) },
+		"move_left":         func() { _ = v.editor.MoveCursorHorizontal(-1, false) },
+		"move_right":        func() { _ = v.editor.MoveCursorHorizontal(1, false) },
+		"move_down": func() {
+			_ = v.editor.MoveDisplayRows(v.getNumericPrefixOrDefault(1), v.lineMovementWidth(), false)
+			v.centerCursor()
+		},
+		"move_up": func() {
+			_ = v.editor.MoveDisplayRows(-v.getNumericPrefixOrDefault(1), v.lineMovementWidth(), false)
+			v.centerCursor()
+		},
+		"move_display_down": func() {
+			_ = v.editor.MoveDisplayRows(v.getNumericPrefixOrDefault(1), v.wrapWidth(), false)
+			v.centerCursor()
+			v.goToMenu.Hide()
+		},
+		"move_display_up": func() {
+			_ = v.editor.MoveDisplayRows(-v.getNumericPrefixOrDefault(1), v.wrapWidth(), false)
+			v.centerCursor()
+			v.goToMenu.Hide()
+		},
+		"select_left":  func() { _ = v.editor.MoveCursorHorizontal(-1, true) },
+		"select_right": func() { _ = v.editor.MoveCursorHorizontal(1, true) },
+		"select_down": func() {
+			_ = v.editor.MoveDisplayRows(v.getNumericPrefixOrDefault(1), v.lineMovementWidth(), true)
+		},
+		"select_up": func() {
+			_ = v.editor.MoveDisplayRows(-v.getNumericPrefixOrDefault(1), v.lineMovementWidth(), true)
+		},
+		"collapse_selection": func() { _ = v.editor.CollapseSelection() },
+		"count_text": func() {
+			v.reportTextStats()
+			v.goToMenu.Hide()
+		},
+		"record_macro": func() {
+			if _, recording := v.editor.Recording(); recording {
+				v.editor.StopRecording()
+			} else {
+				v.awaitRegister = "record_macro"
+			}
+		},
+		"play_macro":        func() { v.awaitRegister = "play_macro" },
+		"set_mark":          func() { v.awaitRegister = "set_mark" },
+		"jump_to_mark":      func() { v.awaitRegister = "jump_to_mark" },
+		"jump_to_mark_line": func() { v.awaitRegister = "jump_to_mark_line" },
+		"repeat_change": func() {
+			count := v.numericPrefix
+			v.numericPrefix = ""
+			v.editor.SetError(v.repeatChange(count))
+		},
+		"undo": func() {
+			// undoing isn't a change for "." to repeat
+			v.change = typedChange{}
+			v.editor.SetError(v.editor.Undo(v.getNumericPrefixOrDefault(1)))
+			v.centerCursor()
+		},
+		"redo": func() {
+			v.change = typedChange{}
+			v.editor.SetError(v.editor.Redo(v.getNumericPrefixOrDefault(1)))
+			v.centerCursor()
+		},
+		"match_pair": func() {
+			v.editor.SetError(v.editor.MatchPair(false))
+			v.centerCursor()
+		},
+		"move_next_word": func() {
+			_ = v.editor.MoveToNextWord(false)
+			v.centerCursor()
+		},
+		"move_prev_word": func() {
+			_ = v.editor.MoveToPrevWord(false)
+			v.centerCursor()
+		},
+		"delete_char":        func() { v.editor.SetError(v.editor.DeleteChars(v.getNumericPrefixOrDefault(1))) },
+		"delete_char_before": func() { v.editor.SetError(v.editor.DeleteCharsBefore(v.getNumericPrefixOrDefault(1))) },
+		"replace_char":       func() { v.awaitRegister = "replace_char" },
+		"toggle_case":        func() { v.editor.SetError(v.editor.ToggleCase(v.getNumericPrefixOrDefault(1))) },
+		"delete_backwards":   func() { _ = v.editor.Backspace() },
+		"delete_forward":     func() { _ = v.editor.DeleteText(1) },
+		"delete_word_before": func() { _ = v.editor.DeleteWordBefore() },
+		"delete_word_after":  func() { _ = v.editor.DeleteWordAfter() },
+		"delete_line_start":  func() { _ = v.editor.DeleteToLineStart() },
+		"new_line":           v.insertNewLine,
+		"insert_literal":     func() { v.literal = &literalEntry{} },
+		"complete":           func() { v.languageAction("complete") },
+		"go_to_definition":   func() { v.languageAction("go_to_definition") },
+		"hover":              func() { v.languageAction("hover") },
+		"jump_older":         func() { v.jumpAction(v.editor.JumpToJump(-v.getNumericPrefixOrDefault(1))) },
+		"jump_newer":         func() { v.jumpAction(v.editor.JumpToJump(v.getNumericPrefixOrDefault(1))) },
+		"enter_command_mode": v.openCommandLine,
+		"search_forward":     func() { v.openSearch(true) },
+		"search_backward":    func() { v.openSearch(false) },
+		"search_next":        func() { v.reportSearch(v.editor.SearchNext(false, v.searchOptions())) },
+		"search_prev":        func() { v.reportSearch(v.editor.SearchNext(true, v.searchOptions())) },
+		"search_word_forward": func() {
+			v.reportSearch(v.editor.SearchWordUnderCursor(true, v.searchOptions()))
+		},
+		"search_word_backward": func() {
+			v.reportSearch(v.editor.SearchWordUnderCursor(false, v.searchOptions()))
+		},
+		"buffer_next": func() {
+			v.switchBuffer(v.getNumericPrefixOrDefault(1))
+			v.goToMenu.Hide()
+		},
+		"buffer_prev": func() {
+			v.switchBuffer(-v.getNumericPrefixOrDefault(1))
+			v.goToMenu.Hide()
+		},
+		"scroll_half_page_down": func() { v.scrollHalfPage(v.getNumericPrefixOrDefault(1)) },
+		"scroll_half_page_up":   func() { v.scrollHalfPage(-v.getNumericPrefixOrDefault(1)) },
+		"yank_line":             func() { v.editor.SetError(v.editor.YankLines(v.getNumericPrefixOrDefault(1))) },
+		"delete_line":           func() { v.editor.SetError(v.editor.DeleteLines(v.getNumericPrefixOrDefault(1))) },
+		"operator_delete":       func() { v.startOperator("delete") },
+		"operator_change":       func() { v.startOperator("change") },
+		"operator_yank":         func() { v.startOperator("yank") },
+		"operator_indent":       func() { v.startOperator("indent") },
+		"operator_dedent":       func() { v.startOperator("dedent") },
+		"operator_comment":      v.startComment,
+		"surround_selection":    v.surroundSelection,
+		"output_toggle":         v.toggleOutput,
+		"output_scroll_down":    func() { v.scrollOutput(true) },
+		"output_scroll_up":      func() { v.scrollOutput(false) },
+		"grep_prompt":           v.openGrep,
+		"quickfix_next":         func() { v.jumpToQuickfix(v.getNumericPrefixOrDefault(1)) },
+		"quickfix_prev":         func() { v.jumpToQuickfix(-v.getNumericPrefixOrDefault(1)) },
+		"join_lines": func() {
+			// a count joins that many lines, two at least
+			if line, _, err := v.editor.GetCurrentPosition(); err == nil {
+				v.editor.SetError(v.editor.JoinLines(line, line+max(v.getNumericPrefixOrDefault(2), 2)-1))
+			}
+		},
+		"select_register": func() { v.awaitRegister = "select_register" },
+		"paste_after":     func() { v.editor.SetError(v.editor.Paste(v.pasteRegister(), false, nil)) },
+		"paste_before":    func() { v.editor.SetError(v.editor.Paste(v.pasteRegister(), true, nil)) },
+		"paste_after_indented": func() {
+			indent := v.indent()
+			v.editor.SetError(v.editor.Paste(v.pasteRegister(), false, &indent))
+		},
+		"paste_before_indented": func() {
+			indent := v.indent()
+			v.editor.SetError(v.editor.Paste(v.pasteRegister(), true, &indent))
+		},
+		"reflow_paragraph": func() {
+			v.reflowParagraphs(v.getNumericPrefixOrDefault(1))
+			v.goToMenu.Hide()
+		},
+		"toggle_line_numbers": func() {
+			_, _ = config.SetOption(&v.cfg.Editor, "invnumber")
+			v.goToMenu.Hide()
+		},
+		"toggle_relative_line_numbers": func() {
+			_, _ = config.SetOption(&v.cfg.Editor, "invrelativenumber")
+			v.goToMenu.Hide()
+		},
+		"toggle_zen":      v.ToggleZen,
+		"window_split":    func() { v.windowAction("window_split") },
+		"window_vsplit":   func() { v.windowAction("window_vsplit") },
+		"window_left":     func() { v.windowAction("window_left") },
+		"window_down":     func() { v.windowAction("window_down") },
+		"window_up":       func() { v.windowAction("window_up") },
+		"window_right":    func() { v.windowAction("window_right") },
+		"window_next":     func() { v.windowAction("window_next") },
+		"window_close":    func() { v.windowAction("window_close") },
+		"window_only":     func() { v.windowAction("window_only") },
+		"window_grow":     func() { v.windowActionCounted("window_grow") },
+		"window_shrink":   func() { v.windowActionCounted("window_shrink") },
+		"window_wider":    func() { v.windowActionCounted("window_wider") },
+		"window_narrower": func() { v.windowActionCounted("window_narrower") },
+		"window_equalize": func() { v.windowAction("window_equalize") },
+		"window_zoom":     func() { v.windowAction("window_zoom") },
+		"tab_next":        func() { v.windowAction("tab_next") },
+		"tab_prev":        func() { v.windowAction("tab_prev") },
+		"show_goto_menu":  v.goToMenu.Show,
+		"go_to_top": func() {
+			_ = v.editor.JumpToLine(max(v.getNumericPrefixOrDefault(1)-1, 0), false)
+			v.centerCursor()
+			v.goToMenu.Hide()
+		},
+		"prev_change": func() { v.jumpAction(v.editor.JumpToChange(-v.getNumericPrefixOrDefault(1))) },
+		"next_change": func() { v.jumpAction(v.editor.JumpToChange(v.getNumericPrefixOrDefault(1))) },
+		"prev_hunk":   func() { v.jumpAction(v.editor.JumpToHunk(-v.getNumericPrefixOrDefault(1))) },
+		"next_hunk":   func() { v.jumpAction(v.editor.JumpToHunk(v.getNumericPrefixOrDefault(1))) },
+		"go_to_bottom": func() {
+			// a count is a line number, like G
+			if v.numericPrefix != "" {
+				_ = v.editor.JumpToLine(v.getNumericPrefixOrDefault(1)-1, false)
+			} else {
+				_ = v.editor.JumpToBottom(false)
+			}
+			v.centerCursor()
+			v.goToMenu.Hide()
+		},
+		"go_to_line_start": func() {
+			v.goToLineEdge(false)
+			v.goToMenu.Hide()
+		},
+		"go_to_line_end": func() {
+			v.goToLineEdge(true)
+			v.goToMenu.Hide()
+		},
+	}
+}
+
+// executeAction runs the named command action, reporting whether there is one by that name.
+func (v *DocumentView) executeAction(action string) bool {
+	run, ok := v.actions()[action]
+	if !ok {
+		return false
+	}
+	run()
+	// a register chosen with " is for the command right after it; a count waits with a command
+	// for the name or character it takes, e.g. "3rx"
+	v.register = 0
+	if v.awaitRegister == "" {
+		v.numericPrefix = ""
+	}
+	return true
+}
+
+// pasteRegister returns the register chosen with " for a paste, or the unnamed one.
+func (v *DocumentView) pasteRegister() rune {
+	if v.register == 0 {
+		return editor.UnnamedRegister
+	}
+	return v.register
+}
+
+// languageAction asks the language server, reporting errors in the message line.
+func (v *DocumentView) languageAction(action string) {
+	v.editor.SetError(v.runLanguageCommand(action))
+	v.goToMenu.Hide()
+}
+
+// windowAction runs a window or tab page action, reporting errors in the message line.
+func (v *DocumentView) windowAction(action string) {
+	v.editor.SetError(v.runWindowCommand(action))
+	v.goToMenu.Hide()
+}
+
+// windowActionCounted runs a window action as many times as the count typed before it, e.g.
+// 5<c-w>+ to grow the window by five rows.
+func (v *DocumentView) windowActionCounted(action string) {
+	for range v.getNumericPrefixOrDefault(1) {
+		if err := v.runWindowCommand(action); err != nil {
+			v.editor.SetError(err)
+			break
+		}
+	}
+	v.goToMenu.Hide()
+}
+
+// jumpAction reports the outcome of a jump and brings the cursor into view.
+func (v *DocumentView) jumpAction(err error) {
+	v.editor.SetError(err)
+	v.centerCursor()
+	v.goToMenu.Hide()
+}
+
+// openCommandLine opens the ":" prompt.
+func (v *DocumentView) openCommandLine() {
+	v.cmdline.Open(":", v.executeCommandLine)
+	v.cmdline.SetCompleter(v.completeCommandLine)
+}
+
+// exRun implements ":run {name}", running a named command, or an alias of one, as if its keys
+// were typed; a count before the name is passed on, e.g. ":run 3 move_down".
+func (v *DocumentView) exRun(cmd command.Command) error {
+	fields := strings.Fields(cmd.Args)
+	if len(fields) == 0 {
+		return ErrArgumentRequired
+	}
+	if _, err := strconv.Atoi(fields[0]); err == nil && len(fields) > 1 {
+		v.numericPrefix, fields = fields[0], fields[1:]
+	}
+	if !v.executeAction(v.cfg.ResolveAlias(fields[0])) {
+		v.numericPrefix = ""
+		return fmt.Errorf("%w: %s", ErrUnknownAction, fields[0])
+	}
+	return nil
+}
+
+// completeAction offers the named commands and aliases whose names start with arg.
+func (v *DocumentView) completeAction(arg string) []string {
+	var names []string
+	for name := range v.actions() {
+		if strings.HasPrefix(name, arg) {
+			names = append(names, name)
+		}
+	}
+	for name := range v.cfg.Aliases {
+		if strings.HasPrefix(name, arg) {
+			names = append(names, name)
+		}
+	}
+	slices.Sort(names)
+	return names
+}
@@ -0,0 +1,221 @@
+package ui
+
+import (
+	"slices"
+
+	"github.com/gdamore/tcell/v2"
+	"github.com/lg2m/athena/internal/editor"
+	"github.com/lg2m/athena/internal/editor/state"
+)
+
+// CommandLineView represents the bottom line, showing either a prompt being typed or the last message.
+type CommandLineView struct {
+	BaseView
+	editor *editor.Editor
+
+	active    bool
+	prefix    string
+	prompt    *Prompt
+	histories map[string]*PromptHistory // per prompt prefix, so ":" and "/" remember separately
+	onSubmit  func(string)
+	onChange  func(string)
+	onCancel  func()
+	onKey     func(rune)
+	prevMode  state.EditorMode
+	pasting   bool // newlines in pasted text don't submit the line
+
+	style      tcell.Style
+	errorStyle tcell.Style
+}
+
+func NewCommandLineView(e *editor.Editor) *CommandLineView {
+	return &CommandLineView{
+		editor:     e,
+		histories:  make(map[string]*PromptHistory),
+		style:      tcell.StyleDefault,
+		errorStyle: tcell.StyleDefault.Foreground(tcell.ColorRed),
+	}
+}
+
+// Open activates the prompt with the given prefix; onSubmit receives the typed text when enter is pressed.
+func (v *CommandLineView) Open(prefix string, onSubmit func(string)) {
+	history, ok := v.histories[prefix]
+	if !ok {
+		history = &PromptHistory{}
+		v.histories[prefix] = history
+	}
+	v.start(prefix, history)
+	v.onSubmit = onSubmit
+}
+
+// Histories returns the lines entered at each prompt, by prefix, oldest first.
+func (v *CommandLineView) Histories() map[string][]string {
+	histories := make(map[string][]string, len(v.histories))
+	for prefix, history := range v.histories {
+		if len(history.entries) > 0 {
+			histories[prefix] = slices.Clone(history.entries)
+		}
+	}
+	return histories
+}
+
+// SetHistories replaces the lines entered at each prompt, e.g. with those of a saved session.
+func (v *CommandLineView) SetHistories(histories map[string][]string) {
+	v.histories = make(map[string]*PromptHistory, len(histories))
+	for prefix, entries := range histories {
+		v.histories[prefix] = &PromptHistory{entries: slices.Clone(entries[max(0, len(entries)-maxPromptHistory):])}
+	}
+}
+
+// Confirm shows prompt and passes the next key typed to onKey instead of editing a line.
+//
+//	Escape is reported as 'q' so callers can treat it as quitting.
+func (v *CommandLineView) Confirm(prompt string, onKey func(key rune)) {
+	v.start(prompt, nil)
+	v.onKey = onKey
+}
+
+// start activates the prompt, switching to command mode until it closes.
+func (v *CommandLineView) start(prefix string, history *PromptHistory) {
+	v.active = true
+	v.prefix = prefix
+	v.prompt = NewPrompt(history)
+	v.onSubmit = nil
+	v.onChange = nil
+	v.onCancel = nil
+	v.onKey = nil
+	v.prevMode = v.editor.GetMode()
+	v.editor.SetMode(state.Command)
+}
+
+// SetCompleter sets the function offering completions for the typed text when tab is pressed.
+func (v *CommandLineView) SetCompleter(complete func(input string) []string) {
+	if v.prompt != nil {
+		v.prompt.SetCompleter(complete)
+	}
+}
+
+// SetOnChange sets a function receiving the typed text after every key, e.g. for incremental search.
+func (v *CommandLineView) SetOnChange(onChange func(text string)) {
+	v.onChange = onChange
+}
+
+// SetOnCancel sets a function called when the prompt is closed without entering the line.
+func (v *CommandLineView) SetOnCancel(onCancel func()) {
+	v.onCancel = onCancel
+}
+
+// Active reports whether the prompt is accepting input.
+func (v *CommandLineView) Active() bool {
+	return v.active
+}
+
+// close deactivates the prompt and restores the mode it was opened from.
+func (v *CommandLineView) close() {
+	v.active = false
+	v.editor.SetMode(v.prevMode)
+}
+
+// HandleEvent implements line editing while the prompt is active.
+func (v *CommandLineView) HandleEvent(ev tcell.Event) bool {
+	if !v.active {
+		return false
+	}
+
+	if paste, ok := ev.(*tcell.EventPaste); ok {
+		v.pasting = paste.Start()
+		return true
+	}
+	key, ok := ev.(*tcell.EventKey)
+	if !ok {
+		return false
+	}
+	if v.pasting && (key.Key() == tcell.KeyEnter || key.Key() == tcell.KeyLF) {
+		return true
+	}
+
+	if v.onKey != nil {
+		return v.handleConfirmKey(key)
+	}
+
+	switch v.prompt.HandleKey(key) {
+	case PromptEditing:
+		if v.onChange != nil {
+			v.onChange(v.prompt.Text())
+		}
+	case PromptCancelled:
+		onCancel := v.onCancel
+		v.close()
+		if onCancel != nil {
+			onCancel()
+		}
+	case PromptAccepted:
+		text, submit := v.prompt.Text(), v.onSubmit
+		v.close()
+		if submit != nil {
+			submit(text)
+		}
+	}
+	return true
+}
+
+// handleConfirmKey closes a confirmation prompt and hands the typed key to its callback.
+func (v *CommandLineView) handleConfirmKey(key *tcell.EventKey) bool {
+	var r rune
+	switch key.Key() {
+	case tcell.KeyEscape:
+		r = 'q'
+	case tcell.KeyRune:
+		r = key.Rune()
+	default:
+		return true
+	}
+
+	onKey := v.onKey
+	v.onKey = nil
+	v.close()
+	onKey(r)
+	return true
+}
+
+// Draw implements the command line view.
+func (v *CommandLineView) Draw(screen Surface) {
+	for x := v.x; x < v.x+v.width; x++ {
+		screen.SetContent(x, v.y, ' ', nil, v.style)
+	}
+
+	if v.active {
+		x := v.drawString(screen, v.x, v.prefix, v.style)
+		input, cursor := []rune(v.prompt.Text()), v.prompt.Cursor()
+		for i, r := range input {
+			style := v.style
+			if i == cursor {
+				style = style.Reverse(true)
+			}
+			screen.SetContent(x+i, v.y, r, nil, style)
+		}
+		if cursor == len(input) {
+			screen.SetContent(x+cursor, v.y, ' ', nil, v.style.Reverse(true))
+		}
+		return
+	}
+
+	msg := v.editor.Message()
+	style := v.style
+	if msg.IsError {
+		style = v.errorStyle
+	}
+	v.drawString(screen, v.x, msg.Text, style)
+}
+
+// drawString draws s starting at x, returning the column after the last character.
+func (v *CommandLineView) drawString(screen Surface, x int, s string, style tcell.Style) int {
+	for _, r := range s {
+		if x >= v.x+v.width {
+			break
+		}
+		screen.SetContent(x, v.y, r, nil, style)
+		x++
+	}
+	return x
+}
@@ -0,0 +1,664 @@
+package ui
+
+import (
+	"errors"
+	"fmt"
+	"io"
+	"io/fs"
+	"os"
+	"os/exec"
+	"path/filepath"
+	"strconv"
+	"strings"
+
+	"github.com/gdamore/tcell/v2"
+	"github.com/lg2m/athena/internal/athena/config"
+	"github.com/lg2m/athena/internal/editor"
+	"github.com/lg2m/athena/internal/editor/command"
+	"github.com/lg2m/athena/internal/editor/search"
+	"github.com/lg2m/athena/internal/editor/state"
+	"github.com/lg2m/athena/internal/util"
+	"github.com/lg2m/athena/internal/util/fuzzy"
+)
+
+var (
+	ErrUnknownCommand   = errors.New("not an editor command")
+	ErrArgumentRequired = errors.New("argument required")
+	ErrNoTerminal       = errors.New("cannot hand over the terminal")
+	ErrUnknownTheme     = errors.New("cannot find theme")
+	ErrNoOutput         = errors.New("no output panel")
+	ErrReadOnly         = errors.New("'readonly' option is set (add ! to override)")
+	ErrChangedOnDisk    = errors.New("file changed on disk since it was read (add ! to override, :e! to reload)")
+)
+
+// defaultThemeName names the built-in theme at the ":colorscheme" prompt.
+const defaultThemeName = "default"
+
+// registerPreview is how many characters of each register ":registers" shows.
+const registerPreview = 20
+
+// exCommand describes a command that can be run from the ":" prompt.
+type exCommand struct {
+	name     string                    // full command name
+	abbrev   int                       // shortest accepted abbreviation
+	path     bool                      // the argument is a file path, completed with tab
+	complete func(arg string) []string // completes the argument with tab, when it isn't a path
+	run      func(cmd command.Command) error
+}
+
+// exCommands returns the commands available at the ":" prompt.
+func (v *DocumentView) exCommands() []exCommand {
+	return []exCommand{
+		{name: "!", abbrev: 1, run: v.exFilter},
+		{name: "bdelete", abbrev: 2, run: v.exBufferDelete},
+		{name: "bnext", abbrev: 2, run: v.exBufferNext},
+		{name: "bprevious", abbrev: 2, run: v.exBufferPrev},
+		{name: "close", abbrev: 3, run: v.exClose},
+		{name: "colorscheme", abbrev: 4, complete: v.completeTheme, run: v.exColorscheme},
+		{name: "edit", abbrev: 1, path: true, run: v.exEdit},
+		{name: "grep", abbrev: 2, run: v.exGrep},
+		{name: "normal", abbrev: 4, run: v.exNormal},
+		{name: "only", abbrev: 2, run: v.exOnly},
+		{name: "output", abbrev: 2, run: v.exOutput},
+		{name: "quit", abbrev: 1, run: v.exQuit},
+		{name: "read", abbrev: 1, path: true, run: v.exRead},
+		{name: "recover", abbrev: 3, run: v.exRecover},
+		{name: "reflow", abbrev: 3, run: v.exReflow},
+		{name: "registers", abbrev: 3, run: v.exRegisters},
+		{name: "run", abbrev: 2, complete: v.completeAction, run: v.exRun},
+		{name: "saveas", abbrev: 3, path: true, run: v.exSaveAs},
+		{name: "session", abbrev: 4, complete: v.completeSession, run: v.exSession},
+		{name: "set", abbrev: 2, run: v.exSet},
+		{name: "split", abbrev: 2, path: true, run: v.exSplit},
+		{name: "substitute", abbrev: 1, run: v.exSubstitute},
+		{name: "tabclose", abbrev: 4, run: v.exTabClose},
+		{name: "tabnew", abbrev: 6, path: true, run: v.exTabNew},
+		{name: "tabnext", abbrev: 4, run: v.exTabNext},
+		{name: "tabprevious", abbrev: 4, run: v.exTabPrev},
+		{name: "vsplit", abbrev: 2, path: true, run: v.exVsplit},
+		{name: "wq", abbrev: 2, run: v.exWriteQuit},
+		{name: "wqall", abbrev: 3, run: v.exWriteQuitAll},
+		{name: "write", abbrev: 1, path: true, run: v.exWrite},
+		{name: "xit", abbrev: 1, run: v.exExit},
+		{name: "zen", abbrev: 3, run: v.exZen},
+	}
+}
+
+// executeCommandLine parses and runs a line entered at the ":" prompt, reporting errors in the message line.
+func (v *DocumentView) executeCommandLine(line string) {
+	if strings.TrimSpace(line) == "" {
+		return
+	}
+
+	cmd, err := command.Parse(line)
+	if err != nil {
+		v.editor.SetError(err)
+		return
+	}
+
+	if cmd.Name == "" {
+		v.editor.SetError(v.goToLine(cmd.Range))
+		return
+	}
+
+	// an alias names an ex command, e.g. "W" for "write", or a command bound to keys
+	name := v.cfg.ResolveAlias(cmd.Name)
+	ex, ok := v.findExCommand(name)
+	if !ok {
+		if name != cmd.Name && v.executeAction(name) {
+			return
+		}
+		v.editor.SetError(fmt.Errorf("%w: %s", ErrUnknownCommand, cmd.Name))
+		return
+	}
+	v.editor.SetError(ex.run(cmd))
+}
+
+// findExCommand looks up a command by its full name or an accepted abbreviation.
+func (v *DocumentView) findExCommand(name string) (exCommand, bool) {
+	for _, ex := range v.exCommands() {
+		if len(name) >= ex.abbrev && strings.HasPrefix(ex.name, name) {
+			return ex, true
+		}
+	}
+	return exCommand{}, false
+}
+
+// completeCommandLine offers tab completions for the ":" prompt: command names, fuzzy matched, then file paths.
+func (v *DocumentView) completeCommandLine(line string) []string {
+	cmd, err := command.Parse(line)
+	if err != nil {
+		return nil
+	}
+
+	// still typing the name
+	if strings.HasSuffix(line, cmd.Name) && cmd.Range == nil && !strings.ContainsAny(line, " \t") {
+		var all []string
+		for _, ex := range v.exCommands() {
+			all = append(all, ex.name)
+		}
+		var names []string
+		for _, m := range fuzzy.Filter(cmd.Name, all) {
+			names = append(names, m.Text)
+		}
+		return names
+	}
+
+	ex, ok := v.findExCommand(cmd.Name)
+	if !ok {
+		return nil
+	}
+	var args []string
+	switch {
+	case ex.path:
+		args = util.CompletePath(cmd.Args)
+	case ex.complete != nil:
+		args = ex.complete(cmd.Args)
+	}
+
+	prefix := line[:len(line)-len(cmd.Args)]
+	var lines []string
+	for _, arg := range args {
+		lines = append(lines, prefix+arg)
+	}
+	return lines
+}
+
+// goToLine implements ":{line}", moving the cursor to the line like {line}G.
+func (v *DocumentView) goToLine(rng *command.Range) error {
+	line, _, err := v.editor.GetCurrentPosition()
+	if err != nil {
+		return err
+	}
+	total, err := v.editor.GetLineCount()
+	if err != nil {
+		return err
+	}
+	if err := v.editor.JumpToLine(rng.Line(line, total), false); err != nil {
+		return err
+	}
+	v.centerCursor()
+	return nil
+}
+
+// exEdit implements ":e {path}", opening a file or switching to it, and ":e!", reloading it from disk.
+func (v *DocumentView) exEdit(cmd command.Command) error {
+	if cmd.Args != "" {
+		if err := v.editor.OpenFile(expandPath(cmd.Args)); err != nil {
+			return err
+		}
+		if !cmd.Bang {
+			v.reportOpened()
+			return nil
+		}
+	}
+
+	if err := v.editor.ReloadCurrentBuffer(cmd.Bang); err != nil {
+		if errors.Is(err, editor.ErrUnsavedChanges) {
+			return fmt.Errorf("%w (add ! to override)", err)
+		}
+		return err
+	}
+	v.reportOpened()
+	return nil
+}
+
+// exBufferNext implements ":bn", switching to the next buffer.
+func (v *DocumentView) exBufferNext(cmd command.Command) error {
+	return v.cycleBuffer(1)
+}
+
+// exBufferPrev implements ":bp", switching to the previous buffer.
+func (v *DocumentView) exBufferPrev(cmd command.Command) error {
+	return v.cycleBuffer(-1)
+}
+
+// exBufferDelete implements ":bd", closing the current buffer unless it has unsaved changes, and ":bd!".
+func (v *DocumentView) exBufferDelete(cmd command.Command) error {
+	if err := v.editor.CloseCurrentBuffer(cmd.Bang); err != nil {
+		if errors.Is(err, editor.ErrUnsavedChanges) {
+			return fmt.Errorf("%w (add ! to override)", err)
+		}
+		return err
+	}
+	v.reportOpened()
+	return nil
+}
+
+// switchBuffer cycles through buffers from a key binding, reporting errors in the message line.
+func (v *DocumentView) switchBuffer(offset int) {
+	v.editor.SetError(v.cycleBuffer(offset))
+}
+
+// cycleBuffer switches offset buffers forward (or back) and shows the file now being edited.
+func (v *DocumentView) cycleBuffer(offset int) error {
+	if err := v.editor.CycleBuffer(offset); err != nil {
+		return err
+	}
+	v.reportOpened()
+	return nil
+}
+
+// exRecover implements ":recover", restoring the buffer from a swap file left behind by a run
+// that didn't exit cleanly.
+func (v *DocumentView) exRecover(cmd command.Command) error {
+	if err := v.editor.RecoverSwap(); err != nil {
+		return err
+	}
+	v.editor.SetMessage("Recovered from the swap file; :w keeps it, :e! discards it")
+	return nil
+}
+
+// exRegisters implements ":reg", showing the start of what each of "1 to "9 holds, with newlines
+// as ^J like vim.
+func (v *DocumentView) exRegisters(cmd command.Command) error {
+	var shown []string
+	for i, reg := range v.editor.NumberedRegisters() {
+		text := []rune(strings.ReplaceAll(reg.Text, "\n", "^J"))
+		if len(text) > registerPreview {
+			text = append(text[:registerPreview], []rune("...")...)
+		}
+		shown = append(shown, fmt.Sprintf("\"%d %s", i+1, string(text)))
+	}
+	if len(shown) == 0 {
+		return editor.ErrEmptyRegister
+	}
+	v.editor.SetMessage(strings.Join(shown, "  "))
+	return nil
+}
+
+// exReflow implements ":{range}reflow [width]", rewrapping the lines in range (the paragraph by default).
+func (v *DocumentView) exReflow(cmd command.Command) error {
+	width := v.cfg.Editor.TextWidth
+	if cmd.Args != "" {
+		n, err := strconv.Atoi(cmd.Args)
+		if err != nil || n <= 0 {
+			return fmt.Errorf("%w: %s", config.ErrInvalidArgument, cmd.Args)
+		}
+		width = n
+	}
+
+	line, _, err := v.editor.GetCurrentPosition()
+	if err != nil {
+		return err
+	}
+	first, last, err := v.editor.ParagraphAt(line)
+	if err != nil {
+		return err
+	}
+	if cmd.Range != nil {
+		total, err := v.editor.GetLineCount()
+		if err != nil {
+			return err
+		}
+		if first, last, err = cmd.Range.Resolve(line, total); err != nil {
+			return err
+		}
+	}
+
+	return v.editor.Reflow(first, last, width, v.indent())
+}
+
+// exQuit implements ":q", leaving the editor once asked what to do with unsaved changes, and ":q!",
+// dropping them; with split windows it closes the focused one instead.
+func (v *DocumentView) exQuit(cmd command.Command) error {
+	if v.window != nil && v.window("window_close") == nil {
+		return nil
+	}
+	if cmd.Bang {
+		v.exit()
+		return nil
+	}
+	v.ConfirmQuit()
+	return nil
+}
+
+// ConfirmQuit leaves the editor, first asking whether to save the buffers with unsaved changes, if
+// there are any, or drop them; any other answer stays.
+func (v *DocumentView) ConfirmQuit() {
+	paths := v.editor.ModifiedBuffers()
+	if len(paths) == 0 {
+		v.exit()
+		return
+	}
+	what := fmt.Sprintf("%q", filepath.Base(paths[0]))
+	if len(paths) > 1 {
+		what = fmt.Sprintf("%d buffers", len(paths))
+	}
+	v.cmdline.Confirm(fmt.Sprintf("save changes to %s before quitting (y/n/c)?", what), func(key rune) {
+		switch key {
+		case 'y':
+			v.editor.SetError(v.writeQuitAll(false))
+		case 'n':
+			v.exit()
+		}
+	})
+}
+
+// exit leaves the editor.
+func (v *DocumentView) exit() {
+	if v.quit != nil {
+		v.quit()
+	}
+}
+
+// exWriteQuitAll implements ":wqa", saving every buffer with unsaved changes and then leaving the
+// editor, and ":wqa!", saving read-only buffers and files changed on disk too.
+func (v *DocumentView) exWriteQuitAll(cmd command.Command) error {
+	return v.writeQuitAll(cmd.Bang)
+}
+
+// writeQuitAll saves every buffer with unsaved changes, then leaves the editor.
+func (v *DocumentView) writeQuitAll(force bool) error {
+	// written in the foreground, since the editor is about to go away
+	if err := v.editor.SaveModifiedBuffers(force); err != nil {
+		if errors.Is(err, editor.ErrReadOnlyBuffer) || errors.Is(err, editor.ErrChangedOnDisk) {
+			return fmt.Errorf("%w (add ! to override)", err)
+		}
+		return sudoHint(err)
+	}
+	v.exit()
+	return nil
+}
+
+// exWriteQuit implements ":wq", saving the buffer and then quitting like ":q".
+func (v *DocumentView) exWriteQuit(cmd command.Command) error {
+	if err := v.checkSave(cmd.Bang); err != nil {
+		return err
+	}
+	// written in the foreground, since the editor is about to go away
+	if err := v.editor.SaveCurrentBuffer(); err != nil {
+		return sudoHint(err)
+	}
+	return v.exQuit(cmd)
+}
+
+// exExit implements ":x", like ":wq" but only saving when there are changes.
+func (v *DocumentView) exExit(cmd command.Command) error {
+	if modified, _ := v.editor.Modified(); modified {
+		return v.exWriteQuit(cmd)
+	}
+	return v.exQuit(cmd)
+}
+
+// exOutput implements ":output", showing or hiding the output panel.
+func (v *DocumentView) exOutput(cmd command.Command) error {
+	if v.output == nil {
+		return ErrNoOutput
+	}
+	v.output.Toggle()
+	return nil
+}
+
+// exZen implements ":zen", toggling the distraction-free writing layout.
+func (v *DocumentView) exZen(cmd command.Command) error {
+	v.ToggleZen()
+	return nil
+}
+
+// reportOpened shows the name and size of the file being edited.
+func (v *DocumentView) reportOpened() {
+	path, _ := v.editor.FilePath()
+	if isNew, _ := v.editor.IsNewFile(); isNew {
+		v.editor.SetMessage(fmt.Sprintf("%q [New]", path))
+		return
+	}
+	lines, _ := v.editor.GetLineCount()
+	v.editor.SetMessage(fmt.Sprintf("%q %dL", path, lines))
+}
+
+// exWrite implements ":w", saving the buffer, and ":w {path}", writing a copy of it elsewhere.
+func (v *DocumentView) exWrite(cmd command.Command) error {
+	if cmd.Bang && cmd.Args == "!" {
+		return v.sudoWrite()
+	}
+
+	if cmd.Args == "" {
+		if err := v.checkSave(cmd.Bang); err != nil {
+			return err
+		}
+		path, _ := v.editor.FilePath()
+		lines, _ := v.editor.GetLineCount()
+
+		// written in the background, so saving a huge file doesn't freeze the screen
+		v.editor.SetMessage(fmt.Sprintf("writing %q...", path))
+		err := v.editor.SaveCurrentBufferInBackground(func(err error) {
+			if err != nil {
+				v.editor.SetError(fmt.Errorf("writing %q: %w", path, sudoHint(err)))
+				return
+			}
+			v.editor.SetMessage(fmt.Sprintf("%q %dL written", path, lines))
+		})
+		return sudoHint(err)
+	}
+
+	path := expandPath(cmd.Args)
+	if err := v.editor.WriteCurrentBufferTo(path, cmd.Bang); err != nil {
+		return err
+	}
+	v.reportWritten(path)
+	return nil
+}
+
+// checkSave refuses to save over the file of a read-only buffer, or one another program changed
+// since it was read, unless forced with !; writing a copy elsewhere is still allowed.
+func (v *DocumentView) checkSave(bang bool) error {
+	if bang {
+		return nil
+	}
+	if readOnly, _ := v.editor.ReadOnly(); readOnly {
+		return ErrReadOnly
+	}
+	if changed, _ := v.editor.ChangedOnDisk(); changed {
+		return ErrChangedOnDisk
+	}
+	return nil
+}
+
+// sudoWrite implements ":w!!", saving the buffer through "sudo tee" for files the user may not write.
+func (v *DocumentView) sudoWrite() error {
+	if v.runExternal == nil {
+		return ErrNoTerminal
+	}
+
+	err := v.editor.SaveCurrentBufferWith(func(path string, content io.Reader) error {
+		cmd := exec.Command("sudo", "tee", "--", path)
+		cmd.Stdin = content
+		cmd.Stdout = io.Discard
+		cmd.Stderr = os.Stderr
+		return v.runExternal(cmd)
+	})
+	if err != nil {
+		return fmt.Errorf("sudo write: %w", err)
+	}
+
+	path, _ := v.editor.FilePath()
+	v.reportWritten(path)
+	return nil
+}
+
+// sudoHint points at ":w!!" when a save failed for lack of permission.
+func sudoHint(err error) error {
+	if errors.Is(err, fs.ErrPermission) {
+		return fmt.Errorf("%w (use :w!! to write with sudo)", err)
+	}
+	return err
+}
+
+// exSaveAs implements ":saveas {path}", writing the buffer to path and editing that file from then on.
+func (v *DocumentView) exSaveAs(cmd command.Command) error {
+	if cmd.Args == "" {
+		return ErrArgumentRequired
+	}
+
+	if err := v.editor.SaveCurrentBufferAs(expandPath(cmd.Args), cmd.Bang); err != nil {
+		return err
+	}
+	path, _ := v.editor.FilePath()
+	v.reportWritten(path)
+	return nil
+}
+
+// reportWritten shows that the buffer was written to path.
+func (v *DocumentView) reportWritten(path string) {
+	lines, _ := v.editor.GetLineCount()
+	v.editor.SetMessage(fmt.Sprintf("%q %dL written", path, lines))
+}
+
+// expandPath expands a leading "~" in a path typed at the prompt to the home directory.
+func expandPath(path string) string {
+	if path != "~" && !strings.HasPrefix(path, "~/") {
+		return path
+	}
+	home, err := os.UserHomeDir()
+	if err != nil {
+		return path
+	}
+	return filepath.Join(home, path[1:])
+}
+
+// exSet implements ":set", changing editor options at runtime.
+func (v *DocumentView) exSet(cmd command.Command) error {
+	exprs := strings.Fields(cmd.Args)
+	if len(exprs) == 0 {
+		return ErrArgumentRequired
+	}
+
+	// readonly is the current buffer's own, so it is only passed through the config
+	v.cfg.Editor.ReadOnly, _ = v.editor.ReadOnly()
+	var shown []string
+	for _, expr := range exprs {
+		msg, err := config.SetOption(&v.cfg.Editor, expr)
+		if err != nil {
+			return err
+		}
+		if msg != "" {
+			shown = append(shown, msg)
+		}
+	}
+
+	v.editor.SetTabWidth(v.cfg.Editor.TabWidth)
+	_ = v.editor.SetReadOnly(v.cfg.Editor.ReadOnly)
+	if len(shown) > 0 {
+		v.editor.SetMessage(strings.Join(shown, "  "))
+	}
+	return nil
+}
+
+// exColorscheme implements ":colorscheme [name]", switching to a theme from the themes directory
+// or showing the current one.
+func (v *DocumentView) exColorscheme(cmd command.Command) error {
+	name := strings.TrimSpace(cmd.Args)
+	if name == "" {
+		current := v.cfg.Editor.Theme
+		if current == "" {
+			current = defaultThemeName
+		}
+		v.editor.SetMessage(current)
+		return nil
+	}
+
+	if name == defaultThemeName {
+		name = ""
+	}
+	if !v.cfg.HasTheme(name) {
+		return fmt.Errorf("%w: %s", ErrUnknownTheme, name)
+	}
+	v.cfg.Editor.Theme = name
+	return nil
+}
+
+// completeTheme offers the themes whose names start with arg.
+func (v *DocumentView) completeTheme(arg string) []string {
+	var names []string
+	for _, name := range append([]string{defaultThemeName}, v.cfg.ThemeNames()...) {
+		if strings.HasPrefix(name, arg) {
+			names = append(names, name)
+		}
+	}
+	return names
+}
+
+// exNormal implements ":{range}normal {keys}", replaying keys in normal mode at the start of each line.
+func (v *DocumentView) exNormal(cmd command.Command) error {
+	if cmd.Args == "" {
+		return ErrArgumentRequired
+	}
+
+	line, _, err := v.editor.GetCurrentPosition()
+	if err != nil {
+		return err
+	}
+	total, err := v.editor.GetLineCount()
+	if err != nil {
+		return err
+	}
+	startLine, endLine, err := cmd.Range.Resolve(line, total)
+	if err != nil {
+		return err
+	}
+
+	keys := ParseKeys(cmd.Args)
+	for lineIdx := startLine; lineIdx <= endLine; lineIdx++ {
+		// the keys may have deleted lines
+		start, _, err := v.editor.LineRange(lineIdx)
+		if err != nil {
+			break
+		}
+		if err := v.editor.MoveCursorTo(start, false); err != nil {
+			return err
+		}
+
+		v.editor.SetMode(state.Normal)
+		v.keyBuffer, v.numericPrefix = "", ""
+		for _, ev := range keys {
+			v.feedKey(ev)
+		}
+
+		// an unfinished command is abandoned, like vim does
+		if v.cmdline.Active() {
+			v.cmdline.HandleEvent(tcell.NewEventKey(tcell.KeyEscape, 0, tcell.ModNone))
+		}
+		v.keyBuffer, v.numericPrefix = "", ""
+		v.goToMenu.Hide()
+	}
+
+	v.editor.SetMode(state.Normal)
+	return nil
+}
+
+// searchOptions returns the search options currently configured.
+func (v *DocumentView) searchOptions() search.Options {
+	return search.Options{
+		IgnoreCase: v.cfg.Editor.Search.IgnoreCase,
+		SmartCase:  v.cfg.Editor.Search.SmartCase,
+		WrapScan:   v.cfg.Editor.Search.WrapScan,
+		Regex:      v.cfg.Editor.Search.Regex,
+	}
+}
+
+// reportSearch shows the outcome of a search in the message line.
+func (v *DocumentView) reportSearch(res editor.SearchResult, err error) {
+	if err != nil {
+		v.editor.SetError(err)
+		return
+	}
+
+	prefix := "/"
+	if !res.Forward {
+		prefix = "?"
+	}
+
+	msg := fmt.Sprintf("%s%s [%d/%d]", prefix, res.Pattern, res.Index, res.Total)
+	if !v.searchOptions().CaseSensitive(res.Pattern) {
+		msg += " (ignoring case)"
+	}
+	if res.Wrapped {
+		if res.Forward {
+			msg = "search hit BOTTOM, continuing at TOP  " + msg
+		} else {
+			msg = "search hit TOP, continuing at BOTTOM  " + msg
+		}
+	}
+	v.editor.SetMessage(msg)
+}
@@ -0,0 +1,90 @@
+package ui
+
+import (
+	"slices"
+
+	"github.com/lg2m/athena/internal/editor/state"
+)
+
+// completionMenuHeight is the most completion items shown at once.
+const completionMenuHeight = 10
+
+// handleCompletionKey moves through and accepts items of the open completion menu, reporting
+// whether it took key; other keys go on as typed and close the menu once they leave the word.
+func (v *DocumentView) handleCompletionKey(key string) bool {
+	if _, ok := v.editor.Completion(); !ok {
+		return false
+	}
+	switch key {
+	case "<c-n>", "<down>", "<tab>":
+		v.editor.SelectCompletion(1)
+	case "<c-p>", "<up>":
+		v.editor.SelectCompletion(-1)
+	case "<cr>":
+		v.editor.SetError(v.editor.AcceptCompletion())
+	case "<c-e>":
+		v.editor.CloseCompletion()
+	default:
+		return false
+	}
+	return true
+}
+
+// drawCompletion draws the completion menu under the word being completed, or above it when
+// there is no room below, with the characters of each label the word matched in bold.
+func (v *DocumentView) drawCompletion(screen Surface, theme *theme) {
+	menu, ok := v.editor.Completion()
+	if !ok || !v.cursor.shown || v.editor.GetMode() != state.Insert {
+		return
+	}
+	sel, err := v.editor.Selection()
+	if err != nil {
+		return
+	}
+	typed, err := v.editor.Substring(menu.Start, sel.End)
+	if err != nil {
+		return
+	}
+
+	rows := min(len(menu.Items), completionMenuHeight)
+	y := v.cursor.y + 1
+	if y+rows > v.y+v.height && v.cursor.y-rows >= v.y {
+		y = v.cursor.y - rows
+	}
+	// scrolled just far enough to show the selected item
+	first := max(0, menu.Selected-rows+1)
+	items := menu.Items[first : first+rows]
+
+	labels := make([]string, len(items))
+	width := 0
+	for i, item := range items {
+		labels[i] = " " + item.Label + " "
+		if item.Detail != "" {
+			labels[i] += " " + item.Detail + " "
+		}
+		width = max(width, len([]rune(labels[i])))
+	}
+	width = min(width, v.width)
+	// lined up with the start of the word, as far as the document allows
+	x := max(v.x, min(v.cursor.x-len([]rune(typed)), v.x+v.width-width))
+
+	for i, label := range labels {
+		style := theme.menu
+		if first+i == menu.Selected {
+			style = theme.menu.Reverse(true)
+		}
+		runes := []rune(label)
+		matched := menu.Matched[first+i]
+		for col := 0; col < width; col++ {
+			ch, cellStyle := ' ', style
+			if col < len(runes) {
+				ch = runes[col]
+			}
+			// labels start after a space
+			if slices.Contains(matched, col-1) {
+				cellStyle = style.Bold(true)
+			}
+			screen.SetContent(x+col, y+i, ch, nil, cellStyle)
+		}
+	}
+}
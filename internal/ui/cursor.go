@@ -0,0 +1,43 @@
+package ui
+
+import (
+	"github.com/gdamore/tcell/v2"
+	"github.com/lg2m/athena/internal/athena/config"
+	"github.com/lg2m/athena/internal/editor/state"
+)
+
+// screenCursor is where a view drew its cursor.
+type screenCursor struct {
+	x, y  int
+	shown bool // the cursor was in view
+}
+
+// terminalCursorStyles are the terminal cursor styles (set with DECSCUSR) for each cursor shape.
+var terminalCursorStyles = map[config.CursorShape]tcell.CursorStyle{
+	config.CursorBlock: tcell.CursorStyleSteadyBlock,
+	config.CursorBar:   tcell.CursorStyleSteadyBar,
+	config.CursorLine:  tcell.CursorStyleSteadyBar,
+	config.CursorUnder: tcell.CursorStyleSteadyUnderline,
+}
+
+// cursorShape returns the configured cursor shape for mode.
+func (v *DocumentView) cursorShape(mode state.EditorMode) config.CursorShape {
+	switch mode {
+	case state.Insert:
+		return v.cfg.Editor.CursorShape.Insert
+	default:
+		return v.cfg.Editor.CursorShape.Normal
+	}
+}
+
+// Cursor returns where the last frame put the cursor and the terminal cursor style for the
+// current mode; false means the cursor is out of view.
+//
+//	The terminal draws the cursor itself, so the character under it stays readable in every shape.
+func (v *DocumentView) Cursor() (int, int, tcell.CursorStyle, bool) {
+	style, ok := terminalCursorStyles[v.cursorShape(v.editor.GetMode())]
+	if !ok {
+		style = tcell.CursorStyleDefault
+	}
+	return v.cursor.x, v.cursor.y, style, v.cursor.shown
+}
@@ -0,0 +1,59 @@
+package ui
+
+import (
+	"github.com/gdamore/tcell/v2"
+	"github.com/lg2m/athena/internal/athena/config"
+)
+
+// DividerView draws the border between split windows: a column beside a window, or a row under
+// it naming the buffer it shows.
+type DividerView struct {
+	BaseView
+	cfg      *config.Config
+	vertical bool
+	label    string
+	focused  bool // the row belongs to the focused window
+}
+
+func NewDividerView(cfg *config.Config, vertical bool) *DividerView {
+	return &DividerView{cfg: cfg, vertical: vertical}
+}
+
+// SetLabel sets the name shown on a row divider and whether its window is focused.
+func (v *DividerView) SetLabel(label string, focused bool) {
+	v.label = label
+	v.focused = focused
+}
+
+// Draw implements the divider view.
+func (v *DividerView) Draw(screen Surface) {
+	theme := themeFor(v.cfg)
+	if v.vertical {
+		for y := v.y; y < v.y+v.height; y++ {
+			screen.SetContent(v.x, y, theme.box.vertical, nil, theme.gutter)
+		}
+		return
+	}
+
+	// the focused window's row stands out like the status bar, the others are dimmed
+	style := theme.gutter.Reverse(true)
+	if v.focused {
+		style = theme.statusBar
+	}
+	for x := v.x; x < v.x+v.width; x++ {
+		screen.SetContent(x, v.y, ' ', nil, style)
+	}
+	x := v.x + 1
+	for _, r := range v.label {
+		if x >= v.x+v.width {
+			break
+		}
+		screen.SetContent(x, v.y, r, nil, style)
+		x++
+	}
+}
+
+// HandleEvent implements View; dividers take no input.
+func (v *DividerView) HandleEvent(ev tcell.Event) bool {
+	return false
+}
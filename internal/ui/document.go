@@ -2,13 +2,20 @@ package ui
 
 import (
 	"fmt"
+	"os/exec"
 	"strconv"
+	"strings"
+	"time"
 	"unicode"
+	"unicode/utf8"
 
 	"github.com/gdamore/tcell/v2"
 	"github.com/lg2m/athena/internal/athena/config"
 	"github.com/lg2m/athena/internal/editor"
+	"github.com/lg2m/athena/internal/editor/format"
+	"github.com/lg2m/athena/internal/editor/search"
 	"github.com/lg2m/athena/internal/editor/state"
+	"github.com/lg2m/athena/internal/util"
 )
 
 // DocumentView represents the main document (or file) view.
@@ -17,88 +24,187 @@ type DocumentView struct {
 	editor   *editor.Editor
 	cfg      *config.Config
 	viewport *Viewport
+	cmdline  *CommandLineView
 
 	keyBuffer     string
+	keyBufferAt   time.Time // when the last key of keyBuffer arrived, see ExpirePendingKeys
 	numericPrefix string
+	awaitRegister string         // action waiting for a register or mark name, e.g. "play_macro"
+	awaitKeys     string         // keys of the waiting action, shown as pending
+	literal       *literalEntry  // insert-mode <c-v> waiting for what to insert, see literal.go
+	register      rune           // chosen with " for the next paste, 0 for the unnamed register
+	operator      *operator      // d, c or y waiting for its motion, see handleOperatorKey
+	surround      surroundTarget // what a ys, S or cs waiting for its delimiter applies to
+	replaying     bool           // a macro or a repeated change is being played back
+	change        typedChange    // command being typed, see trackChange
+	lastChange    []string       // keys of the last change, repeated by "."
+
+	// pasted text is inserted as is; auto-indent and the like must leave it alone too
+	pasting   bool            // inside a bracketed paste
+	pasted    strings.Builder // text of the bracketed paste so far
+	lastKeyAt time.Time       // when the previous key arrived, to spot pastes without brackets
+	burstGap  time.Duration   // see pasteBurstGap
+
+	// matches drawn over syntax highlighting, e.g. while confirming a substitution
+	matchHighlights []search.Match
+	currentMatch    *search.Match
+
+	zen bool // distraction-free layout, see ToggleZen
+
+	runExternal func(cmd *exec.Cmd) error       // runs a program with the terminal handed over to it
+	quit        func()                          // asks the editor to exit, see SetQuit
+	window      func(action string) error       // splits and switches windows, see SetWindowCommand
+	language    func(action string) error       // asks a language server about the cursor, see SetLanguageCommand
+	session     func(action, name string) error // saves and loads sessions, see SetSessionCommand
+
+	cursor screenCursor // where the last frame put the cursor, see Cursor
+	syntax *SyntaxCache // highlights shared with other windows, nil to compute them every frame
+	rows   *RowCache    // rows of lines drawn before, nil to lay every line out every frame
+	output *OutputView  // panel ":!" shows command output in, see SetOutput
 
 	goToMenu *GoToMenu
 }
 
-func NewDocumentView(e *editor.Editor, cfg *config.Config, v *Viewport) *DocumentView {
+func NewDocumentView(e *editor.Editor, cfg *config.Config, v *Viewport, cmdline *CommandLineView) *DocumentView {
 	return &DocumentView{
 		editor:   e,
 		cfg:      cfg,
 		viewport: v,
-		goToMenu: NewGoToMenu(cfg),
+		cmdline:  cmdline,
+		burstGap: pasteBurstGap,
+		goToMenu: NewGoToMenu(cfg, themeFor(cfg)),
 	}
 }
 
+// SetPasteBurstGap sets how closely keys must follow each other to be taken as pasted; zero turns
+// the detection off, for keys that are fed rather than typed.
+func (v *DocumentView) SetPasteBurstGap(gap time.Duration) {
+	v.burstGap = gap
+}
+
+// SetRunExternal sets how programs that need the terminal, like sudo asking for a password, are run.
+func (v *DocumentView) SetRunExternal(run func(cmd *exec.Cmd) error) {
+	v.runExternal = run
+}
+
+// SetQuit sets what ":q" and the like call to exit the editor.
+func (v *DocumentView) SetQuit(quit func()) {
+	v.quit = quit
+}
+
 // Draw implements the document view.
-func (v *DocumentView) Draw(screen tcell.Screen) {
-	currLine, currCol, _ := v.editor.GetCurrentPosition()
+func (v *DocumentView) Draw(screen Surface) {
+	currLine, currCol, _ := v.editor.GetVisualPosition()
 	total, _ := v.editor.GetLineCount()
+	tabWidth := v.editor.TabWidth()
 
 	// Update viewport to ensure cursor visibility
 	v.viewport.Update(currLine, v.height)
+	wrapWidth := v.wrapWidth()
+	if wrapWidth > 0 {
+		v.scrollToWrappedCursor(currLine, wrapWidth, tabWidth)
+		v.viewport.ResetLeft()
+	} else {
+		v.viewport.UpdateLeft(currCol, v.width)
+	}
+	left := v.viewport.Left()
+	// animations are off in accessibility mode
+	v.viewport.SetSmooth(v.cfg.Editor.SmoothScroll && !v.cfg.Editor.Accessibility)
+	v.viewport.Animate(v.height)
 
 	// Get visible range from viewport
-	start, end := v.viewport.VisibleRange(v.height, total)
+	start, _ := v.viewport.VisibleRange(v.height, total)
 
-	mode := v.editor.GetMode()
-	cursorShape := v.getCursorShape(mode)
+	// found again while the lines are laid out
+	v.cursor = screenCursor{}
 
 	// Get the current selection range
 	// selection, _ := v.editor.Selection()
 
-	highlights, err := v.editor.GetHighlights()
+	highlights, err := v.highlights()
 	if err != nil {
 		return
 	}
 
-	type HighlightRange struct {
-		StartCol int
-		EndCol   int
-		Style    tcell.Style
+	theme := themeFor(v.cfg)
+	if theme.text != tcell.StyleDefault {
+		for y := v.y; y < v.y+v.height; y++ {
+			for x := v.x; x < v.x+v.width; x++ {
+				screen.SetContent(x, y, ' ', nil, theme.text)
+			}
+		}
 	}
-
-	lineHighlightMap := make(map[int][]HighlightRange)
+	lineHighlightMap := make(map[int][]highlightRange)
 	for _, h := range highlights {
-		startLine := int(h.Start.Row)
-		startCol := int(h.Start.Column)
-		endLine := int(h.End.Row)
-		endCol := int(h.End.Column)
-		style := h.Style
-
-		for line := startLine; line <= endLine; line++ {
-			lineStartCol := 0
-			lineEndCol := -1
-			if line == startLine {
-				lineStartCol = startCol
-			}
-			if line == endLine {
-				lineEndCol = endCol
-			}
+		addHighlight(lineHighlightMap, int(h.Start.Row), int(h.Start.Column), int(h.End.Row), int(h.End.Column), theme.highlightStyle(h))
+	}
 
-			lineRanges, exists := lineHighlightMap[line]
-			if !exists {
-				lineRanges = []HighlightRange{}
-			}
+	// selections and search matches are drawn over syntax highlighting
+	if sel, err := v.editor.Selection(); err == nil && !sel.Empty() {
+		selStart, selEnd := sel.Range()
+		v.addPositionHighlight(lineHighlightMap, selStart, selEnd, theme.selection)
+	}
+	for _, m := range v.matchHighlights {
+		v.addPositionHighlight(lineHighlightMap, m.Start, m.End, theme.match)
+	}
+	if v.currentMatch != nil {
+		v.addPositionHighlight(lineHighlightMap, v.currentMatch.Start, v.currentMatch.End, theme.currentMatch)
+	}
+	if pair, ok := v.editor.PairAtCursor(); ok {
+		v.addPositionHighlight(lineHighlightMap, pair.Open.Start, pair.Open.End, theme.matchPair)
+		v.addPositionHighlight(lineHighlightMap, pair.Close.Start, pair.Close.End, theme.matchPair)
+	}
 
-			lineRanges = append(lineRanges, HighlightRange{
-				StartCol: lineStartCol,
-				EndCol:   lineEndCol,
-				Style:    style,
-			})
+	// problems found by a language server are underlined, keeping the style of the text
+	underlines := make(map[int][]highlightRange)
+	if diagnostics, err := v.editor.Diagnostics(); err == nil {
+		for _, d := range diagnostics {
+			v.addPositionHighlight(underlines, d.Start, d.End, tcell.StyleDefault)
+		}
+	}
 
-			lineHighlightMap[line] = lineRanges
+	// the cursor line and column get a background of their own, filled in before a row's text
+	cursorX := -1
+	if v.cfg.Editor.CursorColumn {
+		cursorX = v.cursorRowColumn(currLine, currCol, wrapWidth, tabWidth) - left
+	}
+	fillRow := func(surface Surface, y, lineIdx int) {
+		style := theme.onCursorLine(theme.text)
+		if v.cfg.Editor.CursorLine && lineIdx == currLine {
+			for x := v.x; x < v.x+v.width; x++ {
+				surface.SetContent(x, y, ' ', nil, style)
+			}
+		}
+		if cursorX >= 0 && cursorX < v.width {
+			surface.SetContent(v.x+cursorX, y, ' ', nil, style)
 		}
 	}
 
-	for i := 0; i < v.height; i++ {
-		lineIdx := start + i
-		if lineIdx >= end {
-			break
+	// lines drawn before and untouched since are drawn from their rows, all but the cursor's
+	var drawn map[rowKey]*drawnLine
+	if v.rows != nil {
+		drawn, _ = v.rows.lines()
+	}
+	layout := rowLayout{theme: theme, width: v.width, left: left, wrapWidth: wrapWidth, tabWidth: tabWidth, cursorX: cursorX, hyperlinks: v.cfg.Editor.Hyperlinks}
+
+	rows := make([]ViewRow, 0, v.height)
+	for lineIdx := start; lineIdx < total && len(rows) < v.height; lineIdx++ {
+		var key rowKey
+		surface := screen
+		var recorder *rowRecorder
+		if lineStart, lineEnd, err := v.editor.LineRange(lineIdx); err == nil && drawn != nil && lineIdx != currLine {
+			key = rowKey{span: state.Span{Start: lineStart, End: lineEnd + 1}, layout: layout}
+			if d, ok := drawn[key]; ok && d.matches(lineHighlightMap[lineIdx], underlines[lineIdx]) && len(rows)+d.rows <= v.height {
+				d.draw(screen, v.x, v.y+len(rows))
+				for row := range d.rows {
+					rows = append(rows, ViewRow{Line: lineIdx, Continuation: row > 0})
+				}
+				continue
+			}
+			recorder = &rowRecorder{Surface: screen, x: v.x, y: v.y + len(rows)}
+			surface = recorder
 		}
+		firstRow := len(rows)
 
 		line, err := v.editor.GetLine(lineIdx)
 		if err != nil {
@@ -108,7 +214,7 @@ func (v *DocumentView) Draw(screen tcell.Screen) {
 		runes := []rune(line)
 		styles := make([]tcell.Style, len(runes))
 		for j := range styles {
-			styles[j] = tcell.StyleDefault
+			styles[j] = theme.text
 		}
 
 		if lineRanges, exists := lineHighlightMap[lineIdx]; exists {
@@ -127,41 +233,239 @@ func (v *DocumentView) Draw(screen tcell.Screen) {
 			}
 		}
 
-		for x := range runes {
+		// tcell emits OSC 8 where the terminal supports it, so only the underline shows elsewhere
+		if v.cfg.Editor.Hyperlinks {
+			for _, u := range util.FindURLs(line) {
+				for j := u.Start; j < u.End && j < len(styles); j++ {
+					styles[j] = styles[j].Underline(true).Url(u.URL)
+				}
+			}
+		}
+		for _, r := range underlines[lineIdx] {
+			endCol := r.EndCol
+			if endCol == -1 || endCol > len(styles) {
+				endCol = len(styles)
+			}
+			for j := max(r.StartCol, 0); j < endCol; j++ {
+				styles[j] = styles[j].Underline(true)
+			}
+		}
+
+		// lay the line out in logical order first, so right-to-left runs can be mirrored per row
+		cells := make([]state.Cell, len(runes))
+		columns := make([]int, len(runes)) // logical visual column, which the cursor position uses
+		// a grapheme's later runes, like those joining an emoji sequence, share its first one's cells
+		widths := state.RuneWidths(line, tabWidth)
+		vx, rowStart, row := 0, 0, 0
+		rowFirst, breakAt, breakVx := 0, 0, 0 // first rune of the row, and the rune after its last blank
+		for x, r := range runes {
+			width := widths[x]
+
+			// continue on the next screen row once the wrap width is reached, taking the word
+			// since the last blank along as state.WrapLine does
+			if wrapWidth > 0 && vx > rowStart && vx-rowStart+width > wrapWidth && breakAt > rowFirst && !state.IsBlank(string(r)) {
+				row++
+				rowStart, rowFirst = breakVx, breakAt
+				for k := breakAt; k < x; k++ {
+					cells[k].Col, cells[k].Row = columns[k]-rowStart-left, row
+				}
+			}
+			if wrapWidth > 0 && vx > rowStart && vx-rowStart+width > wrapWidth {
+				row++
+				rowStart, rowFirst = vx, x
+			}
+			cells[x] = state.Cell{Col: vx - rowStart - left, Width: width, Row: row}
+			columns[x] = vx
+			vx += width
+			if state.IsBlank(string(r)) {
+				breakAt, breakVx = x+1, vx
+			}
+		}
+		state.ReorderRTL(runes, cells)
+
+		y := v.y + len(rows)
+		rows = append(rows, ViewRow{Line: lineIdx})
+		fillRow(surface, y, lineIdx)
+
+		row = 0
+		clipped := false
+		for x, r := range runes {
 			style := styles[x]
+			cell := cells[x]
+
+			if cell.Row > row {
+				if len(rows) >= v.height {
+					clipped = true
+					break
+				}
+				rows = append(rows, ViewRow{Line: lineIdx, Continuation: true})
+				y++
+				row = cell.Row
+				fillRow(surface, y, lineIdx)
+			}
+
+			// unwrapped lines scrolled sideways only show the characters wholly in view
+			if cell.Col < 0 || cell.Col+cell.Width > v.width {
+				continue
+			}
+
+			if lineIdx == currLine && columns[x] == currCol && cell.Width > 0 {
+				v.cursor = screenCursor{x: v.x + cell.Col, y: y, shown: true}
+			}
+			if (v.cfg.Editor.CursorLine && lineIdx == currLine) || (cursorX >= cell.Col && cursorX < cell.Col+cell.Width) {
+				style = theme.onCursorLine(style)
+			}
 
-			// apply cursor style if this is the cursor position
-			if lineIdx == currLine && x == currCol {
-				if mode == state.Normal {
-					style = v.getCursorStyle(cursorShape)
-				} else {
-					style = style.Reverse(true)
+			if r == '\t' {
+				// expand tabs to the next tab stop
+				for k := 0; k < cell.Width; k++ {
+					surface.SetContent(v.x+cell.Col+k, y, ' ', nil, style)
+				}
+			} else if cell.Width > 0 {
+				combining := combiningAfter(runes, cells, x)
+				switch g := string(r) + string(combining); {
+				case state.IsControl(g):
+					// caret notation, ^A for \x01 and ^? for delete
+					surface.SetContent(v.x+cell.Col, y, '^', nil, style)
+					surface.SetContent(v.x+cell.Col+1, y, r^0x40, nil, style)
+				case state.IsInvisible(g):
+					// drawn over a blank, so it keeps the cell it takes
+					surface.SetContent(v.x+cell.Col, y, ' ', []rune(g), style)
+				default:
+					surface.SetContent(v.x+cell.Col, y, r, combining, style)
 				}
 			}
+		}
 
-			screen.SetContent(v.x+x, v.y+i, runes[x], nil, style)
+		// the cursor can sit just past the end of the line
+		if endCol := vx - rowStart - left; lineIdx == currLine && currCol >= vx && !clipped && endCol >= 0 && endCol < v.width {
+			v.cursor = screenCursor{x: v.x + endCol, y: y, shown: true}
 		}
 
-		// Handle cursor at end of line
-		if lineIdx == currLine && currCol >= len(runes) {
-			style := tcell.StyleDefault
-			if mode == state.Normal {
-				style = v.getCursorStyle(cursorShape)
-			} else {
-				style = style.Reverse(true)
+		// a line cut off at the bottom is laid out again once it is wholly in view
+		if recorder != nil && !clipped {
+			drawn[key] = &drawnLine{
+				highlights: lineHighlightMap[lineIdx],
+				underlines: underlines[lineIdx],
+				rows:       len(rows) - firstRow,
+				cells:      recorder.cells,
+				drawn:      true,
 			}
-			screen.SetContent(v.x+len(runes), v.y+i, ' ', nil, style)
 		}
 	}
 
-	v.goToMenu.Draw(screen, v.height)
+	// rows past the end of the buffer
+	marker, _ := utf8.DecodeRuneInString(v.cfg.Editor.EOFMarker)
+	for len(rows) < v.height {
+		if marker != utf8.RuneError {
+			screen.SetContent(v.x, v.y+len(rows), marker, nil, theme.onText(theme.eofMarker))
+		}
+		rows = append(rows, ViewRow{Line: -1})
+	}
+	v.viewport.SetRows(rows)
+}
+
+// DrawOverlays draws the popups floating over the text drawn by Draw: the goto menu, completion
+// menu and hover documentation.
+//
+//	They may reach past the view, so they are drawn once everything else is, over other windows,
+//	their gutters and the status bar.
+func (v *DocumentView) DrawOverlays(screen Surface) {
+	theme := themeFor(v.cfg)
+	v.goToMenu.Draw(screen, v.x, v.y, v.width, v.height, theme)
+	v.drawCompletion(screen, theme)
+	v.drawHover(screen, theme)
+}
+
+// combiningAfter returns the zero-width runes following runes[i], which share its cell.
+func combiningAfter(runes []rune, cells []state.Cell, i int) []rune {
+	end := i + 1
+	for end < len(runes) && cells[end].Width == 0 && runes[end] != '\t' {
+		end++
+	}
+	if end == i+1 {
+		return nil
+	}
+	return runes[i+1 : end]
+}
+
+// highlightRange is a styled column range on a single line; an EndCol of -1 runs to the end of the line.
+type highlightRange struct {
+	StartCol int
+	EndCol   int
+	Style    tcell.Style
+}
+
+// addHighlight splits a possibly multi-line range into per-line ranges.
+func addHighlight(lineHighlightMap map[int][]highlightRange, startLine, startCol, endLine, endCol int, style tcell.Style) {
+	for line := startLine; line <= endLine; line++ {
+		lineStartCol := 0
+		lineEndCol := -1
+		if line == startLine {
+			lineStartCol = startCol
+		}
+		if line == endLine {
+			lineEndCol = endCol
+		}
+
+		lineHighlightMap[line] = append(lineHighlightMap[line], highlightRange{
+			StartCol: lineStartCol,
+			EndCol:   lineEndCol,
+			Style:    style,
+		})
+	}
+}
+
+// addPositionHighlight highlights the text between two buffer positions.
+func (v *DocumentView) addPositionHighlight(lineHighlightMap map[int][]highlightRange, start, end int, style tcell.Style) {
+	startLine, startCol, err := v.editor.LineCol(start)
+	if err != nil {
+		return
+	}
+	endLine, endCol, err := v.editor.LineCol(end)
+	if err != nil {
+		return
+	}
+	addHighlight(lineHighlightMap, startLine, startCol, endLine, endCol, style)
 }
 
 func (v *DocumentView) HandleEvent(ev tcell.Event) bool {
 	switch ev := ev.(type) {
+	case *tcell.EventPaste:
+		return v.handlePaste(ev)
 	case *tcell.EventKey:
+		if v.pasting {
+			if r, ok := pastedRune(ev); ok {
+				v.pasted.WriteRune(r)
+			}
+			return true
+		}
+		// a sequence that timed out isn't part of the command this key starts
+		v.ExpirePendingKeys(ev.When())
+		defer v.trackChange(getKeyString(ev))()
+		defer v.showPendingKeys()
+		if v.literal != nil && v.handleLiteralKey(ev) {
+			return true
+		}
+		if v.insertBurst(ev) {
+			return true
+		}
+
+		if v.awaitRegister != "" {
+			return v.handleRegisterKey(ev)
+		}
+		if v.operator != nil {
+			return v.handleOperatorKey(ev)
+		}
+
 		key := getKeyString(ev)
+		if v.closeHover(key) {
+			return true
+		}
 		mode := v.editor.GetMode()
+		if mode == state.Insert && v.handleCompletionKey(key) {
+			return true
+		}
 		var keymap map[string]config.KeyAction
 
 		switch mode {
@@ -178,11 +482,21 @@ func (v *DocumentView) HandleEvent(ev tcell.Event) bool {
 		}
 
 		v.keyBuffer += key
+		v.keyBufferAt = ev.When()
 
 		action, partial, matched := v.matchKeySequence(keymap)
 		if matched {
+			keys := v.keyBuffer
 			v.keyBuffer = ""
-			return v.executeAction(action)
+			handled := v.executeAction(action)
+			if v.awaitRegister != "" || v.literal != nil {
+				v.awaitKeys = keys
+			}
+			if v.operator != nil {
+				v.operator.keys = keys
+				v.awaitKeys = keys
+			}
+			return handled
 		} else if partial {
 			if v.keyBuffer[0] == 'g' && !v.goToMenu.visible {
 				v.goToMenu.Show()
@@ -199,7 +513,7 @@ func (v *DocumentView) HandleEvent(ev tcell.Event) bool {
 		} else {
 			v.keyBuffer = ""
 			if ev.Key() == tcell.KeyRune && mode == state.Insert {
-				_ = v.editor.InsertText(string(ev.Rune()))
+				_ = v.editor.TypeRune(ev.Rune(), v.autoPairs())
 				return true
 			}
 		}
@@ -214,77 +528,56 @@ func (v *DocumentView) matchKeySequence(keymap config.KeyMap) (string, bool, boo
 
 	if actionVal, exists := keymap[v.keyBuffer]; exists {
 		if actionStr, ok := actionVal.(string); ok {
-			return actionStr, true, true
+			return v.cfg.ResolveAlias(actionStr), true, true
 		}
 	}
 
-	firstKey := string(v.keyBuffer[0])
-	actionVal, exists := keymap[firstKey]
+	// follow nested maps one key at a time; keys like "<c-g>" span several bytes
+	keys := splitKeys(v.keyBuffer)
+	actionVal, exists := keymap[keys[0]]
+	for _, key := range keys[1:] {
+		nested, ok := actionVal.(map[string]interface{})
+		if !exists || !ok {
+			return "", false, false
+		}
+		actionVal, exists = nested[key]
+	}
 	if !exists {
-		// First key does not exist in keymap.
 		return "", false, false
 	}
 
 	switch val := actionVal.(type) {
+	case string:
+		return v.cfg.ResolveAlias(val), true, true
 	case map[string]interface{}:
-
-		if len(v.keyBuffer) == 1 {
-			// Only the first key is present; it's a partial match.
-			return "", true, false
-		}
-
-		secondKey := string(v.keyBuffer[1])
-		if secondAction, exists := val[secondKey]; exists {
-			if actionStr, ok := secondAction.(string); ok {
-				return actionStr, true, true
-			}
-			// If the secondAction exists but is not a string, it's an unexpected type.
-			return "", false, false
-		}
-
+		// more keys are needed
 		return "", true, false
-
 	default:
 		// Unsupported type encountered in keymap.
 		return "", false, false
 	}
+}
 
-	// if action, ok := keymap[v.keyBuffer]; ok {
-	// 	if s, isStr := action.(string); isStr {
-	// 		return s, true, true
-	// 	}
-	// }
-
-	// key := string(v.keyBuffer[0])
-
-	// action, exists := keymap[key]
-	// if !exists {
-	// 	return "", false, false
-	// }
-
-	// switch val := action.(type) {
-	// case map[string]interface{}:
-	// 	if len(v.keyBuffer) == 1 {
-	// 		// partial, not full
-	// 		return "", true, false
-	// 	}
-
-	// 	// We have a second key
-	// 	secondKey := string(v.keyBuffer[1])
-	// 	if secondAction, exists := val[secondKey]; exists {
-	// 		return secondAction.(string), true, true
-	// 	}
-
-	// 	return "", true, false
-
-	// case string:
-	// 	if len(v.keyBuffer) == 1 {
-	// 		_ = v.editor.InsertText(fmt.Sprintf("Matching key: %+v %+v\n", key, keymap))
-	// 		return val, true, true
-	// 	}
-	// }
+// ExpirePendingKeys drops a partly typed key sequence once the keys timeout has passed since its
+// last key, reporting whether it did.
+func (v *DocumentView) ExpirePendingKeys(now time.Time) bool {
+	deadline, ok := v.PendingKeysDeadline()
+	if !ok || now.Before(deadline) {
+		return false
+	}
+	v.keyBuffer = ""
+	v.goToMenu.Hide()
+	v.showPendingKeys()
+	return true
+}
 
-	// return "", false, false
+// PendingKeysDeadline returns when a partly typed key sequence will be dropped, if one is waiting
+// for its next key.
+func (v *DocumentView) PendingKeysDeadline() (time.Time, bool) {
+	if v.keyBuffer == "" || v.replaying || v.cfg.Keymap.Timeout == 0 {
+		return time.Time{}, false
+	}
+	return v.keyBufferAt.Add(time.Duration(v.cfg.Keymap.Timeout) * time.Millisecond), true
 }
 
 func (v *DocumentView) getNumericPrefixOrDefault(defaultValue int) int {
@@ -298,55 +591,24 @@ func (v *DocumentView) getNumericPrefixOrDefault(defaultValue int) int {
 	return defaultValue
 }
 
-func (v *DocumentView) executeAction(action string) bool {
-	switch action {
-	case "enter_insert_mode":
-		v.editor.SetMode(state.Insert)
-	case "enter_normal_mode":
-		v.editor.SetMode(state.Normal)
-	case "move_left":
-		_ = v.editor.MoveCursorHorizontal(-1, false)
-	case "move_right":
-		_ = v.editor.MoveCursorHorizontal(1, false)
-	case "move_down":
-		mult := v.getNumericPrefixOrDefault(1)
-		_ = v.editor.JumpFromCursor(mult, false)
-		v.centerCursor()
-	case "move_up":
-		mult := v.getNumericPrefixOrDefault(1)
-		_ = v.editor.JumpFromCursor(-mult, false)
-		v.centerCursor()
-	case "move_next_word":
-		_ = v.editor.MoveToNextWord(false)
-		v.centerCursor()
-	case "move_prev_word":
-		_ = v.editor.MoveToPrevWord(false)
-		v.centerCursor()
-	case "delete_backwards":
-		_ = v.editor.DeleteText(-1)
-	case "delete_forward":
-		_ = v.editor.DeleteText(1)
-	case "new_line":
-		_ = v.editor.InsertText("\n")
-	case "show_goto_menu":
-		v.goToMenu.Show()
-	case "go_to_top":
-		lineNum := v.getNumericPrefixOrDefault(1) - 1
-		if lineNum < 0 {
-			lineNum = 0
-		}
-		_ = v.editor.JumpToLine(lineNum, false)
-		v.centerCursor()
-		v.goToMenu.Hide()
-	case "go_to_bottom":
-		_ = v.editor.JumpToBottom(false)
-		v.centerCursor()
-		v.goToMenu.Hide()
-	default:
-		return false
+// goToLineEdge moves the cursor to the first or last character of its line.
+func (v *DocumentView) goToLineEdge(end bool) {
+	sel, err := v.editor.Selection()
+	if err != nil {
+		return
 	}
-	v.numericPrefix = ""
-	return true
+	line, _, err := v.editor.LineCol(sel.End)
+	if err != nil {
+		return
+	}
+	start, stop, err := v.editor.LineRange(line)
+	if err != nil {
+		return
+	}
+	if end {
+		start = max(start, stop-1)
+	}
+	_ = v.editor.MoveCursorTo(start, false)
 }
 
 func (v *DocumentView) centerCursor() {
@@ -362,58 +624,171 @@ func (v *DocumentView) centerCursor() {
 	}
 }
 
-func (v *DocumentView) getCursorShape(mode state.EditorMode) config.CursorShape {
-	switch mode {
-	case state.Insert:
-		return v.cfg.Editor.CursorShape.Insert
-	default:
-		return v.cfg.Editor.CursorShape.Normal
+// scrollHalfPage scrolls the view and moves the cursor by half a screen per count, like <c-d> and <c-u>.
+func (v *DocumentView) scrollHalfPage(count int) {
+	total, err := v.editor.GetLineCount()
+	if err != nil {
+		return
 	}
+
+	lines := max(1, v.height/2) * count
+	_ = v.editor.JumpFromCursor(lines, false)
+	v.viewport.offset = max(0, min(total-1, v.viewport.offset+lines))
 }
 
-func (v *DocumentView) getCursorStyle(shape config.CursorShape) tcell.Style {
-	style := tcell.StyleDefault
-	switch shape {
-	case config.CursorBlock:
-		return style.Reverse(true)
-	case config.CursorLine:
-		return style.Background(tcell.ColorWhiteSmoke)
-	case config.CursorUnder:
-		return style.Underline(true)
-	default:
-		return style.Reverse(true)
-	}
-}
-
-func getKeyString(ev *tcell.EventKey) string {
-	if ev.Modifiers()&tcell.ModCtrl != 0 && ev.Key() == tcell.KeyRune {
-		return fmt.Sprintf("<c-%c>", ev.Rune())
-	}
-
-	switch ev.Key() {
-	case tcell.KeyEscape:
-		return "<esc>"
-	case tcell.KeyEnter:
-		return "<cr>"
-	case tcell.KeyBackspace, tcell.KeyBackspace2:
-		return "<bs>"
-	case tcell.KeyDelete:
-		return "<del>"
-	case tcell.KeyTab:
-		return "<tab>"
-	case tcell.KeyLeft:
-		return "<left>"
-	case tcell.KeyRight:
-		return "<right>"
-	case tcell.KeyUp:
-		return "<up>"
-	case tcell.KeyDown:
-		return "<down>"
-	case tcell.KeyRune:
-		return string(ev.Rune())
-	default:
-		return ev.Name()
+// reflowParagraphs rewraps count paragraphs from the cursor at the text width, like gqap.
+func (v *DocumentView) reflowParagraphs(count int) {
+	line, _, err := v.editor.GetCurrentPosition()
+	if err != nil {
+		return
+	}
+	first, last, err := v.editor.ParagraphAt(line)
+	if err != nil {
+		return
+	}
+	total, _ := v.editor.GetLineCount()
+	for ; count > 1 && last+2 < total; count-- {
+		// take the blank separator and the paragraph after it
+		_, next, err := v.editor.ParagraphAt(last + 2)
+		if err != nil {
+			break
+		}
+		last = next
+	}
+
+	v.editor.SetError(v.editor.Reflow(first, last, v.cfg.Editor.TextWidth, v.indent()))
+}
+
+// indent returns how the current buffer's language is indented.
+func (v *DocumentView) indent() format.Indent {
+	fileType, _ := v.editor.FileType()
+	return v.cfg.Indent(fileType)
+}
+
+// comment returns the strings the current buffer's language is commented with.
+func (v *DocumentView) comment() format.Comment {
+	fileType, _ := v.editor.FileType()
+	return v.cfg.Comment(fileType)
+}
+
+// autoPairs returns the brackets and quotes typed in pairs in the current buffer's language.
+func (v *DocumentView) autoPairs() []string {
+	fileType, _ := v.editor.FileType()
+	return v.cfg.AutoPairs(fileType)
+}
+
+// insertNewLine breaks the line at the cursor; with auto-indent on the new line starts at the
+// indentation of the text before the cursor, a level deeper after an opening like "{".
+func (v *DocumentView) insertNewLine() {
+	text := "\n"
+	if v.cfg.Editor.Indent.Auto {
+		text += v.indentAfterCursor()
+	}
+	_ = v.editor.InsertText(text)
+}
+
+// indentAfterCursor returns the indentation for a line opened at the cursor.
+func (v *DocumentView) indentAfterCursor() string {
+	sel, err := v.editor.Selection()
+	if err != nil {
+		return ""
+	}
+	pos, _ := sel.Range()
+	line, _, err := v.editor.LineCol(pos)
+	if err != nil {
+		return ""
+	}
+	start, _, err := v.editor.LineRange(line)
+	if err != nil {
+		return ""
+	}
+	before, err := v.editor.Substring(start, pos)
+	if err != nil {
+		return ""
+	}
+	fileType, _ := v.editor.FileType()
+	return format.NextIndent(before, v.cfg.IndentAfter(fileType), v.indent())
+}
+
+// ToggleZen switches the distraction-free writing layout on or off.
+//
+//	Zen mode soft wraps the view's lines, leaving the soft-wrap setting and other windows alone;
+//	the layout itself is applied by the owner of the views, see Zen.
+func (v *DocumentView) ToggleZen() {
+	v.zen = !v.zen
+}
+
+// Zen reports whether the distraction-free writing layout is on.
+func (v *DocumentView) Zen() bool {
+	return v.zen
+}
+
+// wrapWidth returns the width lines are soft wrapped at, or zero when soft wrap is disabled and
+// the view isn't in zen mode.
+func (v *DocumentView) wrapWidth() int {
+	if !v.cfg.Editor.SoftWrap && !v.zen {
+		return 0
+	}
+	return v.width
+}
+
+// lineMovementWidth returns the wrap width j/k move by, or zero when they move by buffer line.
+func (v *DocumentView) lineMovementWidth() int {
+	if !v.cfg.Editor.DisplayLineMovement {
+		return 0
+	}
+	return v.wrapWidth()
+}
+
+// cursorRowColumn returns the display column of the cursor within its row, which is its column
+// in the line unless the line is soft wrapped.
+func (v *DocumentView) cursorRowColumn(currLine, currCol, wrapWidth, tabWidth int) int {
+	if wrapWidth <= 0 {
+		return currCol
+	}
+	line, err := v.editor.GetLine(currLine)
+	if err != nil {
+		return currCol
+	}
+	rows := state.WrapLine(line, wrapWidth, tabWidth)
+	rowStart := rows[state.RowOf(rows, state.ColumnAtVisual(line, currCol, tabWidth))]
+	return currCol - state.VisualColumn(line, rowStart, tabWidth)
+}
+
+// scrollToWrappedCursor scrolls down until the cursor line fits on screen when the lines above it wrap.
+func (v *DocumentView) scrollToWrappedCursor(currLine, wrapWidth, tabWidth int) {
+	for v.viewport.offset < currLine && v.wrappedRows(v.viewport.offset, currLine, wrapWidth, tabWidth) > v.height-v.viewport.padding {
+		v.viewport.offset++
+	}
+}
+
+// wrappedRows counts the screen rows needed to display lines from through to (inclusive).
+func (v *DocumentView) wrappedRows(from, to, wrapWidth, tabWidth int) int {
+	rows := 0
+	for lineIdx := from; lineIdx <= to; lineIdx++ {
+		line, err := v.editor.GetLine(lineIdx)
+		if err != nil {
+			continue
+		}
+		rows += len(state.WrapLine(line, wrapWidth, tabWidth))
+	}
+	return rows
+}
+
+// reportTextStats shows the line, word, character and byte counts of the selection or buffer.
+func (v *DocumentView) reportTextStats() {
+	stats, selected, err := v.editor.TextStats()
+	if err != nil {
+		v.editor.SetError(err)
+		return
+	}
+
+	scope := "Buffer"
+	if selected {
+		scope = "Selection"
 	}
+	v.editor.SetMessage(fmt.Sprintf("%s: %d lines, %d words, %d chars, %d bytes",
+		scope, stats.Lines, stats.Words, stats.Chars, stats.Bytes))
 }
 
 func isDigit(key string) bool {
@@ -428,12 +803,12 @@ type GoToMenu struct {
 	options []string
 }
 
-func NewGoToMenu(cfg *config.Config) *GoToMenu {
-	options := []string{"[g] → goto commands"}
+func NewGoToMenu(cfg *config.Config, theme *theme) *GoToMenu {
+	options := []string{fmt.Sprintf("[g] %s goto commands", theme.arrow)}
 	if gotoMappings, ok := cfg.Keymap.Normal["g"].(map[string]interface{}); ok {
 		for key, action := range gotoMappings {
 			if key != "default" {
-				option := fmt.Sprintf("  g%s → %s", key, action)
+				option := fmt.Sprintf("  g%s %s %s", key, theme.arrow, action)
 				options = append(options, option)
 			}
 		}
@@ -458,36 +833,37 @@ func (m *GoToMenu) Visible() bool {
 	return m.visible
 }
 
-func (m *GoToMenu) Draw(screen tcell.Screen, viewHeight int) {
+// Draw draws the menu in the bottom right corner of the view at x, y of the given size.
+func (m *GoToMenu) Draw(screen Surface, x, y, width, height int, theme *theme) {
 	if !m.visible {
 		return
 	}
 
 	// Position menu at bottom right
-	startY := viewHeight - len(m.options) - 1
-	w, _ := screen.Size()
-	startX := w - m.width - 2
+	startY := y + height - len(m.options) - 1
+	startX := x + width - m.width - 2
 
 	// Store position for potential future use
 	m.x = startX
 	m.y = startY
 
 	// Styles
-	style := tcell.StyleDefault.Background(tcell.ColorGray).Foreground(tcell.ColorWhite)
-	borderStyle := tcell.StyleDefault.Foreground(tcell.ColorWhite)
+	style := theme.menu
+	borderStyle := theme.menuBorder
+	box := theme.box
 
 	// Draw top border
-	screen.SetContent(startX, startY-1, '╭', nil, borderStyle)
-	screen.SetContent(startX+m.width+1, startY-1, '╮', nil, borderStyle)
+	screen.SetContent(startX, startY-1, box.topLeft, nil, borderStyle)
+	screen.SetContent(startX+m.width+1, startY-1, box.topRight, nil, borderStyle)
 	for x := startX + 1; x < startX+m.width+1; x++ {
-		screen.SetContent(x, startY-1, '─', nil, borderStyle)
+		screen.SetContent(x, startY-1, box.horizontal, nil, borderStyle)
 	}
 
 	// Draw options with background
 	for i, opt := range m.options {
 		y := startY + i
 		// Draw left border
-		screen.SetContent(startX, y, '│', nil, borderStyle)
+		screen.SetContent(startX, y, box.vertical, nil, borderStyle)
 
 		// Draw option text
 		runes := []rune(opt)
@@ -500,13 +876,13 @@ func (m *GoToMenu) Draw(screen tcell.Screen, viewHeight int) {
 		}
 
 		// Draw right border
-		screen.SetContent(startX+m.width+1, y, '│', nil, borderStyle)
+		screen.SetContent(startX+m.width+1, y, box.vertical, nil, borderStyle)
 	}
 
 	// Draw bottom border
-	screen.SetContent(startX, startY+len(m.options), '╰', nil, borderStyle)
-	screen.SetContent(startX+m.width+1, startY+len(m.options), '╯', nil, borderStyle)
+	screen.SetContent(startX, startY+len(m.options), box.bottomLeft, nil, borderStyle)
+	screen.SetContent(startX+m.width+1, startY+len(m.options), box.bottomRight, nil, borderStyle)
 	for x := startX + 1; x < startX+m.width+1; x++ {
-		screen.SetContent(x, startY+len(m.options), '─', nil, borderStyle)
+		screen.SetContent(x, startY+len(m.options), box.horizontal, nil, borderStyle)
 	}
 }
@@ -0,0 +1,166 @@
+package ui
+
+import (
+	"fmt"
+	"os"
+	"os/exec"
+	"strings"
+
+	"github.com/lg2m/athena/internal/editor/command"
+)
+
+// exFilter implements ":{range}!{cmd}", piping the lines through the shell command cmd and
+// replacing them with what it writes, e.g. ":%!sort"; without a range it runs cmd and shows what
+// it writes in the output panel.
+//
+//	Both run in the background, so the screen stays responsive; edits made before a filter
+//	finishes keep the lines as they are.
+func (v *DocumentView) exFilter(cmd command.Command) error {
+	if cmd.Args == "" {
+		return ErrArgumentRequired
+	}
+	if cmd.Range == nil {
+		return v.runInOutput(cmd.Args)
+	}
+
+	line, _, err := v.editor.GetCurrentPosition()
+	if err != nil {
+		return err
+	}
+	total, err := v.editor.GetLineCount()
+	if err != nil {
+		return err
+	}
+	first, last, err := cmd.Range.Resolve(line, total)
+	if err != nil {
+		return err
+	}
+	start, _, err := v.editor.LineRange(first)
+	if err != nil {
+		return err
+	}
+	_, end, err := v.editor.LineRange(last)
+	if err != nil {
+		return err
+	}
+
+	filter := func(text string) (string, error) {
+		out, err := runShell(cmd.Args, text+"\n")
+		return strings.TrimSuffix(out, "\n"), err
+	}
+	v.editor.SetMessage(fmt.Sprintf("running %s...", cmd.Args))
+	return v.editor.ReplaceInBackground(start, end, filter, func(err error) {
+		if err != nil {
+			v.editor.SetError(err)
+			return
+		}
+		v.editor.SetMessage(fmt.Sprintf("%d lines filtered through %s", last-first+1, cmd.Args))
+	})
+}
+
+// exRead implements ":r !{cmd}", putting what the shell command cmd writes below the cursor line,
+// or the last line of a range, and ":r {path}", putting the file there.
+func (v *DocumentView) exRead(cmd command.Command) error {
+	shell, isShell := strings.CutPrefix(cmd.Args, "!")
+	if cmd.Bang {
+		shell, isShell = cmd.Args, true
+	}
+	if cmd.Args == "" {
+		return ErrArgumentRequired
+	}
+
+	line, _, err := v.editor.GetCurrentPosition()
+	if err != nil {
+		return err
+	}
+	if cmd.Range != nil {
+		total, err := v.editor.GetLineCount()
+		if err != nil {
+			return err
+		}
+		if _, line, err = cmd.Range.Resolve(line, total); err != nil {
+			return err
+		}
+	}
+	_, end, err := v.editor.LineRange(line)
+	if err != nil {
+		return err
+	}
+
+	if isShell {
+		read := func(string) (string, error) {
+			out, err := runShell(shell, "")
+			return belowLine(out), err
+		}
+		v.editor.SetMessage(fmt.Sprintf("running %s...", shell))
+		return v.editor.ReplaceInBackground(end, end, read, func(err error) {
+			if err != nil {
+				v.editor.SetError(err)
+				return
+			}
+			v.editor.ClearMessage()
+		})
+	}
+
+	data, err := os.ReadFile(expandPath(cmd.Args))
+	if err != nil {
+		return err
+	}
+	if _, err := v.editor.Replace(end, end, belowLine(string(data))); err != nil {
+		return err
+	}
+	return v.editor.MoveCursorTo(end+1, false)
+}
+
+// belowLine returns text to insert at the end of a line so it goes on the lines below, nothing
+// when it is empty.
+func belowLine(text string) string {
+	if text == "" {
+		return ""
+	}
+	return "\n" + strings.TrimSuffix(text, "\n")
+}
+
+// runInOutput runs line with the user's shell in the background, showing what it writes to stdout
+// and stderr in the output panel as it goes.
+func (v *DocumentView) runInOutput(line string) error {
+	if v.output == nil {
+		return ErrNoOutput
+	}
+	cmd := shellCommand(line)
+	w, finish := v.output.Start(":!" + line)
+	cmd.Stdout, cmd.Stderr = w, w
+	if err := cmd.Start(); err != nil {
+		finish(err)
+		return err
+	}
+	go func() {
+		finish(cmd.Wait())
+	}()
+	return nil
+}
+
+// runShell runs line with the user's shell, feeding it input, and returns what it writes; when it
+// fails, the error carries the first line it wrote to stderr.
+func runShell(line, input string) (string, error) {
+	cmd := shellCommand(line)
+	cmd.Stdin = strings.NewReader(input)
+	var stdout, stderr strings.Builder
+	cmd.Stdout, cmd.Stderr = &stdout, &stderr
+	if err := cmd.Run(); err != nil {
+		if msg, _, _ := strings.Cut(strings.TrimSpace(stderr.String()), "\n"); msg != "" {
+			return "", fmt.Errorf("%s: %s", line, msg)
+		}
+		return "", fmt.Errorf("%s: %w", line, err)
+	}
+	return stdout.String(), nil
+}
+
+// shellCommand returns the command running line with the user's shell.
+func shellCommand(line string) *exec.Cmd {
+	shell := os.Getenv("SHELL")
+	if shell == "" {
+		shell = "sh"
+	}
+	return exec.Command(shell, "-c", line)
+}
@@ -0,0 +1,88 @@
+package ui
+
+import (
+	"fmt"
+	"io"
+	"os"
+	"path/filepath"
+
+	"github.com/lg2m/athena/internal/editor"
+	"github.com/lg2m/athena/internal/editor/command"
+	"github.com/lg2m/athena/internal/editor/grep"
+	"github.com/lg2m/athena/internal/editor/search"
+)
+
+// exGrep implements ":grep {pattern}", searching the files under the working directory.
+func (v *DocumentView) exGrep(cmd command.Command) error {
+	if cmd.Args == "" {
+		return ErrArgumentRequired
+	}
+	return v.grep(cmd.Args)
+}
+
+// openGrep opens the "grep: " prompt, searching the files under the working directory for the
+// pattern entered.
+func (v *DocumentView) openGrep() {
+	v.cmdline.Open("grep: ", func(pattern string) {
+		if pattern == "" {
+			return
+		}
+		v.editor.SetError(v.grep(pattern))
+	})
+}
+
+// grep searches the files under the working directory for the regular expression pattern in the
+// background, listing the matching lines in the output panel as they are found and filling the
+// quickfix list ]q and [q go through.
+//
+//	Case follows the search options, like "/".
+func (v *DocumentView) grep(pattern string) error {
+	opts := v.searchOptions()
+	opts.Regex = true
+	re, err := search.Compile(pattern, opts)
+	if err != nil {
+		return err
+	}
+
+	title := ":grep " + pattern
+	w, finish := io.Writer(io.Discard), func(error) {}
+	if v.output != nil {
+		w, finish = v.output.Start(title)
+	}
+	count := 0 // found is called by one goroutine at a time, and done after it
+	found := func(m grep.Match) {
+		count++
+		_, _ = fmt.Fprintf(w, "%s:%d:%d: %s\n", m.Path, m.Line+1, m.Col+1, m.Text)
+	}
+	done := func(err error) {
+		finish(err)
+		switch {
+		case err != nil:
+			v.editor.SetError(err)
+		case count == 0:
+			v.editor.SetError(fmt.Errorf("%w: %s", editor.ErrPatternNotFound, pattern))
+		default:
+			v.editor.SetMessage(fmt.Sprintf("%d matching lines, ]q goes to the first", count))
+		}
+	}
+
+	v.editor.SetMessage(fmt.Sprintf("searching for %s...", pattern))
+	return v.editor.Grep(title, ".", re, found, done)
+}
+
+// jumpToQuickfix moves the cursor count entries through the quickfix list, backwards when count is
+// negative, showing the entry landed on.
+func (v *DocumentView) jumpToQuickfix(count int) {
+	res, err := v.editor.JumpToQuickfix(count)
+	if err != nil {
+		v.editor.SetError(err)
+		return
+	}
+	path := res.Entry.Path
+	if wd, err := os.Getwd(); err == nil {
+		if rel, err := filepath.Rel(wd, path); err == nil {
+			path = rel
+		}
+	}
+	v.editor.SetMessage(fmt.Sprintf("(%d of %d) %s:%d: %s", res.Index, res.Total, path, res.Entry.Line+1, res.Entry.Text))
+}
@@ -21,55 +21,194 @@ func NewGuttersView(e *editor.Editor, cfg *config.Config, v *Viewport) *GuttersV
 }
 
 // Draw implements the gutter view.
-func (v *GuttersView) Draw(screen tcell.Screen) {
+func (v *GuttersView) Draw(screen Surface) {
+	if v.width <= 0 {
+		return
+	}
+
 	currLine, _, _ := v.editor.GetCurrentPosition()
 	total, _ := v.editor.GetLineCount()
 
 	start, _ := v.viewport.VisibleRange(v.height, total)
+	rows := v.viewport.Rows()
 
-	style := tcell.StyleDefault.Foreground(tcell.ColorPurple)
-	currStyle := tcell.StyleDefault.Foreground(tcell.ColorWhite)
+	theme := themeFor(v.cfg)
+	style := theme.gutter
+	currStyle := theme.gutterCurrent
+	columns := gutterColumns(v.cfg.Editor.Gutters, v.width)
+	signs := signLines(v.editor)
 
 	for i := 0; i < v.height; i++ {
-		lineNum := start + i + 1
+		row := ViewRow{Line: start + i}
+		if i < len(rows) {
+			// follow the rows laid out by the document, which may soft wrap
+			row = rows[i]
+		}
+		lineNum := row.Line + 1
 		y := i
 
 		var numStr string
 		lineStyle := style
 
-		if lineNum > total {
-			// Draw '~' for lines beyond the end of the file (EOF).
-			numStr = fmt.Sprintf("%*s", v.width-1, "~")
-		} else {
-			switch v.cfg.Editor.LineNumber {
-			case config.LineNumberAbsolute:
-				// Absolute numbering: display the actual line number.
+		if row.Line < 0 || lineNum > total || row.Continuation {
+			// Rows past the end of the buffer are marked in the document, and wrapped rows are left blank.
+			continue
+		}
+		switch v.cfg.Editor.LineNumber {
+		case config.LineNumberAbsolute:
+			// Absolute numbering: display the actual line number.
+			numStr = fmt.Sprintf("%*d", v.width-1, lineNum)
+			if lineNum == currLine+1 {
+				// Highlight the current line number.
+				lineStyle = currStyle
+			}
+		case config.LineNumberRelative:
+			if lineNum == currLine+1 {
+				// Current line: display absolute number with a distinct style.
 				numStr = fmt.Sprintf("%*d", v.width-1, lineNum)
-				if lineNum == currLine+1 {
-					// Highlight the current line number.
-					lineStyle = currStyle
-				}
-			case config.LineNumberRelative:
-				if lineNum == currLine+1 {
-					// Current line: display absolute number with a distinct style.
-					numStr = fmt.Sprintf("%*d", v.width-1, lineNum)
-					lineStyle = currStyle
-				} else {
-					// Relative numbering: display the distance from the current line.
-					distance := lineNum - (currLine + 1)
-					if distance < 0 {
-						distance = -distance
-					}
-					numStr = fmt.Sprintf("%*d", v.width-1, distance)
+				lineStyle = currStyle
+			} else {
+				// Relative numbering: display the distance from the current line.
+				distance := lineNum - (currLine + 1)
+				if distance < 0 {
+					distance = -distance
 				}
-			default:
-				numStr = ""
+				numStr = fmt.Sprintf("%*d", v.width-1, distance)
 			}
+		default:
+			numStr = ""
 		}
 
 		// Render the line number string on the screen.
 		for x, ch := range numStr {
 			screen.SetContent(v.x+x, v.y+y, ch, nil, lineStyle)
 		}
+
+		// Draw the most important sign of the line each sign column takes.
+		for x, column := range columns {
+			if sign, ok := topSign(signs[row.Line], column); ok {
+				ch, signStyle := theme.sign(sign)
+				screen.SetContent(v.x+x, v.y+y, ch, nil, signStyle)
+			}
+		}
+
+		// Mark the cursor line with more than color alone.
+		if theme.currentMarker != 0 && row.Line == currLine {
+			screen.SetContent(v.x+v.width-1, v.y+y, theme.currentMarker, nil, currStyle)
+		}
+	}
+}
+
+// HandleEvent runs the configured gutter-click action when a gutter column is clicked.
+func (v *GuttersView) HandleEvent(ev tcell.Event) bool {
+	mouse, ok := ev.(*tcell.EventMouse)
+	if !ok || mouse.Buttons() != tcell.Button1 {
+		return false
+	}
+	x, y := mouse.Position()
+	if x < v.x || x >= v.x+v.width || y < v.y || y >= v.y+v.height {
+		return false
+	}
+
+	rows := v.viewport.Rows()
+	if y-v.y >= len(rows) || rows[y-v.y].Line < 0 {
+		return true
+	}
+	line := rows[y-v.y].Line
+	column := gutterColumns(v.cfg.Editor.Gutters, v.width)[x-v.x]
+
+	start, end, err := v.editor.LineRange(line)
+	if err != nil {
+		return true
+	}
+	switch v.cfg.Editor.GutterClick[column] {
+	case config.GutterActionMoveCursor:
+		_ = v.editor.MoveCursorTo(start, false)
+	case config.GutterActionSelectLine:
+		_ = v.editor.MoveCursorTo(start, false)
+		_ = v.editor.MoveCursorTo(end, true)
+	case config.GutterActionShowDiagnostic:
+		v.showDiagnostic(line)
+	}
+	return true
+}
+
+// showDiagnostic shows the message of the most severe problem found on line, if any.
+func (v *GuttersView) showDiagnostic(line int) {
+	diagnostics, err := v.editor.Diagnostics()
+	if err != nil {
+		return
+	}
+	var shown *editor.Diagnostic
+	for i, d := range diagnostics {
+		// lower severities are more severe, errors first
+		if d.Line == line && (shown == nil || d.Severity < shown.Severity) {
+			shown = &diagnostics[i]
+		}
+	}
+	if shown != nil {
+		v.editor.SetMessage(shown.Message)
+	}
+}
+
+// gutterColumns returns the gutter element drawn in each of width columns; spacers and signs
+// take one column and line numbers the rest.
+func gutterColumns(gutters []config.GutterOption, width int) []config.GutterOption {
+	fixed := 0
+	for _, g := range gutters {
+		if g != config.GutterLineNumbers {
+			fixed++
+		}
+	}
+
+	columns := make([]config.GutterOption, 0, width)
+	for _, g := range gutters {
+		n := 1
+		if g == config.GutterLineNumbers {
+			n = max(0, width-fixed)
+		}
+		for ; n > 0 && len(columns) < width; n-- {
+			columns = append(columns, g)
+		}
+	}
+	for len(columns) < width {
+		columns = append(columns, config.GutterSpacer)
+	}
+	return columns
+}
+
+// signLines returns the signs of the current buffer by line.
+func signLines(e *editor.Editor) map[int][]editor.Sign {
+	lines := make(map[int][]editor.Sign)
+	for _, sign := range e.Signs() {
+		lines[sign.Line] = append(lines[sign.Line], sign)
+	}
+	return lines
+}
+
+// topSign returns the sign of the highest kind among signs that column takes; false when it
+// takes none of them.
+func topSign(signs []editor.Sign, column config.GutterOption) (editor.Sign, bool) {
+	var top editor.Sign
+	found := false
+	for _, sign := range signs {
+		if columnTakes(column, sign.Kind) && (!found || sign.Kind > top.Kind) {
+			top, found = sign, true
+		}
+	}
+	return top, found
+}
+
+// columnTakes reports whether a gutter column shows signs of kind.
+func columnTakes(column config.GutterOption, kind editor.SignKind) bool {
+	switch column {
+	case config.GutterSigns:
+		return true
+	case config.GutterDiagnostics:
+		return kind >= editor.SignHint
+	case config.GutterDiff:
+		return kind >= editor.SignAdded && kind <= editor.SignRemoved
+	default:
+		return false
 	}
 }
@@ -0,0 +1,38 @@
+package ui
+
+import (
+	"reflect"
+	"testing"
+
+	"github.com/lg2m/athena/internal/athena/config"
+)
+
+func TestGutterColumns(t *testing.T) {
+	const (
+		s = config.GutterSpacer
+		n = config.GutterLineNumbers
+		d = config.GutterDiff
+	)
+
+	tests := []struct {
+		name    string
+		gutters []config.GutterOption
+		width   int
+		want    []config.GutterOption
+	}{
+		{name: "default", gutters: []config.GutterOption{s, n, s}, width: 6, want: []config.GutterOption{s, n, n, n, n, s}},
+		{name: "diff first", gutters: []config.GutterOption{d, n}, width: 4, want: []config.GutterOption{d, n, n, n}},
+		{name: "no line numbers", gutters: []config.GutterOption{d, s}, width: 3, want: []config.GutterOption{d, s, s}},
+		{name: "too narrow", gutters: []config.GutterOption{s, d, n, s}, width: 2, want: []config.GutterOption{s, d}},
+		{name: "hidden", gutters: []config.GutterOption{s, n, s}, width: 0, want: []config.GutterOption{}},
+	}
+
+	for _, tt := range tests {
+		t.Run(tt.name, func(t *testing.T) {
+			got := gutterColumns(tt.gutters, tt.width)
+			if !reflect.DeepEqual(got, tt.want) {
+				t.Errorf("gutterColumns(%v, %d) = %v, want %v", tt.gutters, tt.width, got, tt.want)
+			}
+		})
+	}
+}
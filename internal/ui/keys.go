@@ -0,0 +1,158 @@
+package ui
+
+import (
+	"fmt"
+	"strings"
+	"unicode/utf8"
+
+	"github.com/gdamore/tcell/v2"
+)
+
+// namedKeys maps the "<name>" notation used in keymaps to terminal keys.
+//
+//	The first entry for a name is used when parsing notation.
+var namedKeys = []struct {
+	name string
+	key  tcell.Key
+}{
+	{name: "esc", key: tcell.KeyEscape},
+	{name: "cr", key: tcell.KeyEnter},
+	{name: "bs", key: tcell.KeyBackspace2},
+	{name: "bs", key: tcell.KeyBackspace},
+	{name: "del", key: tcell.KeyDelete},
+	{name: "tab", key: tcell.KeyTab},
+	{name: "left", key: tcell.KeyLeft},
+	{name: "right", key: tcell.KeyRight},
+	{name: "up", key: tcell.KeyUp},
+	{name: "down", key: tcell.KeyDown},
+	{name: "home", key: tcell.KeyHome},
+	{name: "end", key: tcell.KeyEnd},
+}
+
+// getKeyString converts a key event into keymap notation, e.g. "a", "<space>", "<esc>", "<c-w>" or "<s-left>".
+func getKeyString(ev *tcell.EventKey) string {
+	if ev.Key() == tcell.KeyRune {
+		switch {
+		case ev.Modifiers()&tcell.ModCtrl != 0:
+			return fmt.Sprintf("<c-%c>", ev.Rune())
+		case ev.Modifiers()&tcell.ModAlt != 0:
+			return fmt.Sprintf("<a-%c>", ev.Rune())
+		}
+	}
+
+	for _, nk := range namedKeys {
+		if ev.Key() == nk.key {
+			return "<" + modifierPrefix(ev.Modifiers()) + nk.name + ">"
+		}
+	}
+
+	// terminals report most control chords as dedicated keys rather than modified runes
+	if ev.Key() >= tcell.KeyCtrlA && ev.Key() <= tcell.KeyCtrlZ {
+		return fmt.Sprintf("<c-%c>", 'a'+rune(ev.Key()-tcell.KeyCtrlA))
+	}
+
+	if ev.Key() == tcell.KeyRune {
+		if ev.Rune() == ' ' {
+			// spelled out so it can start a sequence, e.g. a "<space>f" leader binding
+			return "<space>"
+		}
+		return string(ev.Rune())
+	}
+	return ev.Name()
+}
+
+// ParseKeys converts a string in keymap notation into the key events it describes.
+//
+//	"<lt>" stands for a literal "<"; unrecognised "<...>" sequences are taken literally.
+func ParseKeys(s string) []*tcell.EventKey {
+	var keys []*tcell.EventKey
+	for len(s) > 0 {
+		if s[0] == '<' {
+			if end := strings.IndexByte(s, '>'); end > 0 {
+				if ev, ok := parseNamedKey(strings.ToLower(s[1:end])); ok {
+					keys = append(keys, ev)
+					s = s[end+1:]
+					continue
+				}
+			}
+		}
+
+		r, size := utf8.DecodeRuneInString(s)
+		keys = append(keys, tcell.NewEventKey(tcell.KeyRune, r, tcell.ModNone))
+		s = s[size:]
+	}
+	return keys
+}
+
+// splitKeys splits a sequence in keymap notation into its keys, e.g. "g<c-g>" into "g" and "<c-g>".
+func splitKeys(s string) []string {
+	var keys []string
+	for len(s) > 0 {
+		if s[0] == '<' {
+			if end := strings.IndexByte(s, '>'); end > 0 {
+				if _, ok := parseNamedKey(strings.ToLower(s[1:end])); ok {
+					keys = append(keys, s[:end+1])
+					s = s[end+1:]
+					continue
+				}
+			}
+		}
+
+		_, size := utf8.DecodeRuneInString(s)
+		keys = append(keys, s[:size])
+		s = s[size:]
+	}
+	return keys
+}
+
+// modifierPrefix returns the notation for the modifiers held with a named key, e.g. "s-" for shift.
+func modifierPrefix(mod tcell.ModMask) string {
+	var prefix string
+	if mod&tcell.ModCtrl != 0 {
+		prefix += "c-"
+	}
+	if mod&tcell.ModAlt != 0 {
+		prefix += "a-"
+	}
+	if mod&tcell.ModShift != 0 {
+		prefix += "s-"
+	}
+	return prefix
+}
+
+// parseNamedKey converts the name inside "<...>" into a key event.
+func parseNamedKey(name string) (*tcell.EventKey, bool) {
+	switch name {
+	case "lt":
+		return tcell.NewEventKey(tcell.KeyRune, '<', tcell.ModNone), true
+	case "space":
+		return tcell.NewEventKey(tcell.KeyRune, ' ', tcell.ModNone), true
+	}
+
+	mod := tcell.ModNone
+	for len(name) > 2 && name[1] == '-' {
+		switch name[0] {
+		case 'c':
+			mod |= tcell.ModCtrl
+		case 'a':
+			mod |= tcell.ModAlt
+		case 's':
+			mod |= tcell.ModShift
+		default:
+			return nil, false
+		}
+		name = name[2:]
+	}
+
+	if mod != tcell.ModNone && utf8.RuneCountInString(name) == 1 {
+		r, _ := utf8.DecodeRuneInString(name)
+		return tcell.NewEventKey(tcell.KeyRune, r, mod), true
+	}
+
+	for _, nk := range namedKeys {
+		if nk.name == name {
+			return tcell.NewEventKey(nk.key, 0, mod), true
+		}
+	}
+	return nil, false
+}
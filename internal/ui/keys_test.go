@@ -0,0 +1,83 @@
+package ui
+
+import (
+	"strings"
+	"testing"
+
+	"github.com/gdamore/tcell/v2"
+)
+
+func TestParseKeys(t *testing.T) {
+	tests := []struct {
+		name  string
+		input string
+		want  []string
+	}{
+		{name: "plain", input: "dw", want: []string{"d", "w"}},
+		{name: "named", input: "A;<esc>j", want: []string{"A", ";", "<esc>", "j"}},
+		{name: "case insensitive", input: "<CR><Tab>", want: []string{"<cr>", "<tab>"}},
+		{name: "ctrl", input: "<c-w>", want: []string{"<c-w>"}},
+		{name: "alt", input: "<a-x>", want: []string{"<a-x>"}},
+		{name: "shift arrow", input: "<S-Left><s-down>", want: []string{"<s-left>", "<s-down>"}},
+		{name: "ctrl arrow", input: "<c-right>", want: []string{"<c-right>"}},
+		{name: "literal lt", input: "<lt>x", want: []string{"<", "x"}},
+		{name: "space", input: "a <Space>", want: []string{"a", "<space>", "<space>"}},
+		{name: "unknown name", input: "<foo>", want: []string{"<", "f", "o", "o", ">"}},
+		{name: "unicode", input: "iü", want: []string{"i", "ü"}},
+	}
+
+	for _, tt := range tests {
+		t.Run(tt.name, func(t *testing.T) {
+			var got []string
+			for _, ev := range ParseKeys(tt.input) {
+				got = append(got, getKeyString(ev))
+			}
+			if strings.Join(got, " ") != strings.Join(tt.want, " ") {
+				t.Errorf("ParseKeys(%q) = %q, want %q", tt.input, got, tt.want)
+			}
+		})
+	}
+}
+
+func TestSplitKeys(t *testing.T) {
+	tests := []struct {
+		input string
+		want  []string
+	}{
+		{input: "gg", want: []string{"g", "g"}},
+		{input: "g<c-g>", want: []string{"g", "<c-g>"}},
+		{input: "<c-w>+", want: []string{"<c-w>", "+"}},
+		{input: "<space>f", want: []string{"<space>", "f"}},
+		{input: "<", want: []string{"<"}},
+		{input: "<foo>", want: []string{"<", "f", "o", "o", ">"}},
+		{input: "dü", want: []string{"d", "ü"}},
+	}
+
+	for _, tt := range tests {
+		t.Run(tt.input, func(t *testing.T) {
+			got := splitKeys(tt.input)
+			if strings.Join(got, " ") != strings.Join(tt.want, " ") {
+				t.Errorf("splitKeys(%q) = %q, want %q", tt.input, got, tt.want)
+			}
+		})
+	}
+}
+
+func TestGetKeyStringControlKeys(t *testing.T) {
+	tests := []struct {
+		ev   *tcell.EventKey
+		want string
+	}{
+		{ev: tcell.NewEventKey(tcell.KeyCtrlD, 0, tcell.ModCtrl), want: "<c-d>"},
+		{ev: tcell.NewEventKey(tcell.KeyCtrlU, 0, tcell.ModCtrl), want: "<c-u>"},
+		{ev: tcell.NewEventKey(tcell.KeyRune, 'w', tcell.ModCtrl), want: "<c-w>"},
+		{ev: tcell.NewEventKey(tcell.KeyEnter, 0, tcell.ModNone), want: "<cr>"},
+		{ev: tcell.NewEventKey(tcell.KeyTab, 0, tcell.ModNone), want: "<tab>"},
+	}
+
+	for _, tt := range tests {
+		if got := getKeyString(tt.ev); got != tt.want {
+			t.Errorf("getKeyString(%v) = %q, want %q", tt.ev.Name(), got, tt.want)
+		}
+	}
+}
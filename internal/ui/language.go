@@ -0,0 +1,103 @@
+package ui
+
+import (
+	"errors"
+	"strings"
+)
+
+var ErrNoLanguageServer = errors.New("no language server for this file type")
+
+const (
+	hoverMaxWidth  = 80 // widest a hover popup gets, borders left out
+	hoverMaxHeight = 15 // most lines of documentation shown at once
+)
+
+// SetLanguageCommand sets what asks a language server about the current buffer: "complete" for
+// completions of the word before the cursor, "go_to_definition" and "hover" for the symbol at it.
+//
+//	Answers arrive later, through the editor, e.g. editor.SetCompletion and editor.SetHover.
+func (v *DocumentView) SetLanguageCommand(run func(action string) error) {
+	v.language = run
+}
+
+// runLanguageCommand runs a language server action; without a server, completions come from the
+// words of the open buffers.
+func (v *DocumentView) runLanguageCommand(action string) error {
+	err := ErrNoLanguageServer
+	if v.language != nil {
+		err = v.language(action)
+	}
+	if action == "complete" && errors.Is(err, ErrNoLanguageServer) {
+		return v.editor.CompleteWords()
+	}
+	return err
+}
+
+// closeHover closes the hover popup before key is handled, reporting whether key was spent on it;
+// only <esc> is, other keys go on as typed.
+func (v *DocumentView) closeHover(key string) bool {
+	if _, ok := v.editor.Hover(); !ok {
+		return false
+	}
+	v.editor.CloseHover()
+	return key == "<esc>"
+}
+
+// drawHover draws the hover popup under the cursor, or above it when there is no room below.
+func (v *DocumentView) drawHover(screen Surface, theme *theme) {
+	text, ok := v.editor.Hover()
+	if !ok || !v.cursor.shown {
+		return
+	}
+
+	var lines [][]rune
+	width := 0
+	for _, line := range strings.Split(text, "\n") {
+		// code fences only say which language a block is in
+		if strings.HasPrefix(strings.TrimSpace(line), "```") {
+			continue
+		}
+		runes := []rune(strings.ReplaceAll(line, "\t", "    "))
+		lines = append(lines, runes)
+		width = max(width, len(runes))
+	}
+	width = min(width, hoverMaxWidth, v.width-2)
+	rows := min(len(lines), hoverMaxHeight, v.height-2)
+	if width <= 0 || rows <= 0 {
+		return
+	}
+
+	// the box, borders included, spans top to top+rows+1
+	top := v.cursor.y + 1
+	if top+rows+2 > v.y+v.height && v.cursor.y-rows-2 >= v.y {
+		top = v.cursor.y - rows - 2
+	}
+	rows = min(rows, v.y+v.height-top-2)
+	if rows <= 0 {
+		return
+	}
+	left := max(v.x, min(v.cursor.x, v.x+v.width-width-2))
+	right, bottom := left+width+1, top+rows+1
+	box := theme.box
+
+	screen.SetContent(left, top, box.topLeft, nil, theme.menuBorder)
+	screen.SetContent(right, top, box.topRight, nil, theme.menuBorder)
+	screen.SetContent(left, bottom, box.bottomLeft, nil, theme.menuBorder)
+	screen.SetContent(right, bottom, box.bottomRight, nil, theme.menuBorder)
+	for x := left + 1; x < right; x++ {
+		screen.SetContent(x, top, box.horizontal, nil, theme.menuBorder)
+		screen.SetContent(x, bottom, box.horizontal, nil, theme.menuBorder)
+	}
+	for i, line := range lines[:rows] {
+		y := top + 1 + i
+		screen.SetContent(left, y, box.vertical, nil, theme.menuBorder)
+		screen.SetContent(right, y, box.vertical, nil, theme.menuBorder)
+		for col := 0; col < width; col++ {
+			ch := ' '
+			if col < len(line) {
+				ch = line[col]
+			}
+			screen.SetContent(left+1+col, y, ch, nil, theme.menu)
+		}
+	}
+}
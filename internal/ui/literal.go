@@ -0,0 +1,73 @@
+package ui
+
+import (
+	"errors"
+	"strconv"
+	"unicode/utf8"
+
+	"github.com/gdamore/tcell/v2"
+)
+
+var ErrInvalidCodePoint = errors.New("not a unicode code point")
+
+// maxCodePointDigits is how many hex digits <c-v>u takes at most, enough for any code point.
+const maxCodePointDigits = 6
+
+// literalEntry is an insert-mode <c-v> waiting for what to insert: the next key as it is, or
+// after "u" the code point of a character in hex, e.g. <c-v>u00e9 for é.
+type literalEntry struct {
+	hex    bool   // "u" was typed
+	digits string // hex digits typed so far
+}
+
+// handleLiteralKey takes a key typed after <c-v>, reporting whether it was; a key ending the
+// hex digits of a code point, after inserting it, isn't, and is handled as usual.
+func (v *DocumentView) handleLiteralKey(ev *tcell.EventKey) bool {
+	entry := v.literal
+	if !entry.hex {
+		v.literal, v.awaitKeys = nil, ""
+		switch {
+		case ev.Key() == tcell.KeyRune && ev.Rune() == 'u':
+			v.literal, v.awaitKeys = &literalEntry{hex: true}, "<c-v>u"
+		case ev.Key() == tcell.KeyEscape:
+			// cancels
+		default:
+			// inserted as is, whatever it is bound to or pairs with
+			if r, ok := pastedRune(ev); ok {
+				v.editor.SetError(v.editor.InsertText(normalizeNewlines(string(r))))
+			}
+		}
+		return true
+	}
+
+	if ev.Key() == tcell.KeyRune && isHexDigit(ev.Rune()) {
+		entry.digits += string(ev.Rune())
+		v.awaitKeys += string(ev.Rune())
+		if len(entry.digits) == maxCodePointDigits {
+			v.insertCodePoint(entry.digits)
+		}
+		return true
+	}
+	v.insertCodePoint(entry.digits)
+	// escape only ends the digits, staying in insert mode
+	return ev.Key() == tcell.KeyEscape
+}
+
+// insertCodePoint inserts the character whose code point digits are in hex, ending the entry.
+func (v *DocumentView) insertCodePoint(digits string) {
+	v.literal, v.awaitKeys = nil, ""
+	if digits == "" {
+		return
+	}
+	code, err := strconv.ParseUint(digits, 16, 32)
+	if err != nil || !utf8.ValidRune(rune(code)) || code == 0 {
+		v.editor.SetError(ErrInvalidCodePoint)
+		return
+	}
+	v.editor.SetError(v.editor.InsertText(string(rune(code))))
+}
+
+// isHexDigit reports whether r is a hex digit, in either case.
+func isHexDigit(r rune) bool {
+	return '0' <= r && r <= '9' || 'a' <= r && r <= 'f' || 'A' <= r && r <= 'F'
+}
@@ -0,0 +1,99 @@
+package ui
+
+import (
+	"errors"
+
+	"github.com/gdamore/tcell/v2"
+)
+
+var ErrRecursiveMacro = errors.New("cannot play a macro from a macro")
+
+// RecordKey adds a typed key to the macro being recorded, if any.
+//
+//	Called for every key the user types, whichever view handles it, so prompts are recorded too.
+func (v *DocumentView) RecordKey(ev *tcell.EventKey) {
+	if v.replaying {
+		return
+	}
+	v.editor.RecordKey(getKeyString(ev))
+}
+
+// handleRegisterKey finishes a command that was waiting for a register or mark name, e.g. "q",
+// "@", "m" or `"`, for the character r replaces with or for the delimiters of ys, ds and cs.
+func (v *DocumentView) handleRegisterKey(ev *tcell.EventKey) bool {
+	action, keys := v.awaitRegister, v.awaitKeys
+	v.awaitRegister, v.awaitKeys = "", ""
+
+	if ev.Key() != tcell.KeyRune {
+		// escape, or any other key, cancels
+		v.numericPrefix = ""
+		return true
+	}
+
+	switch action {
+	case "select_register":
+		v.register = ev.Rune()
+	case "replace_char":
+		v.editor.SetError(v.editor.ReplaceChars(ev.Rune(), v.getNumericPrefixOrDefault(1)))
+	case "record_macro":
+		v.editor.SetError(v.editor.StartRecording(ev.Rune()))
+	case "play_macro":
+		v.editor.SetError(v.playMacro(ev.Rune(), v.getNumericPrefixOrDefault(1)))
+	case "set_mark":
+		v.editor.SetError(v.editor.SetMark(ev.Rune()))
+	case "jump_to_mark", "jump_to_mark_line":
+		v.editor.SetError(v.editor.JumpToMark(ev.Rune(), action == "jump_to_mark_line"))
+		v.centerCursor()
+	case "surround_add":
+		v.editor.SetError(v.editor.Surround(v.surround.start, v.surround.end, ev.Rune()))
+	case "surround_delete":
+		v.editor.SetError(v.editor.DeleteSurround(ev.Rune()))
+	case "surround_change":
+		// then the delimiter to change it to
+		v.surround.from = ev.Rune()
+		v.awaitRegister, v.awaitKeys = "surround_change_to", keys+string(ev.Rune())
+		return true
+	case "surround_change_to":
+		v.editor.SetError(v.editor.ChangeSurround(v.surround.from, ev.Rune()))
+	}
+	v.numericPrefix = ""
+	return true
+}
+
+// playMacro replays the keys recorded in register count times.
+func (v *DocumentView) playMacro(register rune, count int) error {
+	if v.replaying {
+		return ErrRecursiveMacro
+	}
+
+	keys, err := v.editor.Macro(register)
+	if err != nil {
+		return err
+	}
+
+	v.replaying = true
+	defer func() { v.replaying = false }()
+
+	for i := 0; i < count; i++ {
+		for _, key := range keys {
+			for _, ev := range ParseKeys(key) {
+				v.feedKey(ev)
+			}
+		}
+	}
+	return nil
+}
+
+// feedKey handles ev as if it was typed, sending it to the command line while a prompt is open.
+func (v *DocumentView) feedKey(ev *tcell.EventKey) {
+	if v.cmdline.Active() {
+		v.cmdline.HandleEvent(ev)
+	} else {
+		v.HandleEvent(ev)
+	}
+}
+
+// showPendingKeys publishes the count and keys of an unfinished command for the status bar.
+func (v *DocumentView) showPendingKeys() {
+	v.editor.SetPendingKeys(v.numericPrefix + v.awaitKeys + v.keyBuffer)
+}
@@ -0,0 +1,289 @@
+package ui
+
+import (
+	"strconv"
+
+	"github.com/gdamore/tcell/v2"
+	"github.com/lg2m/athena/internal/editor/state"
+)
+
+// operator is a pending d, c, y, >, <, gc or ys, waiting for the motion or text object to apply to.
+type operator struct {
+	action string // "delete", "change", "yank", "indent", "dedent", "comment" or "surround"
+	keys   string // keys that started it; typed again, or their last one, apply it to whole lines, e.g. "dd", "gcc"
+	count  int    // count typed before the operator, 0 when none
+	object string // "i" or "a" while waiting for the kind of text object
+}
+
+// motionKind tells which text a motion covers when an operator applies to it.
+type motionKind uint8
+
+const (
+	exclusive motionKind = iota // from the cursor up to the motion's end, e.g. "w"
+	inclusive                   // the character at the motion's end too, e.g. "%"
+	linewise                    // every line from the cursor's to the motion's, e.g. "j"
+)
+
+// operatorMotions are the actions an operator can be applied to.
+var operatorMotions = map[string]motionKind{
+	"move_left":        exclusive,
+	"move_right":       exclusive,
+	"move_next_word":   exclusive,
+	"move_prev_word":   exclusive,
+	"go_to_line_start": exclusive,
+	"go_to_line_end":   inclusive,
+	"match_pair":       inclusive,
+	"search_next":      exclusive,
+	"search_prev":      exclusive,
+	"move_up":          linewise,
+	"move_down":        linewise,
+	"go_to_top":        linewise,
+	"go_to_bottom":     linewise,
+}
+
+// startOperator makes an operator wait for its motion; see handleOperatorKey.
+func (v *DocumentView) startOperator(action string) {
+	count := 0
+	if v.numericPrefix != "" {
+		count = v.getNumericPrefixOrDefault(1)
+	}
+	v.operator = &operator{action: action, count: count}
+}
+
+// startComment makes gc wait for its motion, or comments the lines of the selection right away
+// when there is one, as the shifted arrows extend it.
+func (v *DocumentView) startComment() {
+	v.goToMenu.Hide()
+	sel, err := v.editor.Selection()
+	if err != nil || sel.Empty() {
+		v.startOperator("comment")
+		return
+	}
+	start, end := sel.Range()
+	v.applyRange(&operator{action: "comment"}, start, end)
+}
+
+// handleOperatorKey takes the keys after an operator until they name a motion or text object,
+// then applies the operator to the text it covers; any other key cancels it.
+//
+//	Counts multiply, so "2d3w" deletes six words.
+func (v *DocumentView) handleOperatorKey(ev *tcell.EventKey) bool {
+	op := v.operator
+	key := getKeyString(ev)
+
+	switch {
+	case key == "<esc>":
+		v.finishOperator()
+		return true
+	case op.object != "":
+		v.finishOperator()
+		if key == "w" {
+			start, end, err := v.editor.WordObject(op.object == "a")
+			if err == nil {
+				v.applyRange(op, start, end)
+			}
+		}
+		return true
+	case v.keyBuffer == "" && key == "s" && (op.action == "yank" || op.action == "delete" || op.action == "change"):
+		v.startSurround(op)
+		return true
+	case v.keyBuffer == "" && isDigit(key) && (key != "0" || v.numericPrefix != ""):
+		v.numericPrefix += key
+		return true
+	case v.keyBuffer == "" && (key == "i" || key == "a"):
+		op.object = key
+		return true
+	}
+
+	v.keyBuffer += key
+	v.keyBufferAt = ev.When()
+	if keys := splitKeys(op.keys); v.keyBuffer == op.keys || len(keys) > 1 && v.keyBuffer == keys[len(keys)-1] {
+		count := v.operatorCount(op)
+		v.finishOperator()
+		v.applyCurrentLines(op, max(count, 1))
+		return true
+	}
+
+	action, partial, matched := v.matchKeySequence(v.cfg.Keymap.Normal)
+	switch {
+	case matched:
+		count := v.operatorCount(op)
+		v.finishOperator()
+		if kind, ok := operatorMotions[action]; ok {
+			v.applyMotion(op, action, kind, count)
+		}
+	case !partial:
+		v.finishOperator()
+	}
+	return true
+}
+
+// operatorCount returns the count for an operator's motion: the counts typed before and after
+// the operator multiplied, or 0 when neither was typed.
+func (v *DocumentView) operatorCount(op *operator) int {
+	if op.count == 0 && v.numericPrefix == "" {
+		return 0
+	}
+	return max(op.count, 1) * v.getNumericPrefixOrDefault(1)
+}
+
+// finishOperator leaves operator-pending mode.
+func (v *DocumentView) finishOperator() {
+	v.operator = nil
+	v.keyBuffer, v.numericPrefix, v.awaitKeys = "", "", ""
+}
+
+// applyMotion runs a motion count times and applies op to the text between the cursor and where
+// the motion went.
+func (v *DocumentView) applyMotion(op *operator, action string, kind motionKind, count int) {
+	sel, err := v.editor.Selection()
+	if err != nil {
+		return
+	}
+	from := sel.End
+
+	// a motion that fails, like % off any pair, shows why, and op is left undone
+	v.editor.ClearMessage()
+	if action == "go_to_top" || action == "go_to_bottom" {
+		// the count is a line number for these
+		if count > 0 {
+			v.numericPrefix = strconv.Itoa(count)
+		}
+		v.executeAction(action)
+	} else {
+		for i := 0; i < max(count, 1); i++ {
+			v.executeAction(action)
+		}
+	}
+
+	if v.editor.Message().IsError {
+		return
+	}
+	sel, err = v.editor.Selection()
+	if err != nil {
+		return
+	}
+	to := sel.End
+	lo, hi := min(from, to), max(from, to)
+
+	switch kind {
+	case linewise:
+		first, _, err := v.editor.LineCol(lo)
+		if err != nil {
+			return
+		}
+		last, _, err := v.editor.LineCol(hi)
+		if err != nil {
+			return
+		}
+		v.applyLines(op, first, last, lo)
+		return
+	case inclusive:
+		// the character at the end too, but not a newline, which $ stays on in an empty line
+		if line, _, err := v.editor.LineCol(hi); err == nil {
+			if _, lineEnd, err := v.editor.LineRange(line); err == nil && hi < lineEnd {
+				hi++
+			}
+		}
+	}
+
+	if action == "move_next_word" {
+		hi = v.wordMotionEnd(op, lo, hi)
+	}
+	v.applyRange(op, lo, hi)
+}
+
+// wordMotionEnd adjusts the end of the text a "w" covers like vim: it stops at the end of the
+// line, and "cw" leaves the blanks after the word alone.
+func (v *DocumentView) wordMotionEnd(op *operator, lo, hi int) int {
+	if line, _, err := v.editor.LineCol(lo); err == nil {
+		if _, lineEnd, err := v.editor.LineRange(line); err == nil && lo < lineEnd {
+			hi = min(hi, lineEnd)
+		}
+	}
+	if op.action == "change" {
+		for hi > lo+1 {
+			g, err := v.editor.Substring(hi-1, hi)
+			if err != nil || (g != " " && g != "\t") {
+				break
+			}
+			hi--
+		}
+	}
+	return hi
+}
+
+// applyCurrentLines applies op to count lines from the cursor line, as for "dd", "cc", "yy" and ">>".
+func (v *DocumentView) applyCurrentLines(op *operator, count int) {
+	sel, err := v.editor.Selection()
+	if err != nil {
+		return
+	}
+	line, _, err := v.editor.LineCol(sel.End)
+	if err != nil {
+		return
+	}
+	total, err := v.editor.GetLineCount()
+	if err != nil {
+		return
+	}
+	v.applyLines(op, line, min(line+count-1, total-1), sel.End)
+}
+
+// applyLines applies op to lines first through last; a yank leaves the cursor at pos.
+func (v *DocumentView) applyLines(op *operator, first, last, pos int) {
+	switch op.action {
+	case "delete":
+		v.editor.SetError(v.editor.DeleteLineRange(first, last))
+	case "change":
+		if err := v.editor.ChangeLines(first, last); err != nil {
+			v.editor.SetError(err)
+			return
+		}
+		v.editor.SetMode(state.Insert)
+	case "yank":
+		v.editor.SetError(v.editor.YankLineRange(first, last))
+		_ = v.editor.MoveCursorTo(pos, false)
+	case "indent":
+		v.editor.SetError(v.editor.ShiftLines(first, last, 1, v.indent()))
+	case "dedent":
+		v.editor.SetError(v.editor.ShiftLines(first, last, -1, v.indent()))
+	case "comment":
+		v.editor.SetError(v.editor.ToggleComment(first, last, v.comment()))
+	case "surround":
+		v.surroundLines(first, last)
+	}
+}
+
+// applyRange applies op to the text between start and end.
+func (v *DocumentView) applyRange(op *operator, start, end int) {
+	if op.action == "indent" || op.action == "dedent" || op.action == "comment" {
+		// shifting and commenting take the whole lines the text is on
+		first, _, err := v.editor.LineCol(start)
+		if err != nil {
+			return
+		}
+		last, _, err := v.editor.LineCol(max(start, end-1))
+		if err != nil {
+			return
+		}
+		v.applyLines(op, first, last, start)
+		return
+	}
+
+	switch op.action {
+	case "delete":
+		v.editor.SetError(v.editor.DeleteRange(start, end))
+	case "change":
+		if err := v.editor.DeleteRange(start, end); err != nil {
+			v.editor.SetError(err)
+			return
+		}
+		v.editor.SetMode(state.Insert)
+	case "yank":
+		v.editor.SetError(v.editor.YankRange(start, end))
+		_ = v.editor.MoveCursorTo(start, false)
+	case "surround":
+		v.surroundRange(start, end)
+	}
+}
@@ -0,0 +1,237 @@
+package ui
+
+import (
+	"fmt"
+	"io"
+	"strings"
+	"sync"
+
+	"github.com/gdamore/tcell/v2"
+	"github.com/lg2m/athena/internal/athena/config"
+)
+
+// outputHeight is the most rows the output panel takes, its title included; it takes a third of
+// the screen at most.
+const outputHeight = 12
+
+// OutputView is a panel along the bottom of the screen showing what a command writes, e.g. one
+// run with ":!", until the next one replaces it.
+//
+//	Commands write to it from any goroutine while they run, see Start; the panel follows the end
+//	of the output until it is scrolled up.
+type OutputView struct {
+	BaseView
+	cfg    *config.Config
+	wakeup func() // called when output arrives, so it is drawn
+
+	mu      sync.Mutex
+	title   string
+	lines   []string // the last one is still being written
+	running bool
+	err     error // how the command ended
+	run     int   // counts Start calls, so output of a replaced command is dropped
+	open    bool
+	offset  int  // first line shown
+	follow  bool // the offset keeps the last line in view
+}
+
+func NewOutputView(cfg *config.Config) *OutputView {
+	return &OutputView{cfg: cfg}
+}
+
+// SetWakeup sets the function called when output arrives, to redraw.
+func (v *OutputView) SetWakeup(wakeup func()) {
+	v.wakeup = wakeup
+}
+
+// Start opens the panel, cleared, for a command named title, returning the writer for its output
+// and the function to call once it ends.
+func (v *OutputView) Start(title string) (w io.Writer, finish func(err error)) {
+	v.mu.Lock()
+	defer v.mu.Unlock()
+
+	v.run++
+	v.title, v.lines, v.running, v.err = title, []string{""}, true, nil
+	v.open, v.offset, v.follow = true, 0, true
+
+	run := v.run
+	w = outputWriter{view: v, run: run}
+	finish = func(err error) {
+		v.mu.Lock()
+		if v.run == run {
+			v.running, v.err = false, err
+		}
+		v.mu.Unlock()
+		v.wake()
+	}
+	return w, finish
+}
+
+// outputWriter appends to the panel, as long as its command is the latest one.
+type outputWriter struct {
+	view *OutputView
+	run  int
+}
+
+// Write implements io.Writer.
+func (w outputWriter) Write(p []byte) (int, error) {
+	v := w.view
+	v.mu.Lock()
+	if v.run == w.run {
+		text := strings.ReplaceAll(string(p), "\r", "")
+		text = strings.ReplaceAll(text, "\t", strings.Repeat(" ", max(v.cfg.Editor.TabWidth, 1)))
+		parts := strings.Split(text, "\n")
+		v.lines[len(v.lines)-1] += parts[0]
+		v.lines = append(v.lines, parts[1:]...)
+	}
+	v.mu.Unlock()
+	v.wake()
+	return len(p), nil
+}
+
+// wake calls the wakeup function, if set.
+func (v *OutputView) wake() {
+	if v.wakeup != nil {
+		v.wakeup()
+	}
+}
+
+// Open reports whether the panel is shown.
+func (v *OutputView) Open() bool {
+	v.mu.Lock()
+	defer v.mu.Unlock()
+
+	return v.open
+}
+
+// Toggle shows or hides the panel, with the output of the last command.
+func (v *OutputView) Toggle() {
+	v.mu.Lock()
+	defer v.mu.Unlock()
+
+	v.open = !v.open
+}
+
+// Scroll moves the output shown by delta lines, following the end again once it is reached.
+func (v *OutputView) Scroll(delta int) {
+	v.mu.Lock()
+	defer v.mu.Unlock()
+
+	last := v.lastOffset()
+	v.offset = max(0, min(v.offset+delta, last))
+	v.follow = v.offset == last
+}
+
+// lastOffset returns the offset showing the end of the output; v.mu must be held.
+func (v *OutputView) lastOffset() int {
+	return max(0, len(v.outputLines())-(v.height-1))
+}
+
+// outputLines returns the lines to show, without the empty one a final newline leaves; v.mu must
+// be held.
+func (v *OutputView) outputLines() []string {
+	if n := len(v.lines); n > 0 && v.lines[n-1] == "" {
+		return v.lines[:n-1]
+	}
+	return v.lines
+}
+
+// Height returns the rows the panel takes out of available, none while it is hidden.
+func (v *OutputView) Height(available int) int {
+	if !v.Open() {
+		return 0
+	}
+	return min(outputHeight, available/3)
+}
+
+// Draw draws a title row, saying whether the command is still running or how it failed, over the
+// output.
+func (v *OutputView) Draw(screen Surface) {
+	v.mu.Lock()
+	defer v.mu.Unlock()
+
+	if !v.open || v.height == 0 {
+		return
+	}
+	theme := themeFor(v.cfg)
+
+	title := v.title
+	switch {
+	case v.running:
+		title += " (running)"
+	case v.err != nil:
+		title += fmt.Sprintf(" (%v)", v.err)
+	}
+	for x := v.x; x < v.x+v.width; x++ {
+		screen.SetContent(x, v.y, ' ', nil, theme.statusBar)
+	}
+	drawOutputRow(screen, v.x+1, v.y, v.width-1, title, theme.statusBar)
+
+	if v.follow {
+		v.offset = v.lastOffset()
+	}
+	lines := v.outputLines()
+	for row := 1; row < v.height; row++ {
+		for x := v.x; x < v.x+v.width; x++ {
+			screen.SetContent(x, v.y+row, ' ', nil, theme.text)
+		}
+		if i := v.offset + row - 1; i < len(lines) {
+			drawOutputRow(screen, v.x, v.y+row, v.width, lines[i], theme.text)
+		}
+	}
+}
+
+// drawOutputRow draws text from x, cut at width cells.
+func drawOutputRow(screen Surface, x, y, width int, text string, style tcell.Style) {
+	for _, r := range text {
+		if width <= 0 {
+			return
+		}
+		screen.SetContent(x, y, r, nil, style)
+		x++
+		width--
+	}
+}
+
+// HandleEvent scrolls the panel with the mouse wheel over it.
+func (v *OutputView) HandleEvent(ev tcell.Event) bool {
+	mouse, ok := ev.(*tcell.EventMouse)
+	if !ok || !v.Open() {
+		return false
+	}
+	x, y := mouse.Position()
+	if x < v.x || x >= v.x+v.width || y < v.y || y >= v.y+v.height {
+		return false
+	}
+	switch mouse.Buttons() {
+	case tcell.WheelUp:
+		v.Scroll(-3)
+	case tcell.WheelDown:
+		v.Scroll(3)
+	}
+	return true
+}
+
+// SetOutput sets the panel ":!" shows the output of commands in.
+func (v *DocumentView) SetOutput(output *OutputView) {
+	v.output = output
+}
+
+// toggleOutput shows or hides the output panel.
+func (v *DocumentView) toggleOutput() {
+	if v.output != nil {
+		v.output.Toggle()
+	}
+}
+
+// scrollOutput scrolls the output panel by half its height, down or up.
+func (v *DocumentView) scrollOutput(down bool) {
+	if v.output == nil {
+		return
+	}
+	delta := max(1, v.output.height/2)
+	if !down {
+		delta = -delta
+	}
+	v.output.Scroll(delta)
+}
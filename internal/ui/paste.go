@@ -0,0 +1,68 @@
+package ui
+
+import (
+	"strings"
+	"time"
+
+	"github.com/gdamore/tcell/v2"
+	"github.com/lg2m/athena/internal/editor/state"
+)
+
+// pasteBurstGap is how closely keys must follow each other in insert mode to be taken as pasted,
+// for terminals without bracketed paste; nobody types that fast, but a paste arrives all at once.
+const pasteBurstGap = 2 * time.Millisecond
+
+// handlePaste tracks the start and end of a bracketed paste; the text in between is collected
+// and inserted as is once the paste ends, so no key of it runs a mapping.
+func (v *DocumentView) handlePaste(ev *tcell.EventPaste) bool {
+	if ev.Start() {
+		v.pasting = true
+		v.pasted.Reset()
+		return true
+	}
+
+	v.pasting = false
+	if v.pasted.Len() > 0 {
+		v.editor.SetError(v.editor.InsertText(normalizeNewlines(v.pasted.String())))
+		v.pasted.Reset()
+	}
+	return true
+}
+
+// insertBurst inserts a key that arrived right after another in insert mode as typed, rather
+// than running its mapping, as terminals without bracketed paste send pasted text that way.
+func (v *DocumentView) insertBurst(ev *tcell.EventKey) bool {
+	burst := !v.replaying && v.editor.GetMode() == state.Insert && ev.When().Sub(v.lastKeyAt) < v.burstGap
+	v.lastKeyAt = ev.When()
+	if !burst {
+		return false
+	}
+
+	r, ok := pastedRune(ev)
+	if !ok {
+		return false
+	}
+	_ = v.editor.InsertText(normalizeNewlines(string(r)))
+	return true
+}
+
+// pastedRune returns the character a key stands for in pasted text.
+func pastedRune(ev *tcell.EventKey) (rune, bool) {
+	switch ev.Key() {
+	case tcell.KeyRune:
+		return ev.Rune(), true
+	case tcell.KeyEnter:
+		return '\r', true
+	case tcell.KeyLF:
+		return '\n', true
+	case tcell.KeyTab:
+		return '\t', true
+	}
+	return 0, false
+}
+
+// normalizeNewlines turns the CRLF and lone CR line endings terminals paste newlines as into LF.
+func normalizeNewlines(s string) string {
+	s = strings.ReplaceAll(s, "\r\n", "\n")
+	return strings.ReplaceAll(s, "\r", "\n")
+}
@@ -0,0 +1,24 @@
+package ui
+
+import "testing"
+
+func TestNormalizeNewlines(t *testing.T) {
+	tests := []struct {
+		input string
+		want  string
+	}{
+		{input: "a\nb", want: "a\nb"},
+		{input: "a\rb\r", want: "a\nb\n"},
+		{input: "a\r\nb\r\n", want: "a\nb\n"},
+		{input: "a\r\r\nb", want: "a\n\nb"},
+		{input: "\tx", want: "\tx"},
+	}
+
+	for _, tt := range tests {
+		t.Run(tt.input, func(t *testing.T) {
+			if got := normalizeNewlines(tt.input); got != tt.want {
+				t.Errorf("normalizeNewlines(%q) = %q, want %q", tt.input, got, tt.want)
+			}
+		})
+	}
+}
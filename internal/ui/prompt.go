@@ -0,0 +1,215 @@
+package ui
+
+import (
+	"slices"
+	"unicode"
+
+	"github.com/gdamore/tcell/v2"
+)
+
+// maxPromptHistory is the number of entries a prompt history keeps.
+const maxPromptHistory = 100
+
+// PromptEvent is what a key did to a prompt.
+type PromptEvent uint8
+
+const (
+	PromptEditing   PromptEvent = iota // the line was edited, or the key ignored
+	PromptAccepted                     // enter was pressed
+	PromptCancelled                    // escape was pressed, or backspace on an empty line
+)
+
+// PromptHistory holds the lines accepted at a kind of prompt, oldest first.
+type PromptHistory struct {
+	entries []string
+}
+
+// Add records an accepted line, moving it to the end if it was entered before.
+func (h *PromptHistory) Add(line string) {
+	if line == "" {
+		return
+	}
+	h.entries = slices.DeleteFunc(h.entries, func(e string) bool { return e == line })
+	h.entries = append(h.entries, line)
+	if len(h.entries) > maxPromptHistory {
+		h.entries = h.entries[1:]
+	}
+}
+
+// Prompt is a single line being typed, with cursor editing, history and tab completion.
+//
+//	It only models the line; the view showing it decides what accepting or cancelling does.
+type Prompt struct {
+	input  []rune
+	cursor int
+
+	history   *PromptHistory
+	histIndex int    // history entry shown, len(entries) for the line being typed
+	typed     []rune // line being typed, kept while browsing history
+
+	complete    func(input string) []string
+	completions []string // candidates being cycled through with tab, nil when not completing
+	compIndex   int
+}
+
+// NewPrompt returns an empty prompt browsing history, which may be nil.
+func NewPrompt(history *PromptHistory) *Prompt {
+	if history == nil {
+		history = &PromptHistory{}
+	}
+	return &Prompt{history: history, histIndex: len(history.entries)}
+}
+
+// SetCompleter sets the function offering completions for the typed text when tab is pressed.
+func (p *Prompt) SetCompleter(complete func(input string) []string) {
+	p.complete = complete
+}
+
+// Text returns the line typed so far.
+func (p *Prompt) Text() string {
+	return string(p.input)
+}
+
+// Cursor returns the rune index of the cursor within the line.
+func (p *Prompt) Cursor() int {
+	return p.cursor
+}
+
+// HandleKey edits the line, reporting whether it was accepted or cancelled.
+//
+//	An accepted line is added to the history.
+func (p *Prompt) HandleKey(key *tcell.EventKey) PromptEvent {
+	k := controlKey(key)
+	if k != tcell.KeyTab && k != tcell.KeyBacktab {
+		p.completions = nil
+	}
+
+	switch k {
+	case tcell.KeyTab:
+		p.completeNext(1)
+	case tcell.KeyBacktab:
+		p.completeNext(-1)
+	case tcell.KeyEscape:
+		return PromptCancelled
+	case tcell.KeyEnter:
+		p.history.Add(p.Text())
+		return PromptAccepted
+	case tcell.KeyBackspace, tcell.KeyBackspace2:
+		if len(p.input) == 0 {
+			// backspacing over the prefix cancels, like vim
+			return PromptCancelled
+		}
+		if p.cursor > 0 {
+			p.input = append(p.input[:p.cursor-1], p.input[p.cursor:]...)
+			p.cursor--
+		}
+	case tcell.KeyDelete:
+		if p.cursor < len(p.input) {
+			p.input = append(p.input[:p.cursor], p.input[p.cursor+1:]...)
+		}
+	case tcell.KeyCtrlW:
+		start := p.cursor
+		for start > 0 && p.input[start-1] == ' ' {
+			start--
+		}
+		for start > 0 && p.input[start-1] != ' ' {
+			start--
+		}
+		p.input = append(p.input[:start], p.input[p.cursor:]...)
+		p.cursor = start
+	case tcell.KeyCtrlU:
+		p.input = p.input[p.cursor:]
+		p.cursor = 0
+	case tcell.KeyLeft:
+		if p.cursor > 0 {
+			p.cursor--
+		}
+	case tcell.KeyRight:
+		if p.cursor < len(p.input) {
+			p.cursor++
+		}
+	case tcell.KeyHome, tcell.KeyCtrlA:
+		p.cursor = 0
+	case tcell.KeyEnd, tcell.KeyCtrlE:
+		p.cursor = len(p.input)
+	case tcell.KeyUp:
+		p.browseHistory(-1)
+	case tcell.KeyDown:
+		p.browseHistory(1)
+	case tcell.KeyRune:
+		if key.Modifiers()&(tcell.ModCtrl|tcell.ModAlt) != 0 {
+			break
+		}
+		p.input = slices.Insert(p.input, p.cursor, key.Rune())
+		p.cursor++
+	}
+	return PromptEditing
+}
+
+// controlKey returns the dedicated key for a control chord reported as a modified rune, e.g. "<c-w>".
+func controlKey(key *tcell.EventKey) tcell.Key {
+	r := unicode.ToLower(key.Rune())
+	if key.Key() == tcell.KeyRune && key.Modifiers()&tcell.ModCtrl != 0 && r >= 'a' && r <= 'z' {
+		return tcell.KeyCtrlA + tcell.Key(r-'a')
+	}
+	return key.Key()
+}
+
+// browseHistory shows the previous (or next) history entry starting with the line typed, like vim.
+func (p *Prompt) browseHistory(step int) {
+	entries := p.history.entries
+	if p.histIndex == len(entries) {
+		p.typed = slices.Clone(p.input)
+	}
+
+	for i := p.histIndex + step; i >= 0 && i <= len(entries); i += step {
+		if i == len(entries) {
+			p.setInput(p.typed)
+			p.histIndex = i
+			return
+		}
+		if hasRunePrefix([]rune(entries[i]), p.typed) {
+			p.setInput([]rune(entries[i]))
+			p.histIndex = i
+			return
+		}
+	}
+}
+
+// completeNext replaces the input with the next (or previous) completion candidate.
+func (p *Prompt) completeNext(step int) {
+	if p.complete == nil {
+		return
+	}
+
+	if p.completions == nil {
+		p.completions = p.complete(p.Text())
+		if len(p.completions) == 0 {
+			p.completions = nil
+			return
+		}
+		p.compIndex = 0
+		if step < 0 {
+			p.compIndex = len(p.completions) - 1
+		}
+	} else {
+		p.compIndex = (p.compIndex + step + len(p.completions)) % len(p.completions)
+	}
+
+	p.setInput([]rune(p.completions[p.compIndex]))
+
+	// a single candidate is accepted, so the next tab completes from it, e.g. into a directory
+	if len(p.completions) == 1 {
+		p.completions = nil
+	}
+}
+
+// setInput replaces the line, moving the cursor to its end.
+func (p *Prompt) setInput(input []rune) {
+	p.input = slices.Clone(input)
+	p.cursor = len(p.input)
+}
+
+func hasRunePrefix(s, prefix []rune) bool {
+	return len(s) >= len(prefix) && slices.Equal(s[:len(prefix)], prefix)
+}
@@ -0,0 +1,92 @@
+package ui
+
+import "testing"
+
+func TestPromptEditing(t *testing.T) {
+	tests := []struct {
+		name       string
+		keys       string
+		wantText   string
+		wantCursor int
+	}{
+		{name: "typing", keys: "abc", wantText: "abc", wantCursor: 3},
+		{name: "insert in middle", keys: "ac<left>b", wantText: "abc", wantCursor: 2},
+		{name: "backspace", keys: "abc<left><bs>", wantText: "ac", wantCursor: 1},
+		{name: "delete word", keys: "s/foo bar <c-w>", wantText: "s/foo ", wantCursor: 6},
+		{name: "delete to start", keys: "abcd<left><left><c-u>", wantText: "cd", wantCursor: 0},
+		{name: "home and end", keys: "bc<home>a<end>d", wantText: "abcd", wantCursor: 4},
+	}
+
+	for _, tt := range tests {
+		t.Run(tt.name, func(t *testing.T) {
+			p := NewPrompt(nil)
+			for _, ev := range ParseKeys(tt.keys) {
+				p.HandleKey(ev)
+			}
+			if p.Text() != tt.wantText || p.Cursor() != tt.wantCursor {
+				t.Errorf("after %q: text %q, cursor %d, want %q, %d", tt.keys, p.Text(), p.Cursor(), tt.wantText, tt.wantCursor)
+			}
+		})
+	}
+}
+
+func TestPromptEvents(t *testing.T) {
+	tests := []struct {
+		keys string
+		want PromptEvent
+	}{
+		{keys: "w<cr>", want: PromptAccepted},
+		{keys: "w<esc>", want: PromptCancelled},
+		{keys: "<bs>", want: PromptCancelled},
+		{keys: "w<bs>", want: PromptEditing},
+	}
+
+	for _, tt := range tests {
+		t.Run(tt.keys, func(t *testing.T) {
+			p := NewPrompt(nil)
+			var got PromptEvent
+			for _, ev := range ParseKeys(tt.keys) {
+				got = p.HandleKey(ev)
+			}
+			if got != tt.want {
+				t.Errorf("HandleKey after %q = %d, want %d", tt.keys, got, tt.want)
+			}
+		})
+	}
+}
+
+func TestPromptHistory(t *testing.T) {
+	history := &PromptHistory{}
+	for _, line := range []string{"set nu", "w", "set ts=8", "w"} {
+		p := NewPrompt(history)
+		for _, ev := range ParseKeys(line + "<cr>") {
+			p.HandleKey(ev)
+		}
+	}
+
+	tests := []struct {
+		name string
+		keys string
+		want string
+	}{
+		{name: "most recent", keys: "<up>", want: "w"},
+		{name: "older", keys: "<up><up>", want: "set ts=8"},
+		{name: "stops at oldest", keys: "<up><up><up><up><up>", want: "set nu"},
+		{name: "back to typed", keys: "x<up><down>", want: "x"},
+		{name: "prefix", keys: "se<up>", want: "set ts=8"},
+		{name: "prefix older", keys: "se<up><up>", want: "set nu"},
+		{name: "no match", keys: "q<up>", want: "q"},
+	}
+
+	for _, tt := range tests {
+		t.Run(tt.name, func(t *testing.T) {
+			p := NewPrompt(history)
+			for _, ev := range ParseKeys(tt.keys) {
+				p.HandleKey(ev)
+			}
+			if p.Text() != tt.want {
+				t.Errorf("after %q: text %q, want %q", tt.keys, p.Text(), tt.want)
+			}
+		})
+	}
+}
@@ -0,0 +1,89 @@
+package ui
+
+import (
+	"errors"
+
+	"github.com/lg2m/athena/internal/editor/state"
+)
+
+var ErrNoPreviousChange = errors.New("no previous change to repeat")
+
+// typedChange is the command being typed, kept as its keys until it is known whether it changed
+// the buffer.
+type typedChange struct {
+	keys    []string
+	path    string // buffer the command started in
+	version uint64 // version of the buffer then
+}
+
+// trackChange adds key to the command being typed, starting a new one when none is, and returns
+// what to run once key is handled: a finished command that changed the buffer becomes the one
+// "." repeats.
+//
+//	A command is finished when no more keys are awaited and insert mode, if it entered it, was
+//	left. Keys of a repeat or a macro being played aren't tracked; "@q" itself is, so "." plays
+//	the macro again.
+func (v *DocumentView) trackChange(key string) func() {
+	if v.replaying {
+		return func() {}
+	}
+	if !v.commandPending() && v.editor.GetMode() != state.Insert {
+		path, _ := v.editor.FilePath()
+		version, _ := v.editor.Version()
+		v.change = typedChange{path: path, version: version}
+	}
+	v.change.keys = append(v.change.keys, key)
+
+	return func() {
+		if v.commandPending() || v.editor.GetMode() == state.Insert {
+			return
+		}
+		change := v.change
+		v.change = typedChange{}
+		if len(change.keys) == 0 || v.cmdline.Active() {
+			// e.g. ":" or "/", which aren't repeated
+			return
+		}
+		// commands that left the buffer, like "gn", aren't changes even when its version moved
+		path, _ := v.editor.FilePath()
+		if version, _ := v.editor.Version(); path == change.path && version != change.version {
+			v.lastChange = change.keys
+		}
+	}
+}
+
+// repeatChange runs the keys of the last change again, like ".", with count replacing the count
+// it was typed with, if given.
+func (v *DocumentView) repeatChange(count string) error {
+	// "." itself isn't a change to remember
+	v.change = typedChange{}
+	if len(v.lastChange) == 0 {
+		return ErrNoPreviousChange
+	}
+
+	keys := v.lastChange
+	if count != "" {
+		// the count typed with the change is replaced
+		for len(keys) > 0 && isDigit(keys[0]) {
+			keys = keys[1:]
+		}
+		keys = append([]string{count}, keys...)
+	}
+
+	// a macro may repeat a change while it plays
+	replaying := v.replaying
+	v.replaying = true
+	defer func() { v.replaying = replaying }()
+
+	for _, key := range keys {
+		for _, ev := range ParseKeys(key) {
+			v.feedKey(ev)
+		}
+	}
+	return nil
+}
+
+// commandPending reports whether a command is waiting for more keys, e.g. after a count or "d".
+func (v *DocumentView) commandPending() bool {
+	return v.keyBuffer != "" || v.numericPrefix != "" || v.operator != nil || v.awaitRegister != "" || v.literal != nil
+}
@@ -0,0 +1,169 @@
+package ui
+
+import (
+	"slices"
+
+	"github.com/gdamore/tcell/v2"
+	"github.com/lg2m/athena/internal/editor"
+	"github.com/lg2m/athena/internal/editor/state"
+)
+
+// RowCache keeps the rows the windows drew for the lines of each buffer between frames, shared by
+// the windows showing it, so a frame only lays out again the lines an edit touched or whose
+// highlights changed, rather than every row in view.
+type RowCache struct {
+	editor  *editor.Editor
+	entries map[string]*rowEntry // by buffer path
+}
+
+// rowEntry is the rows drawn for the lines of a buffer at a version of it.
+type rowEntry struct {
+	version uint64
+	lines   map[rowKey]*drawnLine
+}
+
+// rowKey is a line drawn in a layout. The line is the span of its text, newline included, which
+// the edits since are mapped over, so only text they left alone keeps its rows.
+type rowKey struct {
+	span   state.Span
+	layout rowLayout
+}
+
+// rowLayout is what the rows of every line drawn in a frame depend on, other than the line.
+type rowLayout struct {
+	theme      *theme
+	width      int
+	left       int // first column in view of unwrapped lines
+	wrapWidth  int
+	tabWidth   int
+	cursorX    int // column of the cursor column, -1 without one
+	hyperlinks bool
+}
+
+// drawnLine is the rows a line was drawn in, with the highlights it was drawn with.
+type drawnLine struct {
+	highlights []highlightRange
+	underlines []highlightRange
+	rows       int
+	cells      []drawnCell
+	drawn      bool // drawn in the frame being drawn, see EndFrame
+}
+
+// drawnCell is a character drawn for a line, placed from the start of the line's first row.
+type drawnCell struct {
+	x, y      int
+	primary   rune
+	combining []rune
+	style     tcell.Style
+}
+
+// NewRowCache creates a cache for the buffers of e, keeping the rows of the lines each change to a
+// buffer leaves alone.
+func NewRowCache(e *editor.Editor) *RowCache {
+	c := &RowCache{editor: e, entries: make(map[string]*rowEntry)}
+	editor.Subscribe(e, c.bufferChanged)
+	editor.Subscribe(e, c.bufferClosed)
+	return c
+}
+
+// bufferChanged moves the rows of the buffer that changed to where its lines went, dropping those
+// of lines the changes touched.
+//
+//	A line an edit inserted text at the start of keeps its rows under a span that no longer starts
+//	a line, and they are dropped at the end of the frame when nothing draws them.
+func (c *RowCache) bufferChanged(ev editor.BufferChanged) {
+	entry, ok := c.entries[ev.Path]
+	if !ok {
+		return
+	}
+	// a change missed, like one made before the entry was made, leaves nothing to go by
+	if entry.version+1 != ev.Version {
+		delete(c.entries, ev.Path)
+		return
+	}
+
+	lines := make(map[rowKey]*drawnLine, len(entry.lines))
+	for key, line := range entry.lines {
+		span, ok := ev.Changes.MapSpan(key.span)
+		if !ok || span.End-span.Start != key.span.End-key.span.Start {
+			continue
+		}
+		key.span = span
+		lines[key] = line
+	}
+	entry.version, entry.lines = ev.Version, lines
+}
+
+// bufferClosed drops the rows of the buffer closed.
+func (c *RowCache) bufferClosed(ev editor.BufferClosed) {
+	delete(c.entries, ev.Path)
+}
+
+// lines returns the rows drawn for the lines of the editor's current buffer, to look up and add to.
+//
+//	The version is checked too, as for SyntaxCache.Highlights.
+func (c *RowCache) lines() (map[rowKey]*drawnLine, error) {
+	path, err := c.editor.FilePath()
+	if err != nil {
+		return nil, err
+	}
+	version, err := c.editor.Version()
+	if err != nil {
+		return nil, err
+	}
+	entry, ok := c.entries[path]
+	if !ok || entry.version != version {
+		entry = &rowEntry{version: version, lines: make(map[rowKey]*drawnLine)}
+		c.entries[path] = entry
+	}
+	return entry.lines, nil
+}
+
+// EndFrame drops the rows of the lines no window drew in the frame just drawn, so only what is in
+// view is kept.
+func (c *RowCache) EndFrame() {
+	for path, entry := range c.entries {
+		for key, line := range entry.lines {
+			if !line.drawn {
+				delete(entry.lines, key)
+			}
+			line.drawn = false
+		}
+		if len(entry.lines) == 0 {
+			delete(c.entries, path)
+		}
+	}
+}
+
+// matches reports whether the line was drawn with the highlights it has now.
+func (l *drawnLine) matches(highlights, underlines []highlightRange) bool {
+	return slices.Equal(l.highlights, highlights) && slices.Equal(l.underlines, underlines)
+}
+
+// draw draws the line's rows again, the first of them at x, y.
+func (l *drawnLine) draw(surface Surface, x, y int) {
+	for _, cell := range l.cells {
+		surface.SetContent(x+cell.x, y+cell.y, cell.primary, cell.combining, cell.style)
+	}
+	l.drawn = true
+}
+
+// rowRecorder is a Surface that draws on another, keeping what is drawn for a line whose first
+// row is at x, y.
+type rowRecorder struct {
+	Surface
+	x, y  int
+	cells []drawnCell
+}
+
+// SetContent draws a character, and keeps it.
+func (r *rowRecorder) SetContent(x, y int, primary rune, combining []rune, style tcell.Style) {
+	r.Surface.SetContent(x, y, primary, combining, style)
+	r.cells = append(r.cells, drawnCell{x: x - r.x, y: y - r.y, primary: primary, combining: slices.Clone(combining), style: style})
+}
+
+// SetRowCache sets the cache the view takes the rows of lines it drew before from; without one
+// every line in view is laid out for every frame.
+func (v *DocumentView) SetRowCache(c *RowCache) {
+	v.rows = c
+}
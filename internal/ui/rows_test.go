@@ -0,0 +1,76 @@
+package ui
+
+import (
+	"os"
+	"path/filepath"
+	"slices"
+	"testing"
+
+	"github.com/lg2m/athena/internal/editor"
+	"github.com/lg2m/athena/internal/editor/state"
+)
+
+func TestRowCache(t *testing.T) {
+	// the lines of "one\ntwo\nthree\n", newlines included
+	lines := []state.Span{{Start: 0, End: 4}, {Start: 4, End: 8}, {Start: 8, End: 14}}
+
+	tests := []struct {
+		name      string
+		pos       int
+		insert    string
+		wantSpans []state.Span // rows kept, by the span of text they were drawn for
+	}{
+		{name: "inside a line", pos: 5, insert: "x", wantSpans: []state.Span{{Start: 0, End: 4}, {Start: 9, End: 15}}},
+		{name: "at the end of a line", pos: 7, insert: "x", wantSpans: []state.Span{{Start: 0, End: 4}, {Start: 9, End: 15}}},
+		{name: "splitting a line", pos: 6, insert: "\n", wantSpans: []state.Span{{Start: 0, End: 4}, {Start: 9, End: 15}}},
+		// the line the text went before keeps its rows, moved with it
+		{name: "lines before a line", pos: 4, insert: "new\n", wantSpans: []state.Span{{Start: 0, End: 4}, {Start: 8, End: 12}, {Start: 12, End: 18}}},
+		{name: "after every line", pos: 14, insert: "four\n", wantSpans: lines},
+	}
+
+	for _, tt := range tests {
+		t.Run(tt.name, func(t *testing.T) {
+			path := filepath.Join(t.TempDir(), "test.txt")
+			if err := os.WriteFile(path, []byte("one\ntwo\nthree\n"), 0644); err != nil {
+				t.Fatal(err)
+			}
+			e := editor.NewEditor()
+			if err := e.OpenFile(path); err != nil {
+				t.Fatal(err)
+			}
+			c := NewRowCache(e)
+
+			drawn, err := c.lines()
+			if err != nil {
+				t.Fatalf("lines() error = %v", err)
+			}
+			for _, span := range lines {
+				drawn[rowKey{span: span}] = &drawnLine{rows: 1}
+			}
+
+			e.SetMode(state.Insert)
+			if err := e.MoveCursorTo(tt.pos, false); err != nil {
+				t.Fatal(err)
+			}
+			if err := e.InsertText(tt.insert); err != nil {
+				t.Fatal(err)
+			}
+			e.DispatchEvents()
+
+			var spans []state.Span
+			for key := range c.entries[path].lines {
+				spans = append(spans, key.span)
+			}
+			slices.SortFunc(spans, func(a, b state.Span) int { return a.Start - b.Start })
+			if !slices.Equal(spans, tt.wantSpans) {
+				t.Errorf("inserting %q at %d kept rows for %v, want %v", tt.insert, tt.pos, spans, tt.wantSpans)
+			}
+
+			// only the lines drawn in a frame are kept after it
+			c.EndFrame()
+			if _, ok := c.entries[path]; ok {
+				t.Errorf("rows for %s kept after a frame that drew none", path)
+			}
+		})
+	}
+}
@@ -0,0 +1,91 @@
+package ui
+
+import (
+	"github.com/gdamore/tcell/v2"
+	"github.com/lg2m/athena/internal/athena/config"
+	"github.com/lg2m/athena/internal/editor"
+)
+
+// ScrollbarView is a column beside a document showing which part of the buffer is in view, with
+// the lines holding search matches and diagnostics marked along it.
+type ScrollbarView struct {
+	BaseView
+	editor   *editor.Editor
+	cfg      *config.Config
+	viewport *Viewport
+	document *DocumentView // whose search matches are marked
+}
+
+func NewScrollbarView(e *editor.Editor, cfg *config.Config, v *Viewport, document *DocumentView) *ScrollbarView {
+	return &ScrollbarView{editor: e, cfg: cfg, viewport: v, document: document}
+}
+
+// Draw implements the scrollbar view; it follows the rows the document laid out, so it is drawn
+// after it.
+func (v *ScrollbarView) Draw(screen Surface) {
+	if v.width <= 0 || v.height <= 0 {
+		return
+	}
+	total, _ := v.editor.GetLineCount()
+	total = max(total, 1)
+
+	first, last := v.viewport.VisibleRange(v.height, total)
+	last--
+	for i, row := range v.viewport.Rows() {
+		if row.Line < 0 {
+			break
+		}
+		if i == 0 {
+			first = row.Line
+		}
+		last = row.Line
+	}
+	thumbStart, thumbEnd := v.rowOf(first, total), v.rowOf(last, total)+1
+
+	theme := themeFor(v.cfg)
+	marks := v.marks(theme, total)
+	for y := 0; y < v.height; y++ {
+		ch, style := theme.scrollbar.track, theme.scrollTrack
+		if y >= thumbStart && y < thumbEnd {
+			ch, style = theme.scrollbar.thumb, theme.scrollThumb
+		}
+		if fg, ok := marks[y]; ok {
+			ch, style = theme.scrollbar.mark, style.Foreground(fg)
+		}
+		screen.SetContent(v.x, v.y+y, ch, nil, style)
+	}
+}
+
+// rowOf returns the row of the scrollbar standing for line.
+func (v *ScrollbarView) rowOf(line, total int) int {
+	return min(line*v.height/total, v.height-1)
+}
+
+// marks returns the color of the mark on each row standing for lines with search matches or
+// diagnostics; diagnostics are marked over matches, and errors over the rest.
+func (v *ScrollbarView) marks(theme *theme, total int) map[int]tcell.Color {
+	marks := make(map[int]tcell.Color)
+	_, matchColor, _ := theme.match.Decompose()
+	for _, m := range v.document.matchHighlights {
+		if line, _, err := v.editor.LineCol(m.Start); err == nil {
+			marks[v.rowOf(line, total)] = matchColor
+		}
+	}
+
+	worst := make(map[int]editor.Sign)
+	for _, sign := range v.editor.Signs() {
+		if row := v.rowOf(sign.Line, total); sign.Kind >= editor.SignHint && sign.Kind > worst[row].Kind {
+			worst[row] = sign
+		}
+	}
+	for row, sign := range worst {
+		_, style := theme.sign(sign)
+		marks[row], _, _ = style.Decompose()
+	}
+	return marks
+}
+
+// HandleEvent implements View; the scrollbar takes no input.
+func (v *ScrollbarView) HandleEvent(ev tcell.Event) bool {
+	return false
+}
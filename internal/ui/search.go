@@ -0,0 +1,59 @@
+package ui
+
+import (
+	"github.com/lg2m/athena/internal/editor"
+	"github.com/lg2m/athena/internal/editor/search"
+)
+
+// openSearch opens the "/" (or "?") prompt; matches are highlighted while the pattern is typed
+// and enter moves to the next one.
+//
+//	An empty pattern repeats the last search, like vim.
+func (v *DocumentView) openSearch(forward bool) {
+	prefix := "/"
+	if !forward {
+		prefix = "?"
+	}
+
+	v.cmdline.Open(prefix, func(pattern string) {
+		v.clearSearchPreview()
+		if pattern == "" {
+			pattern = v.editor.LastSearchPattern()
+		}
+		if pattern == "" {
+			v.editor.SetError(editor.ErrNoPreviousPattern)
+			return
+		}
+		v.reportSearch(v.editor.Search(pattern, forward, v.searchOptions()))
+	})
+	v.cmdline.SetOnChange(func(pattern string) {
+		v.previewSearch(pattern, forward)
+	})
+	v.cmdline.SetOnCancel(v.clearSearchPreview)
+}
+
+// previewSearch highlights the matches of pattern, marking the one enter would move to.
+func (v *DocumentView) previewSearch(pattern string, forward bool) {
+	v.clearSearchPreview()
+	opts := v.searchOptions()
+	matches, err := v.editor.FindAll(pattern, opts)
+	if err != nil || len(matches) == 0 {
+		return
+	}
+	v.matchHighlights = matches
+
+	sel, err := v.editor.Selection()
+	if err != nil {
+		return
+	}
+	if i, _, ok := search.Next(matches, sel.End, forward, opts.WrapScan); ok {
+		m := matches[i]
+		v.currentMatch = &m
+	}
+}
+
+// clearSearchPreview removes the highlights of a search being typed.
+func (v *DocumentView) clearSearchPreview() {
+	v.matchHighlights = nil
+	v.currentMatch = nil
+}
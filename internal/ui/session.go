@@ -0,0 +1,52 @@
+package ui
+
+import (
+	"errors"
+	"fmt"
+	"strings"
+
+	"github.com/lg2m/athena/internal/editor/command"
+)
+
+var (
+	ErrNoSessions         = errors.New("sessions are not available")
+	ErrUnknownSessionVerb = errors.New("expected save or load")
+)
+
+// sessionVerbs are what ":session" does with a session.
+var sessionVerbs = []string{"load", "save"}
+
+// SetSessionCommand sets what saves and loads sessions, the open buffers and window layout
+// kept under a name: action is "save" or "load", and name is empty for the session last saved
+// or loaded.
+func (v *DocumentView) SetSessionCommand(run func(action, name string) error) {
+	v.session = run
+}
+
+// exSession implements ":session save [name]" and ":session load [name]".
+func (v *DocumentView) exSession(cmd command.Command) error {
+	if v.session == nil {
+		return ErrNoSessions
+	}
+	verb, name, _ := strings.Cut(strings.TrimSpace(cmd.Args), " ")
+	if verb == "" {
+		return ErrArgumentRequired
+	}
+	for _, action := range sessionVerbs {
+		if strings.HasPrefix(action, verb) {
+			return v.session(action, strings.TrimSpace(name))
+		}
+	}
+	return fmt.Errorf("%w: %s", ErrUnknownSessionVerb, verb)
+}
+
+// completeSession offers the verbs of ":session" starting with arg.
+func (v *DocumentView) completeSession(arg string) []string {
+	var verbs []string
+	for _, verb := range sessionVerbs {
+		if strings.HasPrefix(verb, arg) {
+			verbs = append(verbs, verb)
+		}
+	}
+	return verbs
+}
@@ -2,6 +2,7 @@ package ui
 
 import (
 	"fmt"
+	"slices"
 	"strings"
 
 	"github.com/gdamore/tcell/v2"
@@ -9,7 +10,9 @@ import (
 	"github.com/lg2m/athena/internal/athena/config"
 	"github.com/lg2m/athena/internal/editor"
 	"github.com/lg2m/athena/internal/editor/state"
+	"github.com/lg2m/athena/internal/lsp"
 	"github.com/lg2m/athena/internal/util"
+	"github.com/lg2m/athena/internal/vcs"
 )
 
 // statusBarMaxLengths holds the maximum lengths for each section.
@@ -23,9 +26,8 @@ type statusBarMaxLengths struct {
 type StatusBarView struct {
 	BaseView
 	editor *editor.Editor
-	cfg    *config.EditorConfig
+	cfg    *config.Config
 
-	style      tcell.Style
 	left       string
 	center     string
 	right      string
@@ -33,16 +35,14 @@ type StatusBarView struct {
 	maxLengths statusBarMaxLengths
 }
 
-func NewStatusBarView(e *editor.Editor, cfg *config.EditorConfig) *StatusBarView {
-	style := tcell.StyleDefault.Background(tcell.ColorDarkSlateGray).Foreground(tcell.ColorWhite)
+func NewStatusBarView(e *editor.Editor, cfg *config.Config) *StatusBarView {
 	return &StatusBarView{
 		editor: e,
 		cfg:    cfg,
-		style:  style,
 	}
 }
 
-func (v *StatusBarView) Draw(screen tcell.Screen) {
+func (v *StatusBarView) Draw(screen Surface) {
 	v.buildStatusSections()
 	v.handleOverflow()
 	v.render(screen)
@@ -50,9 +50,9 @@ func (v *StatusBarView) Draw(screen tcell.Screen) {
 
 // buildStatusSections constructs the left, center, and right sections.
 func (v *StatusBarView) buildStatusSections() {
-	v.left = v.buildSection(v.cfg.StatusBar.Left)
-	v.center = v.buildSection(v.cfg.StatusBar.Center)
-	v.right = v.buildSection(v.cfg.StatusBar.Right)
+	v.left = v.buildSection(v.cfg.Editor.StatusBar.Left)
+	v.center = v.buildSection(v.cfg.Editor.StatusBar.Center)
+	v.right = v.buildSection(v.cfg.Editor.StatusBar.Right)
 }
 
 // buildSection builds a single section based on the provided options.
@@ -70,29 +70,64 @@ func (v *StatusBarView) getOptionString(opt config.StatusBarOption) string {
 	case config.SectionMode:
 		switch v.editor.GetMode() {
 		case state.Normal:
-			return fmt.Sprintf(" %s ", v.cfg.StatusBar.Mode.Normal)
+			return fmt.Sprintf(" %s ", v.cfg.Editor.StatusBar.Mode.Normal)
 		case state.Insert:
-			return fmt.Sprintf(" %s ", v.cfg.StatusBar.Mode.Insert)
+			return fmt.Sprintf(" %s ", v.cfg.Editor.StatusBar.Mode.Insert)
+		case state.Command:
+			return fmt.Sprintf(" %s ", v.cfg.Editor.StatusBar.Mode.Command)
 		default:
 			return " UNK "
 		}
 	case config.SectionFileName:
 		if fileName, err := v.editor.FileName(); err == nil && fileName != "" {
+			if target, ok := v.editor.LinkTarget(); ok {
+				return fmt.Sprintf(" %s -> %s ", fileName, target)
+			}
 			return fmt.Sprintf(" %s ", fileName)
 		}
 	case config.SectionFileAbsPath:
 		if filePath, err := v.editor.FilePath(); err == nil && filePath != "" {
 			return fmt.Sprintf(" %s ", filePath)
 		}
-	// case config.SectionFileModified:
-	// case config.SectionFileEncoding:
+	case config.SectionFileModified:
+		var flags []string
+		if modified, _ := v.editor.Modified(); modified {
+			flags = append(flags, "[+]")
+		}
+		if readOnly, _ := v.editor.ReadOnly(); readOnly {
+			flags = append(flags, "[RO]")
+		}
+		if len(flags) > 0 {
+			return " " + strings.Join(flags, " ") + " "
+		}
+	case config.SectionFileEncoding:
+		if enc, err := v.editor.Encoding(); err == nil {
+			return fmt.Sprintf(" %s ", enc)
+		}
+	case config.SectionLineEnding:
+		if le, finalNewline, err := v.editor.LineEnding(); err == nil {
+			if !finalNewline {
+				return fmt.Sprintf(" %s noeol ", le)
+			}
+			return fmt.Sprintf(" %s ", le)
+		}
 	case config.SectionFileType:
 		if ext, err := v.editor.FileType(); err == nil && ext != "" {
 			return fmt.Sprintf(" %s ", ext)
 		}
-	// case config.SectionVersionControl:
+	case config.SectionVersionControl:
+		if branch, ok := v.editor.Branch(); ok {
+			return fmt.Sprintf(" %s ", branch)
+		}
+	case config.SectionDiffStats:
+		if hunks, ok := v.editor.Hunks(); ok && len(hunks) > 0 {
+			added, removed := vcs.Stats(hunks)
+			return fmt.Sprintf(" +%d -%d ", added, removed)
+		}
+	case config.SectionDiagnostics:
+		return diagnosticCounts(v.editor)
 	case config.SectionCursorPos:
-		currLine, currCol, _ := v.editor.GetCurrentPosition()
+		currLine, currCol, _ := v.editor.GetVisualPosition()
 		return fmt.Sprintf(" %d:%d ", currLine+1, currCol+1)
 	case config.SectionLineCount:
 		total, _ := v.editor.GetLineCount()
@@ -102,6 +137,23 @@ func (v *StatusBarView) getOptionString(opt config.StatusBarOption) string {
 		currLine, _, _ := v.editor.GetCurrentPosition()
 		scrollPercent := util.CalcProgress(total, currLine+1)
 		return fmt.Sprintf(" %d%% ", scrollPercent)
+	case config.SectionRecording:
+		if register, ok := v.editor.Recording(); ok {
+			return fmt.Sprintf(" recording @%c ", register)
+		}
+	case config.SectionSaving:
+		if saving, _ := v.editor.Saving(); saving {
+			return " writing... "
+		}
+	case config.SectionPendingKeys:
+		if keys := v.editor.PendingKeys(); keys != "" {
+			return fmt.Sprintf(" %s ", keys)
+		}
+	case config.SectionWordCount:
+		if fileType, err := v.editor.FileType(); err == nil && isProse(fileType) {
+			stats, _, _ := v.editor.TextStats()
+			return fmt.Sprintf(" %dW ", stats.Words)
+		}
 	case config.SectionSpacer:
 		return " "
 	default:
@@ -110,6 +162,40 @@ func (v *StatusBarView) getOptionString(opt config.StatusBarOption) string {
 	return ""
 }
 
+// diagnosticCounts returns the number of errors and warnings in the current buffer, leaving out
+// either when there are none.
+func diagnosticCounts(e *editor.Editor) string {
+	diagnostics, _ := e.Diagnostics()
+	var errs, warnings int
+	for _, d := range diagnostics {
+		switch d.Severity {
+		case lsp.SeverityError:
+			errs++
+		case lsp.SeverityWarning:
+			warnings++
+		}
+	}
+
+	var counts strings.Builder
+	if errs > 0 {
+		fmt.Fprintf(&counts, " E%d", errs)
+	}
+	if warnings > 0 {
+		fmt.Fprintf(&counts, " W%d", warnings)
+	}
+	if counts.Len() == 0 {
+		return ""
+	}
+	return counts.String() + " "
+}
+
+// proseFileTypes are the file types the word count is shown for.
+var proseFileTypes = []string{"md", "markdown", "txt", "text", "rst", "org", "adoc", "tex"}
+
+func isProse(fileType string) bool {
+	return slices.Contains(proseFileTypes, fileType)
+}
+
 // handleOverflow manages the truncation of sections if the total length exceeds available width.
 func (v *StatusBarView) handleOverflow() {
 	totalLen := len(v.left) + len(v.center) + len(v.right)
@@ -152,10 +238,12 @@ func truncateString(s string, overflow int) (string, int) {
 }
 
 // render outputs the status bar sections to the screen.
-func (v *StatusBarView) render(screen tcell.Screen) {
+func (v *StatusBarView) render(screen Surface) {
+	style := themeFor(v.cfg).statusBar
+
 	// Clear the status bar area
 	for x := v.x; x < v.x+v.width; x++ {
-		screen.SetContent(x, v.y, ' ', nil, v.style)
+		screen.SetContent(x, v.y, ' ', nil, style)
 	}
 
 	// Calculate positions
@@ -164,18 +252,18 @@ func (v *StatusBarView) render(screen tcell.Screen) {
 	centerX := v.x + (v.width-v.maxLengths.center)/2
 
 	// Render each section
-	v.renderString(screen, v.left, leftX)
-	v.renderString(screen, v.center, centerX)
-	v.renderString(screen, v.right, rightX)
+	v.renderString(screen, v.left, leftX, style)
+	v.renderString(screen, v.center, centerX, style)
+	v.renderString(screen, v.right, rightX, style)
 }
 
 // renderString draws a string on the screen starting at the specified x position.
-func (v *StatusBarView) renderString(screen tcell.Screen, s string, startX int) {
+func (v *StatusBarView) renderString(screen Surface, s string, startX int, style tcell.Style) {
 	for i, ch := range s {
 		xPos := startX + i
 		if xPos >= v.x+v.width {
 			break
 		}
-		screen.SetContent(xPos, v.y, ch, nil, v.style)
+		screen.SetContent(xPos, v.y, ch, nil, style)
 	}
 }
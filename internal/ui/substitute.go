@@ -0,0 +1,190 @@
+package ui
+
+import (
+	"fmt"
+
+	"github.com/lg2m/athena/internal/editor"
+	"github.com/lg2m/athena/internal/editor/command"
+	"github.com/lg2m/athena/internal/editor/search"
+)
+
+// substitution holds the progress of a ":s" command across confirmation prompts.
+type substitution struct {
+	sub     command.Substitute
+	matches []search.Match // matches as found before any replacement
+	index   int            // next match to handle
+	delta   int            // shift applied to later matches by earlier replacements
+	count   int
+	lines   map[int]bool
+}
+
+// current returns the next match adjusted for the replacements made so far.
+func (s *substitution) current() search.Match {
+	m := s.matches[s.index]
+	return search.Match{Start: m.Start + s.delta, End: m.End + s.delta}
+}
+
+// pending returns the matches still to be handled after the current one, for highlighting.
+func (s *substitution) pending() []search.Match {
+	var pending []search.Match
+	for _, m := range s.matches[s.index+1:] {
+		pending = append(pending, search.Match{Start: m.Start + s.delta, End: m.End + s.delta})
+	}
+	return pending
+}
+
+// replace replaces the current match and advances to the next one.
+func (s *substitution) replace(e *editor.Editor) error {
+	m := s.current()
+	matched, err := e.Substring(m.Start, m.End)
+	if err != nil {
+		return err
+	}
+	if line, _, err := e.LineCol(m.Start); err == nil {
+		s.lines[line] = true
+	}
+
+	end, err := e.Replace(m.Start, m.End, s.sub.Expand(matched))
+	if err != nil {
+		return err
+	}
+	s.delta += end - m.End
+	s.count++
+	s.index++
+	return nil
+}
+
+// replaceAll replaces every remaining match.
+func (s *substitution) replaceAll(e *editor.Editor) error {
+	for s.index < len(s.matches) {
+		if err := s.replace(e); err != nil {
+			return err
+		}
+	}
+	return nil
+}
+
+// exSubstitute implements ":s/pattern/replacement/flags" over the given line range.
+func (v *DocumentView) exSubstitute(cmd command.Command) error {
+	sub, err := command.ParseSubstitute(cmd.Args)
+	if err != nil {
+		return err
+	}
+	if sub.Pattern == "" {
+		sub.Pattern = v.editor.LastSearchPattern()
+		if sub.Pattern == "" {
+			return editor.ErrNoPreviousPattern
+		}
+	}
+
+	line, _, err := v.editor.GetCurrentPosition()
+	if err != nil {
+		return err
+	}
+	total, err := v.editor.GetLineCount()
+	if err != nil {
+		return err
+	}
+	startLine, endLine, err := cmd.Range.Resolve(line, total)
+	if err != nil {
+		return err
+	}
+
+	opts := v.searchOptions()
+	switch {
+	case sub.IgnoreCase:
+		opts.IgnoreCase, opts.SmartCase = true, false
+	case sub.MatchCase:
+		opts.IgnoreCase, opts.SmartCase = false, false
+	}
+
+	matches, err := v.editor.FindInLines(startLine, endLine, sub.Pattern, sub.Global, opts)
+	if err != nil {
+		return err
+	}
+	if len(matches) == 0 {
+		return fmt.Errorf("%w: %s", editor.ErrPatternNotFound, sub.Pattern)
+	}
+
+	s := &substitution{sub: sub, matches: matches, lines: make(map[int]bool)}
+	if sub.CountOnly {
+		v.reportMatchCount(s)
+		return nil
+	}
+	if !sub.Confirm {
+		err := s.replaceAll(v.editor)
+		v.finishSubstitution(s)
+		return err
+	}
+
+	v.confirmSubstitution(s)
+	return nil
+}
+
+// reportMatchCount shows how many matches a ":s///n" found, and on how many lines.
+func (v *DocumentView) reportMatchCount(s *substitution) {
+	for _, m := range s.matches {
+		if line, _, err := v.editor.LineCol(m.Start); err == nil {
+			s.lines[line] = true
+		}
+	}
+	if len(s.matches) == 1 {
+		v.editor.SetMessage("1 match on 1 line")
+		return
+	}
+	v.editor.SetMessage(fmt.Sprintf("%d matches on %d lines", len(s.matches), len(s.lines)))
+}
+
+// confirmSubstitution highlights the next match and asks whether to replace it.
+func (v *DocumentView) confirmSubstitution(s *substitution) {
+	if s.index >= len(s.matches) {
+		v.finishSubstitution(s)
+		return
+	}
+
+	m := s.current()
+	_ = v.editor.MoveCursorTo(m.Start, false)
+	v.centerCursor()
+	v.currentMatch = &m
+	v.matchHighlights = s.pending()
+
+	prompt := fmt.Sprintf("replace with %s (y/n/a/q/l)?", s.sub.Replacement)
+	v.cmdline.Confirm(prompt, func(key rune) {
+		var err error
+		switch key {
+		case 'y':
+			err = s.replace(v.editor)
+		case 'n':
+			s.index++
+		case 'a':
+			err = s.replaceAll(v.editor)
+		case 'l':
+			err = s.replace(v.editor)
+			s.index = len(s.matches)
+		case 'q':
+			s.index = len(s.matches)
+		}
+
+		if err != nil {
+			v.finishSubstitution(s)
+			v.editor.SetError(err)
+			return
+		}
+		v.confirmSubstitution(s)
+	})
+}
+
+// finishSubstitution clears the match highlights and reports how many replacements were made.
+func (v *DocumentView) finishSubstitution(s *substitution) {
+	v.currentMatch = nil
+	v.matchHighlights = nil
+
+	switch {
+	case s.count == 0:
+		v.editor.ClearMessage()
+	case s.count == 1 && len(s.lines) == 1:
+		v.editor.SetMessage("1 substitution on 1 line")
+	default:
+		v.editor.SetMessage(fmt.Sprintf("%d substitutions on %d lines", s.count, len(s.lines)))
+	}
+}
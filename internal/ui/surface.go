@@ -0,0 +1,74 @@
+package ui
+
+import (
+	"strings"
+
+	"github.com/gdamore/tcell/v2"
+)
+
+// Surface is what views draw on: a terminal screen, or a Grid kept in memory.
+type Surface interface {
+	SetContent(x, y int, primary rune, combining []rune, style tcell.Style)
+	Size() (width, height int)
+}
+
+// GridCell is a character drawn on a Grid.
+type GridCell struct {
+	Runes []rune // primary rune followed by any combining ones, nil when blank
+	Style tcell.Style
+}
+
+// Grid is a Surface held in memory, for checking what views draw without a terminal.
+type Grid struct {
+	width, height int
+	cells         []GridCell
+}
+
+// NewGrid returns a blank grid of the given size.
+func NewGrid(width, height int) *Grid {
+	return &Grid{width: width, height: height, cells: make([]GridCell, width*height)}
+}
+
+// SetContent draws a character; positions off the grid are ignored, like on a screen.
+func (g *Grid) SetContent(x, y int, primary rune, combining []rune, style tcell.Style) {
+	if x < 0 || y < 0 || x >= g.width || y >= g.height {
+		return
+	}
+	runes := append([]rune{primary}, combining...)
+	g.cells[y*g.width+x] = GridCell{Runes: runes, Style: style}
+}
+
+// Size returns the width and height of the grid.
+func (g *Grid) Size() (int, int) {
+	return g.width, g.height
+}
+
+// Clear blanks every cell.
+func (g *Grid) Clear() {
+	clear(g.cells)
+}
+
+// Cell returns the cell at x, y, blank when off the grid.
+func (g *Grid) Cell(x, y int) GridCell {
+	if x < 0 || y < 0 || x >= g.width || y >= g.height {
+		return GridCell{}
+	}
+	return g.cells[y*g.width+x]
+}
+
+// Rows returns the text of each row, with blank cells as spaces and trailing spaces trimmed.
+func (g *Grid) Rows() []string {
+	rows := make([]string, g.height)
+	for y := range rows {
+		var row strings.Builder
+		for x := 0; x < g.width; x++ {
+			if runes := g.cells[y*g.width+x].Runes; len(runes) > 0 {
+				row.WriteString(string(runes))
+			} else {
+				row.WriteByte(' ')
+			}
+		}
+		rows[y] = strings.TrimRight(row.String(), " ")
+	}
+	return rows
+}
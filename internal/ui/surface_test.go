@@ -0,0 +1,153 @@
+package ui
+
+import (
+	"bytes"
+	"io"
+	"slices"
+	"strings"
+	"sync"
+	"testing"
+
+	"github.com/gdamore/tcell/v2"
+	"github.com/lg2m/athena/internal/editor"
+)
+
+func TestGrid(t *testing.T) {
+	grid := NewGrid(4, 2)
+	bold := tcell.StyleDefault.Bold(true)
+	grid.SetContent(0, 0, 'a', nil, bold)
+	grid.SetContent(2, 0, 'e', []rune{'\u0301'}, tcell.StyleDefault)
+	grid.SetContent(4, 0, 'x', nil, tcell.StyleDefault) // off the grid
+	grid.SetContent(-1, 1, 'x', nil, tcell.StyleDefault)
+
+	want := []string{"a e\u0301", ""}
+	if got := grid.Rows(); !slices.Equal(got, want) {
+		t.Errorf("Rows() = %q, want %q", got, want)
+	}
+	if got := grid.Cell(0, 0).Style; got != bold {
+		t.Errorf("Cell(0, 0).Style = %v, want %v", got, bold)
+	}
+
+	grid.Clear()
+	if got := grid.Rows(); !slices.Equal(got, []string{"", ""}) {
+		t.Errorf("Rows() after Clear() = %q, want blank rows", got)
+	}
+}
+
+func TestCommandLineDraw(t *testing.T) {
+	tests := []struct {
+		name    string
+		keys    string
+		message string
+		want    string
+	}{
+		{name: "message", message: "written", want: "written"},
+		{name: "prompt", keys: "set", want: ":set"},
+		{name: "clipped", keys: "set tab-width=4", want: ":set tab-"},
+	}
+
+	for _, tt := range tests {
+		t.Run(tt.name, func(t *testing.T) {
+			e := editor.NewEditor()
+			v := NewCommandLineView(e)
+			v.Resize(0, 1, 9, 1)
+			if tt.message != "" {
+				e.SetMessage(tt.message)
+			} else {
+				v.Open(":", nil)
+				for _, ev := range ParseKeys(tt.keys) {
+					v.HandleEvent(ev)
+				}
+			}
+
+			grid := NewGrid(9, 2)
+			v.Draw(grid)
+			if got := grid.Rows()[1]; got != tt.want {
+				t.Errorf("Draw() row = %q, want %q", got, tt.want)
+			}
+		})
+	}
+}
+
+func TestScreenSendsChangedCells(t *testing.T) {
+	t.Setenv("TERM", "xterm")
+	tty := &recordingTty{drained: make(chan struct{})}
+	screen, err := tcell.NewTerminfoScreenFromTty(tty)
+	if err != nil {
+		t.Fatal(err)
+	}
+	if err := screen.Init(); err != nil {
+		t.Fatal(err)
+	}
+	defer screen.Fini()
+
+	e := editor.NewEditor()
+	v := NewCommandLineView(e)
+	width, height := screen.Size()
+	v.Resize(0, height-1, width, 1)
+	// drawn the way athena draws a frame, cleared first
+	frame := func(message string) string {
+		e.SetMessage(message)
+		screen.Clear()
+		v.Draw(screen)
+		tty.output()
+		screen.Show()
+		return tty.output()
+	}
+
+	if first := frame("unchanged words then one"); !strings.Contains(first, "unchanged words then one") {
+		t.Fatalf("first frame sent %q, want the whole message", first)
+	}
+	second := frame("unchanged words then two")
+	if !strings.Contains(second, "tw") || strings.Contains(second, "unchanged") {
+		t.Errorf("second frame sent %q, want only the cells that changed", second)
+	}
+	if third := frame("unchanged words then two"); strings.Contains(third, "two") {
+		t.Errorf("unchanged frame sent %q, want none of its text", third)
+	}
+}
+
+// recordingTty is a terminal of 80 by 24 cells that keeps what is written to it and never
+// types anything.
+type recordingTty struct {
+	mu      sync.Mutex
+	written bytes.Buffer
+	drained chan struct{}
+	once    sync.Once
+}
+
+func (t *recordingTty) Start() error           { return nil }
+func (t *recordingTty) Stop() error            { return nil }
+func (t *recordingTty) NotifyResize(cb func()) {}
+func (t *recordingTty) Close() error           { return nil }
+func (t *recordingTty) WindowSize() (tcell.WindowSize, error) {
+	return tcell.WindowSize{Width: 80, Height: 24}, nil
+}
+
+// Drain ends the read the screen is waiting in.
+func (t *recordingTty) Drain() error {
+	t.once.Do(func() { close(t.drained) })
+	return nil
+}
+
+func (t *recordingTty) Read(p []byte) (int, error) {
+	<-t.drained
+	return 0, io.EOF
+}
+
+func (t *recordingTty) Write(p []byte) (int, error) {
+	t.mu.Lock()
+	defer t.mu.Unlock()
+
+	return t.written.Write(p)
+}
+
+// output returns what was written since it was last called.
+func (t *recordingTty) output() string {
+	t.mu.Lock()
+	defer t.mu.Unlock()
+
+	out := t.written.String()
+	t.written.Reset()
+	return out
+}
@@ -0,0 +1,69 @@
+package ui
+
+import (
+	"github.com/lg2m/athena/internal/editor"
+	"github.com/lg2m/athena/internal/editor/format"
+)
+
+// surroundTarget is what a ys, S or cs waiting for its delimiter applies to.
+type surroundTarget struct {
+	start, end int  // text ys or S puts the delimiters around
+	from       rune // delimiter cs changes
+}
+
+// startSurround turns the y, d or c of op followed by "s" into a surround command: ys waits for a
+// motion like an operator, then for the delimiter; ds and cs wait for the delimiter to delete or
+// change.
+func (v *DocumentView) startSurround(op *operator) {
+	keys := op.keys + "s"
+	v.finishOperator()
+	switch op.action {
+	case "yank":
+		v.operator = &operator{action: "surround", keys: keys, count: op.count}
+		v.awaitKeys = keys
+	case "delete":
+		v.awaitRegister, v.awaitKeys = "surround_delete", keys
+	case "change":
+		v.awaitRegister, v.awaitKeys = "surround_change", keys
+	}
+}
+
+// surroundRange makes the text between start and end wait for the delimiter to put around it.
+func (v *DocumentView) surroundRange(start, end int) {
+	v.surround = surroundTarget{start: start, end: end}
+	v.awaitRegister, v.awaitKeys = "surround_add", "ys"
+}
+
+// surroundLines makes lines first through last wait for the delimiter to put around them, their
+// indentation left out, as for "yss".
+func (v *DocumentView) surroundLines(first, last int) {
+	start, _, err := v.editor.LineRange(first)
+	if err != nil {
+		return
+	}
+	_, end, err := v.editor.LineRange(last)
+	if err != nil {
+		return
+	}
+	text, err := v.editor.Substring(start, end)
+	if err != nil {
+		return
+	}
+	v.surroundRange(start+len(format.Leading(text)), end)
+}
+
+// surroundSelection makes the selection wait for the delimiter to put around it, like S in vim's
+// visual mode.
+func (v *DocumentView) surroundSelection() {
+	sel, err := v.editor.Selection()
+	if err != nil {
+		return
+	}
+	if sel.Empty() {
+		v.editor.SetError(editor.ErrNoSelections)
+		return
+	}
+	start, end := sel.Range()
+	v.surroundRange(start, end)
+	v.awaitKeys = "S"
+}
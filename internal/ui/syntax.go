@@ -0,0 +1,72 @@
+package ui
+
+import (
+	"github.com/lg2m/athena/internal/editor"
+	"github.com/lg2m/athena/internal/editor/treesitter"
+)
+
+// SyntaxCache keeps the syntax highlights of each buffer between frames, shared by the windows
+// showing it, so a buffer is only parsed again once it changes.
+type SyntaxCache struct {
+	editor  *editor.Editor
+	entries map[string]syntaxEntry // by buffer path
+}
+
+// syntaxEntry is the highlights of a buffer at a version of it.
+type syntaxEntry struct {
+	version    uint64
+	highlights []treesitter.Highlight
+}
+
+// NewSyntaxCache creates a cache for the buffers of e, dropping a buffer's highlights whenever
+// e reports it changed.
+func NewSyntaxCache(e *editor.Editor) *SyntaxCache {
+	c := &SyntaxCache{editor: e, entries: make(map[string]syntaxEntry)}
+	editor.Subscribe(e, c.bufferChanged)
+	return c
+}
+
+// bufferChanged drops the highlights of the buffer that changed, to be computed again when it is
+// next drawn.
+func (c *SyntaxCache) bufferChanged(ev editor.BufferChanged) {
+	delete(c.entries, ev.Path)
+}
+
+// Highlights returns the highlights of the editor's current buffer.
+//
+//	The version is checked too: a hook can edit the buffer after the cache was told of the last
+//	change, and its change is only reported with the next frame.
+func (c *SyntaxCache) Highlights() ([]treesitter.Highlight, error) {
+	path, err := c.editor.FilePath()
+	if err != nil {
+		return nil, err
+	}
+	version, err := c.editor.Version()
+	if err != nil {
+		return nil, err
+	}
+	if entry, ok := c.entries[path]; ok && entry.version == version {
+		return entry.highlights, nil
+	}
+
+	highlights, err := c.editor.GetHighlights()
+	if err != nil {
+		return nil, err
+	}
+	c.entries[path] = syntaxEntry{version: version, highlights: highlights}
+	return highlights, nil
+}
+
+// SetSyntaxCache sets the cache the view takes syntax highlights from; without one they are
+// computed for every frame.
+func (v *DocumentView) SetSyntaxCache(c *SyntaxCache) {
+	v.syntax = c
+}
+
+// highlights returns the syntax highlights of the buffer shown.
+func (v *DocumentView) highlights() ([]treesitter.Highlight, error) {
+	if v.syntax == nil {
+		return v.editor.GetHighlights()
+	}
+	return v.syntax.Highlights()
+}
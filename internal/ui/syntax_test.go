@@ -0,0 +1,38 @@
+package ui
+
+import (
+	"os"
+	"path/filepath"
+	"testing"
+
+	"github.com/lg2m/athena/internal/editor"
+	"github.com/lg2m/athena/internal/editor/state"
+)
+
+func TestSyntaxCache(t *testing.T) {
+	path := filepath.Join(t.TempDir(), "test.txt")
+	if err := os.WriteFile(path, []byte("one\n"), 0644); err != nil {
+		t.Fatal(err)
+	}
+	e := editor.NewEditor()
+	if err := e.OpenFile(path); err != nil {
+		t.Fatal(err)
+	}
+	c := NewSyntaxCache(e)
+
+	if _, err := c.Highlights(); err != nil {
+		t.Fatalf("Highlights() error = %v", err)
+	}
+	if _, ok := c.entries[path]; !ok {
+		t.Fatalf("Highlights() left no entry for %s", path)
+	}
+
+	e.SetMode(state.Insert)
+	if err := e.InsertText("x"); err != nil {
+		t.Fatal(err)
+	}
+	e.DispatchEvents()
+	if _, ok := c.entries[path]; ok {
+		t.Errorf("entry for %s kept after the buffer changed", path)
+	}
+}
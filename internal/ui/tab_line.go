@@ -0,0 +1,57 @@
+package ui
+
+import (
+	"github.com/gdamore/tcell/v2"
+	"github.com/lg2m/athena/internal/athena/config"
+)
+
+// TabLineView draws the row of tab pages along the top of the screen, marking the current one.
+type TabLineView struct {
+	BaseView
+	cfg     *config.Config
+	labels  []string
+	current int
+}
+
+func NewTabLineView(cfg *config.Config) *TabLineView {
+	return &TabLineView{cfg: cfg}
+}
+
+// SetTabs sets the label of each tab page and which one is current.
+func (v *TabLineView) SetTabs(labels []string, current int) {
+	v.labels = labels
+	v.current = current
+}
+
+// Draw implements the tab line view.
+func (v *TabLineView) Draw(screen Surface) {
+	if v.width <= 0 || v.height <= 0 {
+		return
+	}
+	theme := themeFor(v.cfg)
+	// the current tab stands out like the status bar, the others like the rows naming windows
+	other := theme.gutter.Reverse(true)
+
+	x := v.x
+	for i, label := range v.labels {
+		style := other
+		if i == v.current {
+			style = theme.statusBar
+		}
+		for _, r := range " " + label + " " {
+			if x >= v.x+v.width {
+				return
+			}
+			screen.SetContent(x, v.y, r, nil, style)
+			x++
+		}
+	}
+	for ; x < v.x+v.width; x++ {
+		screen.SetContent(x, v.y, ' ', nil, other)
+	}
+}
+
+// HandleEvent implements View; the tab line takes no input.
+func (v *TabLineView) HandleEvent(ev tcell.Event) bool {
+	return false
+}
@@ -0,0 +1,257 @@
+package ui
+
+import (
+	"cmp"
+
+	"github.com/gdamore/tcell/v2"
+	"github.com/lg2m/athena/internal/athena/config"
+	"github.com/lg2m/athena/internal/editor"
+	"github.com/lg2m/athena/internal/editor/treesitter"
+)
+
+// boxRunes are the characters a bordered overlay is drawn with.
+type boxRunes struct {
+	topLeft, topRight, bottomLeft, bottomRight rune
+	horizontal, vertical                       rune
+}
+
+// scrollbarRunes are the characters a scrollbar is drawn with.
+type scrollbarRunes struct {
+	track, thumb rune
+	mark         rune // a line with search matches or diagnostics
+}
+
+// diffRunes are the gutter signs of lines changed since the last commit.
+type diffRunes struct {
+	added, modified, removed rune // removed marks the line above the removed ones
+}
+
+// theme holds the styles and markers the views draw with.
+type theme struct {
+	text         tcell.Style // plain text; a background fills the document
+	selection    tcell.Style
+	match        tcell.Style
+	currentMatch tcell.Style
+	matchPair    tcell.Style // both delimiters of the bracket or keyword pair under the cursor
+
+	gutter        tcell.Style
+	gutterCurrent tcell.Style
+	currentMarker rune        // drawn in the gutter's last column on the cursor line, 0 for none
+	cursorLine    tcell.Style // background of the cursor line and column, see onCursorLine
+	eofMarker     tcell.Style // rows past the end of the buffer
+
+	// gutter signs of diagnostics by severity; information and hints share one
+	diagnosticError   tcell.Style
+	diagnosticWarning tcell.Style
+	diagnosticInfo    tcell.Style
+
+	// gutter signs of lines changed since the last commit
+	diffAdded    tcell.Style
+	diffModified tcell.Style
+	diffRemoved  tcell.Style
+	diff         diffRunes
+
+	mark tcell.Style // gutter signs of marks, drawn as their names
+
+	scrollTrack tcell.Style
+	scrollThumb tcell.Style // the part of the buffer in view
+	scrollbar   scrollbarRunes
+
+	statusBar tcell.Style
+
+	menu       tcell.Style
+	menuBorder tcell.Style
+	box        boxRunes
+	arrow      string
+
+	syntax treesitter.StyleMap // replaces the highlighter's styles when set
+	plain  bool                // strip italic and dim attributes
+}
+
+var defaultTheme = &theme{
+	text:         tcell.StyleDefault,
+	selection:    tcell.StyleDefault.Background(treesitter.ColorBgSelection),
+	match:        tcell.StyleDefault.Background(tcell.ColorOlive).Foreground(tcell.ColorBlack),
+	currentMatch: tcell.StyleDefault.Background(tcell.ColorOrange).Foreground(tcell.ColorBlack),
+	matchPair:    tcell.StyleDefault.Background(tcell.ColorDarkSlateGray).Bold(true),
+
+	gutter:        tcell.StyleDefault.Foreground(tcell.ColorPurple),
+	gutterCurrent: tcell.StyleDefault.Foreground(tcell.ColorWhite),
+	cursorLine:    tcell.StyleDefault.Background(treesitter.ColorBgHighlight),
+	eofMarker:     tcell.StyleDefault.Foreground(treesitter.ColorFgGutter),
+
+	diagnosticError:   tcell.StyleDefault.Foreground(tcell.ColorRed),
+	diagnosticWarning: tcell.StyleDefault.Foreground(tcell.ColorYellow),
+	diagnosticInfo:    tcell.StyleDefault.Foreground(tcell.ColorSteelBlue),
+
+	diffAdded:    tcell.StyleDefault.Foreground(tcell.ColorGreen),
+	diffModified: tcell.StyleDefault.Foreground(tcell.ColorOlive),
+	diffRemoved:  tcell.StyleDefault.Foreground(tcell.ColorRed),
+	diff:         diffRunes{'▎', '▎', '▁'},
+
+	mark: tcell.StyleDefault.Foreground(tcell.ColorTeal),
+
+	scrollTrack: tcell.StyleDefault.Background(treesitter.ColorBgHighlight),
+	scrollThumb: tcell.StyleDefault.Background(treesitter.ColorFgGutter),
+	scrollbar:   scrollbarRunes{' ', ' ', '─'},
+
+	statusBar: tcell.StyleDefault.Background(tcell.ColorDarkSlateGray).Foreground(tcell.ColorWhite),
+
+	menu:       tcell.StyleDefault.Background(tcell.ColorGray).Foreground(tcell.ColorWhite),
+	menuBorder: tcell.StyleDefault.Foreground(tcell.ColorWhite),
+	box:        boxRunes{'╭', '╮', '╰', '╯', '─', '│'},
+	arrow:      "→",
+}
+
+// accessibleTheme trades decoration for contrast: basic colors, no italic or dim text and
+// only ASCII markers, which also read better through screen readers.
+var accessibleTheme = &theme{
+	text:         tcell.StyleDefault,
+	selection:    tcell.StyleDefault.Reverse(true),
+	match:        tcell.StyleDefault.Background(tcell.ColorYellow).Foreground(tcell.ColorBlack),
+	currentMatch: tcell.StyleDefault.Background(tcell.ColorWhite).Foreground(tcell.ColorBlack).Bold(true),
+	matchPair:    tcell.StyleDefault.Bold(true).Underline(true),
+
+	gutter:        tcell.StyleDefault.Foreground(tcell.ColorSilver),
+	gutterCurrent: tcell.StyleDefault.Foreground(tcell.ColorWhite).Bold(true),
+	currentMarker: '>',
+	cursorLine:    tcell.StyleDefault.Background(tcell.ColorNavy),
+	eofMarker:     tcell.StyleDefault.Foreground(tcell.ColorSilver),
+
+	diagnosticError:   tcell.StyleDefault.Foreground(tcell.ColorRed).Bold(true),
+	diagnosticWarning: tcell.StyleDefault.Foreground(tcell.ColorYellow).Bold(true),
+	diagnosticInfo:    tcell.StyleDefault.Foreground(tcell.ColorWhite),
+
+	diffAdded:    tcell.StyleDefault.Foreground(tcell.ColorGreen).Bold(true),
+	diffModified: tcell.StyleDefault.Foreground(tcell.ColorYellow).Bold(true),
+	diffRemoved:  tcell.StyleDefault.Foreground(tcell.ColorRed).Bold(true),
+	diff:         diffRunes{'+', '~', '_'},
+
+	mark: tcell.StyleDefault.Foreground(tcell.ColorAqua).Bold(true),
+
+	scrollTrack: tcell.StyleDefault.Foreground(tcell.ColorSilver),
+	scrollThumb: tcell.StyleDefault.Foreground(tcell.ColorWhite).Bold(true),
+	scrollbar:   scrollbarRunes{'|', '#', '-'},
+
+	statusBar: tcell.StyleDefault.Background(tcell.ColorWhite).Foreground(tcell.ColorBlack),
+
+	menu:       tcell.StyleDefault.Background(tcell.ColorBlack).Foreground(tcell.ColorWhite),
+	menuBorder: tcell.StyleDefault.Foreground(tcell.ColorWhite),
+	box:        boxRunes{'+', '+', '+', '+', '-', '|'},
+	arrow:      "->",
+
+	syntax: treesitter.HighContrastStyles,
+	plain:  true,
+}
+
+// themeFor returns the theme to draw with under the given settings.
+func themeFor(cfg *config.Config) *theme {
+	if cfg.Editor.Accessibility {
+		return accessibleTheme
+	}
+	if tc, ok := cfg.Themes[cfg.Editor.Theme]; ok {
+		return loadedTheme(tc)
+	}
+	return defaultTheme
+}
+
+// loadedThemes caches the themes built from theme files, which are drawn with every frame.
+var loadedThemes = make(map[*config.ThemeConfig]*theme)
+
+// loadedTheme returns the theme described by a theme file, over the built-in one.
+func loadedTheme(tc *config.ThemeConfig) *theme {
+	if t, ok := loadedThemes[tc]; ok {
+		return t
+	}
+
+	t := *defaultTheme
+	for _, part := range []struct {
+		style *tcell.Style
+		from  config.ThemeStyle
+	}{
+		{&t.text, tc.Text},
+		{&t.selection, tc.Selection},
+		{&t.match, tc.Match},
+		{&t.currentMatch, tc.CurrentMatch},
+		{&t.matchPair, tc.MatchPair},
+		{&t.gutter, tc.Gutter},
+		{&t.gutterCurrent, tc.GutterCurrent},
+		{&t.cursorLine, tc.CursorLine},
+		{&t.eofMarker, tc.EOFMarker},
+		{&t.scrollTrack, tc.Scrollbar},
+		{&t.scrollThumb, tc.ScrollbarThumb},
+		{&t.statusBar, tc.StatusBar},
+		{&t.menu, tc.Menu},
+		{&t.menuBorder, tc.MenuBorder},
+	} {
+		if !part.from.IsZero() {
+			*part.style = part.from.Style()
+		}
+	}
+	if len(tc.Syntax) > 0 {
+		t.syntax = make(treesitter.StyleMap, len(tc.Syntax))
+		for capture, style := range tc.Syntax {
+			t.syntax[capture] = style.Style()
+		}
+	}
+
+	loadedThemes[tc] = &t
+	return &t
+}
+
+// sign returns the character drawing s in the gutter, and its style.
+func (t *theme) sign(s editor.Sign) (rune, tcell.Style) {
+	switch s.Kind {
+	case editor.SignMark:
+		return s.Text, t.mark
+	case editor.SignAdded:
+		return cmp.Or(s.Text, t.diff.added), t.diffAdded
+	case editor.SignModified:
+		return cmp.Or(s.Text, t.diff.modified), t.diffModified
+	case editor.SignRemoved:
+		return cmp.Or(s.Text, t.diff.removed), t.diffRemoved
+	case editor.SignHint:
+		return cmp.Or(s.Text, 'H'), t.diagnosticInfo
+	case editor.SignInfo:
+		return cmp.Or(s.Text, 'I'), t.diagnosticInfo
+	case editor.SignWarning:
+		return cmp.Or(s.Text, 'W'), t.diagnosticWarning
+	default:
+		return cmp.Or(s.Text, 'E'), t.diagnosticError
+	}
+}
+
+// highlightStyle returns the style to draw a syntax highlight with.
+func (t *theme) highlightStyle(h treesitter.Highlight) tcell.Style {
+	style := h.Style
+	if t.syntax != nil {
+		var ok bool
+		if style, ok = t.syntax.Lookup(h.Capture); !ok {
+			style = t.text
+		}
+	}
+	if t.plain {
+		style = style.Italic(false).Dim(false)
+	}
+	return t.onText(style)
+}
+
+// onCursorLine returns style on the cursor line's background, unless it has a background of its
+// own other than the text's.
+func (t *theme) onCursorLine(style tcell.Style) tcell.Style {
+	_, textBg, _ := t.text.Decompose()
+	_, lineBg, _ := t.cursorLine.Decompose()
+	if _, bg, _ := style.Decompose(); bg == tcell.ColorDefault || bg == textBg {
+		style = style.Background(lineBg)
+	}
+	return style
+}
+
+// onText returns style over the text background, unless it has a background of its own.
+func (t *theme) onText(style tcell.Style) tcell.Style {
+	_, textBg, _ := t.text.Decompose()
+	if _, bg, _ := style.Decompose(); bg == tcell.ColorDefault {
+		style = style.Background(textBg)
+	}
+	return style
+}
@@ -0,0 +1,72 @@
+package ui
+
+import (
+	"testing"
+
+	"github.com/gdamore/tcell/v2"
+	"github.com/lg2m/athena/internal/athena/config"
+	"github.com/lg2m/athena/internal/editor/treesitter"
+)
+
+func TestThemeFor(t *testing.T) {
+	night := &config.ThemeConfig{
+		Text:   config.ThemeStyle{Fg: "white", Bg: "navy"},
+		Syntax: map[string]config.ThemeStyle{"keyword": {Fg: "yellow"}},
+	}
+	navy := tcell.StyleDefault.Background(tcell.ColorNavy)
+
+	tests := []struct {
+		name          string
+		theme         string
+		accessibility bool
+		capture       string
+		wantSelection tcell.Style
+		wantSyntax    tcell.Style
+	}{
+		{
+			name:          "built-in",
+			capture:       "string",
+			wantSelection: defaultTheme.selection,
+			wantSyntax:    tcell.StyleDefault.Foreground(tcell.ColorGreen),
+		},
+		{
+			name:          "theme file",
+			theme:         "night",
+			capture:       "keyword.control",
+			wantSelection: defaultTheme.selection,
+			wantSyntax:    navy.Foreground(tcell.ColorYellow),
+		},
+		{
+			name:          "capture missing from theme",
+			theme:         "night",
+			capture:       "string",
+			wantSelection: defaultTheme.selection,
+			wantSyntax:    navy.Foreground(tcell.ColorWhite),
+		},
+		{
+			name:          "accessibility wins",
+			theme:         "night",
+			accessibility: true,
+			capture:       "keyword",
+			wantSelection: accessibleTheme.selection,
+			wantSyntax:    treesitter.HighContrastStyles["keyword"],
+		},
+	}
+
+	for _, tt := range tests {
+		t.Run(tt.name, func(t *testing.T) {
+			cfg := &config.Config{Themes: map[string]*config.ThemeConfig{"night": night}}
+			cfg.Editor.Theme = tt.theme
+			cfg.Editor.Accessibility = tt.accessibility
+
+			theme := themeFor(cfg)
+			if theme.selection != tt.wantSelection {
+				t.Errorf("selection = %v, want %v", theme.selection, tt.wantSelection)
+			}
+			h := treesitter.Highlight{Style: tcell.StyleDefault.Foreground(tcell.ColorGreen), Capture: tt.capture}
+			if got := theme.highlightStyle(h); got != tt.wantSyntax {
+				t.Errorf("highlightStyle(%q) = %v, want %v", tt.capture, got, tt.wantSyntax)
+			}
+		})
+	}
+}
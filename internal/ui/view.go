@@ -4,7 +4,7 @@ import "github.com/gdamore/tcell/v2"
 
 // View represents a drawable UI component.
 type View interface {
-	Draw(screen tcell.Screen)
+	Draw(screen Surface)
 	HandleEvent(event tcell.Event) bool
 	Resize(x, y, width, height int)
 }
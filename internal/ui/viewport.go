@@ -1,9 +1,19 @@
 package ui
 
+// ViewRow describes what a single screen row of the document displays.
+type ViewRow struct {
+	Line         int  // buffer line, or -1 past the end of the buffer
+	Continuation bool // row continues a soft wrapped line
+}
+
 // Viewport handles scrolling and visible area management.
 type Viewport struct {
-	offset  int // lines scrolled from top
-	padding int // lines to keep visible above/below cursor
+	offset  int       // lines scrolled from top
+	left    int       // display columns scrolled from the left, when lines aren't wrapped
+	shown   int       // offset being drawn; trails offset while a smooth scroll animates
+	smooth  bool      // animate scrolling instead of jumping
+	padding int       // lines to keep visible above/below cursor
+	rows    []ViewRow // rows laid out by the last document draw
 }
 
 func NewViewport(padding int) *Viewport {
@@ -23,9 +33,109 @@ func (v *Viewport) Update(currLine, viewHeight int) {
 	}
 }
 
+// UpdateLeft scrolls sideways to keep the cursor's display column visible, with some of the line
+// on either side of it where the view is wide enough.
+func (v *Viewport) UpdateLeft(currCol, viewWidth int) {
+	if viewWidth <= 0 {
+		return
+	}
+	padding := min(v.padding, (viewWidth-1)/2)
+	if currCol-v.left < padding {
+		v.left = max(0, currCol-padding)
+	} else if currCol-v.left >= viewWidth-padding {
+		v.left = currCol - viewWidth + padding + 1
+	}
+}
+
+// Left returns the first display column shown.
+func (v *Viewport) Left() int {
+	return v.left
+}
+
+// ResetLeft scrolls back to the start of the lines, e.g. when they are wrapped again.
+func (v *Viewport) ResetLeft() {
+	v.left = 0
+}
+
+// Offset returns the first line scrolled to.
+func (v *Viewport) Offset() int {
+	return v.offset
+}
+
+// SetOffset scrolls to line without animating, e.g. to restore a saved view.
+func (v *Viewport) SetOffset(line int) {
+	v.offset = max(0, line)
+	v.shown = v.offset
+}
+
+// SetSmooth turns scroll animation on or off; when off the viewport jumps straight to its offset.
+func (v *Viewport) SetSmooth(smooth bool) {
+	v.smooth = smooth
+	if !smooth {
+		v.shown = v.offset
+	}
+}
+
+// Animate advances a smooth scroll by one frame, returning whether more frames are needed.
+//
+//	Each frame covers a third of the remaining distance, and jumps of more than a screen
+//	skip straight to the last screenful so long jumps still take only a few frames.
+func (v *Viewport) Animate(viewHeight int) bool {
+	if !v.smooth {
+		v.shown = v.offset
+		return false
+	}
+
+	diff := v.offset - v.shown
+	if diff == 0 {
+		return false
+	}
+	if abs(diff) > viewHeight {
+		v.shown = v.offset - sign(diff)*viewHeight
+		diff = v.offset - v.shown
+	}
+
+	step := max(1, (abs(diff)+2)/3)
+	v.shown += sign(diff) * step
+	return v.Animating()
+}
+
+// Animating reports whether a smooth scroll is still in progress.
+func (v *Viewport) Animating() bool {
+	return v.shown != v.offset
+}
+
 // VisibleRange returns the range of visible lines.
 func (v *Viewport) VisibleRange(viewHeight, totalLines int) (start, end int) {
-	start = v.offset
-	end = min(totalLines, v.offset+viewHeight)
+	start = v.shown
+	end = min(totalLines, v.shown+viewHeight)
 	return start, end
 }
+
+// SetRows records the screen rows laid out by the document so other views can follow them.
+func (v *Viewport) SetRows(rows []ViewRow) {
+	v.rows = rows
+}
+
+// Rows returns the screen rows laid out by the last document draw.
+func (v *Viewport) Rows() []ViewRow {
+	return v.rows
+}
+
+func abs(n int) int {
+	if n < 0 {
+		return -n
+	}
+	return n
+}
+
+func sign(n int) int {
+	switch {
+	case n > 0:
+		return 1
+	case n < 0:
+		return -1
+	default:
+		return 0
+	}
+}
@@ -0,0 +1,32 @@
+package ui
+
+import "testing"
+
+func TestViewportUpdateLeft(t *testing.T) {
+	tests := []struct {
+		name    string
+		padding int
+		left    int
+		col     int
+		width   int
+		want    int
+	}{
+		{name: "cursor in view", padding: 2, col: 10, width: 20, want: 0},
+		{name: "past right edge", padding: 2, col: 30, width: 20, want: 13},
+		{name: "into right padding", padding: 2, col: 18, width: 20, want: 1},
+		{name: "back past left edge", padding: 2, left: 20, col: 5, width: 20, want: 3},
+		{name: "near line start", padding: 5, left: 20, col: 2, width: 20, want: 0},
+		{name: "padding capped by width", padding: 5, col: 4, width: 4, want: 2},
+	}
+
+	for _, tt := range tests {
+		t.Run(tt.name, func(t *testing.T) {
+			v := NewViewport(tt.padding)
+			v.left = tt.left
+			v.UpdateLeft(tt.col, tt.width)
+			if got := v.Left(); got != tt.want {
+				t.Errorf("UpdateLeft(%d, %d) from %d: left = %d, want %d", tt.col, tt.width, tt.left, got, tt.want)
+			}
+		})
+	}
+}
@@ -0,0 +1,81 @@
+package ui
+
+import (
+	"errors"
+
+	"github.com/lg2m/athena/internal/editor/command"
+)
+
+var (
+	ErrLastWindow = errors.New("cannot close the last window")
+	ErrLastTab    = errors.New("cannot close the last tab page")
+)
+
+// SetWindowCommand sets what runs window actions like "window_split" or "window_left", which
+// split the screen and move between its windows, and tab page actions like "tab_new".
+func (v *DocumentView) SetWindowCommand(run func(action string) error) {
+	v.window = run
+}
+
+// runWindowCommand runs a window action; without windows to manage there is nothing to do.
+func (v *DocumentView) runWindowCommand(action string) error {
+	if v.window == nil {
+		return nil
+	}
+	return v.window(action)
+}
+
+// exSplit implements ":sp", splitting the window in two stacked ones, and ":sp {path}", editing
+// a file in the new one.
+func (v *DocumentView) exSplit(cmd command.Command) error {
+	return v.splitWindow("window_split", cmd)
+}
+
+// exVsplit implements ":vs", like ":sp" with the windows side by side.
+func (v *DocumentView) exVsplit(cmd command.Command) error {
+	return v.splitWindow("window_vsplit", cmd)
+}
+
+// splitWindow splits the window, or opens a tab page, and edits the file named by cmd, if any, in
+// the new window.
+func (v *DocumentView) splitWindow(action string, cmd command.Command) error {
+	if err := v.runWindowCommand(action); err != nil {
+		return err
+	}
+	if cmd.Args == "" {
+		return nil
+	}
+	// the new window is focused and shows the editor's current buffer, so it is the one that switches
+	return v.exEdit(cmd)
+}
+
+// exClose implements ":clo", closing the focused window; its buffer stays loaded.
+func (v *DocumentView) exClose(cmd command.Command) error {
+	return v.runWindowCommand("window_close")
+}
+
+// exOnly implements ":on", closing every window but the focused one.
+func (v *DocumentView) exOnly(cmd command.Command) error {
+	return v.runWindowCommand("window_only")
+}
+
+// exTabNew implements ":tabnew", opening a tab page over the current buffer, and ":tabnew {path}",
+// editing a file in it.
+func (v *DocumentView) exTabNew(cmd command.Command) error {
+	return v.splitWindow("tab_new", cmd)
+}
+
+// exTabNext implements ":tabn", switching to the next tab page.
+func (v *DocumentView) exTabNext(cmd command.Command) error {
+	return v.runWindowCommand("tab_next")
+}
+
+// exTabPrev implements ":tabp", switching to the previous tab page.
+func (v *DocumentView) exTabPrev(cmd command.Command) error {
+	return v.runWindowCommand("tab_prev")
+}
+
+// exTabClose implements ":tabc", closing the current tab page and its windows.
+func (v *DocumentView) exTabClose(cmd command.Command) error {
+	return v.runWindowCommand("tab_close")
+}
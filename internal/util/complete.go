@@ -0,0 +1,51 @@
+package util
+
+import (
+	"os"
+	"path/filepath"
+	"sort"
+	"strings"
+)
+
+// CompletePath returns the paths that complete the partially typed input, sorted by name.
+//
+//	Directories end with a separator so completion can continue into them. Hidden entries
+//	are only offered when the typed name starts with a dot.
+func CompletePath(input string) []string {
+	dir, prefix := filepath.Split(input)
+
+	readDir := dir
+	if readDir == "" {
+		readDir = "."
+	}
+	if readDir == "~" || strings.HasPrefix(readDir, "~/") {
+		if home, err := os.UserHomeDir(); err == nil {
+			readDir = filepath.Join(home, readDir[1:])
+		}
+	}
+
+	entries, err := os.ReadDir(readDir)
+	if err != nil {
+		return nil
+	}
+
+	var matches []string
+	for _, entry := range entries {
+		name := entry.Name()
+		if !strings.HasPrefix(name, prefix) {
+			continue
+		}
+		if strings.HasPrefix(name, ".") && !strings.HasPrefix(prefix, ".") {
+			continue
+		}
+
+		// follow symlinks so linked directories complete like directories
+		if info, err := os.Stat(filepath.Join(readDir, name)); err == nil && info.IsDir() {
+			name += string(filepath.Separator)
+		}
+		matches = append(matches, dir+name)
+	}
+
+	sort.Strings(matches)
+	return matches
+}
@@ -0,0 +1,50 @@
+package util
+
+import (
+	"os"
+	"path/filepath"
+	"reflect"
+	"testing"
+)
+
+func TestCompletePath(t *testing.T) {
+	root := t.TempDir()
+	for _, dir := range []string{"src", "scripts", ".git"} {
+		if err := os.Mkdir(filepath.Join(root, dir), 0755); err != nil {
+			t.Fatal(err)
+		}
+	}
+	for _, file := range []string{"main.go", "Makefile", ".gitignore", "src/lib.go"} {
+		if err := os.WriteFile(filepath.Join(root, file), nil, 0644); err != nil {
+			t.Fatal(err)
+		}
+	}
+
+	sep := string(filepath.Separator)
+	tests := []struct {
+		name  string
+		input string
+		want  []string
+	}{
+		{name: "prefix", input: "s", want: []string{"scripts" + sep, "src" + sep}},
+		{name: "file", input: "ma", want: []string{"main.go"}},
+		{name: "all visible", input: "", want: []string{"Makefile", "main.go", "scripts" + sep, "src" + sep}},
+		{name: "hidden", input: ".g", want: []string{".git" + sep, ".gitignore"}},
+		{name: "nested", input: "src" + sep, want: []string{"src" + sep + "lib.go"}},
+		{name: "no match", input: "zzz", want: nil},
+	}
+
+	for _, tt := range tests {
+		t.Run(tt.name, func(t *testing.T) {
+			got := CompletePath(root + sep + tt.input)
+			var want []string
+			for _, w := range tt.want {
+				want = append(want, root+sep+w)
+			}
+			if !reflect.DeepEqual(got, want) {
+				t.Errorf("CompletePath(%q) = %v, want %v", tt.input, got, want)
+			}
+		})
+	}
+}
+
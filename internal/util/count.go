@@ -0,0 +1,31 @@
+package util
+
+import (
+	"strings"
+
+	"github.com/rivo/uniseg"
+)
+
+// TextStats holds the counts reported for a buffer or selection.
+type TextStats struct {
+	Lines int
+	Words int
+	Chars int // grapheme clusters, as the cursor moves over them
+	Bytes int
+}
+
+// CountText counts the lines, words, characters and bytes in s.
+//
+//	A final line without a trailing newline still counts as a line.
+func CountText(s string) TextStats {
+	lines := strings.Count(s, "\n")
+	if s != "" && !strings.HasSuffix(s, "\n") {
+		lines++
+	}
+	return TextStats{
+		Lines: lines,
+		Words: len(strings.Fields(s)),
+		Chars: uniseg.GraphemeClusterCount(s),
+		Bytes: len(s),
+	}
+}
@@ -0,0 +1,27 @@
+package util
+
+import "testing"
+
+func TestCountText(t *testing.T) {
+	tests := []struct {
+		name string
+		text string
+		want TextStats
+	}{
+		{name: "empty", text: "", want: TextStats{}},
+		{name: "one line", text: "hello world", want: TextStats{Lines: 1, Words: 2, Chars: 11, Bytes: 11}},
+		{name: "trailing newline", text: "a b\nc\n", want: TextStats{Lines: 2, Words: 3, Chars: 6, Bytes: 6}},
+		{name: "no trailing newline", text: "a\nb", want: TextStats{Lines: 2, Words: 2, Chars: 3, Bytes: 3}},
+		{name: "extra whitespace", text: "  a \t b  ", want: TextStats{Lines: 1, Words: 2, Chars: 9, Bytes: 9}},
+		{name: "multibyte", text: "日本 語", want: TextStats{Lines: 1, Words: 2, Chars: 4, Bytes: 10}},
+		{name: "grapheme clusters", text: "👋🏽 hi", want: TextStats{Lines: 1, Words: 2, Chars: 4, Bytes: 11}},
+	}
+
+	for _, tt := range tests {
+		t.Run(tt.name, func(t *testing.T) {
+			if got := CountText(tt.text); got != tt.want {
+				t.Errorf("CountText(%q) = %+v, want %+v", tt.text, got, tt.want)
+			}
+		})
+	}
+}
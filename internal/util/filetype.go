@@ -0,0 +1,65 @@
+package util
+
+import (
+	"path/filepath"
+	"strings"
+)
+
+// fileTypesByName are the types of files known by their name rather than an extension.
+var fileTypesByName = map[string]string{
+	"Makefile":    "make",
+	"makefile":    "make",
+	"GNUmakefile": "make",
+	"Dockerfile":  "dockerfile",
+	"justfile":    "just",
+	"Justfile":    "just",
+}
+
+// fileTypesByInterpreter are the types of scripts run by an interpreter, named in their "#!" line;
+// other interpreters name the type themselves, e.g. "lua".
+var fileTypesByInterpreter = map[string]string{
+	"python": "py",
+	"sh":     "sh",
+	"bash":   "sh",
+	"dash":   "sh",
+	"ksh":    "sh",
+	"zsh":    "sh",
+	"node":   "js",
+	"ruby":   "rb",
+	"perl":   "pl",
+}
+
+// DetectFileType returns the type of the file at path, whose first line is firstLine: its
+// extension without the dot, or for files without one, what their name or "#!" line tells.
+//
+//	Versions are left out of interpreters, so "#!/usr/bin/env python3" is "py" like "python".
+func DetectFileType(path, firstLine string) string {
+	if ext := strings.TrimPrefix(filepath.Ext(path), "."); ext != "" {
+		return ext
+	}
+	if fileType, ok := fileTypesByName[filepath.Base(path)]; ok {
+		return fileType
+	}
+
+	command, ok := strings.CutPrefix(firstLine, "#!")
+	if !ok {
+		return ""
+	}
+	fields := strings.Fields(command)
+	if len(fields) > 0 && filepath.Base(fields[0]) == "env" {
+		// the interpreter is the first argument that isn't an option, e.g. after "-S"
+		fields = fields[1:]
+		for len(fields) > 0 && strings.HasPrefix(fields[0], "-") {
+			fields = fields[1:]
+		}
+	}
+	if len(fields) == 0 {
+		return ""
+	}
+
+	interpreter := strings.TrimRight(filepath.Base(fields[0]), "0123456789.")
+	if fileType, ok := fileTypesByInterpreter[interpreter]; ok {
+		return fileType
+	}
+	return interpreter
+}
@@ -0,0 +1,31 @@
+package util
+
+import "testing"
+
+func TestDetectFileType(t *testing.T) {
+	tests := []struct {
+		name      string
+		path      string
+		firstLine string
+		want      string
+	}{
+		{name: "extension", path: "/src/main.go", want: "go"},
+		{name: "extension wins over shebang", path: "/bin/tool.rb", firstLine: "#!/bin/sh", want: "rb"},
+		{name: "known name", path: "/src/Makefile", want: "make"},
+		{name: "shebang", path: "/bin/build", firstLine: "#!/bin/bash", want: "sh"},
+		{name: "env", path: "/bin/serve", firstLine: "#!/usr/bin/env python3", want: "py"},
+		{name: "env with options", path: "/bin/run", firstLine: "#!/usr/bin/env -S node --harmony", want: "js"},
+		{name: "versioned", path: "/bin/run", firstLine: "#! /usr/bin/python3.12 -u", want: "py"},
+		{name: "unknown interpreter", path: "/bin/conf", firstLine: "#!/usr/bin/lua", want: "lua"},
+		{name: "no shebang", path: "/etc/hosts", firstLine: "127.0.0.1 localhost", want: ""},
+		{name: "bare shebang", path: "/bin/x", firstLine: "#!", want: ""},
+	}
+
+	for _, tt := range tests {
+		t.Run(tt.name, func(t *testing.T) {
+			if got := DetectFileType(tt.path, tt.firstLine); got != tt.want {
+				t.Errorf("DetectFileType(%q, %q) = %q, want %q", tt.path, tt.firstLine, got, tt.want)
+			}
+		})
+	}
+}
@@ -0,0 +1,189 @@
+package fuzzy
+
+import (
+	"slices"
+	"strings"
+	"unicode"
+)
+
+// Match is a candidate that matched a pattern.
+type Match struct {
+	Text      string
+	Index     int   // position of the candidate in the list filtered
+	Score     int   // higher is better
+	Positions []int // rune indexes of the matched characters, for highlighting
+}
+
+const (
+	scoreMatch       = 16 // every matched character
+	bonusBoundary    = 10 // match at the start of a word, e.g. after "/" or "_", or a camelCase hump
+	bonusConsecutive = 6  // match right after the previous one
+	penaltyGap       = 1  // every character skipped between two matches
+)
+
+// Score matches pattern against text, returning the score and the matched rune positions.
+//
+//	Characters of pattern must appear in text in order, not necessarily adjacent. Matching
+//	ignores case unless pattern has an uppercase letter.
+func Score(pattern, text string) (int, []int, bool) {
+	p, t := []rune(pattern), []rune(text)
+	if len(p) == 0 {
+		return 0, nil, true
+	}
+	foldCase := !strings.ContainsFunc(pattern, unicode.IsUpper)
+	eq := func(a, b rune) bool {
+		if foldCase {
+			return unicode.ToLower(a) == unicode.ToLower(b)
+		}
+		return a == b
+	}
+
+	// find where the first complete match ends
+	end, pi := -1, 0
+	for i, r := range t {
+		if eq(r, p[pi]) {
+			pi++
+			if pi == len(p) {
+				end = i
+				break
+			}
+		}
+	}
+	if end < 0 {
+		return 0, nil, false
+	}
+
+	// then match backwards from there, which gives the tightest match ending at end
+	positions := make([]int, len(p))
+	pi = len(p) - 1
+	for i := end; i >= 0 && pi >= 0; i-- {
+		if eq(t[i], p[pi]) {
+			positions[pi] = i
+			pi--
+		}
+	}
+
+	score := 0
+	for i, pos := range positions {
+		score += scoreMatch
+		if isBoundary(t, pos) {
+			score += bonusBoundary
+		}
+		if i > 0 {
+			if gap := pos - positions[i-1] - 1; gap == 0 {
+				score += bonusConsecutive
+			} else {
+				score -= gap * penaltyGap
+			}
+		}
+	}
+	return score, positions, true
+}
+
+// isBoundary reports whether t[i] starts a word.
+func isBoundary(t []rune, i int) bool {
+	if i == 0 {
+		return true
+	}
+	prev, curr := t[i-1], t[i]
+	switch {
+	case !unicode.IsLetter(prev) && !unicode.IsDigit(prev):
+		return true
+	case unicode.IsLower(prev) && unicode.IsUpper(curr):
+		return true
+	}
+	return false
+}
+
+// Filter returns the candidates matching pattern, best first; ties go to the shorter candidate,
+// then to the one listed first.
+func Filter(pattern string, candidates []string) []Match {
+	var matches []Match
+	for i, c := range candidates {
+		if m, ok := match(pattern, c, i); ok {
+			matches = append(matches, m)
+		}
+	}
+	sortMatches(matches)
+	return matches
+}
+
+func match(pattern, text string, index int) (Match, bool) {
+	score, positions, ok := Score(pattern, text)
+	if !ok {
+		return Match{}, false
+	}
+	return Match{Text: text, Index: index, Score: score, Positions: positions}, true
+}
+
+func sortMatches(matches []Match) {
+	slices.SortStableFunc(matches, func(a, b Match) int {
+		if a.Score != b.Score {
+			return b.Score - a.Score
+		}
+		if len(a.Text) != len(b.Text) {
+			return len(a.Text) - len(b.Text)
+		}
+		return a.Index - b.Index
+	})
+}
+
+// Matcher filters a list of candidates as a pattern is typed.
+//
+//	When the pattern only grows, just the previous matches are searched again, so each keystroke
+//	gets cheaper. Candidates may keep arriving, e.g. from a background directory walk, while a
+//	pattern is set. A Matcher is not safe for concurrent use.
+type Matcher struct {
+	candidates []string
+	pattern    string
+	matches    []Match
+}
+
+// NewMatcher returns a matcher over candidates, matching all of them until a pattern is set.
+func NewMatcher(candidates []string) *Matcher {
+	m := &Matcher{}
+	m.Append(candidates...)
+	return m
+}
+
+// Append adds candidates, matching them against the current pattern.
+func (m *Matcher) Append(candidates ...string) {
+	for _, c := range candidates {
+		if found, ok := match(m.pattern, c, len(m.candidates)); ok {
+			m.matches = append(m.matches, found)
+		}
+		m.candidates = append(m.candidates, c)
+	}
+	sortMatches(m.matches)
+}
+
+// SetPattern filters the candidates by pattern.
+func (m *Matcher) SetPattern(pattern string) {
+	if pattern == m.pattern {
+		return
+	}
+
+	var matches []Match
+	if strings.HasPrefix(pattern, m.pattern) {
+		// anything matching the longer pattern matched the shorter one too, even when an
+		// uppercase letter makes the longer one case-sensitive
+		for _, prev := range m.matches {
+			if found, ok := match(pattern, prev.Text, prev.Index); ok {
+				matches = append(matches, found)
+			}
+		}
+	} else {
+		for i, c := range m.candidates {
+			if found, ok := match(pattern, c, i); ok {
+				matches = append(matches, found)
+			}
+		}
+	}
+	sortMatches(matches)
+	m.pattern, m.matches = pattern, matches
+}
+
+// Matches returns the candidates matching the current pattern, best first.
+func (m *Matcher) Matches() []Match {
+	return m.matches
+}
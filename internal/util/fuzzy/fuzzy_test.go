@@ -0,0 +1,104 @@
+package fuzzy
+
+import (
+	"reflect"
+	"testing"
+)
+
+func TestScore(t *testing.T) {
+	tests := []struct {
+		name          string
+		pattern, text string
+		wantPositions []int
+		wantOk        bool
+	}{
+		{name: "empty pattern", pattern: "", text: "abc", wantOk: true},
+		{name: "prefix", pattern: "ab", text: "abc", wantPositions: []int{0, 1}, wantOk: true},
+		{name: "subsequence", pattern: "ac", text: "abc", wantPositions: []int{0, 2}, wantOk: true},
+		{name: "tightest window", pattern: "ab", text: "a_xab", wantPositions: []int{3, 4}, wantOk: true},
+		{name: "ignores case", pattern: "mf", text: "MainFile.go", wantPositions: []int{0, 4}, wantOk: true},
+		{name: "smart case", pattern: "Mf", text: "MainFile.go", wantOk: false},
+		{name: "out of order", pattern: "ba", text: "abc", wantOk: false},
+		{name: "unicode", pattern: "éa", text: "café bar", wantPositions: []int{3, 6}, wantOk: true},
+	}
+
+	for _, tt := range tests {
+		t.Run(tt.name, func(t *testing.T) {
+			_, positions, ok := Score(tt.pattern, tt.text)
+			if ok != tt.wantOk {
+				t.Fatalf("Score(%q, %q) ok = %v, want %v", tt.pattern, tt.text, ok, tt.wantOk)
+			}
+			if ok && !reflect.DeepEqual(positions, tt.wantPositions) {
+				t.Errorf("Score(%q, %q) positions = %v, want %v", tt.pattern, tt.text, positions, tt.wantPositions)
+			}
+		})
+	}
+}
+
+func TestFilter(t *testing.T) {
+	tests := []struct {
+		name       string
+		pattern    string
+		candidates []string
+		want       []string
+	}{
+		{
+			name:       "word starts first",
+			pattern:    "bn",
+			candidates: []string{"cabinet", "bnext", "buffer_next"},
+			want:       []string{"bnext", "buffer_next", "cabinet"},
+		},
+		{
+			name:       "shorter on ties",
+			pattern:    "set",
+			candidates: []string{"settings", "set"},
+			want:       []string{"set", "settings"},
+		},
+		{
+			name:       "path segments",
+			pattern:    "uidoc",
+			candidates: []string{"internal/ui/document.go", "internal/util/doc.go"},
+			want:       []string{"internal/ui/document.go", "internal/util/doc.go"},
+		},
+		{name: "no match", pattern: "xyz", candidates: []string{"abc"}, want: nil},
+	}
+
+	for _, tt := range tests {
+		t.Run(tt.name, func(t *testing.T) {
+			var got []string
+			for _, m := range Filter(tt.pattern, tt.candidates) {
+				got = append(got, m.Text)
+			}
+			if !reflect.DeepEqual(got, tt.want) {
+				t.Errorf("Filter(%q) = %q, want %q", tt.pattern, got, tt.want)
+			}
+		})
+	}
+}
+
+func TestMatcher(t *testing.T) {
+	candidates := []string{"main.go", "Makefile", "README.md", "internal/ui/main_view.go"}
+	m := NewMatcher(candidates[:2])
+
+	// typing, appending while filtering, then deleting back
+	steps := []struct {
+		pattern string
+		append  []string
+	}{
+		{pattern: "m"},
+		{pattern: "ma", append: candidates[2:]},
+		{pattern: "mai"},
+		{pattern: "M"},
+		{pattern: ""},
+	}
+
+	for _, step := range steps {
+		m.SetPattern(step.pattern)
+		m.Append(step.append...)
+
+		want := Filter(step.pattern, candidates[:len(m.candidates)])
+		if !reflect.DeepEqual(m.Matches(), want) {
+			t.Errorf("Matches() for %q = %v, want %v", step.pattern, m.Matches(), want)
+		}
+	}
+}
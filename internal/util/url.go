@@ -0,0 +1,53 @@
+package util
+
+import (
+	"regexp"
+	"strings"
+	"unicode/utf8"
+)
+
+// URLSpan is a URL found in a line of text, with rune offsets so it can be matched to drawn cells.
+type URLSpan struct {
+	Start int // first rune of the URL
+	End   int // rune after the URL
+	URL   string
+}
+
+var urlPattern = regexp.MustCompile(`\b(?:https?|ftp|file)://[^\s<>"'` + "`" + `]+`)
+
+// FindURLs returns the URLs in line.
+//
+//	Trailing punctuation is left out, so a URL ending a sentence or wrapped in parentheses
+//	does not pick up the "." or ")" after it.
+func FindURLs(line string) []URLSpan {
+	var spans []URLSpan
+	for _, loc := range urlPattern.FindAllStringIndex(line, -1) {
+		url := trimURL(line[loc[0]:loc[1]])
+		if !strings.Contains(url, "://") || strings.HasSuffix(url, "://") {
+			continue
+		}
+		start := utf8.RuneCountInString(line[:loc[0]])
+		spans = append(spans, URLSpan{
+			Start: start,
+			End:   start + utf8.RuneCountInString(url),
+			URL:   url,
+		})
+	}
+	return spans
+}
+
+// trimURL drops trailing punctuation, keeping a closing bracket only when the URL opened one.
+func trimURL(url string) string {
+	for url != "" {
+		last := url[len(url)-1]
+		switch {
+		case strings.IndexByte(".,:;!?'\"", last) >= 0:
+		case last == ')' && strings.Count(url, "(") < strings.Count(url, ")"):
+		case last == ']' && strings.Count(url, "[") < strings.Count(url, "]"):
+		default:
+			return url
+		}
+		url = url[:len(url)-1]
+	}
+	return url
+}
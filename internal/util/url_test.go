@@ -0,0 +1,36 @@
+package util
+
+import (
+	"reflect"
+	"testing"
+)
+
+func TestFindURLs(t *testing.T) {
+	tests := []struct {
+		name string
+		line string
+		want []URLSpan
+	}{
+		{name: "none", line: "no links here", want: nil},
+		{name: "plain", line: "see https://example.com for more", want: []URLSpan{{Start: 4, End: 23, URL: "https://example.com"}}},
+		{name: "sentence end", line: "go to http://a.io/x.", want: []URLSpan{{Start: 6, End: 19, URL: "http://a.io/x"}}},
+		{name: "parenthesized", line: "(https://a.io/b)", want: []URLSpan{{Start: 1, End: 15, URL: "https://a.io/b"}}},
+		{name: "balanced parens", line: "https://en.wikipedia.org/wiki/Go_(language)", want: []URLSpan{{Start: 0, End: 43, URL: "https://en.wikipedia.org/wiki/Go_(language)"}}},
+		{name: "quoted", line: `url = "https://a.io"`, want: []URLSpan{{Start: 7, End: 19, URL: "https://a.io"}}},
+		{name: "rune offsets", line: "日本 https://a.io", want: []URLSpan{{Start: 3, End: 15, URL: "https://a.io"}}},
+		{name: "two", line: "https://a.io and ftp://b.io", want: []URLSpan{
+			{Start: 0, End: 12, URL: "https://a.io"},
+			{Start: 17, End: 27, URL: "ftp://b.io"},
+		}},
+		{name: "scheme only", line: "https://.", want: nil},
+	}
+
+	for _, tt := range tests {
+		t.Run(tt.name, func(t *testing.T) {
+			got := FindURLs(tt.line)
+			if !reflect.DeepEqual(got, tt.want) {
+				t.Errorf("FindURLs(%q) = %v, want %v", tt.line, got, tt.want)
+			}
+		})
+	}
+}
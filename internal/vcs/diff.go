@@ -0,0 +1,152 @@
+package vcs
+
+import "strings"
+
+// maxEdits bounds the work of a diff: texts further apart than this many inserted and deleted
+// lines are reported as one hunk covering everything between their common start and end.
+const maxEdits = 4096
+
+// Hunk is a run of changed lines: Count lines of the new text from Start replace Removed lines
+// of the base. Lines only removed have a Count of 0 and sit before the line at Start.
+type Hunk struct {
+	Start   int
+	Count   int
+	Removed int
+}
+
+// Stats returns the number of lines hunks add and remove, counting a changed line as both, like
+// "git diff --numstat".
+func Stats(hunks []Hunk) (added, removed int) {
+	for _, h := range hunks {
+		added += h.Count
+		removed += h.Removed
+	}
+	return added, removed
+}
+
+// Diff returns the hunks turning base into text, compared line by line.
+func Diff(base, text string) []Hunk {
+	a, b := splitLines(base), splitLines(text)
+
+	// edits are usually few and close together, so the common ends are skipped first
+	prefix := 0
+	for prefix < len(a) && prefix < len(b) && a[prefix] == b[prefix] {
+		prefix++
+	}
+	suffix := 0
+	for suffix < len(a)-prefix && suffix < len(b)-prefix && a[len(a)-1-suffix] == b[len(b)-1-suffix] {
+		suffix++
+	}
+	a, b = a[prefix:len(a)-suffix], b[prefix:len(b)-suffix]
+
+	ops, ok := shortestEdit(a, b)
+	if !ok {
+		return []Hunk{{Start: prefix, Count: len(b), Removed: len(a)}}
+	}
+
+	var hunks []Hunk
+	line := prefix
+	for i := 0; i < len(ops); {
+		if ops[i] == opEqual {
+			line++
+			i++
+			continue
+		}
+		h := Hunk{Start: line}
+		for ; i < len(ops) && ops[i] != opEqual; i++ {
+			if ops[i] == opInsert {
+				h.Count++
+			} else {
+				h.Removed++
+			}
+		}
+		line += h.Count
+		hunks = append(hunks, h)
+	}
+	return hunks
+}
+
+// splitLines splits text into lines without their line breaks; a final line break doesn't start
+// another line.
+func splitLines(text string) []string {
+	if text == "" {
+		return nil
+	}
+	return strings.Split(strings.TrimSuffix(text, "\n"), "\n")
+}
+
+type op int
+
+const (
+	opEqual op = iota
+	opDelete
+	opInsert
+)
+
+// shortestEdit returns the fewest deletions from a and insertions of b turning a into b, in
+// order, using Myers' algorithm; false when that takes more than maxEdits.
+func shortestEdit(a, b []string) ([]op, bool) {
+	n, m := len(a), len(b)
+	offset := n + m + 1
+	v := make([]int, 2*offset+1) // furthest x reached on each diagonal k = x - y, at v[offset+k]
+	// trace[d] holds v for diagonals -d-1 to d+1 as it was before step d, for backtracking
+	var trace [][]int
+
+	for d := 0; d <= n+m; d++ {
+		if d > maxEdits {
+			return nil, false
+		}
+		trace = append(trace, append([]int(nil), v[offset-d-1:offset+d+2]...))
+		for k := -d; k <= d; k += 2 {
+			var x int
+			if k == -d || (k != d && v[offset+k-1] < v[offset+k+1]) {
+				x = v[offset+k+1] // down, inserting b[y-1]
+			} else {
+				x = v[offset+k-1] + 1 // right, deleting a[x-1]
+			}
+			y := x - k
+			for x < n && y < m && a[x] == b[y] {
+				x, y = x+1, y+1
+			}
+			v[offset+k] = x
+			if x >= n && y >= m {
+				return backtrack(trace, n, m), true
+			}
+		}
+	}
+	return nil, false
+}
+
+// backtrack walks the trace of shortestEdit back from the end of both texts, returning the edit
+// it found.
+func backtrack(trace [][]int, x, y int) []op {
+	var ops []op
+	for d := len(trace) - 1; d >= 0; d-- {
+		v := func(k int) int { return trace[d][k+d+1] }
+		k := x - y
+		prevK := k - 1
+		if k == -d || (k != d && v(k-1) < v(k+1)) {
+			prevK = k + 1
+		}
+		prevX := v(prevK)
+		prevY := prevX - prevK
+		for x > prevX && y > prevY {
+			ops = append(ops, opEqual)
+			x, y = x-1, y-1
+		}
+		if d > 0 {
+			if x == prevX {
+				ops = append(ops, opInsert)
+			} else {
+				ops = append(ops, opDelete)
+			}
+		}
+		x, y = prevX, prevY
+	}
+
+	// collected from the end
+	for i, j := 0, len(ops)-1; i < j; i, j = i+1, j-1 {
+		ops[i], ops[j] = ops[j], ops[i]
+	}
+	return ops
+}
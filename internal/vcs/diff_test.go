@@ -0,0 +1,40 @@
+package vcs
+
+import (
+	"reflect"
+	"testing"
+)
+
+func TestDiff(t *testing.T) {
+	tests := []struct {
+		name string
+		base string
+		text string
+		want []Hunk
+	}{
+		{name: "same", base: "a\nb\n", text: "a\nb\n", want: nil},
+		{name: "added", base: "a\nc\n", text: "a\nb\nc\n", want: []Hunk{{Start: 1, Count: 1}}},
+		{name: "removed", base: "a\nb\nc\n", text: "a\nc\n", want: []Hunk{{Start: 1, Removed: 1}}},
+		{name: "changed", base: "a\nb\nc\n", text: "a\nx\nc\n", want: []Hunk{{Start: 1, Count: 1, Removed: 1}}},
+		{name: "two hunks", base: "a\nb\nc\nd\ne\n", text: "x\nb\nc\nd\n", want: []Hunk{{Start: 0, Count: 1, Removed: 1}, {Start: 4, Removed: 1}}},
+		{name: "new file", base: "", text: "a\nb\n", want: []Hunk{{Start: 0, Count: 2}}},
+		{name: "emptied", base: "a\nb\n", text: "", want: []Hunk{{Start: 0, Removed: 2}}},
+		{name: "final newline only", base: "a\n", text: "a", want: nil},
+		{name: "moved line", base: "a\nb\nc\n", text: "b\nc\na\n", want: []Hunk{{Start: 0, Removed: 1}, {Start: 2, Count: 1}}},
+	}
+
+	for _, tt := range tests {
+		t.Run(tt.name, func(t *testing.T) {
+			if got := Diff(tt.base, tt.text); !reflect.DeepEqual(got, tt.want) {
+				t.Errorf("Diff(%q, %q) = %+v, want %+v", tt.base, tt.text, got, tt.want)
+			}
+		})
+	}
+}
+
+func TestStats(t *testing.T) {
+	added, removed := Stats([]Hunk{{Start: 0, Count: 2, Removed: 1}, {Start: 5, Removed: 3}})
+	if added != 2 || removed != 4 {
+		t.Errorf("Stats() = %d, %d, want 2, 4", added, removed)
+	}
+}
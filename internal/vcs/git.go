@@ -0,0 +1,41 @@
+package vcs
+
+import (
+	"os/exec"
+	"path/filepath"
+	"strings"
+)
+
+// Branch returns the branch checked out in the git repository holding dir, or the short hash of
+// the commit when none is; false when dir isn't in a repository or git isn't installed.
+func Branch(dir string) (string, bool) {
+	out, err := git(dir, "rev-parse", "--abbrev-ref", "HEAD")
+	if err != nil {
+		return "", false
+	}
+	if branch := strings.TrimSpace(out); branch != "HEAD" {
+		return branch, true
+	}
+	out, err = git(dir, "rev-parse", "--short", "HEAD")
+	if err != nil {
+		return "", false
+	}
+	return strings.TrimSpace(out), true
+}
+
+// HeadText returns the content of the file at path as of the commit checked out; false when the
+// file isn't tracked there.
+func HeadText(path string) (string, bool) {
+	dir, name := filepath.Split(path)
+	out, err := git(dir, "show", "HEAD:./"+name)
+	if err != nil {
+		return "", false
+	}
+	return out, true
+}
+
+// git runs git with args in dir and returns what it printed.
+func git(dir string, args ...string) (string, error) {
+	out, err := exec.Command("git", append([]string{"-C", dir}, args...)...).Output()
+	return string(out), err
+}